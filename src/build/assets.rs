@@ -0,0 +1,418 @@
+//! Fingerprinted asset URLs.
+//!
+//! With `build.fingerprint = true`, static files are written both under their
+//! original name and as `name.<hash>.ext`, and `asset-manifest.json` maps one
+//! to the other (`/static/app.css` → `/static/app.1a2b3c4d.css`). This module
+//! applies that manifest to generated HTML, to the CSS files themselves, and
+//! exposes it to templates through the `asset_url()` Tera function.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// HTML attributes holding a single URL.
+const URL_ATTRS: &[&str] = &["href", "src", "data-src", "poster"];
+
+/// HTML attributes holding a comma-separated srcset list.
+const SRCSET_ATTRS: &[&str] = &["srcset", "imagesrcset"];
+
+/// Build the fingerprinted file name: `app.css` + `1a2b3c4d` → `app.1a2b3c4d.css`.
+pub fn fingerprinted_name(file_name: &str, hash: &str) -> String {
+    let path = Path::new(file_name);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    format!("{stem}.{hash}.{ext}")
+}
+
+/// Resolve a URL against the manifest, preserving any query string, fragment
+/// and base path prefix. Returns `None` if the asset is not fingerprinted.
+pub fn resolve(url: &str, manifest: &HashMap<String, String>, base_path: &str) -> Option<String> {
+    let split = url.find(['?', '#']).unwrap_or(url.len());
+    let (path, suffix) = url.split_at(split);
+    let (prefix, bare) = match path.strip_prefix(base_path) {
+        Some(rest) if !base_path.is_empty() && rest.starts_with('/') => (base_path, rest),
+        _ => ("", path),
+    };
+    manifest
+        .get(bare)
+        .map(|fingerprinted| format!("{prefix}{fingerprinted}{suffix}"))
+}
+
+/// Rewrite references to fingerprinted assets in HTML tags (`href`, `src`,
+/// `srcset`, `imagesrcset`, `data-src`, `poster`), including
+/// `<link rel="preload">` hints. Text content is left untouched.
+///
+/// URLs that already carry `base_path` keep it, so this can run before or
+/// after base path rewriting.
+pub fn rewrite_html_assets(
+    html: &str,
+    manifest: &HashMap<String, String>,
+    base_path: &str,
+) -> String {
+    if manifest.is_empty() {
+        return html.to_string();
+    }
+
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        result.push_str(&rest[..start]);
+        let tag_and_after = &rest[start..];
+        match find_tag_end(tag_and_after) {
+            Some(end) => {
+                let tag = &tag_and_after[..=end];
+                if tag.starts_with("<!") || tag.starts_with("</") {
+                    result.push_str(tag);
+                } else {
+                    result.push_str(&rewrite_tag(tag, manifest, base_path));
+                }
+                rest = &tag_and_after[end + 1..];
+            }
+            None => {
+                result.push_str(tag_and_after);
+                rest = "";
+            }
+        }
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Find the `>` closing the tag at the start of `html`, skipping quoted values.
+fn find_tag_end(html: &str) -> Option<usize> {
+    let mut in_quote: Option<u8> = None;
+    for (i, &b) in html.as_bytes().iter().enumerate().skip(1) {
+        match in_quote {
+            Some(q) if b == q => in_quote = None,
+            Some(_) => {}
+            None if b == b'"' || b == b'\'' => in_quote = Some(b),
+            None if b == b'>' => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+/// Rewrite the URL-bearing attribute values of a single tag.
+fn rewrite_tag(tag: &str, manifest: &HashMap<String, String>, base_path: &str) -> String {
+    let mut result = String::with_capacity(tag.len());
+    let mut rest = tag;
+
+    while let Some(eq) = rest.find('=') {
+        let quote = match rest.as_bytes().get(eq + 1) {
+            Some(&q @ (b'"' | b'\'')) => q as char,
+            _ => {
+                result.push_str(&rest[..=eq]);
+                rest = &rest[eq + 1..];
+                continue;
+            }
+        };
+        let value_start = eq + 2;
+        let Some(len) = rest[value_start..].find(quote) else {
+            break;
+        };
+        let name = rest[..eq]
+            .trim_end()
+            .rsplit(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let value = &rest[value_start..value_start + len];
+
+        result.push_str(&rest[..value_start]);
+        if URL_ATTRS.contains(&name.as_str()) {
+            match resolve(value, manifest, base_path) {
+                Some(rewritten) => result.push_str(&rewritten),
+                None => result.push_str(value),
+            }
+        } else if SRCSET_ATTRS.contains(&name.as_str()) {
+            result.push_str(&rewrite_srcset(value, manifest, base_path));
+        } else {
+            result.push_str(value);
+        }
+        result.push(quote);
+        rest = &rest[value_start + len + 1..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Rewrite each URL of a `srcset` value (`url [descriptor], ...`).
+fn rewrite_srcset(srcset: &str, manifest: &HashMap<String, String>, base_path: &str) -> String {
+    srcset
+        .split(',')
+        .map(|candidate| {
+            let trimmed = candidate.trim();
+            let (url, descriptor) = match trimmed.split_once(char::is_whitespace) {
+                Some((url, descriptor)) => (url, Some(descriptor.trim())),
+                None => (trimmed, None),
+            };
+            let url = resolve(url, manifest, base_path).unwrap_or_else(|| url.to_string());
+            match descriptor {
+                Some(d) => format!("{url} {d}"),
+                None => url,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Rewrite `url(...)` and `@import "..."` references in a stylesheet served
+/// at `css_url` (e.g. `/static/css/app.css`) to their fingerprinted names.
+///
+/// Relative references are resolved against the stylesheet's directory and
+/// stay relative; only the file name changes.
+pub fn rewrite_css_urls(css: &str, css_url: &str, manifest: &HashMap<String, String>) -> String {
+    if manifest.is_empty() {
+        return css.to_string();
+    }
+    let css_dir = &css_url[..css_url.rfind('/').map_or(0, |i| i + 1)];
+
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+
+    loop {
+        // Position just after the next `url(` or `@import`, whichever comes first
+        let next = [
+            ("url(", rest.find("url(")),
+            ("@import", rest.find("@import")),
+        ]
+        .into_iter()
+        .filter_map(|(token, pos)| pos.map(|p| p + token.len()))
+        .min();
+        let Some(value_from) = next else {
+            break;
+        };
+
+        // Skip whitespace, then read a quoted or bare reference
+        let after = &rest[value_from..];
+        let ws = after.len() - after.trim_start().len();
+        let value_start = value_from + ws;
+        let (value, value_end) = match rest.as_bytes().get(value_start) {
+            Some(&q @ (b'"' | b'\'')) => {
+                let inner_start = value_start + 1;
+                match rest[inner_start..].find(q as char) {
+                    Some(len) => (&rest[inner_start..inner_start + len], inner_start + len),
+                    None => break,
+                }
+            }
+            // `@import url(...)` is handled when the scan reaches `url(`
+            Some(_) if rest[value_start..].starts_with("url(") => ("", value_start),
+            Some(_) => {
+                let len = rest[value_start..]
+                    .find(|c: char| c == ')' || c == ';' || c.is_whitespace())
+                    .unwrap_or(rest.len() - value_start);
+                (&rest[value_start..value_start + len], value_start + len)
+            }
+            None => break,
+        };
+
+        let start_of_value = value_end - value.len();
+        result.push_str(&rest[..start_of_value]);
+        match rewrite_css_reference(value, css_dir, manifest) {
+            Some(rewritten) => result.push_str(&rewritten),
+            None => result.push_str(value),
+        }
+        rest = &rest[value_end..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Rewrite one stylesheet reference, keeping it relative if it was.
+fn rewrite_css_reference(
+    reference: &str,
+    css_dir: &str,
+    manifest: &HashMap<String, String>,
+) -> Option<String> {
+    if reference.is_empty()
+        || reference.starts_with("data:")
+        || reference.starts_with('#')
+        || reference.contains("://")
+        || reference.starts_with("//")
+    {
+        return None;
+    }
+    let split = reference.find(['?', '#']).unwrap_or(reference.len());
+    let (path, suffix) = reference.split_at(split);
+    let absolute = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        normalize_path(&format!("{css_dir}{path}"))
+    };
+    let fingerprinted = manifest.get(&absolute)?;
+    let new_name = &fingerprinted[fingerprinted.rfind('/').map_or(0, |i| i + 1)..];
+    let dir = &path[..path.rfind('/').map_or(0, |i| i + 1)];
+    Some(format!("{dir}{new_name}{suffix}"))
+}
+
+/// Collapse `.` and `..` segments of a root-relative URL path.
+fn normalize_path(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            s => segments.push(s),
+        }
+    }
+    format!("/{}", segments.join("/"))
+}
+
+/// Tera function `asset_url(path="/static/app.css")`: returns the
+/// fingerprinted URL when fingerprinting is on, otherwise `path` unchanged.
+pub struct AssetUrl {
+    manifest: HashMap<String, String>,
+}
+
+impl AssetUrl {
+    pub fn new(manifest: HashMap<String, String>) -> Self {
+        Self { manifest }
+    }
+}
+
+impl tera::Function for AssetUrl {
+    fn call(&self, args: &HashMap<String, tera::Value>) -> tera::Result<tera::Value> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| tera::Error::msg("asset_url() requires a `path` string argument"))?;
+        let path = if path.starts_with('/') || path.contains("://") {
+            path.to_string()
+        } else {
+            format!("/{path}")
+        };
+        let url = resolve(&path, &self.manifest, "").unwrap_or(path);
+        Ok(tera::Value::String(url))
+    }
+
+    fn is_safe(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> HashMap<String, String> {
+        [
+            ("/static/app.css", "/static/app.1111aaaa.css"),
+            ("/static/img/hero.jpg", "/static/img/hero.2222bbbb.jpg"),
+            (
+                "/static/img/hero-2x.jpg",
+                "/static/img/hero-2x.3333cccc.jpg",
+            ),
+            (
+                "/static/fonts/inter.woff2",
+                "/static/fonts/inter.4444dddd.woff2",
+            ),
+        ]
+        .into_iter()
+        .map(|(a, b)| (a.to_string(), b.to_string()))
+        .collect()
+    }
+
+    #[test]
+    fn test_fingerprinted_name() {
+        assert_eq!(
+            fingerprinted_name("app.css", "abcd1234"),
+            "app.abcd1234.css"
+        );
+        assert_eq!(
+            fingerprinted_name("Logo.PNG", "abcd1234"),
+            "Logo.abcd1234.png"
+        );
+    }
+
+    #[test]
+    fn test_resolve_keeps_suffix_and_base_path() {
+        let m = manifest();
+        assert_eq!(
+            resolve("/static/app.css?v=1#x", &m, "").unwrap(),
+            "/static/app.1111aaaa.css?v=1#x"
+        );
+        assert_eq!(
+            resolve("/repo/static/app.css", &m, "/repo").unwrap(),
+            "/repo/static/app.1111aaaa.css"
+        );
+        assert!(resolve("/static/other.css", &m, "").is_none());
+    }
+
+    #[test]
+    fn test_rewrite_html_assets_attributes() {
+        let html = r#"<link rel="stylesheet" href="/static/app.css"><link rel='preload' as='font' href='/static/fonts/inter.woff2' crossorigin>
+<img src="/static/img/hero.jpg" srcset="/static/img/hero.jpg 1x, /static/img/hero-2x.jpg 2x" alt="/static/app.css">
+<p>Link to /static/app.css in text</p>"#;
+        let out = rewrite_html_assets(html, &manifest(), "");
+        assert!(out.contains(r#"href="/static/app.1111aaaa.css""#));
+        assert!(out.contains("href='/static/fonts/inter.4444dddd.woff2'"));
+        assert!(out.contains(r#"src="/static/img/hero.2222bbbb.jpg""#));
+        assert!(out.contains(
+            r#"srcset="/static/img/hero.2222bbbb.jpg 1x, /static/img/hero-2x.3333cccc.jpg 2x""#
+        ));
+        // Non-URL attributes and text content are untouched
+        assert!(out.contains(r#"alt="/static/app.css""#));
+        assert!(out.contains("Link to /static/app.css in text"));
+    }
+
+    #[test]
+    fn test_rewrite_html_assets_preload_imagesrcset() {
+        let html = r#"<link rel="preload" as="image" imagesrcset="/static/img/hero.jpg 800w, /static/img/hero-2x.jpg 1600w">"#;
+        let out = rewrite_html_assets(html, &manifest(), "");
+        assert_eq!(
+            out,
+            r#"<link rel="preload" as="image" imagesrcset="/static/img/hero.2222bbbb.jpg 800w, /static/img/hero-2x.3333cccc.jpg 1600w">"#
+        );
+    }
+
+    #[test]
+    fn test_rewrite_html_assets_empty_manifest_is_noop() {
+        let html = r#"<link href="/static/app.css">"#;
+        assert_eq!(rewrite_html_assets(html, &HashMap::new(), ""), html);
+    }
+
+    #[test]
+    fn test_rewrite_css_urls_relative_and_absolute() {
+        let css = r#"@font-face { src: url("../fonts/inter.woff2") format("woff2"); }
+.hero { background: url(/static/img/hero.jpg); }
+.x { background: url('img/missing.png'); }
+.y { background: url(data:image/png;base64,AAAA); }"#;
+        let out = rewrite_css_urls(css, "/static/css/site.css", &manifest());
+        assert!(out.contains(r#"url("../fonts/inter.4444dddd.woff2")"#));
+        assert!(out.contains("url(/static/img/hero.2222bbbb.jpg)"));
+        assert!(out.contains("url('img/missing.png')"));
+        assert!(out.contains("url(data:image/png;base64,AAAA)"));
+    }
+
+    #[test]
+    fn test_rewrite_css_urls_import() {
+        let css = "@import \"app.css\";\n@import url(app.css);\nbody{}";
+        let out = rewrite_css_urls(css, "/static/theme.css", &manifest());
+        assert_eq!(
+            out,
+            "@import \"app.1111aaaa.css\";\n@import url(app.1111aaaa.css);\nbody{}"
+        );
+    }
+
+    #[test]
+    fn test_asset_url_function() {
+        use tera::Function;
+        let f = AssetUrl::new(manifest());
+        let mut args = HashMap::new();
+        args.insert("path".to_string(), tera::Value::from("/static/app.css"));
+        assert_eq!(f.call(&args).unwrap(), "/static/app.1111aaaa.css");
+        args.insert("path".to_string(), tera::Value::from("static/unknown.js"));
+        assert_eq!(f.call(&args).unwrap(), "/static/unknown.js");
+        assert!(f.call(&HashMap::new()).is_err());
+    }
+}
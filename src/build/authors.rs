@@ -0,0 +1,301 @@
+//! Author profiles from `data/authors.yaml`, keyed by author id:
+//!
+//! ```yaml
+//! jane:
+//!   name: Jane Doe
+//!   bio: Writes about compilers.
+//!   avatar: /static/authors/jane.jpg
+//!   social:
+//!     github: https://github.com/jane
+//! ```
+//!
+//! Items credit authors with `authors: [jane]`. Every author gets a page
+//! listing their items at `/authors/{id}/` and an RSS feed of their posts.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    feed, insert_build_flags, insert_i18n_context, lang_prefix_for, write_index_page, ItemSummary,
+    PageContext, SiteContext, TranslationLink,
+};
+use crate::config::{FeedFormat, SiteConfig};
+use crate::content::ContentItem;
+use crate::error::{PageError, Result};
+
+/// One entry of `data/authors.yaml`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Author {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bio: Option<String>,
+    /// Path or URL of the author's picture.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<String>,
+    /// Profile links keyed by network, e.g. `github` or `mastodon`.
+    #[serde(default)]
+    pub social: BTreeMap<String, String>,
+}
+
+/// Author profiles keyed by id.
+pub type Registry = BTreeMap<String, Author>;
+
+/// An author as exposed to templates: the profile plus its page and feed URLs.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuthorContext {
+    pub id: String,
+    #[serde(flatten)]
+    pub profile: Author,
+    pub url: String,
+    pub feed_url: String,
+}
+
+/// Parse the `authors` data file (`data["authors"]`). Empty if there is none.
+pub fn load(data: &serde_json::Value, data_dir: &Path) -> Result<Registry> {
+    let Some(value) = data.get("authors") else {
+        return Ok(Registry::new());
+    };
+    serde_json::from_value(value.clone()).map_err(|e| PageError::Data {
+        path: crate::data::file_for_key(data_dir, None, "authors"),
+        message: format!("expected a mapping of author ids to profiles: {e}"),
+    })
+}
+
+/// Check that every author an item credits is in the registry, reporting all
+/// unknown ids together.
+pub fn check(items: &[&ContentItem], registry: &Registry, root: &Path) -> Result<()> {
+    let mut unknown: Vec<String> = items
+        .iter()
+        .flat_map(|item| {
+            item.frontmatter
+                .authors
+                .iter()
+                .filter(|id| !registry.contains_key(*id))
+                .map(|id| {
+                    let file = item
+                        .source_path
+                        .strip_prefix(root)
+                        .unwrap_or(&item.source_path);
+                    format!("{}: unknown author `{id}`", file.display())
+                })
+        })
+        .collect();
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    unknown.sort();
+    Err(PageError::Build(format!(
+        "{} unknown author(s), add them to data/authors.yaml:\n  {}",
+        unknown.len(),
+        unknown.join("\n  ")
+    )))
+}
+
+fn author_url(id: &str, lang: &str, default_lang: &str) -> String {
+    format!("{}/authors/{id}/", lang_prefix_for(lang, default_lang))
+}
+
+fn context(id: &str, author: &Author, lang: &str, default_lang: &str) -> AuthorContext {
+    let url = author_url(id, lang, default_lang);
+    AuthorContext {
+        id: id.to_string(),
+        profile: author.clone(),
+        feed_url: format!("{url}feed.xml"),
+        url,
+    }
+}
+
+/// Resolve an item's `authors` to their profiles, linking pages in the item's
+/// language. Unknown ids are skipped; [`check`] reports them.
+pub fn resolve(registry: &Registry, item: &ContentItem, default_lang: &str) -> Vec<AuthorContext> {
+    item.frontmatter
+        .authors
+        .iter()
+        .filter_map(|id| {
+            registry
+                .get(id)
+                .map(|author| context(id, author, &item.lang, default_lang))
+        })
+        .collect()
+}
+
+/// Render a page and an RSS feed for every author with items, per language.
+/// Returns the URLs of the pages, for the sitemap.
+pub(super) fn render(
+    tera: &tera::Tera,
+    config: &SiteConfig,
+    registry: &Registry,
+    items: &[&ContentItem],
+    data: &serde_json::Value,
+    output_dir: &Path,
+) -> Result<Vec<String>> {
+    let default_lang = &config.site.language;
+    let tz = config.timezone();
+    let mut urls = Vec::new();
+    for lang in &config.all_languages() {
+        let site_ctx = SiteContext::for_lang(config, lang);
+        for (id, author) in registry {
+            let mut author_items: Vec<&ContentItem> = items
+                .iter()
+                .filter(|item| item.lang == *lang && item.frontmatter.authors.contains(id))
+                .copied()
+                .collect();
+            if author_items.is_empty() {
+                continue;
+            }
+            author_items.sort_by_key(|item| std::cmp::Reverse(item.frontmatter.date_key(tz)));
+            let author_ctx = context(id, author, lang, default_lang);
+
+            let summaries: Vec<ItemSummary> = author_items
+                .iter()
+                .map(|item| ItemSummary::from_item(item))
+                .collect();
+            let mut ctx = tera::Context::new();
+            ctx.insert("site", &site_ctx);
+            ctx.insert("data", data);
+            ctx.insert("lang", lang);
+            insert_i18n_context(&mut ctx, lang, default_lang, data);
+            insert_build_flags(&mut ctx, config);
+            ctx.insert("translations", &Vec::<TranslationLink>::new());
+            ctx.insert("author", &author_ctx);
+            ctx.insert("items", &summaries);
+            let mut page = PageContext::listing(
+                author.name.clone(),
+                format!("authors/{id}"),
+                author_ctx.url.clone(),
+            );
+            page.description = author.bio.clone();
+            ctx.insert("page", &page);
+            let html = tera
+                .render("author.html", &ctx)
+                .map_err(|e| PageError::Build(format!("rendering author '{id}': {e}")))?;
+            write_index_page(output_dir, &author_ctx.url, html)?;
+            urls.push(author_ctx.url.clone());
+
+            let feed_items: Vec<&ContentItem> = author_items
+                .iter()
+                .filter(|item| {
+                    config
+                        .collections
+                        .iter()
+                        .any(|c| c.name == item.collection && c.has_rss)
+                })
+                .copied()
+                .collect();
+            let title = format!("{} — {}", config.title_for_lang(lang), author.name);
+            let link = format!(
+                "{}{}",
+                config.site.base_url.trim_end_matches('/'),
+                author_ctx.url
+            );
+            let channel = feed::Channel {
+                title: &title,
+                link: &link,
+                dir_url: &link,
+                description: config.description_for_lang(lang),
+                language: lang,
+            };
+            let rss = feed::generate(FeedFormat::Rss, config, &channel, &feed_items, registry)?;
+            fs::write(
+                output_dir.join(author_ctx.feed_url.trim_start_matches('/')),
+                rss,
+            )?;
+        }
+    }
+    Ok(urls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::Frontmatter;
+    use std::path::PathBuf;
+
+    fn item(path: &str, lang: &str, authors: &[&str]) -> ContentItem {
+        ContentItem {
+            frontmatter: Frontmatter {
+                title: "T".into(),
+                authors: authors.iter().map(|a| a.to_string()).collect(),
+                ..Default::default()
+            },
+            raw_body: String::new(),
+            markdown_body: String::new(),
+            html_body: String::new(),
+            source_path: PathBuf::from(format!("/site/{path}")),
+            slug: String::new(),
+            collection: "posts".into(),
+            url: String::new(),
+            lang: lang.into(),
+            excerpt: String::new(),
+            toc: Vec::new(),
+            word_count: 0,
+            reading_time: 0,
+            excerpt_html: String::new(),
+        }
+    }
+
+    fn registry() -> Registry {
+        let data = serde_json::json!({
+            "authors": {
+                "jane": {
+                    "name": "Jane Doe",
+                    "avatar": "/static/jane.jpg",
+                    "social": { "github": "https://github.com/jane" }
+                },
+                "sam": { "name": "Sam" }
+            }
+        });
+        load(&data, Path::new("data")).unwrap()
+    }
+
+    #[test]
+    fn test_load_registry() {
+        let registry = registry();
+        assert_eq!(registry["jane"].social["github"], "https://github.com/jane");
+        assert!(registry["sam"].bio.is_none());
+        assert!(load(&serde_json::json!({}), Path::new("data"))
+            .unwrap()
+            .is_empty());
+
+        let data = serde_json::json!({ "authors": { "jane": { "nmae": "Jane" } } });
+        let err = load(&data, Path::new("data")).unwrap_err();
+        assert!(err.to_string().contains("authors.yaml"));
+    }
+
+    #[test]
+    fn test_check_reports_every_unknown_author() {
+        let registry = registry();
+        let a = item("content/posts/a.md", "en", &["jane", "bob"]);
+        let b = item("content/posts/b.md", "en", &["alice"]);
+        assert!(check(&[&a], &Registry::new(), Path::new("/other")).is_err());
+        let err = check(&[&a, &b], &registry, Path::new("/site"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("2 unknown author(s)"));
+        assert!(err.contains("content/posts/a.md: unknown author `bob`"));
+        assert!(err.contains("content/posts/b.md: unknown author `alice`"));
+        assert!(check(
+            &[&item("x.md", "en", &["sam"])],
+            &registry,
+            Path::new("/site")
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_resolve_links_in_item_language() {
+        let registry = registry();
+        let resolved = resolve(&registry, &item("a.md", "es", &["sam", "jane"]), "en");
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(resolved[0].profile.name, "Sam");
+        assert_eq!(resolved[1].url, "/es/authors/jane/");
+        assert_eq!(resolved[1].feed_url, "/es/authors/jane/feed.xml");
+        let json = serde_json::to_value(&resolved[1]).unwrap();
+        assert_eq!(json["name"], "Jane Doe");
+        assert_eq!(json["id"], "jane");
+    }
+}
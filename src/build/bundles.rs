@@ -0,0 +1,267 @@
+//! Page bundles: a page written as `index.md` in its own directory, next to
+//! the files it uses:
+//!
+//! ```text
+//! content/posts/my-post/
+//!   index.md
+//!   cover.jpg
+//!   diagram.png
+//! ```
+//!
+//! The directory names the page, as a plain `my-post.md` would. Relative
+//! references in the page (`![](diagram.png)`, `image: cover.jpg`) resolve to
+//! the bundle's files, which are copied under the page's URL and go through
+//! the image pipeline like `static/` images. A reference to a file the bundle
+//! does not have fails the build.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use walkdir::WalkDir;
+
+use super::cache;
+
+static HTML_REF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\b(src|href)="([^"]*)""#).expect("valid regex"));
+static MARKDOWN_REF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\]\(\s*<?([^)\s>]+)").expect("valid regex"));
+
+/// The path a bundle's page stands for, relative to the collection directory:
+/// `my-post/index.md` → `my-post.md`, `my-post/index.es.md` → `my-post.es.md`.
+/// `None` if `rel` is not the page of a bundle.
+pub fn page_path(rel: &Path, configured_langs: &HashSet<&str>) -> Option<PathBuf> {
+    let stem = rel.file_stem()?.to_str()?;
+    let lang_suffix = match stem.split_once('.') {
+        Some(("index", lang)) if configured_langs.contains(lang) => format!(".{lang}"),
+        None if stem == "index" => String::new(),
+        _ => return None,
+    };
+    let dir = rel.parent()?;
+    let name = dir.file_name()?.to_str()?;
+    Some(
+        dir.parent()
+            .unwrap_or(Path::new(""))
+            .join(format!("{name}{lang_suffix}.md")),
+    )
+}
+
+fn is_bundle_dir(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|entries| {
+        entries.filter_map(|e| e.ok()).any(|e| {
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("index.") && name.ends_with(".md")
+        })
+    })
+}
+
+/// Files of the bundle in `dir`, relative to it with forward slashes: all but
+/// markdown files, hidden files and nested bundles.
+pub fn resources(dir: &Path) -> Vec<String> {
+    let mut files: Vec<String> = WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0
+                || !(e.file_name().to_string_lossy().starts_with('.')
+                    || e.file_type().is_dir() && is_bundle_dir(e.path()))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_none_or(|ext| ext != "md"))
+        .map(|e| {
+            e.path()
+                .strip_prefix(dir)
+                .unwrap_or(e.path())
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Fingerprint of a bundle's files: names, sizes and modification times.
+pub fn fingerprint(dir: &Path, resources: &[String]) -> String {
+    let entries: Vec<(&str, u64, u128)> = resources
+        .iter()
+        .map(|rel| {
+            let meta = dir.join(rel).metadata().ok();
+            let len = meta.as_ref().map_or(0, |m| m.len());
+            let mtime = meta
+                .and_then(|m| m.modified().ok())
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_nanos());
+            (rel.as_str(), len, mtime)
+        })
+        .collect();
+    cache::hash_json(&entries)
+}
+
+/// The bundle file a reference names, or `None` if it isn't a relative file
+/// reference: URLs, root-relative paths, anchors and links to other pages
+/// (no extension, `.html` or `.md`) are left alone.
+fn local_file(reference: &str) -> Option<(&str, &str)> {
+    if reference.is_empty()
+        || reference.starts_with(['/', '#', '?'])
+        || reference
+            .split('/')
+            .next()
+            .is_some_and(|first| first.contains(':'))
+    {
+        return None;
+    }
+    let end = reference.find(['?', '#']).unwrap_or(reference.len());
+    let (path, suffix) = reference.split_at(end);
+    let path = path.trim_start_matches("./");
+    let ext = path.rsplit('/').next()?.rsplit_once('.')?.1;
+    if ext.is_empty() || ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("md") {
+        return None;
+    }
+    Some((path, suffix))
+}
+
+/// Resolve a reference against a bundle whose files are served under
+/// `base_url`. `Ok(None)` if the reference isn't to a bundle file, `Err` with
+/// the reference if it names a file the bundle does not have.
+pub fn resolve<'a>(
+    reference: &'a str,
+    base_url: &str,
+    resources: &[String],
+) -> std::result::Result<Option<String>, &'a str> {
+    match local_file(reference) {
+        Some((path, suffix)) if resources.iter().any(|r| r == path) => Ok(Some(format!(
+            "{}/{path}{suffix}",
+            base_url.trim_end_matches('/')
+        ))),
+        Some(_) => Err(reference),
+        None => Ok(None),
+    }
+}
+
+/// Point `src` and `href` attributes at the bundle's files. Returns the HTML
+/// and the references to files the bundle does not have.
+pub fn rewrite_html(html: &str, base_url: &str, resources: &[String]) -> (String, Vec<String>) {
+    let mut missing = Vec::new();
+    let html = HTML_REF.replace_all(html, |caps: &regex::Captures| {
+        match resolve(&caps[2], base_url, resources) {
+            Ok(Some(url)) => format!("{}=\"{url}\"", &caps[1]),
+            Ok(None) => caps[0].to_string(),
+            Err(reference) => {
+                missing.push(reference.to_string());
+                caps[0].to_string()
+            }
+        }
+    });
+    (html.into_owned(), missing)
+}
+
+/// Point markdown link and image targets at the bundle's files, for the
+/// `.md` copy of the page. Unknown references are left as written.
+pub fn rewrite_markdown(markdown: &str, base_url: &str, resources: &[String]) -> String {
+    MARKDOWN_REF
+        .replace_all(markdown, |caps: &regex::Captures| {
+            match resolve(&caps[1], base_url, resources) {
+                Ok(Some(url)) => caps[0].replacen(&caps[1], &url, 1),
+                _ => caps[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn resources_of(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_page_path() {
+        let langs: HashSet<&str> = ["en", "es"].into();
+        assert_eq!(
+            page_path(Path::new("2025-01-15-my-post/index.md"), &langs),
+            Some(PathBuf::from("2025-01-15-my-post.md"))
+        );
+        assert_eq!(
+            page_path(Path::new("guides/setup/index.es.md"), &langs),
+            Some(PathBuf::from("guides/setup.es.md"))
+        );
+        assert_eq!(page_path(Path::new("index.md"), &langs), None);
+        assert_eq!(page_path(Path::new("a/about.md"), &langs), None);
+        assert_eq!(page_path(Path::new("a/index.min.md"), &langs), None);
+    }
+
+    #[test]
+    fn test_resources_skip_markdown_and_nested_bundles() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path();
+        for file in [
+            "index.md",
+            "index.es.md",
+            "cover.jpg",
+            "files/report.pdf",
+            ".DS_Store",
+            "child/index.md",
+            "child/photo.png",
+        ] {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "x").unwrap();
+        }
+        assert_eq!(resources(dir), vec!["cover.jpg", "files/report.pdf"]);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let files = resources_of(&["diagram.png", "files/report.pdf"]);
+        let resolve = |r| resolve(r, "/posts/my-post", &files);
+        assert_eq!(
+            resolve("diagram.png"),
+            Ok(Some("/posts/my-post/diagram.png".into()))
+        );
+        assert_eq!(
+            resolve("./files/report.pdf#page=2"),
+            Ok(Some("/posts/my-post/files/report.pdf#page=2".into()))
+        );
+        assert_eq!(resolve("missing.png"), Err("missing.png"));
+        for untouched in [
+            "/static/a.png",
+            "https://x.dev/a.png",
+            "mailto:a@b.c",
+            "#top",
+            "other-post",
+            "../other.html",
+            "",
+        ] {
+            assert_eq!(resolve(untouched), Ok(None), "{untouched}");
+        }
+    }
+
+    #[test]
+    fn test_rewrite_html_and_markdown() {
+        let files = resources_of(&["diagram.png"]);
+        let (html, missing) = rewrite_html(
+            r#"<img src="diagram.png" alt="d"><a href="gone.pdf">x</a><a href="/about">y</a>"#,
+            "/posts/p",
+            &files,
+        );
+        assert_eq!(
+            html,
+            r#"<img src="/posts/p/diagram.png" alt="d"><a href="gone.pdf">x</a><a href="/about">y</a>"#
+        );
+        assert_eq!(missing, vec!["gone.pdf"]);
+
+        let md = rewrite_markdown(
+            "![Diagram](diagram.png \"Flow\") and [a](other-post)",
+            "/posts/p",
+            &files,
+        );
+        assert_eq!(
+            md,
+            "![Diagram](/posts/p/diagram.png \"Flow\") and [a](other-post)"
+        );
+    }
+}
@@ -0,0 +1,824 @@
+//! Persistent build cache for incremental rebuilds.
+//!
+//! Every output directory gets its own cache under `.seite/cache/build/`. The
+//! cache records, for each rendered page, the inputs that produced it (source
+//! hash, template closure, data keys, shortcodes and the serialized render
+//! context) plus a fingerprint for each derived output step (feeds, sitemap,
+//! search index, discovery files).
+//!
+//! Every build writes into a staging directory that replaces the output
+//! directory only when the build finishes, so a server keeps serving the
+//! previous output while it runs, and a failed build leaves it untouched.
+//! Pages and step outputs whose fingerprints are unchanged are copied from the
+//! previous output instead of being regenerated; everything else is rendered
+//! fresh. Files that nothing claims (e.g. outputs of deleted sources) are gone
+//! with the previous output once the staging directory is swapped in.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::build::markdown::TocEntry;
+use crate::error::Result;
+
+/// Bump when the on-disk cache layout or fingerprint inputs change.
+const CACHE_FORMAT: u32 = 1;
+
+/// Streaming FNV-1a hasher used for all build fingerprints.
+pub(crate) struct Fingerprint(u64);
+
+impl Fingerprint {
+    pub fn new() -> Self {
+        Self(14695981039346656037)
+    }
+
+    pub fn bytes(&mut self, data: &[u8]) -> &mut Self {
+        for &byte in data {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(1099511628211);
+        }
+        self
+    }
+
+    /// Feed a string followed by a separator, so `("ab", "c")` and `("a", "bc")`
+    /// produce different fingerprints.
+    pub fn str(&mut self, s: &str) -> &mut Self {
+        self.bytes(s.as_bytes()).bytes(&[0xff])
+    }
+
+    pub fn finish(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+/// Hash a byte slice into a 16-character hex fingerprint.
+pub(crate) fn hash_bytes(data: &[u8]) -> String {
+    Fingerprint::new().bytes(data).finish()
+}
+
+/// Hash any serializable value via its JSON representation.
+///
+/// Goes through `serde_json::Value` so map keys are sorted and `HashMap`
+/// iteration order does not leak into the fingerprint.
+pub(crate) fn hash_json<T: Serialize + ?Sized>(value: &T) -> String {
+    let json = serde_json::to_value(value).unwrap_or_default();
+    hash_bytes(json.to_string().as_bytes())
+}
+
+/// Rendered markdown for a single source file, reused when the source and the
+/// shortcodes it expands are unchanged.
+#[derive(Serialize, Deserialize)]
+pub struct Fragment {
+    pub excerpt: String,
+    pub html_body: String,
+    pub markdown_body: String,
+    pub excerpt_html: String,
+    pub toc: Vec<TocEntry>,
+}
+
+/// Recorded inputs of a rendered content page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageRecord {
+    /// Source markdown file, relative to the project root.
+    pub source: String,
+    pub source_hash: String,
+    /// Templates in the render closure (the page template plus everything it
+    /// extends, includes or imports).
+    pub templates: Vec<String>,
+    /// Top-level `data` keys the templates read (`*` when they use all of it).
+    pub data: Vec<String>,
+    /// Shortcodes expanded in the page body.
+    pub shortcodes: Vec<String>,
+    /// Combined fingerprint of all inputs, including the render context.
+    pub fingerprint: String,
+    /// Output files produced for this page, relative to the output directory.
+    pub outputs: Vec<String>,
+}
+
+/// Fingerprint and outputs of a derived output step (feed, sitemap, ...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepRecord {
+    pub fingerprint: String,
+    pub outputs: Vec<String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct CacheIndex {
+    format: u32,
+    /// Hash of the site config, binary version and other build-wide inputs.
+    /// A mismatch invalidates the whole cache.
+    config_hash: String,
+    /// Page records keyed by output HTML path.
+    pages: HashMap<String, PageRecord>,
+    steps: HashMap<String, StepRecord>,
+}
+
+/// Incremental build state for a single output directory.
+pub struct BuildCache {
+    dir: PathBuf,
+    output: PathBuf,
+    /// Where this build writes; replaces `output` in [`BuildCache::finish`].
+    staging: PathBuf,
+    config_hash: String,
+    incremental: bool,
+    /// Records of the current output, if pages can be reused from it.
+    previous: CacheIndex,
+    next: CacheIndex,
+    used_fragments: HashSet<String>,
+}
+
+impl BuildCache {
+    /// Prepare a build of `output`.
+    ///
+    /// The build writes to an empty staging directory ([`BuildCache::staging`]);
+    /// `output` is left as it is until [`BuildCache::finish`]. With
+    /// `incremental` set and a cache whose `config_hash` matches, unchanged
+    /// pages can be restored from the current output. Otherwise every page is
+    /// rendered.
+    pub fn begin(
+        project_root: &Path,
+        output: &Path,
+        config_hash: String,
+        incremental: bool,
+    ) -> Result<Self> {
+        let dir = cache_dir(project_root, output);
+        // Left behind by a failed or interrupted build
+        let staging = dir.join("staging");
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        fs::create_dir_all(&staging)?;
+
+        let previous: CacheIndex = fs::read_to_string(dir.join("index.json"))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .filter(|c: &CacheIndex| c.format == CACHE_FORMAT && c.config_hash == config_hash)
+            .filter(|_| incremental && output.exists())
+            .unwrap_or_default();
+
+        Ok(Self {
+            dir,
+            output: output.to_path_buf(),
+            staging,
+            config_hash,
+            incremental,
+            previous,
+            next: CacheIndex::default(),
+            used_fragments: HashSet::new(),
+        })
+    }
+
+    /// The directory this build writes to.
+    pub fn staging(&self) -> &Path {
+        &self.staging
+    }
+
+    /// Load the cached rendering of a source file.
+    pub fn fragment(&self, key: &str) -> Option<Fragment> {
+        if !self.incremental {
+            return None;
+        }
+        let raw = fs::read_to_string(self.fragment_path(key)).ok()?;
+        serde_json::from_str(&raw).ok()
+    }
+
+    /// Store the rendering of a source file for the next build.
+    pub fn store_fragment(&self, key: &str, fragment: &Fragment) -> Result<()> {
+        let path = self.fragment_path(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(fragment).unwrap_or_default())?;
+        Ok(())
+    }
+
+    /// Mark fragments as referenced by this build so they survive pruning.
+    pub fn keep_fragments<I: IntoIterator<Item = String>>(&mut self, keys: I) {
+        self.used_fragments.extend(keys);
+    }
+
+    /// Restore a page from the previous output if its fingerprint is unchanged.
+    ///
+    /// Returns `true` when every output of the page was copied into staging.
+    /// Safe to call from multiple threads.
+    pub fn restore_page(&self, html_rel: &str, fingerprint: &str) -> bool {
+        match self.previous.pages.get(html_rel) {
+            Some(record) if record.fingerprint == fingerprint => {
+                self.restore_outputs(&record.outputs)
+            }
+            _ => false,
+        }
+    }
+
+    /// Record the inputs of a page rendered (or restored) in this build.
+    pub fn record_page(&mut self, html_rel: String, record: PageRecord) {
+        self.next.pages.insert(html_rel, record);
+    }
+
+    /// Restore the outputs of a derived step if its fingerprint is unchanged,
+    /// in which case the step is recorded for the next build as-is.
+    pub fn restore_step(&mut self, name: &str, fingerprint: &str) -> bool {
+        match self.previous.steps.get(name) {
+            Some(record)
+                if record.fingerprint == fingerprint && self.restore_outputs(&record.outputs) =>
+            {
+                self.next.steps.insert(name.to_string(), record.clone());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Record the outputs written by a derived step.
+    pub fn record_step(&mut self, name: &str, fingerprint: String, outputs: Vec<String>) {
+        self.next.steps.insert(
+            name.to_string(),
+            StepRecord {
+                fingerprint,
+                outputs,
+            },
+        );
+    }
+
+    /// Swap the staging directory in as the output and persist the cache for
+    /// the next build.
+    pub fn finish(self) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        // Prune fragments no source referenced in this build
+        let fragments_dir = self.dir.join("fragments");
+        if fragments_dir.exists() {
+            for entry in fs::read_dir(&fragments_dir)?.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                let key = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+                if !self.used_fragments.contains(key) {
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+
+        // The index describes the output, so it goes while the output is
+        // replaced: an interrupted swap cannot leave a cache for the wrong files
+        let index_path = self.dir.join("index.json");
+        if index_path.exists() {
+            fs::remove_file(&index_path)?;
+        }
+        swap_in(&self.staging, &self.output, &self.dir.join("old-output"))?;
+
+        let index = CacheIndex {
+            format: CACHE_FORMAT,
+            config_hash: self.config_hash,
+            pages: self.next.pages,
+            steps: self.next.steps,
+        };
+        fs::write(
+            index_path,
+            serde_json::to_string(&index).unwrap_or_default(),
+        )?;
+        Ok(())
+    }
+
+    fn fragment_path(&self, key: &str) -> PathBuf {
+        self.dir.join("fragments").join(format!("{key}.json"))
+    }
+
+    fn restore_outputs(&self, outputs: &[String]) -> bool {
+        if !outputs.iter().all(|rel| self.output.join(rel).is_file()) {
+            return false;
+        }
+        outputs.iter().all(|rel| {
+            let dest = self.staging.join(rel);
+            dest.parent()
+                .is_none_or(|parent| fs::create_dir_all(parent).is_ok())
+                && fs::copy(self.output.join(rel), &dest).is_ok()
+        })
+    }
+}
+
+/// Replace `output` with `staging`. The old output is renamed to `old` first
+/// (or deleted when it can't be renamed there), so the output is missing only
+/// between two renames. Falls back to copying across file systems.
+fn swap_in(staging: &Path, output: &Path, old: &Path) -> Result<()> {
+    if old.exists() {
+        fs::remove_dir_all(old)?;
+    }
+    if output.exists() && fs::rename(output, old).is_err() {
+        fs::remove_dir_all(output)?;
+    }
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(staging, output).is_err() {
+        for entry in WalkDir::new(staging).into_iter().filter_map(|e| e.ok()) {
+            let dest = output.join(entry.path().strip_prefix(staging).unwrap_or(entry.path()));
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(&dest)?;
+            } else {
+                fs::copy(entry.path(), &dest)?;
+            }
+        }
+        fs::remove_dir_all(staging)?;
+    }
+    if old.exists() {
+        fs::remove_dir_all(old)?;
+    }
+    Ok(())
+}
+
+/// Cache directory for an output directory: `.seite/cache/build/<name>/`.
+fn cache_dir(project_root: &Path, output: &Path) -> PathBuf {
+    crate::meta::meta_dir(project_root)
+        .join("cache")
+        .join("build")
+        .join(output_name(project_root, output))
+}
+
+/// File-name-safe name of an output directory: its path relative to the
+/// project root (`dist`, `dist-subdomains-docs`), or a hash outside it.
+pub(crate) fn output_name(project_root: &Path, output: &Path) -> String {
+    match output.strip_prefix(project_root) {
+        Ok(rel) if !rel.as_os_str().is_empty() => rel.to_string_lossy().replace(['/', '\\'], "-"),
+        _ => hash_bytes(output.to_string_lossy().as_bytes()),
+    }
+}
+
+/// Which parts of the global `data` object a template reads.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataRefs {
+    /// The template passes `data` around as a whole (or indexes it dynamically).
+    All,
+    Keys(BTreeSet<String>),
+}
+
+impl DataRefs {
+    fn merge(&mut self, other: &DataRefs) {
+        match (&mut *self, other) {
+            (DataRefs::All, _) => {}
+            (_, DataRefs::All) => *self = DataRefs::All,
+            (DataRefs::Keys(a), DataRefs::Keys(b)) => a.extend(b.iter().cloned()),
+        }
+    }
+
+    /// Names recorded in a [`PageRecord`].
+    pub fn names(&self) -> Vec<String> {
+        match self {
+            DataRefs::All => vec!["*".to_string()],
+            DataRefs::Keys(keys) => keys.iter().cloned().collect(),
+        }
+    }
+
+    /// Fingerprint the referenced parts of `data`.
+    pub fn fingerprint(&self, data: &serde_json::Value) -> String {
+        match self {
+            DataRefs::All => hash_json(data),
+            DataRefs::Keys(keys) => {
+                let mut fp = Fingerprint::new();
+                for key in keys {
+                    fp.str(key);
+                    fp.str(&data.get(key).map(hash_json).unwrap_or_default());
+                }
+                fp.finish()
+            }
+        }
+    }
+}
+
+/// Resolved dependency information for a template.
+#[derive(Debug, Clone)]
+pub struct TemplateClosure {
+    pub names: Vec<String>,
+    pub hash: String,
+    pub data: DataRefs,
+}
+
+/// Dependency graph of the templates registered in a Tera instance.
+pub struct TemplateGraph {
+    closures: HashMap<String, TemplateClosure>,
+}
+
+impl TemplateGraph {
+    /// Scan every registered template for `extends`, `include` and `import`
+    /// references and `data.<key>` lookups.
+    ///
+    /// User templates are read from the first of `template_dirs` that has
+    /// them; templates not found there are embedded defaults, whose content is
+    /// covered by the binary version.
+    pub fn new(tera: &tera::Tera, template_dirs: &[&Path]) -> Self {
+        let mut nodes: HashMap<String, (String, Vec<String>, DataRefs)> = HashMap::new();
+        for name in tera.get_template_names() {
+            let source = template_dirs
+                .iter()
+                .find_map(|dir| fs::read_to_string(dir.join(name)).ok());
+            let (hash, deps, data) = match source {
+                Some(src) => (
+                    hash_bytes(src.as_bytes()),
+                    template_dependencies(&src),
+                    template_data_refs(&src),
+                ),
+                None => {
+                    let src = crate::templates::get_default_template(name).unwrap_or("");
+                    (
+                        format!("builtin:{name}"),
+                        template_dependencies(src),
+                        template_data_refs(src),
+                    )
+                }
+            };
+            nodes.insert(name.to_string(), (hash, deps, data));
+        }
+
+        let closures = nodes
+            .keys()
+            .map(|name| (name.clone(), resolve_closure(name, &nodes)))
+            .collect();
+        Self { closures }
+    }
+
+    pub fn closure(&self, name: &str) -> Option<&TemplateClosure> {
+        self.closures.get(name)
+    }
+}
+
+fn resolve_closure(
+    root: &str,
+    nodes: &HashMap<String, (String, Vec<String>, DataRefs)>,
+) -> TemplateClosure {
+    let mut seen: BTreeSet<String> = BTreeSet::new();
+    let mut stack = vec![root.to_string()];
+    let mut data = DataRefs::Keys(BTreeSet::new());
+    while let Some(name) = stack.pop() {
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+        if let Some((_, deps, refs)) = nodes.get(&name) {
+            data.merge(refs);
+            stack.extend(deps.iter().cloned());
+        }
+    }
+    let mut fp = Fingerprint::new();
+    for name in &seen {
+        fp.str(name);
+        fp.str(nodes.get(name).map(|n| n.0.as_str()).unwrap_or("missing"));
+    }
+    TemplateClosure {
+        names: seen.into_iter().collect(),
+        hash: fp.finish(),
+        data,
+    }
+}
+
+/// Iterate over the inner text of Tera tags (`{% ... %}` and `{{ ... }}`).
+fn tera_tags(source: &str) -> Vec<(&str, &str)> {
+    let mut tags = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start..];
+        let close = if after.starts_with("{%") {
+            "%}"
+        } else if after.starts_with("{{") {
+            "}}"
+        } else {
+            rest = &rest[start + 1..];
+            continue;
+        };
+        match after[2..].find(close) {
+            Some(end) => {
+                tags.push((&after[..2], &after[2..2 + end]));
+                rest = &after[2 + end + 2..];
+            }
+            None => break,
+        }
+    }
+    tags
+}
+
+/// Template names referenced by `extends`, `include` and `import` tags.
+pub(crate) fn template_dependencies(source: &str) -> Vec<String> {
+    let mut deps = Vec::new();
+    for (open, inner) in tera_tags(source) {
+        if open != "{%" {
+            continue;
+        }
+        let inner = inner.trim_matches(|c: char| c == '-' || c.is_whitespace());
+        let keyword = inner.split_whitespace().next().unwrap_or("");
+        if !matches!(keyword, "extends" | "include" | "import") {
+            continue;
+        }
+        // Collect every quoted string: `include ["a.html", "b.html"] ignore missing`
+        let mut chars = inner.char_indices();
+        while let Some((i, c)) = chars.next() {
+            if c == '"' || c == '\'' {
+                if let Some(len) = inner[i + 1..].find(c) {
+                    deps.push(inner[i + 1..i + 1 + len].to_string());
+                    for _ in 0..=len {
+                        chars.next();
+                    }
+                }
+            }
+        }
+    }
+    deps
+}
+
+/// Top-level `data` keys read by a template.
+pub(crate) fn template_data_refs(source: &str) -> DataRefs {
+    let mut keys = BTreeSet::new();
+    for (_, inner) in tera_tags(source) {
+        let bytes = inner.as_bytes();
+        let mut search = 0;
+        while let Some(pos) = inner[search..].find("data") {
+            let at = search + pos;
+            search = at + 4;
+            let before_ok = at == 0 || {
+                let b = bytes[at - 1];
+                !(b.is_ascii_alphanumeric() || b == b'_' || b == b'.')
+            };
+            let after = &inner[at + 4..];
+            if !before_ok
+                || after
+                    .bytes()
+                    .next()
+                    .is_some_and(|b| b.is_ascii_alphanumeric() || b == b'_')
+            {
+                continue;
+            }
+            match after.strip_prefix('.') {
+                Some(tail) => {
+                    let key: String = tail
+                        .chars()
+                        .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+                        .collect();
+                    if key.is_empty() {
+                        return DataRefs::All;
+                    }
+                    keys.insert(key);
+                }
+                None => return DataRefs::All,
+            }
+        }
+    }
+    DataRefs::Keys(keys)
+}
+
+/// Fingerprint of the user shortcode templates (HTML and markdown renderings)
+/// named in `names`. Built-in shortcodes are covered by the binary version.
+pub(crate) fn shortcode_fingerprint(shortcodes_dir: &Path, names: &[String]) -> String {
+    let mut fp = Fingerprint::new();
+    for name in names {
+        fp.str(name);
+        for ext in ["html", "md"] {
+            match fs::read(shortcodes_dir.join(format!("{name}.{ext}"))) {
+                Ok(bytes) => fp.bytes(&bytes),
+                Err(_) => fp.str("builtin"),
+            };
+        }
+    }
+    fp.finish()
+}
+
+/// Fingerprint the files under `dir` whose extension is in `extensions`,
+/// using path, size and modification time (cheap enough to run every build).
+pub(crate) fn dir_fingerprint(dir: &Path, extensions: &[&str]) -> String {
+    let mut entries: Vec<(String, u64, u128)> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .and_then(|x| x.to_str())
+                .is_some_and(|x| extensions.contains(&x.to_lowercase().as_str()))
+        })
+        .map(|e| {
+            let meta = e.metadata().ok();
+            let len = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+            let mtime = meta
+                .and_then(|m| m.modified().ok())
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_nanos())
+                .unwrap_or(0);
+            (e.path().to_string_lossy().into_owned(), len, mtime)
+        })
+        .collect();
+    entries.sort();
+    hash_json(&entries)
+}
+
+/// Relative output path with forward slashes.
+pub(crate) fn rel_output(output: &Path, path: &Path) -> String {
+    path.strip_prefix(output)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_separates_parts() {
+        let a = Fingerprint::new().str("ab").str("c").finish();
+        let b = Fingerprint::new().str("a").str("bc").finish();
+        assert_ne!(a, b);
+        assert_eq!(a.len(), 16);
+    }
+
+    #[test]
+    fn test_template_dependencies() {
+        let src = r#"{% extends "base.html" %}
+{% import 'macros.html' as m %}
+{%- include "partials/nav.html" -%}
+{% include ["a.html", "b.html"] ignore missing %}
+{{ "not-a-dep.html" }}"#;
+        assert_eq!(
+            template_dependencies(src),
+            vec![
+                "base.html",
+                "macros.html",
+                "partials/nav.html",
+                "a.html",
+                "b.html"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_template_data_refs_keys() {
+        let src = r#"<div data-theme="x">{{ data.nav.title }}</div>
+{% for a in data.authors %}{{ a }}{% endfor %}{{ page.data }}"#;
+        let refs = template_data_refs(src);
+        assert_eq!(
+            refs,
+            DataRefs::Keys(["authors".to_string(), "nav".to_string()].into())
+        );
+    }
+
+    #[test]
+    fn test_template_data_refs_whole_object() {
+        assert_eq!(
+            template_data_refs("{{ data | json_encode }}"),
+            DataRefs::All
+        );
+        assert_eq!(template_data_refs(r#"{{ data["x"] }}"#), DataRefs::All);
+    }
+
+    #[test]
+    fn test_data_refs_fingerprint_ignores_unrelated_keys() {
+        let refs = DataRefs::Keys(["nav".to_string()].into());
+        let a = serde_json::json!({"nav": [1], "other": 1});
+        let b = serde_json::json!({"nav": [1], "other": 2});
+        let c = serde_json::json!({"nav": [2], "other": 1});
+        assert_eq!(refs.fingerprint(&a), refs.fingerprint(&b));
+        assert_ne!(refs.fingerprint(&a), refs.fingerprint(&c));
+    }
+
+    #[test]
+    fn test_template_closure_follows_includes() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(
+            tmp.path().join("post.html"),
+            r#"{% extends "base.html" %}{% block content %}{% include "partials/meta.html" %}{% endblock %}"#,
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("base.html"),
+            "<html>{% block content %}{% endblock %}</html>",
+        )
+        .unwrap();
+        fs::create_dir_all(tmp.path().join("partials")).unwrap();
+        fs::write(
+            tmp.path().join("partials/meta.html"),
+            "{{ data.authors | length }}",
+        )
+        .unwrap();
+        let tera = tera::Tera::new(&format!("{}/**/*.html", tmp.path().display())).unwrap();
+        let graph = TemplateGraph::new(&tera, &[tmp.path()]);
+        let closure = graph.closure("post.html").unwrap();
+        assert_eq!(
+            closure.names,
+            vec!["base.html", "partials/meta.html", "post.html"]
+        );
+        assert_eq!(closure.data, DataRefs::Keys(["authors".to_string()].into()));
+
+        let before = closure.hash.clone();
+        fs::write(tmp.path().join("partials/meta.html"), "changed").unwrap();
+        let graph = TemplateGraph::new(&tera, &[tmp.path()]);
+        assert_ne!(graph.closure("post.html").unwrap().hash, before);
+        // base.html does not include the partial, so it is unaffected
+        assert_eq!(graph.closure("base.html").unwrap().names, vec!["base.html"]);
+    }
+
+    #[test]
+    fn test_begin_keeps_output_until_finish() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output = tmp.path().join("dist");
+
+        // First build: nothing to reuse
+        let mut cache = BuildCache::begin(tmp.path(), &output, "cfg".into(), true).unwrap();
+        fs::write(cache.staging().join("a.html"), "a").unwrap();
+        fs::write(cache.staging().join("stale.html"), "stale").unwrap();
+        cache.record_page(
+            "a.html".into(),
+            PageRecord {
+                source: "content/a.md".into(),
+                source_hash: "1".into(),
+                templates: vec![],
+                data: vec![],
+                shortcodes: vec![],
+                fingerprint: "fp".into(),
+                outputs: vec!["a.html".into()],
+            },
+        );
+        assert!(!output.exists());
+        cache.finish().unwrap();
+        assert_eq!(fs::read_to_string(output.join("a.html")).unwrap(), "a");
+
+        // Second build: the output stays served while a.html is restored
+        let cache = BuildCache::begin(tmp.path(), &output, "cfg".into(), true).unwrap();
+        assert!(!cache.staging().join("a.html").exists());
+        assert!(!cache.restore_page("a.html", "other"));
+        assert!(cache.restore_page("a.html", "fp"));
+        assert_eq!(
+            fs::read_to_string(cache.staging().join("a.html")).unwrap(),
+            "a"
+        );
+        assert!(output.join("a.html").exists());
+        assert!(output.join("stale.html").exists());
+        cache.finish().unwrap();
+        assert_eq!(fs::read_to_string(output.join("a.html")).unwrap(), "a");
+        assert!(!output.join("stale.html").exists());
+    }
+
+    #[test]
+    fn test_failed_build_leaves_output_and_cache() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output = tmp.path().join("dist");
+        let mut cache = BuildCache::begin(tmp.path(), &output, "cfg".into(), true).unwrap();
+        fs::write(cache.staging().join("feed.xml"), "x").unwrap();
+        cache.record_step("rss", "fp".into(), vec!["feed.xml".into()]);
+        cache.finish().unwrap();
+
+        // A build that never finishes leaves its staging directory behind
+        let cache = BuildCache::begin(tmp.path(), &output, "cfg".into(), true).unwrap();
+        fs::write(cache.staging().join("half.html"), "half").unwrap();
+        drop(cache);
+        assert!(output.join("feed.xml").exists());
+
+        let mut cache = BuildCache::begin(tmp.path(), &output, "cfg".into(), true).unwrap();
+        assert!(!cache.staging().join("half.html").exists());
+        assert!(cache.restore_step("rss", "fp"));
+    }
+
+    #[test]
+    fn test_begin_config_change_invalidates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output = tmp.path().join("dist");
+        let mut cache = BuildCache::begin(tmp.path(), &output, "cfg".into(), true).unwrap();
+        fs::write(cache.staging().join("feed.xml"), "x").unwrap();
+        cache.record_step("rss", "fp".into(), vec!["feed.xml".into()]);
+        cache.finish().unwrap();
+
+        let mut cache = BuildCache::begin(tmp.path(), &output, "changed".into(), true).unwrap();
+        assert!(!cache.restore_step("rss", "fp"));
+        assert!(!cache.staging().join("feed.xml").exists());
+    }
+
+    #[test]
+    fn test_begin_non_incremental_ignores_cache() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output = tmp.path().join("dist");
+        let mut cache = BuildCache::begin(tmp.path(), &output, "cfg".into(), true).unwrap();
+        fs::write(cache.staging().join("feed.xml"), "x").unwrap();
+        cache.record_step("rss", "fp".into(), vec!["feed.xml".into()]);
+        cache.finish().unwrap();
+
+        let mut cache = BuildCache::begin(tmp.path(), &output, "cfg".into(), false).unwrap();
+        assert!(!cache.restore_step("rss", "fp"));
+    }
+
+    #[test]
+    fn test_fragments_pruned_when_unused() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output = tmp.path().join("dist");
+        let mut cache = BuildCache::begin(tmp.path(), &output, "cfg".into(), true).unwrap();
+        let fragment = Fragment {
+            excerpt: "e".into(),
+            html_body: "<p>b</p>".into(),
+            markdown_body: "b".into(),
+            excerpt_html: "<p>e</p>".into(),
+            toc: vec![],
+        };
+        cache.store_fragment("keep", &fragment).unwrap();
+        cache.store_fragment("drop", &fragment).unwrap();
+        cache.keep_fragments(["keep".to_string()]);
+        cache.finish().unwrap();
+
+        let cache = BuildCache::begin(tmp.path(), &output, "cfg".into(), true).unwrap();
+        assert_eq!(cache.fragment("keep").unwrap().html_body, "<p>b</p>");
+        assert!(cache.fragment("drop").is_none());
+    }
+}
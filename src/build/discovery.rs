@@ -7,13 +7,14 @@ use crate::config::SiteConfig;
 use crate::content::ContentItem;
 
 /// Generate robots.txt with sitemap reference and AI crawler directives.
-pub fn generate_robots_txt(config: &SiteConfig) -> String {
+/// `sitemap_file` is `sitemap.xml`, or `sitemap_index.xml` for split sitemaps.
+pub fn generate_robots_txt(config: &SiteConfig, sitemap_file: &str) -> String {
     let base = config.site.base_url.trim_end_matches('/');
     let mut out = String::new();
     out.push_str("User-agent: *\n");
     out.push_str("Allow: /\n");
     out.push('\n');
-    out.push_str(&format!("Sitemap: {base}/sitemap.xml\n"));
+    out.push_str(&format!("Sitemap: {base}/{sitemap_file}\n"));
     out.push('\n');
 
     // AI search crawlers — allow so content appears in AI-generated answers
@@ -61,7 +62,7 @@ pub fn generate_llms_txt(
         }
         out.push_str(&format!("## {label}\n\n"));
         for item in items {
-            let md_url = format!("{base}{}.md", item.url);
+            let md_url = format!("{base}{}", super::markdown_url(&item.url));
             let desc = item.frontmatter.description.as_deref().unwrap_or("");
             if desc.is_empty() {
                 out.push_str(&format!("- [{}]({})\n", item.frontmatter.title, md_url));
@@ -104,7 +105,7 @@ pub fn generate_llms_full_txt(
             if let Some(ref desc) = item.frontmatter.description {
                 out.push_str(&format!("*{desc}*\n\n"));
             }
-            out.push_str(&item.raw_body);
+            out.push_str(&item.markdown_body);
             out.push_str("\n\n---\n\n");
         }
     }
@@ -125,6 +126,7 @@ mod tests {
                 base_url: base_url.into(),
                 language: "en".into(),
                 author: "".into(),
+                timezone: None,
             },
             collections: vec![],
             build: Default::default(),
@@ -134,6 +136,11 @@ mod tests {
             analytics: None,
             trust: None,
             contact: None,
+            og: None,
+            search: None,
+            related: None,
+            feed: None,
+            taxonomies: Vec::new(),
         }
     }
 
@@ -145,6 +152,7 @@ mod tests {
                 ..Default::default()
             },
             raw_body: body.into(),
+            markdown_body: body.into(),
             html_body: String::new(),
             source_path: std::path::PathBuf::from("test.md"),
             slug: "test".into(),
@@ -162,7 +170,7 @@ mod tests {
     #[test]
     fn test_robots_txt_basic() {
         let config = test_config("https://example.com", "");
-        let result = generate_robots_txt(&config);
+        let result = generate_robots_txt(&config, "sitemap.xml");
         assert!(result.contains("User-agent: *"));
         assert!(result.contains("Allow: /"));
         assert!(result.contains("Sitemap: https://example.com/sitemap.xml"));
@@ -182,7 +190,7 @@ mod tests {
     #[test]
     fn test_robots_txt_strips_trailing_slash() {
         let config = test_config("https://example.com/", "");
-        let result = generate_robots_txt(&config);
+        let result = generate_robots_txt(&config, "sitemap.xml");
         assert!(result.contains("https://example.com/sitemap.xml"));
         assert!(!result.contains("https://example.com//"));
     }
@@ -1,15 +1,211 @@
+//! Syndication feeds: RSS 2.0 (`feed.xml`), Atom 1.0 (`atom.xml`) and JSON
+//! Feed 1.1 (`feed.json`). Collections choose their formats with `feeds`;
+//! `[feed]` adds full content and per-tag feeds.
+
+use std::fs;
 use std::io::Cursor;
+use std::path::Path;
+use std::sync::OnceLock;
 
 use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Writer;
+use regex::Regex;
+use serde::Serialize;
 
-use crate::config::SiteConfig;
+use super::authors::Registry;
+use crate::config::{FeedFormat, SiteConfig};
 use crate::content::ContentItem;
 use crate::error::{PageError, Result};
 
+/// What a feed is about and where it lives.
+pub struct Channel<'a> {
+    pub title: &'a str,
+    /// Absolute URL of the page the feed belongs to.
+    pub link: &'a str,
+    /// Absolute URL of the directory the feed files are written to, with a
+    /// trailing slash, e.g. `https://example.com/tags/rust/`.
+    pub dir_url: &'a str,
+    pub description: &'a str,
+    pub language: &'a str,
+}
+
+/// A written feed, as exposed to templates.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FeedLink {
+    pub format: FeedFormat,
+    pub url: String,
+    pub mime_type: &'static str,
+}
+
 pub fn generate_rss(config: &SiteConfig, items: &[&ContentItem]) -> Result<String> {
+    generate_channel_rss(config, &config.site.title, &config.site.base_url, items)
+}
+
+/// RSS feed with its own channel title and link, e.g. for a taxonomy term.
+pub fn generate_channel_rss(
+    config: &SiteConfig,
+    title: &str,
+    link: &str,
+    items: &[&ContentItem],
+) -> Result<String> {
+    let dir_url = format!("{}/", config.site.base_url.trim_end_matches('/'));
+    let channel = Channel {
+        title,
+        link,
+        dir_url: &dir_url,
+        description: &config.site.description,
+        language: &config.site.language,
+    };
+    generate(FeedFormat::Rss, config, &channel, items, &Registry::new())
+}
+
+/// Render `items` as a feed in `format`. Author ids are resolved to names
+/// through `authors`.
+pub fn generate(
+    format: FeedFormat,
+    config: &SiteConfig,
+    channel: &Channel,
+    items: &[&ContentItem],
+    authors: &Registry,
+) -> Result<String> {
+    let entries: Vec<Entry> = items
+        .iter()
+        .map(|item| Entry::new(item, config, authors))
+        .collect();
+    match format {
+        FeedFormat::Rss => rss(channel, &entries),
+        FeedFormat::Atom => atom(config, channel, &entries),
+        FeedFormat::Json => json(config, channel, &entries),
+    }
+}
+
+/// Write the feeds of `items` into `dir` (the output directory of the
+/// channel's `dir_url`) in every format their collections ask for. A format is
+/// skipped when none of `items` is in it, unless `items` is empty; a site
+/// without feed collections still gets an empty RSS feed. Returns the feeds
+/// written, with site-relative URLs under `url_dir`.
+pub fn write_feeds(
+    dir: &Path,
+    url_dir: &str,
+    config: &SiteConfig,
+    channel: &Channel,
+    items: &[&ContentItem],
+    authors: &Registry,
+) -> Result<Vec<FeedLink>> {
+    let mut formats = config.feed_formats();
+    if formats.is_empty() {
+        formats.push(FeedFormat::Rss);
+    }
+    let mut written = Vec::new();
+    for format in formats {
+        let format_items: Vec<&ContentItem> = items
+            .iter()
+            .filter(|item| {
+                config
+                    .collections
+                    .iter()
+                    .any(|c| c.name == item.collection && c.feed_formats().contains(&format))
+            })
+            .copied()
+            .collect();
+        if format_items.is_empty() && !items.is_empty() {
+            continue;
+        }
+        let feed = generate(format, config, channel, &format_items, authors)?;
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(format.file_name()), feed)?;
+        written.push(FeedLink {
+            format,
+            url: format!("{url_dir}{}", format.file_name()),
+            mime_type: format.mime_type(),
+        });
+    }
+    Ok(written)
+}
+
+/// An item with everything the feed formats need, URLs made absolute.
+struct Entry {
+    title: String,
+    url: String,
+    published: Option<chrono::DateTime<chrono::FixedOffset>>,
+    updated: Option<chrono::DateTime<chrono::FixedOffset>>,
+    description: String,
+    /// Full rendered body, when `[feed] full_content` is set.
+    content: Option<String>,
+    authors: Vec<String>,
+    tags: Vec<String>,
+    image: Option<String>,
+}
+
+impl Entry {
+    fn new(item: &ContentItem, config: &SiteConfig, authors: &Registry) -> Self {
+        let base = config.site.base_url.trim_end_matches('/');
+        let full_content = config.feed.as_ref().is_some_and(|f| f.full_content);
+        let tz = config.timezone();
+        Self {
+            title: item.frontmatter.title.clone(),
+            url: format!("{base}{}", item.url),
+            published: item.frontmatter.date.map(|d| d.instant(tz)),
+            updated: item.frontmatter.updated.map(|d| d.instant(tz)),
+            description: item.frontmatter.description.clone().unwrap_or_default(),
+            content: full_content.then(|| absolute_urls(&item.html_body, base)),
+            authors: item
+                .frontmatter
+                .authors
+                .iter()
+                .map(|id| authors.get(id).map_or(id.clone(), |a| a.name.clone()))
+                .collect(),
+            tags: item.frontmatter.tags.clone(),
+            image: item.frontmatter.image.as_deref().map(|image| {
+                if image.starts_with('/') {
+                    format!("{base}{image}")
+                } else {
+                    image.to_string()
+                }
+            }),
+        }
+    }
+
+    fn last_modified(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.updated.or(self.published)
+    }
+}
+
+/// Prefix root-relative `href` and `src` attributes with the site's base URL,
+/// so feed readers resolve links and images.
+fn absolute_urls(html: &str, base: &str) -> String {
+    static ROOT_RELATIVE: OnceLock<Regex> = OnceLock::new();
+    let re = ROOT_RELATIVE.get_or_init(|| Regex::new(r#"\b(href|src)="/([^/"][^"]*)?""#).unwrap());
+    re.replace_all(html, |caps: &regex::Captures| {
+        format!(
+            "{}=\"{base}/{}\"",
+            &caps[1],
+            caps.get(2).map_or("", |m| m.as_str())
+        )
+    })
+    .into_owned()
+}
+
+/// MIME type of an image URL, from its extension.
+fn image_type(url: &str) -> &'static str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    match path
+        .rsplit('.')
+        .next()
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("png") => "image/png",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("avif") => "image/avif",
+        Some("svg") => "image/svg+xml",
+        _ => "image/jpeg",
+    }
+}
+
+fn rss(channel: &Channel, entries: &[Entry]) -> Result<String> {
     let mut writer = Writer::new(Cursor::new(Vec::new()));
-    let base = config.site.base_url.trim_end_matches('/');
 
     write(
         &mut writer,
@@ -18,32 +214,49 @@ pub fn generate_rss(config: &SiteConfig, items: &[&ContentItem]) -> Result<Strin
 
     let mut rss = BytesStart::new("rss");
     rss.push_attribute(("version", "2.0"));
+    rss.push_attribute(("xmlns:atom", "http://www.w3.org/2005/Atom"));
+    rss.push_attribute(("xmlns:content", "http://purl.org/rss/1.0/modules/content/"));
+    rss.push_attribute(("xmlns:dc", "http://purl.org/dc/elements/1.1/"));
+    rss.push_attribute(("xmlns:media", "http://search.yahoo.com/mrss/"));
     write(&mut writer, Event::Start(rss))?;
     write(&mut writer, Event::Start(BytesStart::new("channel")))?;
 
-    write_text_element(&mut writer, "title", &config.site.title)?;
-    write_text_element(&mut writer, "link", &config.site.base_url)?;
-    write_text_element(&mut writer, "description", &config.site.description)?;
-    write_text_element(&mut writer, "language", &config.site.language)?;
+    write_text_element(&mut writer, "title", channel.title)?;
+    write_text_element(&mut writer, "link", channel.link)?;
+    write_text_element(&mut writer, "description", channel.description)?;
+    write_text_element(&mut writer, "language", channel.language)?;
+    let mut self_link = BytesStart::new("atom:link");
+    let self_url = format!("{}{}", channel.dir_url, FeedFormat::Rss.file_name());
+    self_link.push_attribute(("href", self_url.as_str()));
+    self_link.push_attribute(("rel", "self"));
+    self_link.push_attribute(("type", FeedFormat::Rss.mime_type()));
+    write(&mut writer, Event::Empty(self_link))?;
 
-    for item in items {
+    for entry in entries {
         write(&mut writer, Event::Start(BytesStart::new("item")))?;
-        write_text_element(&mut writer, "title", &item.frontmatter.title)?;
-        let link = format!("{}{}", base, item.url);
-        write_text_element(&mut writer, "link", &link)?;
-        write_text_element(&mut writer, "guid", &link)?;
-        if let Some(date) = item.frontmatter.date {
-            if let Some(datetime) = date.and_hms_opt(12, 0, 0) {
-                let rfc2822 = chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
-                    datetime,
-                    chrono::Utc,
-                )
-                .to_rfc2822();
-                write_text_element(&mut writer, "pubDate", &rfc2822)?;
-            }
-        }
-        let desc = item.frontmatter.description.as_deref().unwrap_or("");
-        write_text_element(&mut writer, "description", desc)?;
+        write_text_element(&mut writer, "title", &entry.title)?;
+        write_text_element(&mut writer, "link", &entry.url)?;
+        write_text_element(&mut writer, "guid", &entry.url)?;
+        if let Some(published) = entry.published {
+            write_text_element(&mut writer, "pubDate", &published.to_rfc2822())?;
+        }
+        for author in &entry.authors {
+            write_text_element(&mut writer, "dc:creator", author)?;
+        }
+        for tag in &entry.tags {
+            write_text_element(&mut writer, "category", tag)?;
+        }
+        write_text_element(&mut writer, "description", &entry.description)?;
+        if let Some(content) = &entry.content {
+            write_text_element(&mut writer, "content:encoded", content)?;
+        }
+        if let Some(image) = &entry.image {
+            let mut media = BytesStart::new("media:content");
+            media.push_attribute(("url", image.as_str()));
+            media.push_attribute(("medium", "image"));
+            media.push_attribute(("type", image_type(image)));
+            write(&mut writer, Event::Empty(media))?;
+        }
         write(&mut writer, Event::End(BytesEnd::new("item")))?;
     }
 
@@ -54,10 +267,177 @@ pub fn generate_rss(config: &SiteConfig, items: &[&ContentItem]) -> Result<Strin
     String::from_utf8(bytes).map_err(|e| PageError::Build(format!("RSS encoding error: {e}")))
 }
 
+fn atom(config: &SiteConfig, channel: &Channel, entries: &[Entry]) -> Result<String> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+    let self_url = format!("{}{}", channel.dir_url, FeedFormat::Atom.file_name());
+
+    write(
+        &mut writer,
+        Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)),
+    )?;
+    let mut feed = BytesStart::new("feed");
+    feed.push_attribute(("xmlns", "http://www.w3.org/2005/Atom"));
+    feed.push_attribute(("xml:lang", channel.language));
+    write(&mut writer, Event::Start(feed))?;
+
+    write_text_element(&mut writer, "title", channel.title)?;
+    if !channel.description.is_empty() {
+        write_text_element(&mut writer, "subtitle", channel.description)?;
+    }
+    write_link(&mut writer, channel.link, None, None)?;
+    write_link(
+        &mut writer,
+        &self_url,
+        Some("self"),
+        Some(FeedFormat::Atom.mime_type()),
+    )?;
+    write_text_element(&mut writer, "id", &self_url)?;
+    // The newest entry dates the feed; an empty feed gets a fixed date so
+    // rebuilding it is reproducible.
+    let updated = entries
+        .iter()
+        .filter_map(Entry::last_modified)
+        .max()
+        .map_or_else(|| "1970-01-01T00:00:00Z".to_string(), |d| d.to_rfc3339());
+    write_text_element(&mut writer, "updated", &updated)?;
+    if !config.site.author.is_empty() {
+        write_author(&mut writer, &config.site.author)?;
+    }
+
+    for entry in entries {
+        write(&mut writer, Event::Start(BytesStart::new("entry")))?;
+        write_text_element(&mut writer, "title", &entry.title)?;
+        write_link(&mut writer, &entry.url, None, None)?;
+        write_text_element(&mut writer, "id", &entry.url)?;
+        if let Some(published) = entry.published {
+            write_text_element(&mut writer, "published", &published.to_rfc3339())?;
+        }
+        if let Some(updated) = entry.last_modified() {
+            write_text_element(&mut writer, "updated", &updated.to_rfc3339())?;
+        }
+        for author in &entry.authors {
+            write_author(&mut writer, author)?;
+        }
+        for tag in &entry.tags {
+            let mut category = BytesStart::new("category");
+            category.push_attribute(("term", tag.as_str()));
+            write(&mut writer, Event::Empty(category))?;
+        }
+        if !entry.description.is_empty() {
+            write_text_element(&mut writer, "summary", &entry.description)?;
+        }
+        if let Some(content) = &entry.content {
+            let mut start = BytesStart::new("content");
+            start.push_attribute(("type", "html"));
+            write(&mut writer, Event::Start(start))?;
+            write(&mut writer, Event::Text(BytesText::new(content)))?;
+            write(&mut writer, Event::End(BytesEnd::new("content")))?;
+        }
+        if let Some(image) = &entry.image {
+            write_link(
+                &mut writer,
+                image,
+                Some("enclosure"),
+                Some(image_type(image)),
+            )?;
+        }
+        write(&mut writer, Event::End(BytesEnd::new("entry")))?;
+    }
+
+    write(&mut writer, Event::End(BytesEnd::new("feed")))?;
+    let bytes = writer.into_inner().into_inner();
+    String::from_utf8(bytes).map_err(|e| PageError::Build(format!("Atom encoding error: {e}")))
+}
+
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    feed_url: String,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    description: &'a str,
+    language: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<JsonAuthor<'a>>,
+    items: Vec<JsonItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonAuthor<'a> {
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+struct JsonItem<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_html: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_text: Option<&'a str>,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    summary: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_published: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_modified: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<JsonAuthor<'a>>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+}
+
+fn json(config: &SiteConfig, channel: &Channel, entries: &[Entry]) -> Result<String> {
+    let feed = JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: channel.title,
+        home_page_url: channel.link,
+        feed_url: format!("{}{}", channel.dir_url, FeedFormat::Json.file_name()),
+        description: channel.description,
+        language: channel.language,
+        authors: (!config.site.author.is_empty())
+            .then(|| JsonAuthor {
+                name: &config.site.author,
+            })
+            .into_iter()
+            .collect(),
+        items: entries
+            .iter()
+            .map(|entry| JsonItem {
+                id: &entry.url,
+                url: &entry.url,
+                title: &entry.title,
+                // Every item needs content: the full body, or else the description
+                content_html: entry.content.as_deref(),
+                content_text: entry
+                    .content
+                    .is_none()
+                    .then_some(entry.description.as_str()),
+                summary: &entry.description,
+                image: entry.image.as_deref(),
+                date_published: entry.published.map(|d| d.to_rfc3339()),
+                date_modified: entry.updated.map(|d| d.to_rfc3339()),
+                authors: entry
+                    .authors
+                    .iter()
+                    .map(|name| JsonAuthor { name })
+                    .collect(),
+                tags: &entry.tags,
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&feed)
+        .map_err(|e| PageError::Build(format!("JSON Feed encoding error: {e}")))
+}
+
 fn write(writer: &mut Writer<Cursor<Vec<u8>>>, event: Event<'_>) -> Result<()> {
     writer
         .write_event(event)
-        .map_err(|e| PageError::Build(format!("RSS write error: {e}")))
+        .map_err(|e| PageError::Build(format!("Feed write error: {e}")))
 }
 
 fn write_text_element(writer: &mut Writer<Cursor<Vec<u8>>>, tag: &str, text: &str) -> Result<()> {
@@ -66,6 +446,31 @@ fn write_text_element(writer: &mut Writer<Cursor<Vec<u8>>>, tag: &str, text: &st
     write(writer, Event::End(BytesEnd::new(tag)))
 }
 
+/// Atom `<link>` element.
+fn write_link(
+    writer: &mut Writer<Cursor<Vec<u8>>>,
+    href: &str,
+    rel: Option<&str>,
+    mime_type: Option<&str>,
+) -> Result<()> {
+    let mut link = BytesStart::new("link");
+    link.push_attribute(("href", href));
+    if let Some(rel) = rel {
+        link.push_attribute(("rel", rel));
+    }
+    if let Some(mime_type) = mime_type {
+        link.push_attribute(("type", mime_type));
+    }
+    write(writer, Event::Empty(link))
+}
+
+/// Atom `<author>` element.
+fn write_author(writer: &mut Writer<Cursor<Vec<u8>>>, name: &str) -> Result<()> {
+    write(writer, Event::Start(BytesStart::new("author")))?;
+    write_text_element(writer, "name", name)?;
+    write(writer, Event::End(BytesEnd::new("author")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,6 +484,7 @@ mod tests {
                 base_url: "https://example.com".into(),
                 language: "en".into(),
                 author: "".into(),
+                timezone: None,
             },
             collections: vec![],
             build: Default::default(),
@@ -88,6 +494,11 @@ mod tests {
             analytics: None,
             trust: None,
             contact: None,
+            og: None,
+            search: None,
+            related: None,
+            feed: None,
+            taxonomies: Vec::new(),
         }
     }
 
@@ -95,19 +506,27 @@ mod tests {
         ContentItem {
             frontmatter: Frontmatter {
                 title: title.into(),
-                date,
+                date: date.map(Into::into),
                 updated: None,
                 description: desc.map(|d| d.into()),
                 image: None,
                 slug: None,
                 tags: vec![],
+                authors: Vec::new(),
                 draft: false,
                 template: None,
                 robots: None,
                 weight: None,
+                related: Vec::new(),
+                aliases: Vec::new(),
+                sitemap: None,
+                publish_at: None,
+                expires_at: None,
+                terms: Default::default(),
                 extra: Default::default(),
             },
             raw_body: "test".into(),
+            markdown_body: "test".into(),
             html_body: "<p>test</p>".into(),
             source_path: std::path::PathBuf::from("test.md"),
             slug: "test-post".into(),
@@ -178,4 +597,200 @@ mod tests {
         assert!(rss.contains("https://example.com/posts/test-post"));
         assert!(!rss.contains("https://example.com//posts/test-post"));
     }
+
+    fn rich_item() -> ContentItem {
+        let mut item = test_item(
+            "Hello World",
+            Some(chrono::NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()),
+            Some("A first post"),
+        );
+        item.frontmatter.tags = vec!["rust".into(), "web".into()];
+        item.frontmatter.authors = vec!["jane".into()];
+        item.frontmatter.image = Some("/static/cover.png".into());
+        item.html_body =
+            r#"<p><a href="/docs/intro">Intro</a> <img src="/static/a.png"> <a href="https://x.org/">x</a></p>"#
+                .into();
+        item
+    }
+
+    fn registry() -> Registry {
+        serde_yaml_ng::from_str("jane:\n  name: Jane Doe\n").unwrap()
+    }
+
+    fn channel(config: &SiteConfig) -> Channel<'_> {
+        Channel {
+            title: &config.site.title,
+            link: &config.site.base_url,
+            dir_url: "https://example.com/",
+            description: &config.site.description,
+            language: "en",
+        }
+    }
+
+    #[test]
+    fn test_rss_categories_authors_and_media() {
+        let config = test_config();
+        let item = rich_item();
+        let rss = generate(
+            FeedFormat::Rss,
+            &config,
+            &channel(&config),
+            &[&item],
+            &registry(),
+        )
+        .unwrap();
+        assert!(rss.contains("<category>rust</category><category>web</category>"));
+        assert!(rss.contains("<dc:creator>Jane Doe</dc:creator>"));
+        assert!(rss.contains(
+            r#"<media:content url="https://example.com/static/cover.png" medium="image" type="image/png"/>"#
+        ));
+        assert!(rss.contains(r#"<atom:link href="https://example.com/feed.xml" rel="self""#));
+        assert!(!rss.contains("content:encoded>"));
+    }
+
+    #[test]
+    fn test_full_content_uses_absolute_urls() {
+        let mut config = test_config();
+        config.feed = Some(crate::config::FeedSection {
+            full_content: true,
+            tags: false,
+        });
+        let item = rich_item();
+        let rss = generate(
+            FeedFormat::Rss,
+            &config,
+            &channel(&config),
+            &[&item],
+            &registry(),
+        )
+        .unwrap();
+        assert!(rss.contains("<content:encoded>"));
+        assert!(rss.contains("href=&quot;https://example.com/docs/intro&quot;"));
+        assert!(rss.contains("src=&quot;https://example.com/static/a.png&quot;"));
+        assert!(rss.contains("href=&quot;https://x.org/&quot;"));
+    }
+
+    #[test]
+    fn test_atom_feed() {
+        let config = test_config();
+        let item = rich_item();
+        let atom = generate(
+            FeedFormat::Atom,
+            &config,
+            &channel(&config),
+            &[&item],
+            &registry(),
+        )
+        .unwrap();
+        assert!(atom.contains(r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="en">"#));
+        assert!(atom.contains(r#"<link href="https://example.com/atom.xml" rel="self""#));
+        assert!(atom.contains("<updated>2025-01-15T12:00:00+00:00</updated>"));
+        assert!(atom.contains("<author><name>Jane Doe</name></author>"));
+        assert!(atom.contains(r#"<category term="rust"/>"#));
+        assert!(atom.contains("<summary>A first post</summary>"));
+        assert!(atom.contains(r#"rel="enclosure" type="image/png"/>"#));
+    }
+
+    #[test]
+    fn test_json_feed() {
+        let mut config = test_config();
+        config.site.author = "Site Author".into();
+        let item = rich_item();
+        let json = generate(
+            FeedFormat::Json,
+            &config,
+            &channel(&config),
+            &[&item],
+            &registry(),
+        )
+        .unwrap();
+        let feed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(feed["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(feed["feed_url"], "https://example.com/feed.json");
+        assert_eq!(feed["authors"][0]["name"], "Site Author");
+        let entry = &feed["items"][0];
+        assert_eq!(entry["url"], "https://example.com/posts/test-post");
+        assert_eq!(entry["content_text"], "A first post");
+        assert!(entry.get("content_html").is_none());
+        assert_eq!(entry["image"], "https://example.com/static/cover.png");
+        assert_eq!(entry["tags"][1], "web");
+        assert_eq!(entry["authors"][0]["name"], "Jane Doe");
+        assert_eq!(entry["date_published"], "2025-01-15T12:00:00+00:00");
+    }
+
+    #[test]
+    fn test_feed_dates_use_timestamps_and_site_timezone() {
+        let mut config = test_config();
+        config.site.timezone = Some("America/New_York".into());
+        let mut timed = test_item("Timed", None, None);
+        timed.frontmatter.date = Some("2025-01-15T16:45:00+01:00".parse().unwrap());
+        let dated = test_item(
+            "Dated",
+            Some(chrono::NaiveDate::from_ymd_opt(2025, 1, 14).unwrap()),
+            None,
+        );
+        let rss = generate_rss(&config, &[&timed, &dated]).unwrap();
+        assert!(rss.contains("<pubDate>Wed, 15 Jan 2025 16:45:00 +0100</pubDate>"));
+        assert!(rss.contains("<pubDate>Tue, 14 Jan 2025 12:00:00 -0500</pubDate>"));
+
+        let json = generate(
+            FeedFormat::Json,
+            &config,
+            &channel(&config),
+            &[&timed],
+            &registry(),
+        )
+        .unwrap();
+        let feed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            feed["items"][0]["date_published"],
+            "2025-01-15T16:45:00+01:00"
+        );
+    }
+
+    #[test]
+    fn test_write_feeds_per_collection_formats() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut config = test_config();
+        let mut posts = crate::config::CollectionConfig::preset_posts();
+        posts.feeds = vec![FeedFormat::Rss, FeedFormat::Json];
+        let mut changelog = crate::config::CollectionConfig::preset_changelog();
+        changelog.feeds = vec![FeedFormat::Atom];
+        config.collections = vec![posts, changelog];
+
+        let post = rich_item();
+        let written = write_feeds(
+            tmp.path(),
+            "/",
+            &config,
+            &channel(&config),
+            &[&post],
+            &registry(),
+        )
+        .unwrap();
+        let urls: Vec<&str> = written.iter().map(|f| f.url.as_str()).collect();
+        // No changelog entries, so no Atom feed
+        assert_eq!(urls, ["/feed.xml", "/feed.json"]);
+        assert_eq!(written[1].mime_type, "application/feed+json");
+        assert!(tmp.path().join("feed.json").exists());
+        assert!(!tmp.path().join("atom.xml").exists());
+    }
+
+    #[test]
+    fn test_write_feeds_without_feed_collections() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config();
+        let written = write_feeds(
+            tmp.path(),
+            "/",
+            &config,
+            &channel(&config),
+            &[],
+            &Registry::new(),
+        )
+        .unwrap();
+        assert_eq!(written.len(), 1);
+        assert_eq!(written[0].format, FeedFormat::Rss);
+        assert!(tmp.path().join("feed.xml").exists());
+    }
 }
@@ -0,0 +1,248 @@
+//! Content-addressed cache of processed images under `.seite/cache/images/`.
+//!
+//! Every resized or re-encoded variant is stored under a key combining the
+//! source image's hash with the settings that produced it (width, format and
+//! quality), so an unchanged image is copied from the cache instead of being
+//! decoded and encoded again. Source dimensions, placeholder and dominant
+//! colour are cached too, which lets a build with a warm cache skip decoding
+//! altogether.
+//!
+//! Each output directory records the entries it used in `outputs/<name>.json`.
+//! When a build finishes, entries that no output uses are pruned.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use image::ImageFormat;
+use serde::{Deserialize, Serialize};
+
+use super::cache::{self, Fingerprint};
+use crate::error::Result;
+
+/// Bump when encoder settings change in a way the key does not capture.
+const CACHE_FORMAT: u32 = 1;
+
+/// Entries an output directory used in its last build.
+#[derive(Serialize, Deserialize)]
+struct OutputManifest {
+    output: PathBuf,
+    entries: BTreeSet<String>,
+}
+
+/// What the pipeline derives from a source image besides its variants.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceMeta {
+    pub width: u32,
+    pub height: u32,
+    /// `data:image/webp;base64,...` preview.
+    pub placeholder: String,
+    /// Dominant colour, `#rrggbb`.
+    pub color: String,
+}
+
+pub struct ImageCache {
+    dir: PathBuf,
+    output: PathBuf,
+    output_name: String,
+    used: Mutex<BTreeSet<String>>,
+}
+
+/// Cache directory of a project: `.seite/cache/images/`.
+fn cache_dir(project_root: &Path) -> PathBuf {
+    crate::meta::meta_dir(project_root)
+        .join("cache")
+        .join("images")
+}
+
+/// Delete a project's image cache.
+pub fn clean(project_root: &Path) -> Result<()> {
+    let dir = cache_dir(project_root);
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+/// Key of one processed variant of a source image.
+pub fn variant_key(source_hash: &str, width: u32, format: ImageFormat, quality: u8) -> String {
+    Fingerprint::new()
+        .str(&CACHE_FORMAT.to_string())
+        .str(source_hash)
+        .str(&width.to_string())
+        .str(format.extensions_str().first().copied().unwrap_or(""))
+        .str(&quality.to_string())
+        .finish()
+}
+
+impl ImageCache {
+    /// Open the project's image cache for a build into `output`.
+    pub fn open(project_root: &Path, output: &Path) -> Self {
+        Self {
+            dir: cache_dir(project_root),
+            output: output.to_path_buf(),
+            output_name: cache::output_name(project_root, output),
+            used: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    fn entry_path(&self, name: &str) -> PathBuf {
+        self.dir.join("entries").join(name)
+    }
+
+    fn mark_used(&self, name: String) {
+        self.used
+            .lock()
+            .expect("image cache lock poisoned")
+            .insert(name);
+    }
+
+    /// Cached metadata of a source image.
+    pub fn source_meta(&self, source_hash: &str) -> Option<SourceMeta> {
+        let name = format!("{source_hash}.json");
+        let raw = fs::read_to_string(self.entry_path(&name)).ok()?;
+        let meta = serde_json::from_str(&raw).ok()?;
+        self.mark_used(name);
+        Some(meta)
+    }
+
+    pub fn store_source_meta(&self, source_hash: &str, meta: &SourceMeta) -> Result<()> {
+        let name = format!("{source_hash}.json");
+        let path = self.entry_path(&name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(meta).unwrap_or_default())?;
+        self.mark_used(name);
+        Ok(())
+    }
+
+    /// Copy a cached variant to `dest`. Returns `false` on a cache miss.
+    pub fn restore(&self, key: &str, dest: &Path) -> bool {
+        let name = key.to_string();
+        if fs::copy(self.entry_path(&name), dest).is_err() {
+            return false;
+        }
+        self.mark_used(name);
+        true
+    }
+
+    /// Store a variant just written to `written` under `key`.
+    pub fn store(&self, key: &str, written: &Path) -> Result<()> {
+        let name = key.to_string();
+        let path = self.entry_path(&name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(written, path)?;
+        self.mark_used(name);
+        Ok(())
+    }
+
+    /// Record the entries this build used and prune those no output uses.
+    pub fn finish(self) -> Result<()> {
+        let used = self.used.into_inner().expect("image cache lock poisoned");
+        if used.is_empty() && !self.dir.exists() {
+            return Ok(());
+        }
+        let outputs_dir = self.dir.join("outputs");
+        fs::create_dir_all(&outputs_dir)?;
+        let manifest = OutputManifest {
+            output: self.output,
+            entries: used,
+        };
+        let own = outputs_dir.join(format!("{}.json", self.output_name));
+        fs::write(&own, serde_json::to_string(&manifest).unwrap_or_default())?;
+
+        // Entries of this build (whose output is swapped in after it) and of
+        // every other output that still exists
+        let mut live: HashSet<String> = HashSet::new();
+        for entry in fs::read_dir(&outputs_dir)?.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let manifest = fs::read_to_string(&path)
+                .ok()
+                .and_then(|s| serde_json::from_str::<OutputManifest>(&s).ok());
+            match manifest {
+                Some(m) if path == own || m.output.exists() => live.extend(m.entries),
+                _ => {
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+
+        let entries_dir = self.dir.join("entries");
+        if entries_dir.exists() {
+            for entry in fs::read_dir(&entries_dir)?.filter_map(|e| e.ok()) {
+                if !live.contains(entry.file_name().to_string_lossy().as_ref()) {
+                    let _ = fs::remove_file(entry.path());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_key_covers_settings() {
+        let key = variant_key("abc", 480, ImageFormat::WebP, 80);
+        assert_eq!(key, variant_key("abc", 480, ImageFormat::WebP, 80));
+        assert_ne!(key, variant_key("abd", 480, ImageFormat::WebP, 80));
+        assert_ne!(key, variant_key("abc", 800, ImageFormat::WebP, 80));
+        assert_ne!(key, variant_key("abc", 480, ImageFormat::Avif, 80));
+        assert_ne!(key, variant_key("abc", 480, ImageFormat::WebP, 70));
+    }
+
+    #[test]
+    fn test_restore_store_and_prune() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        let dist = root.join("dist");
+        let other = root.join("dist-subdomains/docs");
+        fs::create_dir_all(&dist).unwrap();
+        fs::create_dir_all(&other).unwrap();
+        let written = dist.join("a.webp");
+        fs::write(&written, "webp").unwrap();
+
+        let cache = ImageCache::open(root, &dist);
+        assert!(!cache.restore("k1", &dist.join("copy.webp")));
+        cache.store("k1", &written).unwrap();
+        cache.store("k2", &written).unwrap();
+        let meta = SourceMeta {
+            width: 10,
+            height: 20,
+            placeholder: "data:image/webp;base64,AAAA".into(),
+            color: "#102030".into(),
+        };
+        cache.store_source_meta("src", &meta).unwrap();
+        assert_eq!(cache.source_meta("src"), Some(meta));
+        cache.finish().unwrap();
+
+        // Another output keeps k2 alive
+        let cache = ImageCache::open(root, &other);
+        assert!(cache.restore("k2", &other.join("a.webp")));
+        cache.finish().unwrap();
+
+        // The next build of dist only uses k1: k2 survives, nothing else does
+        let cache = ImageCache::open(root, &dist);
+        assert!(cache.restore("k1", &dist.join("copy.webp")));
+        assert_eq!(fs::read_to_string(dist.join("copy.webp")).unwrap(), "webp");
+        cache.finish().unwrap();
+        let entries = cache_dir(root).join("entries");
+        assert!(entries.join("k1").exists());
+        assert!(entries.join("k2").exists());
+        assert!(!entries.join("src.json").exists());
+
+        // Outputs that no longer exist stop pinning entries
+        fs::remove_dir_all(&other).unwrap();
+        ImageCache::open(root, &dist).finish().unwrap();
+        assert!(!entries.join("k2").exists());
+
+        clean(root).unwrap();
+        assert!(!cache_dir(root).exists());
+    }
+}
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 use std::fs;
 use std::io::BufWriter;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use base64::prelude::{Engine, BASE64_STANDARD};
 use image::codecs::jpeg::JpegEncoder;
 use image::codecs::png::{CompressionType, FilterType as PngFilterType, PngEncoder};
 use image::imageops::FilterType;
@@ -10,16 +11,24 @@ use image::{ImageEncoder, ImageFormat};
 use rayon::prelude::*;
 use walkdir::WalkDir;
 
-use crate::config::{ImageSection, ResolvedPaths};
+use super::cache;
+use super::image_cache::{self, ImageCache, SourceMeta};
+use crate::config::{ImagePlaceholder, ImageSection, ResolvedPaths};
 use crate::error::{PageError, Result};
 
 /// Supported input image extensions.
-const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "avif"];
+pub(crate) const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "avif"];
+
+/// Longest side of the placeholder preview, in pixels.
+const PLACEHOLDER_SIZE: u32 = 16;
+
+/// Longest side of the thumbnail the dominant colour is sampled from.
+const COLOR_SAMPLE_SIZE: u32 = 64;
 
 /// An entry in the image manifest mapping original paths to processed outputs.
 #[derive(Debug, Clone)]
 pub struct ProcessedImage {
-    /// Relative path from static dir, e.g., "images/photo.jpg"
+    /// Path relative to the static dir (or the page bundle), e.g., "images/photo.jpg"
     pub rel_path: String,
     /// Map of width → URL path for srcset, e.g., {480: "/static/images/photo-480w.jpg"}
     pub srcset_entries: Vec<(u32, String)>,
@@ -31,92 +40,148 @@ pub struct ProcessedImage {
     pub original_width: u32,
     /// Original height of the source image.
     pub original_height: u32,
+    /// Tiny blurred preview, as a `data:image/webp;base64,...` URI.
+    pub placeholder: String,
+    /// Dominant colour, e.g., "#a4c2e0".
+    pub color: String,
 }
 
-/// Process all images in the static directory: generate resized copies and WebP variants.
-/// Returns a manifest mapping original `/static/...` paths to their processed outputs.
+/// Process all images in the static directory: generate resized copies and WebP variants,
+/// reusing those already in the image cache. Returns a manifest mapping original `/static/...` paths to their processed outputs.
 pub fn process_images(
     paths: &ResolvedPaths,
     config: &ImageSection,
+    image_cache: &ImageCache,
 ) -> Result<HashMap<String, ProcessedImage>> {
     if !paths.static_dir.exists() {
         return Ok(HashMap::new());
     }
 
-    let entries: Vec<_> = WalkDir::new(&paths.static_dir)
+    let sources: Vec<(PathBuf, String)> = WalkDir::new(&paths.static_dir)
         .into_iter()
         .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.file_type().is_file()
-                && e.path()
-                    .extension()
-                    .and_then(|ext| ext.to_str())
-                    .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .filter(|e| e.file_type().is_file() && is_image(e.path()))
+        .map(|e| {
+            let rel = e
+                .path()
+                .strip_prefix(&paths.static_dir)
+                .unwrap_or(e.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+            (e.path().to_path_buf(), format!("/static/{rel}"))
         })
         .collect();
+    Ok(process_sources(
+        &sources,
+        &paths.output,
+        config,
+        image_cache,
+    ))
+}
+
+/// Process the images of page bundles, given as `(source, URL of the original)`
+/// pairs. Variants are written next to the original's URL.
+pub fn process_bundle_images(
+    images: &[(PathBuf, String)],
+    output_dir: &Path,
+    config: &ImageSection,
+    image_cache: &ImageCache,
+) -> HashMap<String, ProcessedImage> {
+    let sources: Vec<(PathBuf, String)> = images
+        .iter()
+        .filter(|(source, _)| is_image(source))
+        .cloned()
+        .collect();
+    process_sources(&sources, output_dir, config, image_cache)
+}
+
+pub(crate) fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
 
-    let manifest: HashMap<String, ProcessedImage> = entries
+/// Process images given as `(source, URL of the original)` pairs into a
+/// manifest keyed by URL. Images that fail to process are skipped with a warning.
+fn process_sources(
+    sources: &[(PathBuf, String)],
+    output_dir: &Path,
+    config: &ImageSection,
+    image_cache: &ImageCache,
+) -> HashMap<String, ProcessedImage> {
+    sources
         .par_iter()
-        .filter_map(|entry| {
-            let ext = entry
-                .path()
+        .filter_map(|(source, url)| {
+            let ext = source
                 .extension()
                 .and_then(|e| e.to_str())
                 .unwrap_or("")
                 .to_lowercase();
-
-            let rel = entry
-                .path()
-                .strip_prefix(&paths.static_dir)
-                .unwrap_or(entry.path());
-
-            match process_single_image(entry.path(), rel, &paths.output, config, &ext) {
-                Ok(processed) => {
-                    let key = format!("/static/{}", rel.to_string_lossy().replace('\\', "/"));
-                    Some((key, processed))
-                }
+            let (url_dir, file_name) = url.trim_start_matches('/').rsplit_once('/')?;
+            let rel = url
+                .strip_prefix("/static/")
+                .unwrap_or(file_name)
+                .to_string();
+
+            match process_single_image(source, &rel, url_dir, output_dir, config, &ext, image_cache)
+            {
+                Ok(processed) => Some((url.clone(), processed)),
                 Err(e) => {
-                    tracing::warn!("Failed to process image {}: {e}", entry.path().display());
+                    tracing::warn!("Failed to process image {}: {e}", source.display());
                     None
                 }
             }
         })
-        .collect();
+        .collect()
+}
 
-    Ok(manifest)
+fn open_image(source: &Path) -> Result<image::DynamicImage> {
+    image::open(source)
+        .map_err(|e| PageError::Build(format!("failed to open image '{}': {e}", source.display())))
 }
 
 fn process_single_image(
     source: &Path,
-    rel: &Path,
+    rel: &str,
+    url_dir: &str,
     output_dir: &Path,
     config: &ImageSection,
     ext: &str,
+    image_cache: &ImageCache,
 ) -> Result<ProcessedImage> {
-    let img = image::open(source).map_err(|e| {
-        PageError::Build(format!("failed to open image '{}': {e}", source.display()))
-    })?;
-
-    let original_width = img.width();
-    let original_height = img.height();
+    let source_hash = cache::hash_bytes(&fs::read(source)?);
+    // Decoded lazily: a warm cache has the metadata and every variant
+    let mut img = None;
+    let meta = match image_cache.source_meta(&source_hash) {
+        Some(meta) => meta,
+        None => {
+            let decoded = open_image(source)?;
+            let meta = source_meta(&decoded)?;
+            image_cache.store_source_meta(&source_hash, &meta)?;
+            img = Some(decoded);
+            meta
+        }
+    };
+    let (original_width, original_height) = (meta.width, meta.height);
 
     let stem = source
         .file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or("image");
-    let rel_dir = rel.parent().unwrap_or(Path::new(""));
-    let rel_dir_str = rel_dir.to_string_lossy().replace('\\', "/");
+    let file_name = source
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("image");
 
-    let output_base = if rel_dir_str.is_empty() {
-        output_dir.join("static")
-    } else {
-        output_dir.join("static").join(rel_dir)
-    };
+    let output_base = output_dir.join(url_dir);
     fs::create_dir_all(&output_base)?;
+    let url_for = |name: &str| format!("/{url_dir}/{name}");
 
     let mut srcset_entries = Vec::new();
     let mut webp_entries = Vec::new();
     let mut avif_entries = Vec::new();
+    // Files to write: (name, width, format, quality)
+    let mut variants: Vec<(String, u32, ImageFormat, u8)> = Vec::new();
 
     // Determine the original image format for saving resized copies
     let save_format = match ext {
@@ -134,82 +199,72 @@ fn process_single_image(
             continue;
         }
 
-        let scale = width as f64 / original_width as f64;
-        let new_height = (original_height as f64 * scale).round() as u32;
-
-        let resized = img.resize_exact(width, new_height, FilterType::Lanczos3);
-
-        // Save resized in original format
+        // Resized copy in the original format
         let resized_name = format!("{stem}-{width}w.{ext}");
-        let resized_path = output_base.join(&resized_name);
-        save_image(&resized, &resized_path, save_format, config.quality)?;
-
-        let url = if rel_dir_str.is_empty() {
-            format!("/static/{resized_name}")
-        } else {
-            format!("/static/{rel_dir_str}/{resized_name}")
-        };
-        srcset_entries.push((width, url));
+        srcset_entries.push((width, url_for(&resized_name)));
+        variants.push((resized_name, width, save_format, config.quality));
 
-        // Save WebP variant
+        // WebP variant
         if config.webp && ext != "webp" {
             let webp_name = format!("{stem}-{width}w.webp");
-            let webp_path = output_base.join(&webp_name);
-            save_image(&resized, &webp_path, ImageFormat::WebP, config.quality)?;
-
-            let webp_url = if rel_dir_str.is_empty() {
-                format!("/static/{webp_name}")
-            } else {
-                format!("/static/{rel_dir_str}/{webp_name}")
-            };
-            webp_entries.push((width, webp_url));
+            webp_entries.push((width, url_for(&webp_name)));
+            variants.push((webp_name, width, ImageFormat::WebP, config.quality));
         }
 
-        // Save AVIF variant
+        // AVIF variant
         if config.avif && ext != "avif" {
             let avif_name = format!("{stem}-{width}w.avif");
-            let avif_path = output_base.join(&avif_name);
-            save_image(&resized, &avif_path, ImageFormat::Avif, avif_quality)?;
-
-            let avif_url = if rel_dir_str.is_empty() {
-                format!("/static/{avif_name}")
-            } else {
-                format!("/static/{rel_dir_str}/{avif_name}")
-            };
-            avif_entries.push((width, avif_url));
+            avif_entries.push((width, url_for(&avif_name)));
+            variants.push((avif_name, width, ImageFormat::Avif, avif_quality));
         }
     }
 
     // Also add the original width as the largest entry
-    let orig_url = format!("/static/{}", rel.to_string_lossy().replace('\\', "/"));
-    srcset_entries.push((original_width, orig_url.clone()));
+    srcset_entries.push((original_width, url_for(file_name)));
 
-    // Generate a full-size WebP if webp is enabled and source isn't already webp
+    // A full-size WebP if webp is enabled and source isn't already webp
     if config.webp && ext != "webp" {
         let webp_name = format!("{stem}.webp");
-        let webp_path = output_base.join(&webp_name);
-        save_image(&img, &webp_path, ImageFormat::WebP, config.quality)?;
-
-        let webp_url = if rel_dir_str.is_empty() {
-            format!("/static/{webp_name}")
-        } else {
-            format!("/static/{rel_dir_str}/{webp_name}")
-        };
-        webp_entries.push((original_width, webp_url));
+        webp_entries.push((original_width, url_for(&webp_name)));
+        variants.push((webp_name, original_width, ImageFormat::WebP, config.quality));
     }
 
-    // Generate a full-size AVIF if avif is enabled and source isn't already avif
+    // A full-size AVIF if avif is enabled and source isn't already avif
     if config.avif && ext != "avif" {
         let avif_name = format!("{stem}.avif");
-        let avif_path = output_base.join(&avif_name);
-        save_image(&img, &avif_path, ImageFormat::Avif, avif_quality)?;
+        avif_entries.push((original_width, url_for(&avif_name)));
+        variants.push((avif_name, original_width, ImageFormat::Avif, avif_quality));
+    }
 
-        let avif_url = if rel_dir_str.is_empty() {
-            format!("/static/{avif_name}")
-        } else {
-            format!("/static/{rel_dir_str}/{avif_name}")
+    // Encode only what the cache doesn't have, resizing once per width
+    let pending: Vec<_> = variants
+        .iter()
+        .map(|(name, width, format, quality)| {
+            let key = image_cache::variant_key(&source_hash, *width, *format, *quality);
+            (key, name, *width, *format, *quality)
+        })
+        .filter(|(key, name, ..)| !image_cache.restore(key, &output_base.join(name)))
+        .collect();
+    if !pending.is_empty() {
+        let img = match img {
+            Some(img) => img,
+            None => open_image(source)?,
         };
-        avif_entries.push((original_width, avif_url));
+        let mut resized: HashMap<u32, image::DynamicImage> = HashMap::new();
+        for (key, name, width, format, quality) in pending {
+            let variant = if width == original_width {
+                &img
+            } else {
+                resized.entry(width).or_insert_with(|| {
+                    let scale = width as f64 / original_width as f64;
+                    let new_height = (original_height as f64 * scale).round() as u32;
+                    img.resize_exact(width, new_height, FilterType::Lanczos3)
+                })
+            };
+            let dest = output_base.join(name);
+            save_image(variant, &dest, format, quality)?;
+            image_cache.store(&key, &dest)?;
+        }
     }
 
     // Sort by width ascending
@@ -218,15 +273,63 @@ fn process_single_image(
     avif_entries.sort_by_key(|(w, _)| *w);
 
     Ok(ProcessedImage {
-        rel_path: rel.to_string_lossy().replace('\\', "/").to_string(),
+        rel_path: rel.to_string(),
         srcset_entries,
         webp_entries,
         avif_entries,
         original_width,
         original_height,
+        placeholder: meta.placeholder,
+        color: meta.color,
+    })
+}
+
+/// Dimensions, placeholder and dominant colour of a decoded source image.
+fn source_meta(img: &image::DynamicImage) -> Result<SourceMeta> {
+    let preview = image::DynamicImage::ImageRgba8(
+        img.thumbnail(PLACEHOLDER_SIZE, PLACEHOLDER_SIZE).to_rgba8(),
+    );
+    let encoded = webp::Encoder::from_image(&preview)
+        .map_err(|e| PageError::Build(format!("failed to encode placeholder: {e}")))?
+        .encode(50.0);
+    Ok(SourceMeta {
+        width: img.width(),
+        height: img.height(),
+        placeholder: format!(
+            "data:image/webp;base64,{}",
+            BASE64_STANDARD.encode(&*encoded)
+        ),
+        color: dominant_color(
+            &img.thumbnail(COLOR_SAMPLE_SIZE, COLOR_SAMPLE_SIZE)
+                .to_rgba8(),
+        ),
     })
 }
 
+/// The most common colour of an image as `#rrggbb`. Pixels are bucketed by
+/// the top four bits of each channel and the fullest bucket's average wins.
+/// Mostly transparent pixels only count if there is nothing else.
+fn dominant_color(img: &image::RgbaImage) -> String {
+    let has_opaque = img.pixels().any(|p| p[3] >= 128);
+    let mut buckets = vec![(0u32, [0u32; 3]); 4096];
+    for p in img.pixels().filter(|p| !has_opaque || p[3] >= 128) {
+        let key =
+            (usize::from(p[0] >> 4) << 8) | (usize::from(p[1] >> 4) << 4) | usize::from(p[2] >> 4);
+        let (count, sums) = &mut buckets[key];
+        *count += 1;
+        for (sum, channel) in sums.iter_mut().zip(p.0) {
+            *sum += u32::from(channel);
+        }
+    }
+    let (count, sums) = buckets
+        .iter()
+        .max_by_key(|(count, _)| *count)
+        .copied()
+        .unwrap_or_default();
+    let [r, g, b] = sums.map(|sum| sum.checked_div(count).unwrap_or(0));
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
 fn save_image(
     img: &image::DynamicImage,
     path: &Path,
@@ -313,12 +416,14 @@ fn save_image(
     Ok(())
 }
 
-/// Rewrite `<img>` tags in HTML to add srcset, loading="lazy", and `<picture>` wrapping.
+/// Rewrite `<img>` tags in HTML to add srcset, loading="lazy", an inline
+/// loading placeholder, and `<picture>` wrapping.
 /// The first image in each page skips `loading="lazy"` to avoid penalizing LCP.
 pub fn rewrite_html_images(
     html: &str,
     manifest: &HashMap<String, ProcessedImage>,
     lazy_loading: bool,
+    placeholder: ImagePlaceholder,
 ) -> String {
     if manifest.is_empty() {
         if lazy_loading {
@@ -345,7 +450,12 @@ pub fn rewrite_html_images(
             if let Some(src) = extract_attr(img_tag, "src") {
                 if let Some(processed) = manifest.get(&src) {
                     // Build the enhanced tag
-                    result.push_str(&build_picture_element(img_tag, processed, use_lazy));
+                    result.push_str(&build_picture_element(
+                        img_tag,
+                        processed,
+                        use_lazy,
+                        placeholder,
+                    ));
                 } else {
                     // No processing for this image, just add lazy loading
                     if use_lazy {
@@ -371,7 +481,7 @@ pub fn rewrite_html_images(
     result
 }
 
-fn extract_attr(tag: &str, attr_name: &str) -> Option<String> {
+pub(crate) fn extract_attr(tag: &str, attr_name: &str) -> Option<String> {
     let search = format!("{attr_name}=\"");
     let start = tag.find(&search)?;
     let value_start = start + search.len();
@@ -380,16 +490,16 @@ fn extract_attr(tag: &str, attr_name: &str) -> Option<String> {
     Some(rest[..end].to_string())
 }
 
-fn build_picture_element(img_tag: &str, processed: &ProcessedImage, lazy_loading: bool) -> String {
+fn build_picture_element(
+    img_tag: &str,
+    processed: &ProcessedImage,
+    lazy_loading: bool,
+    placeholder: ImagePlaceholder,
+) -> String {
     let mut picture = String::new();
 
     // Build srcset string for original format
-    let srcset: Vec<String> = processed
-        .srcset_entries
-        .iter()
-        .map(|(w, url)| format!("{url} {w}w"))
-        .collect();
-    let srcset_str = srcset.join(", ");
+    let srcset_str = srcset(&processed.srcset_entries);
 
     // Build sizes attribute — sensible defaults
     let sizes = "(max-width: 480px) 480px, (max-width: 800px) 800px, 1200px";
@@ -397,66 +507,73 @@ fn build_picture_element(img_tag: &str, processed: &ProcessedImage, lazy_loading
     let has_avif = !processed.avif_entries.is_empty();
     let has_webp = !processed.webp_entries.is_empty();
 
+    // The original <img> with srcset, lazy loading, width/height for layout
+    // stability and the loading placeholder
+    let mut new_tag = add_srcset_to_tag(img_tag, &srcset_str, sizes);
+    if lazy_loading {
+        new_tag = add_lazy_to_tag(&new_tag);
+    }
+    new_tag = add_dimensions_to_tag(
+        &new_tag,
+        processed.original_width,
+        processed.original_height,
+    );
+    if let Some(style) = placeholder_style(processed, placeholder) {
+        new_tag = add_style_to_tag(&new_tag, &style);
+    }
+
     if has_avif || has_webp {
         // Wrap in <picture> with <source> for modern formats
         picture.push_str("<picture>");
 
         // AVIF first (most compressed, browsers pick first supported source)
         if has_avif {
-            let avif_srcset: Vec<String> = processed
-                .avif_entries
-                .iter()
-                .map(|(w, url)| format!("{url} {w}w"))
-                .collect();
             picture.push_str(&format!(
                 "<source type=\"image/avif\" srcset=\"{}\" sizes=\"{sizes}\">",
-                avif_srcset.join(", ")
+                srcset(&processed.avif_entries)
             ));
         }
 
         // WebP second
         if has_webp {
-            let webp_srcset: Vec<String> = processed
-                .webp_entries
-                .iter()
-                .map(|(w, url)| format!("{url} {w}w"))
-                .collect();
             picture.push_str(&format!(
                 "<source type=\"image/webp\" srcset=\"{}\" sizes=\"{sizes}\">",
-                webp_srcset.join(", ")
+                srcset(&processed.webp_entries)
             ));
         }
 
-        // Add the original <img> with srcset and lazy loading
-        let mut new_tag = add_srcset_to_tag(img_tag, &srcset_str, sizes);
-        if lazy_loading {
-            new_tag = add_lazy_to_tag(&new_tag);
-        }
-        // Add width/height for layout stability
-        new_tag = add_dimensions_to_tag(
-            &new_tag,
-            processed.original_width,
-            processed.original_height,
-        );
         picture.push_str(&new_tag);
         picture.push_str("</picture>");
     } else {
-        // No WebP or AVIF, just add srcset to the img tag
-        let mut new_tag = add_srcset_to_tag(img_tag, &srcset_str, sizes);
-        if lazy_loading {
-            new_tag = add_lazy_to_tag(&new_tag);
-        }
-        new_tag = add_dimensions_to_tag(
-            &new_tag,
-            processed.original_width,
-            processed.original_height,
-        );
+        // No WebP or AVIF, just the img tag
         picture.push_str(&new_tag);
     }
 
     picture
 }
 
+/// A srcset list: `url 480w, url 800w`.
+fn srcset(entries: &[(u32, String)]) -> String {
+    entries
+        .iter()
+        .map(|(w, url)| format!("{url} {w}w"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Inline style painting an image's placeholder behind its `<img>`, or
+/// `None` when placeholders are off.
+fn placeholder_style(processed: &ProcessedImage, placeholder: ImagePlaceholder) -> Option<String> {
+    match placeholder {
+        ImagePlaceholder::None => None,
+        ImagePlaceholder::Color => Some(format!("background-color:{}", processed.color)),
+        ImagePlaceholder::Blur => Some(format!(
+            "background:{} url({}) center/cover no-repeat",
+            processed.color, processed.placeholder
+        )),
+    }
+}
+
 fn add_srcset_to_tag(tag: &str, srcset: &str, sizes: &str) -> String {
     // Insert srcset and sizes before the closing >
     if let Some(base) = tag.strip_suffix("/>") {
@@ -492,6 +609,20 @@ fn add_dimensions_to_tag(tag: &str, width: u32, height: u32) -> String {
     }
 }
 
+fn add_style_to_tag(tag: &str, style: &str) -> String {
+    // Prepend to an existing style so the author's declarations win
+    if let Some(pos) = tag.find(" style=\"") {
+        let at = pos + " style=\"".len();
+        return format!("{}{style};{}", &tag[..at], &tag[at..]);
+    }
+    if let Some(base) = tag.strip_suffix("/>") {
+        format!("{base} style=\"{style}\" />")
+    } else {
+        let base = &tag[..tag.len() - 1];
+        format!("{base} style=\"{style}\">")
+    }
+}
+
 /// Add `loading="lazy"` to all `<img>` tags except the first (likely LCP element).
 fn add_lazy_loading(html: &str) -> String {
     let mut result = String::with_capacity(html.len() + 256);
@@ -519,6 +650,48 @@ fn add_lazy_loading(html: &str) -> String {
     result
 }
 
+/// The `image_meta()` Tera function: `image_meta(path="/static/images/hero.jpg")`
+/// returns a processed image's `src`, `width`, `height`, `srcset`,
+/// `webp_srcset`, `avif_srcset`, `placeholder` and `color`, for templates
+/// that lay out images themselves (hero banners, cards). Images the pipeline
+/// didn't process give null.
+pub struct ImageMeta {
+    manifest: HashMap<String, ProcessedImage>,
+}
+
+impl ImageMeta {
+    pub fn new(manifest: HashMap<String, ProcessedImage>) -> Self {
+        Self { manifest }
+    }
+}
+
+impl tera::Function for ImageMeta {
+    fn call(&self, args: &HashMap<String, tera::Value>) -> tera::Result<tera::Value> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| tera::Error::msg("image_meta() requires a `path` string argument"))?;
+        let path = if path.starts_with('/') || path.contains("://") {
+            path.to_string()
+        } else {
+            format!("/{path}")
+        };
+        let Some(processed) = self.manifest.get(&path) else {
+            return Ok(tera::Value::Null);
+        };
+        Ok(serde_json::json!({
+            "src": path,
+            "width": processed.original_width,
+            "height": processed.original_height,
+            "srcset": srcset(&processed.srcset_entries),
+            "webp_srcset": srcset(&processed.webp_entries),
+            "avif_srcset": srcset(&processed.avif_entries),
+            "placeholder": processed.placeholder,
+            "color": processed.color,
+        }))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -623,8 +796,10 @@ mod tests {
             avif_entries: vec![],
             original_width: 800,
             original_height: 600,
+            placeholder: String::new(),
+            color: String::new(),
         };
-        let result = build_picture_element(img, &processed, true);
+        let result = build_picture_element(img, &processed, true, ImagePlaceholder::None);
         assert!(result.starts_with("<picture>"));
         assert!(result.ends_with("</picture>"));
         assert!(result.contains("image/webp"));
@@ -642,8 +817,10 @@ mod tests {
             avif_entries: vec![],
             original_width: 1200,
             original_height: 800,
+            placeholder: String::new(),
+            color: String::new(),
         };
-        let result = build_picture_element(img, &processed, false);
+        let result = build_picture_element(img, &processed, false, ImagePlaceholder::None);
         assert!(!result.contains("<picture>"));
         assert!(result.contains("srcset=\""));
         assert!(!result.contains("loading="));
@@ -666,8 +843,10 @@ mod tests {
             ],
             original_width: 800,
             original_height: 600,
+            placeholder: String::new(),
+            color: String::new(),
         };
-        let result = build_picture_element(img, &processed, true);
+        let result = build_picture_element(img, &processed, true, ImagePlaceholder::None);
         assert!(result.starts_with("<picture>"));
         assert!(result.ends_with("</picture>"));
         // AVIF source should appear before WebP
@@ -691,8 +870,10 @@ mod tests {
             avif_entries: vec![(480, "/photo-480w.avif".into())],
             original_width: 800,
             original_height: 600,
+            placeholder: String::new(),
+            color: String::new(),
         };
-        let result = build_picture_element(img, &processed, false);
+        let result = build_picture_element(img, &processed, false, ImagePlaceholder::None);
         assert!(result.contains("<picture>"));
         assert!(result.contains("image/avif"));
         assert!(!result.contains("image/webp"));
@@ -713,10 +894,12 @@ mod tests {
                 avif_entries: vec![(480, "/static/photo-480w.avif".into())],
                 original_width: 800,
                 original_height: 600,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         let html = r#"<img src="/static/photo.jpg" alt="photo">"#;
-        let result = rewrite_html_images(html, &manifest, false);
+        let result = rewrite_html_images(html, &manifest, false, ImagePlaceholder::None);
         assert!(result.contains("<picture>"));
         assert!(result.contains("image/avif"));
         assert!(result.contains("image/webp"));
@@ -757,7 +940,7 @@ mod tests {
     fn test_rewrite_html_images_empty_manifest_no_lazy() {
         let html = r#"<p>Hello</p><img src="/static/photo.jpg" alt="pic"><p>End</p>"#;
         let manifest = HashMap::new();
-        let result = rewrite_html_images(html, &manifest, false);
+        let result = rewrite_html_images(html, &manifest, false, ImagePlaceholder::None);
         // With no manifest and lazy_loading=false, html is returned unchanged
         assert_eq!(result, html);
     }
@@ -766,7 +949,7 @@ mod tests {
     fn test_rewrite_html_images_empty_manifest_with_lazy() {
         let html = r#"<img src="/photo.jpg" alt="pic">"#;
         let manifest = HashMap::new();
-        let result = rewrite_html_images(html, &manifest, true);
+        let result = rewrite_html_images(html, &manifest, true, ImagePlaceholder::None);
         // Delegates to add_lazy_loading — first image is skipped for LCP
         assert!(!result.contains(r#"loading="lazy""#));
         assert!(result.contains(r#"src="/photo.jpg""#));
@@ -790,10 +973,12 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 1200,
                 original_height: 800,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         let html = r#"<p>Text</p><img src="/static/photo.jpg" alt="photo"><p>More</p>"#;
-        let result = rewrite_html_images(html, &manifest, true);
+        let result = rewrite_html_images(html, &manifest, true, ImagePlaceholder::None);
         assert!(result.contains("<picture>"));
         assert!(result.contains("</picture>"));
         assert!(result.contains("image/webp"));
@@ -817,10 +1002,12 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 800,
                 original_height: 600,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         let html = r#"<img src="/static/unknown.jpg" alt="not in manifest">"#;
-        let result = rewrite_html_images(html, &manifest, true);
+        let result = rewrite_html_images(html, &manifest, true, ImagePlaceholder::None);
         // Not in manifest, first image skipped for LCP — no lazy loading
         assert!(!result.contains(r#"loading="lazy""#));
         assert!(!result.contains("<picture>"));
@@ -839,10 +1026,12 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 800,
                 original_height: 600,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         let html = r#"<img src="/static/unknown.jpg" alt="not in manifest">"#;
-        let result = rewrite_html_images(html, &manifest, false);
+        let result = rewrite_html_images(html, &manifest, false, ImagePlaceholder::None);
         // Not in manifest, no lazy loading → tag unchanged
         assert_eq!(
             result,
@@ -862,11 +1051,13 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 100,
                 original_height: 100,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         // img tag without src — should be left as-is
         let html = r#"<img alt="no source">"#;
-        let result = rewrite_html_images(html, &manifest, false);
+        let result = rewrite_html_images(html, &manifest, false, ImagePlaceholder::None);
         assert_eq!(result, html);
     }
 
@@ -882,11 +1073,13 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 100,
                 original_height: 100,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         // img tag without closing > — malformed path
         let html = r#"<p>Before</p><img src="/static/photo.jpg" alt="oops"#;
-        let result = rewrite_html_images(html, &manifest, false);
+        let result = rewrite_html_images(html, &manifest, false, ImagePlaceholder::None);
         // Should copy everything after the <img  as-is
         assert!(result.contains("<p>Before</p>"));
         assert!(result.contains("<img src="));
@@ -904,11 +1097,13 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 480,
                 original_height: 320,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         // Two images: first is in manifest, second is not
         let html = r#"<img src="/static/a.jpg" alt="first"><img src="/static/b.jpg" alt="second">"#;
-        let result = rewrite_html_images(html, &manifest, false);
+        let result = rewrite_html_images(html, &manifest, false, ImagePlaceholder::None);
         // First image gets srcset
         assert!(result.contains("srcset="));
         // Second image stays as-is (no lazy, no srcset)
@@ -927,10 +1122,12 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 100,
                 original_height: 100,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         let html = "<p>No images at all</p>";
-        let result = rewrite_html_images(html, &manifest, true);
+        let result = rewrite_html_images(html, &manifest, true, ImagePlaceholder::None);
         assert_eq!(result, html);
     }
 
@@ -949,10 +1146,12 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 960,
                 original_height: 540,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         let html = r#"<header><h1>Title</h1></header><img src="/static/hero.png" alt="hero"><footer>End</footer>"#;
-        let result = rewrite_html_images(html, &manifest, false);
+        let result = rewrite_html_images(html, &manifest, false, ImagePlaceholder::None);
         assert!(result.starts_with("<header><h1>Title</h1></header>"));
         assert!(result.ends_with("<footer>End</footer>"));
         assert!(result.contains("srcset="));
@@ -975,8 +1174,10 @@ mod tests {
             avif_entries: vec![],
             original_width: 800,
             original_height: 600,
+            placeholder: String::new(),
+            color: String::new(),
         };
-        let result = build_picture_element(img, &processed, false);
+        let result = build_picture_element(img, &processed, false, ImagePlaceholder::None);
         assert!(result.starts_with("<picture>"));
         assert!(result.ends_with("</picture>"));
         assert!(result.contains("image/webp"));
@@ -996,8 +1197,10 @@ mod tests {
             avif_entries: vec![],
             original_width: 1200,
             original_height: 800,
+            placeholder: String::new(),
+            color: String::new(),
         };
-        let result = build_picture_element(img, &processed, true);
+        let result = build_picture_element(img, &processed, true, ImagePlaceholder::None);
         // No <picture> wrapping when there are no webp entries
         assert!(!result.contains("<picture>"));
         assert!(result.contains("srcset="));
@@ -1016,8 +1219,10 @@ mod tests {
             avif_entries: vec![],
             original_width: 500,
             original_height: 300,
+            placeholder: String::new(),
+            color: String::new(),
         };
-        let result = build_picture_element(img, &processed, true);
+        let result = build_picture_element(img, &processed, true, ImagePlaceholder::None);
         assert!(result.contains("srcset="));
         assert!(result.contains(r#"loading="lazy""#));
         assert!(result.contains(r#"width="500""#));
@@ -1036,8 +1241,10 @@ mod tests {
             avif_entries: vec![],
             original_width: 1000,
             original_height: 700,
+            placeholder: String::new(),
+            color: String::new(),
         };
-        let result = build_picture_element(img, &processed, true);
+        let result = build_picture_element(img, &processed, true, ImagePlaceholder::None);
         // Should not add a second loading attribute
         assert!(result.contains(r#"loading="eager""#));
         assert!(!result.contains(r#"loading="lazy""#));
@@ -1054,8 +1261,10 @@ mod tests {
             avif_entries: vec![],
             original_width: 1000,
             original_height: 700,
+            placeholder: String::new(),
+            color: String::new(),
         };
-        let result = build_picture_element(img, &processed, false);
+        let result = build_picture_element(img, &processed, false, ImagePlaceholder::None);
         assert!(result.contains(r#"width="100""#));
         assert!(result.contains(r#"height="50""#));
         // Should NOT contain the processed dimensions
@@ -1072,8 +1281,10 @@ mod tests {
             avif_entries: vec![],
             original_width: 1000,
             original_height: 750,
+            placeholder: String::new(),
+            color: String::new(),
         };
-        let result = build_picture_element(img, &processed, false);
+        let result = build_picture_element(img, &processed, false, ImagePlaceholder::None);
         assert!(result.starts_with("<picture>"));
         assert!(result.contains(r#"srcset="/photo.jpg 1000w""#));
         assert!(result.contains(r#"srcset="/photo.webp 1000w""#));
@@ -1097,8 +1308,10 @@ mod tests {
             avif_entries: vec![],
             original_width: 1200,
             original_height: 900,
+            placeholder: String::new(),
+            color: String::new(),
         };
-        let result = build_picture_element(img, &processed, true);
+        let result = build_picture_element(img, &processed, true, ImagePlaceholder::None);
         // Check srcset has all entries comma-separated
         assert!(result.contains("/photo-480w.jpg 480w"));
         assert!(result.contains("/photo-800w.jpg 800w"));
@@ -1285,10 +1498,12 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 480,
                 original_height: 320,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         let html = r#"<img src="/static/photo.jpg" alt="test" />"#;
-        let result = rewrite_html_images(html, &manifest, false);
+        let result = rewrite_html_images(html, &manifest, false, ImagePlaceholder::None);
         assert!(result.contains("srcset="));
         assert!(result.contains("/>"));
     }
@@ -1311,6 +1526,8 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 1000,
                 original_height: 667,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         let html = concat!(
@@ -1318,7 +1535,7 @@ mod tests {
             r#"<img src="/static/known.jpg" alt="known">"#,
             r#"<img src="/static/other.png" alt="other">"#,
         );
-        let result = rewrite_html_images(html, &manifest, true);
+        let result = rewrite_html_images(html, &manifest, true, ImagePlaceholder::None);
         // First: not in manifest, skipped for LCP (no lazy)
         assert!(result.contains(r#"src="/external.jpg""#));
         // Second: in manifest, gets <picture> + srcset + webp + lazy + dimensions
@@ -1342,6 +1559,8 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 800,
                 original_height: 600,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         manifest.insert(
@@ -1353,10 +1572,12 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 600,
                 original_height: 400,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         let html = r#"<img src="/static/a.jpg"><img src="/static/b.jpg">"#;
-        let result = rewrite_html_images(html, &manifest, false);
+        let result = rewrite_html_images(html, &manifest, false, ImagePlaceholder::None);
         assert!(result.contains(r#"width="800""#));
         assert!(result.contains(r#"width="600""#));
         assert!(result.contains(r#"height="600""#));
@@ -1366,7 +1587,7 @@ mod tests {
     #[test]
     fn test_rewrite_html_images_empty_html() {
         let manifest = HashMap::new();
-        let result = rewrite_html_images("", &manifest, true);
+        let result = rewrite_html_images("", &manifest, true, ImagePlaceholder::None);
         assert_eq!(result, "");
     }
 
@@ -1382,9 +1603,11 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 100,
                 original_height: 100,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
-        let result = rewrite_html_images("", &manifest, false);
+        let result = rewrite_html_images("", &manifest, false, ImagePlaceholder::None);
         assert_eq!(result, "");
     }
 
@@ -1393,7 +1616,7 @@ mod tests {
         // Ensure that "img" in text doesn't confuse the parser — only "<img " triggers parsing
         let manifest = HashMap::new();
         let html = "<p>We display img tags here, but not <img src=\"/a.jpg\"></p>";
-        let result = rewrite_html_images(html, &manifest, true);
+        let result = rewrite_html_images(html, &manifest, true, ImagePlaceholder::None);
         // The <img tag is the first image, so it's skipped for LCP
         assert!(!result.contains(r#"loading="lazy""#));
         assert!(result.contains(r#"src="/a.jpg""#));
@@ -1403,6 +1626,10 @@ mod tests {
     // process_images — filesystem-based tests with tempdir
     // ---------------------------------------------------------------
 
+    fn test_cache(paths: &ResolvedPaths) -> ImageCache {
+        ImageCache::open(&paths.root, &paths.output)
+    }
+
     #[test]
     fn test_process_images_nonexistent_static_dir() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1414,9 +1641,10 @@ mod tests {
             static_dir: tmp.path().join("nonexistent_static"),
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = ImageSection::default();
-        let result = process_images(&paths, &config).unwrap();
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
         assert!(result.is_empty());
     }
 
@@ -1432,9 +1660,10 @@ mod tests {
             static_dir: tmp.path().join("static"),
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = ImageSection::default();
-        let result = process_images(&paths, &config).unwrap();
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
         assert!(result.is_empty());
     }
 
@@ -1455,9 +1684,10 @@ mod tests {
             static_dir,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = ImageSection::default();
-        let result = process_images(&paths, &config).unwrap();
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
         assert!(result.is_empty());
     }
 
@@ -1476,10 +1706,11 @@ mod tests {
             static_dir,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = ImageSection::default();
         // Should not fail — just warns and skips the invalid image
-        let result = process_images(&paths, &config).unwrap();
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
         assert!(result.is_empty());
     }
 
@@ -1505,6 +1736,7 @@ mod tests {
             static_dir,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = ImageSection {
             widths: vec![50],
@@ -1513,7 +1745,7 @@ mod tests {
             webp: true,
             ..Default::default()
         };
-        let result = process_images(&paths, &config).unwrap();
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
         assert_eq!(result.len(), 1);
         let processed = result.get("/static/test.png").unwrap();
         assert_eq!(processed.rel_path, "test.png");
@@ -1527,6 +1759,208 @@ mod tests {
         assert_eq!(processed.webp_entries.len(), 2);
     }
 
+    #[test]
+    fn test_process_images_reuses_cached_variants() {
+        let tmp = tempfile::tempdir().unwrap();
+        let static_dir = tmp.path().join("static");
+        fs::create_dir_all(&static_dir).unwrap();
+        let img = image::RgbaImage::from_fn(100, 80, |_, _| image::Rgba([9, 9, 9, 255]));
+        img.save(static_dir.join("a.png")).unwrap();
+        let paths = ResolvedPaths {
+            root: tmp.path().to_path_buf(),
+            output: tmp.path().join("dist"),
+            content: tmp.path().join("content"),
+            templates: tmp.path().join("templates"),
+            static_dir,
+            data_dir: tmp.path().join("data"),
+            public_dir: tmp.path().join("public"),
+            shared: Default::default(),
+        };
+        let config = ImageSection {
+            widths: vec![50],
+            webp: true,
+            ..Default::default()
+        };
+        let cache = test_cache(&paths);
+        process_images(&paths, &config, &cache).unwrap();
+        cache.finish().unwrap();
+
+        // Doctor the cached 50w WebP: a second build copies it instead of encoding
+        let source_hash = cache::hash_bytes(&fs::read(paths.static_dir.join("a.png")).unwrap());
+        let key = image_cache::variant_key(&source_hash, 50, ImageFormat::WebP, config.quality);
+        let entry = tmp.path().join(".seite/cache/images/entries").join(&key);
+        assert!(entry.exists());
+        fs::write(&entry, "cached").unwrap();
+        fs::remove_dir_all(&paths.output).unwrap();
+
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
+        assert_eq!(result["/static/a.png"].original_height, 80);
+        assert_eq!(
+            fs::read_to_string(paths.output.join("static/a-50w.webp")).unwrap(),
+            "cached"
+        );
+        assert!(paths.output.join("static/a-50w.png").exists());
+
+        // Other settings are other variants
+        let config = ImageSection {
+            quality: config.quality - 1,
+            ..config
+        };
+        process_images(&paths, &config, &test_cache(&paths)).unwrap();
+        assert_ne!(
+            fs::read(paths.output.join("static/a-50w.webp")).unwrap(),
+            b"cached"
+        );
+    }
+
+    #[test]
+    fn test_dominant_color_picks_fullest_bucket() {
+        // Three quarters red, one quarter blue, with a transparent corner
+        let img = image::RgbaImage::from_fn(8, 8, |x, y| match (x, y) {
+            (0, 0) => image::Rgba([0, 255, 0, 0]),
+            (_, y) if y < 6 => image::Rgba([200, 10, 10, 255]),
+            _ => image::Rgba([10, 10, 200, 255]),
+        });
+        assert_eq!(dominant_color(&img), "#c80a0a");
+
+        let clear = image::RgbaImage::from_pixel(4, 4, image::Rgba([0, 255, 0, 0]));
+        assert_eq!(dominant_color(&clear), "#00ff00");
+    }
+
+    #[test]
+    fn test_process_images_computes_placeholder_and_color() {
+        let tmp = tempfile::tempdir().unwrap();
+        let static_dir = tmp.path().join("static");
+        fs::create_dir_all(&static_dir).unwrap();
+        let img = image::RgbImage::from_fn(100, 50, |_, _| image::Rgb([16, 32, 48]));
+        img.save(static_dir.join("a.png")).unwrap();
+        let paths = ResolvedPaths {
+            root: tmp.path().to_path_buf(),
+            output: tmp.path().join("dist"),
+            content: tmp.path().join("content"),
+            templates: tmp.path().join("templates"),
+            static_dir,
+            data_dir: tmp.path().join("data"),
+            public_dir: tmp.path().join("public"),
+            shared: Default::default(),
+        };
+        let config = ImageSection {
+            widths: vec![50],
+            ..Default::default()
+        };
+        let cache = test_cache(&paths);
+        let cold = process_images(&paths, &config, &cache).unwrap();
+        cache.finish().unwrap();
+        let processed = &cold["/static/a.png"];
+        assert_eq!(processed.color, "#102030");
+        let preview = processed
+            .placeholder
+            .strip_prefix("data:image/webp;base64,")
+            .unwrap();
+        let preview = image::load_from_memory(&BASE64_STANDARD.decode(preview).unwrap()).unwrap();
+        assert_eq!((preview.width(), preview.height()), (16, 8));
+
+        // A warm cache gives the same metadata without decoding
+        let warm = process_images(&paths, &config, &test_cache(&paths)).unwrap();
+        assert_eq!(warm["/static/a.png"].placeholder, processed.placeholder);
+        assert_eq!(warm["/static/a.png"].color, processed.color);
+    }
+
+    #[test]
+    fn test_build_picture_element_placeholder_styles() {
+        let processed = ProcessedImage {
+            rel_path: "a.jpg".into(),
+            srcset_entries: vec![(100, "/static/a.jpg".into())],
+            webp_entries: vec![],
+            avif_entries: vec![],
+            original_width: 100,
+            original_height: 50,
+            placeholder: "data:image/webp;base64,AAAA".into(),
+            color: "#102030".into(),
+        };
+        let img = r#"<img src="/static/a.jpg" alt="A">"#;
+        let none = build_picture_element(img, &processed, false, ImagePlaceholder::None);
+        assert!(!none.contains("style="));
+        let color = build_picture_element(img, &processed, false, ImagePlaceholder::Color);
+        assert!(color.contains(r#" style="background-color:#102030">"#));
+        let blur = build_picture_element(img, &processed, false, ImagePlaceholder::Blur);
+        assert!(blur.contains(
+            r#" style="background:#102030 url(data:image/webp;base64,AAAA) center/cover no-repeat">"#
+        ));
+
+        // An author's style keeps the last word
+        let styled = r#"<img src="/static/a.jpg" style="border:0" />"#;
+        let result = build_picture_element(styled, &processed, false, ImagePlaceholder::Color);
+        assert!(result.contains(r#"style="background-color:#102030;border:0""#));
+    }
+
+    #[test]
+    fn test_image_meta_function() {
+        use tera::Function;
+        let mut manifest = HashMap::new();
+        manifest.insert(
+            "/static/hero.jpg".to_string(),
+            ProcessedImage {
+                rel_path: "hero.jpg".into(),
+                srcset_entries: vec![(480, "/static/hero-480w.jpg".into())],
+                webp_entries: vec![(480, "/static/hero-480w.webp".into())],
+                avif_entries: vec![],
+                original_width: 1600,
+                original_height: 900,
+                placeholder: "data:image/webp;base64,AAAA".into(),
+                color: "#102030".into(),
+            },
+        );
+        let f = ImageMeta::new(manifest);
+        let mut args = HashMap::new();
+        args.insert("path".to_string(), tera::Value::from("static/hero.jpg"));
+        let meta = f.call(&args).unwrap();
+        assert_eq!(meta["src"], "/static/hero.jpg");
+        assert_eq!(meta["width"], 1600);
+        assert_eq!(meta["height"], 900);
+        assert_eq!(meta["srcset"], "/static/hero-480w.jpg 480w");
+        assert_eq!(meta["webp_srcset"], "/static/hero-480w.webp 480w");
+        assert_eq!(meta["avif_srcset"], "");
+        assert_eq!(meta["color"], "#102030");
+        args.insert("path".to_string(), tera::Value::from("/static/other.jpg"));
+        assert_eq!(f.call(&args).unwrap(), tera::Value::Null);
+        assert!(f.call(&HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_process_bundle_images_writes_next_to_page() {
+        let tmp = tempfile::tempdir().unwrap();
+        let bundle = tmp.path().join("content/posts/my-post");
+        fs::create_dir_all(&bundle).unwrap();
+        let img = image::RgbaImage::from_fn(100, 80, |_, _| image::Rgba([0, 0, 0, 255]));
+        img.save(bundle.join("cover.png")).unwrap();
+        fs::write(bundle.join("notes.pdf"), "pdf").unwrap();
+
+        let config = ImageSection {
+            widths: vec![50],
+            webp: false,
+            ..Default::default()
+        };
+        let dist = tmp.path().join("dist");
+        let result = process_bundle_images(
+            &[
+                (bundle.join("cover.png"), "/posts/my-post/cover.png".into()),
+                (bundle.join("notes.pdf"), "/posts/my-post/notes.pdf".into()),
+            ],
+            &dist,
+            &config,
+            &ImageCache::open(tmp.path(), &dist),
+        );
+        assert_eq!(result.len(), 1);
+        let processed = &result["/posts/my-post/cover.png"];
+        assert_eq!(processed.rel_path, "cover.png");
+        assert_eq!(
+            processed.srcset_entries[0],
+            (50, "/posts/my-post/cover-50w.png".to_string())
+        );
+        assert!(dist.join("posts/my-post/cover-50w.png").exists());
+    }
+
     #[test]
     fn test_process_images_skips_widths_larger_than_original() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1546,6 +1980,7 @@ mod tests {
             static_dir,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = ImageSection {
             widths: vec![480, 800, 1200], // All larger than the 50px image
@@ -1554,7 +1989,7 @@ mod tests {
             webp: true,
             ..Default::default()
         };
-        let result = process_images(&paths, &config).unwrap();
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
         let processed = result.get("/static/small.png").unwrap();
         // Only the original (50w) should be in srcset — all configured widths are larger
         assert_eq!(processed.srcset_entries.len(), 1);
@@ -1585,7 +2020,7 @@ mod tests {
 
         // Test process_single_image directly with widths all larger than original
         // so no resize happens (avoids JPEG RGBA8 encoding limitation)
-        let rel = Path::new("photo.jpg");
+        let rel = "photo.jpg";
         let config = ImageSection {
             widths: vec![480, 800], // all larger than 100px
             quality: 75,
@@ -1593,9 +2028,16 @@ mod tests {
             webp: true,
             ..Default::default()
         };
-        let result =
-            process_single_image(&jpeg_path, rel, &tmp.path().join("dist"), &config, "jpg")
-                .unwrap();
+        let result = process_single_image(
+            &jpeg_path,
+            rel,
+            "static",
+            &tmp.path().join("dist"),
+            &config,
+            "jpg",
+            &ImageCache::open(tmp.path(), &tmp.path().join("dist")),
+        )
+        .unwrap();
         assert_eq!(result.original_width, 100);
         assert_eq!(result.original_height, 80);
         // Only the original entry (no resizes since all widths > original)
@@ -1628,6 +2070,7 @@ mod tests {
             static_dir,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = ImageSection {
             widths: vec![50],
@@ -1636,7 +2079,7 @@ mod tests {
             webp: true, // webp is enabled but source is already webp
             ..Default::default()
         };
-        let result = process_images(&paths, &config).unwrap();
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
         let processed = result.get("/static/photo.webp").unwrap();
         // No WebP variants should be generated when source is already WebP
         assert!(processed.webp_entries.is_empty());
@@ -1662,6 +2105,7 @@ mod tests {
             static_dir,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = ImageSection {
             widths: vec![50],
@@ -1670,7 +2114,7 @@ mod tests {
             webp: false, // WebP disabled
             ..Default::default()
         };
-        let result = process_images(&paths, &config).unwrap();
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
         let processed = result.get("/static/test.png").unwrap();
         // No WebP entries when webp is disabled
         assert!(processed.webp_entries.is_empty());
@@ -1695,6 +2139,7 @@ mod tests {
             static_dir,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = ImageSection {
             widths: vec![50],
@@ -1703,7 +2148,7 @@ mod tests {
             webp: false,
             ..Default::default()
         };
-        let result = process_images(&paths, &config).unwrap();
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
         // Key should include the subdirectory path
         assert!(result.contains_key("/static/images/gallery/photo.png"));
         let processed = result.get("/static/images/gallery/photo.png").unwrap();
@@ -1733,6 +2178,7 @@ mod tests {
             static_dir,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = ImageSection {
             widths: vec![], // No resize widths
@@ -1741,7 +2187,7 @@ mod tests {
             webp: true,
             ..Default::default()
         };
-        let result = process_images(&paths, &config).unwrap();
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
         let processed = result.get("/static/test.png").unwrap();
         // Only original width in srcset (no resizes)
         assert_eq!(processed.srcset_entries.len(), 1);
@@ -1770,6 +2216,7 @@ mod tests {
             static_dir,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = ImageSection {
             widths: vec![150, 50, 100], // Unsorted widths
@@ -1778,7 +2225,7 @@ mod tests {
             webp: true,
             ..Default::default()
         };
-        let result = process_images(&paths, &config).unwrap();
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
         let processed = result.get("/static/test.png").unwrap();
         // Entries should be sorted by width ascending
         let widths: Vec<u32> = processed.srcset_entries.iter().map(|(w, _)| *w).collect();
@@ -1808,6 +2255,7 @@ mod tests {
             static_dir,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = ImageSection {
             widths: vec![50],
@@ -1816,7 +2264,7 @@ mod tests {
             webp: false,
             ..Default::default()
         };
-        let result = process_images(&paths, &config).unwrap();
+        let result = process_images(&paths, &config, &test_cache(&paths)).unwrap();
         assert_eq!(result.len(), 2);
         assert!(result.contains_key("/static/a.png"));
         assert!(result.contains_key("/static/b.png"));
@@ -1835,6 +2283,8 @@ mod tests {
             avif_entries: vec![],
             original_width: 1200,
             original_height: 800,
+            placeholder: String::new(),
+            color: String::new(),
         };
         let cloned = p.clone();
         assert_eq!(cloned.rel_path, p.rel_path);
@@ -1853,6 +2303,8 @@ mod tests {
             avif_entries: vec![],
             original_width: 100,
             original_height: 50,
+            placeholder: String::new(),
+            color: String::new(),
         };
         let debug = format!("{:?}", p);
         assert!(debug.contains("ProcessedImage"));
@@ -1885,11 +2337,13 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 1200,
                 original_height: 800,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
 
         let html = r#"<article><h1>Post</h1><img src="/static/images/hero.jpg" alt="Hero image"><p>Content</p></article>"#;
-        let result = rewrite_html_images(html, &manifest, true);
+        let result = rewrite_html_images(html, &manifest, true, ImagePlaceholder::None);
 
         // Structure checks
         assert!(result.contains("<article><h1>Post</h1>"));
@@ -1929,10 +2383,12 @@ mod tests {
                 avif_entries: vec![],
                 original_width: 100,
                 original_height: 100,
+                placeholder: String::new(),
+                color: String::new(),
             },
         );
         let html = r#"<img src="/static/a.jpg">trailing text here"#;
-        let result = rewrite_html_images(html, &manifest, false);
+        let result = rewrite_html_images(html, &manifest, false, ImagePlaceholder::None);
         assert!(result.ends_with("trailing text here"));
         assert!(result.contains("srcset="));
     }
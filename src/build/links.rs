@@ -21,8 +21,25 @@ pub struct LinkCheckResult {
     pub total_links_checked: usize,
     /// Broken links grouped by target href. Each entry lists all source files linking to it.
     pub broken_links: Vec<BrokenLink>,
+    /// Links into other workspace sites. These can only be validated once every
+    /// site is built, so they are collected here and checked by the workspace build.
+    pub cross_site_links: Vec<CrossSiteLink>,
 }
 
+/// A link into another site of the same workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossSiteLink {
+    /// Relative path of the HTML file containing the link.
+    pub source_file: String,
+    /// Name of the target workspace site.
+    pub site: String,
+    /// Root-relative path within the target site (e.g., "/docs/getting-started").
+    pub path: String,
+}
+
+/// Prefix for links into other workspace sites: `@site:<name>/<path>`.
+pub const CROSS_SITE_PREFIX: &str = "@site:";
+
 /// Check all internal links in HTML files under `output_dir`.
 ///
 /// Walks every `.html` file, extracts `href="/..."` values, and validates each
@@ -64,6 +81,7 @@ pub fn check_internal_links(output_dir: &Path) -> Result<LinkCheckResult> {
     Ok(LinkCheckResult {
         total_links_checked: total_checked,
         broken_links: broken,
+        cross_site_links: Vec::new(),
     })
 }
 
@@ -73,7 +91,7 @@ pub fn check_internal_links(output_dir: &Path) -> Result<LinkCheckResult> {
 /// - Exact file path: `/feed.xml`, `/static/style.css`
 /// - Clean URL for `.html` files: `/posts/hello-world` (from `posts/hello-world.html`)
 /// - Directory index variants: `/posts/` and `/posts` (from `posts/index.html`)
-fn build_valid_urls(output_dir: &Path) -> HashSet<String> {
+pub fn build_valid_urls(output_dir: &Path) -> HashSet<String> {
     let entries: Vec<_> = WalkDir::new(output_dir)
         .into_iter()
         .filter_map(|e| e.ok())
@@ -185,7 +203,123 @@ pub fn rewrite_subdomain_links(html: &str, rewrites: &HashMap<String, String>) -
     if rewrites.is_empty() {
         return html.to_string();
     }
+    rewrite_hrefs(html, |href| rewrite_href(href, rewrites))
+}
+
+/// Resolve `@site:<name>/<path>` links against the workspace site base URLs.
+///
+/// With `{"docs" => "https://docs.example.com"}`:
+/// - `href="@site:docs/getting-started"` → `href="https://docs.example.com/getting-started"`
+/// - `href="@site:docs"` → `href="https://docs.example.com/"`
+///
+/// Links naming an unknown site are left untouched so the link checker can report them.
+pub fn rewrite_cross_site_links(html: &str, sites: &HashMap<String, String>) -> String {
+    if sites.is_empty() || !html.contains(CROSS_SITE_PREFIX) {
+        return html.to_string();
+    }
+    rewrite_hrefs(html, |href| resolve_cross_site_href(href, sites))
+}
+
+/// Resolve a single `@site:<name>/<path>` href to an absolute URL.
+pub fn resolve_cross_site_href(href: &str, sites: &HashMap<String, String>) -> Option<String> {
+    let (name, rest) = split_cross_site_href(href)?;
+    let base = sites.get(name)?.trim_end_matches('/');
+    if rest.starts_with('/') {
+        Some(format!("{base}{rest}"))
+    } else {
+        Some(format!("{base}/{rest}"))
+    }
+}
+
+/// Split `@site:<name><rest>` into the site name and the remainder
+/// (path, query and fragment).
+fn split_cross_site_href(href: &str) -> Option<(&str, &str)> {
+    let spec = href.strip_prefix(CROSS_SITE_PREFIX)?;
+    let split = spec.find(['/', '#', '?']).unwrap_or(spec.len());
+    let (name, rest) = spec.split_at(split);
+    if name.is_empty() {
+        return None;
+    }
+    Some((name, rest))
+}
+
+/// Extract links into other workspace sites from an HTML string.
+///
+/// Matches hrefs that start with another site's base URL (longest base URL wins,
+/// so nested sites resolve correctly) as well as `@site:` links that could not be
+/// resolved. Links into the site that owns `own_base_url` are skipped — the
+/// internal link check covers those.
+pub fn extract_cross_site_links(
+    html: &str,
+    sites: &HashMap<String, String>,
+    own_base_url: &str,
+) -> Vec<(String, String)> {
+    let own_base = own_base_url.trim_end_matches('/');
+    let mut links = Vec::new();
+    let mut seen = HashSet::new();
+
+    for href in href_values(html) {
+        let target = if let Some((name, rest)) = split_cross_site_href(href) {
+            Some((name.to_string(), rest))
+        } else {
+            sites
+                .iter()
+                .filter_map(|(name, base)| {
+                    let base = base.trim_end_matches('/');
+                    let rest = href.strip_prefix(base)?;
+                    let boundary = rest.is_empty() || rest.starts_with(['/', '#', '?']);
+                    boundary.then_some((name, base, rest))
+                })
+                .max_by_key(|(_, base, _)| base.len())
+                .filter(|(_, base, _)| *base != own_base)
+                .map(|(name, _, rest)| (name.clone(), rest))
+        };
+
+        if let Some((site, rest)) = target {
+            let (path, _) = split_href_suffix(rest);
+            let path = if path.starts_with('/') {
+                path.to_string()
+            } else {
+                format!("/{path}")
+            };
+            if seen.insert((site.clone(), path.clone())) {
+                links.push((site, path));
+            }
+        }
+    }
+
+    links
+}
+
+/// Collect every quoted `href` attribute value in an HTML string.
+fn href_values(html: &str) -> Vec<&str> {
+    let mut values = Vec::new();
+    let bytes = html.as_bytes();
+    let len = bytes.len();
+    let mut pos = 0;
+
+    while let Some(idx) = html[pos..].find("href=") {
+        let attr_start = pos + idx + 5;
+        if attr_start >= len {
+            break;
+        }
+        let quote = bytes[attr_start];
+        if quote == b'"' || quote == b'\'' {
+            let val_start = attr_start + 1;
+            if let Some(end_offset) = html[val_start..].find(quote as char) {
+                values.push(&html[val_start..val_start + end_offset]);
+                pos = val_start + end_offset + 1;
+                continue;
+            }
+        }
+        pos = attr_start;
+    }
 
+    values
+}
+
+/// Rewrite every quoted `href` attribute value for which `rewrite` returns `Some`.
+fn rewrite_hrefs(html: &str, rewrite: impl Fn(&str) -> Option<String>) -> String {
     let mut result = String::with_capacity(html.len());
     let bytes = html.as_bytes();
     let len = bytes.len();
@@ -209,7 +343,7 @@ pub fn rewrite_subdomain_links(html: &str, rewrites: &HashMap<String, String>) -
                     if let Some(end_offset) = html[val_start..].find(quote as char) {
                         let href = &html[val_start..val_start + end_offset];
 
-                        if let Some(rewritten) = rewrite_href(href, rewrites) {
+                        if let Some(rewritten) = rewrite(href) {
                             result.push_str(&rewritten);
                         } else {
                             result.push_str(href);
@@ -566,4 +700,44 @@ mod tests {
         let result = rewrite_subdomain_links(html, &rewrites);
         assert_eq!(result, html);
     }
+
+    #[test]
+    fn test_rewrite_cross_site_links_resolves_known_site() {
+        let sites = make_rewrites(&[("docs", "https://docs.example.com/")]);
+        let html = r#"<a href="@site:docs/getting-started#install">Start</a> <a href="@site:docs">Docs</a>"#;
+        let result = rewrite_cross_site_links(html, &sites);
+        assert_eq!(
+            result,
+            r#"<a href="https://docs.example.com/getting-started#install">Start</a> <a href="https://docs.example.com/">Docs</a>"#
+        );
+    }
+
+    #[test]
+    fn test_rewrite_cross_site_links_leaves_unknown_site() {
+        let sites = make_rewrites(&[("docs", "https://docs.example.com")]);
+        let html = r#"<a href="@site:wiki/page">Wiki</a>"#;
+        assert_eq!(rewrite_cross_site_links(html, &sites), html);
+    }
+
+    #[test]
+    fn test_extract_cross_site_links_matches_other_sites() {
+        let sites = make_rewrites(&[
+            ("main", "https://example.com"),
+            ("docs", "https://example.com/docs"),
+        ]);
+        let html = r#"<a href="https://example.com/docs/setup?x=1">Setup</a>
+            <a href="https://example.com/about">About</a>
+            <a href="https://example.com/docs">Docs home</a>
+            <a href="https://example.com/docsy">Not docs</a>
+            <a href="@site:wiki/page">Unknown</a>"#;
+        let links = extract_cross_site_links(html, &sites, "https://example.com/");
+        assert_eq!(
+            links,
+            vec![
+                ("docs".to_string(), "/setup".to_string()),
+                ("docs".to_string(), "/".to_string()),
+                ("wiki".to_string(), "/page".to_string()),
+            ]
+        );
+    }
 }
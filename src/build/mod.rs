@@ -1,13 +1,25 @@
 pub mod analytics;
+pub mod assets;
+pub mod authors;
 pub mod base_path;
+pub mod bundles;
+pub mod cache;
 pub mod code_copy;
 pub mod discovery;
 pub mod feed;
+pub mod image_cache;
 pub mod images;
 pub mod links;
 pub mod markdown;
 pub mod math;
+pub mod nav;
+pub mod og;
+pub mod permalinks;
+pub mod redirects;
+pub mod related;
+pub mod search;
 pub mod sitemap;
+pub mod taxonomies;
 
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -18,14 +30,25 @@ use rayon::prelude::*;
 use serde::Serialize;
 use walkdir::WalkDir;
 
-use crate::config::{AnalyticsSection, CollectionConfig, ResolvedPaths, SiteConfig};
-use crate::content::{self, ContentItem, Frontmatter};
+use crate::config::{
+    AnalyticsSection, CollectionConfig, ImagePlaceholder, ResolvedPaths, SiteConfig, UrlStyle,
+};
+use crate::content::{self, ContentItem, Frontmatter, Timestamp};
 use crate::error::{PageError, Result};
 use crate::output::CommandOutput;
 use crate::templates;
 
 pub struct BuildOptions {
     pub include_drafts: bool,
+    /// Time that decides which items are live (`publish_at` / `expires_at`).
+    /// `None` means now; `seite build --as-of` sets it to preview a future state.
+    pub as_of: Option<Timestamp>,
+    /// Reuse unchanged pages and derived outputs from the previous build
+    /// (recorded in `.seite/cache/build/`) instead of rendering everything.
+    pub incremental: bool,
+    /// Base URLs of the workspace sites keyed by site name, used to resolve
+    /// `@site:<name>/<path>` links. Empty outside workspaces.
+    pub cross_site_urls: HashMap<String, String>,
 }
 
 pub struct BuildResult {
@@ -51,6 +74,10 @@ pub struct BuildStats {
     pub static_files_copied: usize,
     pub public_files_copied: usize,
     pub data_files_loaded: usize,
+    /// Content pages restored unchanged from the previous build.
+    pub pages_reused: usize,
+    /// Content pages rendered in this build.
+    pub pages_rendered: usize,
     pub duration_ms: u64,
     /// Per-step timing: (step_name, duration_ms)
     pub step_timings: Vec<(String, f64)>,
@@ -78,6 +105,12 @@ impl CommandOutput for BuildStats {
         if self.data_files_loaded > 0 {
             out.push_str(&format!(", {} data files loaded", self.data_files_loaded));
         }
+        if self.pages_reused > 0 {
+            out.push_str(&format!(
+                ", {} pages re-rendered, {} reused",
+                self.pages_rendered, self.pages_reused
+            ));
+        }
         out.push(')');
         if !self.step_timings.is_empty() {
             out.push_str("\n  Timings:");
@@ -117,6 +150,10 @@ struct PageContext {
     image: Option<String>,
     slug: String,
     tags: Vec<String>,
+    /// Profiles of the page's `authors`, from `data/authors.yaml`.
+    authors: Vec<authors::AuthorContext>,
+    /// Related pages, best match first.
+    related: Vec<related::RelatedLink>,
     url: String,
     /// Collection name ("posts", "docs", "pages", etc.).
     collection: String,
@@ -157,20 +194,6 @@ struct ItemSummary {
     excerpt: String,
 }
 
-#[derive(Serialize, Clone)]
-struct NavItem {
-    title: String,
-    url: String,
-    active: bool,
-}
-
-#[derive(Serialize, Clone)]
-struct NavSection {
-    name: String,
-    label: String,
-    items: Vec<NavItem>,
-}
-
 #[derive(Serialize)]
 struct PaginationContext {
     current_page: usize,
@@ -180,6 +203,17 @@ struct PaginationContext {
     base_url: String,
 }
 
+/// Per-source inputs recorded in the build cache.
+struct ItemInputs {
+    source_hash: String,
+    /// Cache key of the rendered markdown; also identifies the item's content
+    /// in derived-output fingerprints (feeds, sitemap, search).
+    fragment_key: String,
+    shortcodes: Vec<String>,
+    /// Files of the item's page bundle and the URLs they are copied to.
+    resources: Vec<(PathBuf, String)>,
+}
+
 /// A translation link used in templates and the sitemap's xhtml:link alternates.
 #[derive(Serialize, Clone)]
 pub(crate) struct TranslationLink {
@@ -222,6 +256,57 @@ impl SiteContext {
     }
 }
 
+impl PageContext {
+    /// Context of a generated listing page, such as a taxonomy term or an
+    /// author's page.
+    fn listing(title: String, slug: String, url: String) -> Self {
+        Self {
+            title,
+            content: String::new(),
+            date: None,
+            updated: None,
+            description: None,
+            image: None,
+            slug,
+            tags: Vec::new(),
+            authors: Vec::new(),
+            related: Vec::new(),
+            url,
+            collection: String::new(),
+            robots: None,
+            word_count: 0,
+            reading_time: 0,
+            excerpt: String::new(),
+            toc: Vec::new(),
+            extra: std::collections::HashMap::new(),
+        }
+    }
+}
+
+impl ItemSummary {
+    fn from_item(item: &ContentItem) -> Self {
+        Self {
+            title: item.frontmatter.title.clone(),
+            date: item.frontmatter.date.map(|d| d.to_string()),
+            description: item.frontmatter.description.clone(),
+            slug: item.slug.clone(),
+            tags: item.frontmatter.tags.clone(),
+            url: item.url.clone(),
+            word_count: item.word_count,
+            reading_time: item.reading_time,
+            excerpt: item.excerpt_html.clone(),
+        }
+    }
+}
+
+/// Write a generated page to `{url}/index.html` under the output directory.
+fn write_index_page(output_dir: &Path, url: &str, html: String) -> Result<()> {
+    let dir = output_dir.join(url.trim_matches('/'));
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("index.html"), html)?;
+    Ok(())
+}
+
 pub fn build_site(
     config: &SiteConfig,
     paths: &ResolvedPaths,
@@ -242,11 +327,23 @@ pub fn build_site(
         config
     };
 
-    // Clean subdomain output directories before building
-    if has_subdomains {
-        let subdomains_root = paths.root.join("dist-subdomains");
-        if subdomains_root.exists() {
-            fs::remove_dir_all(&subdomains_root)?;
+    // Remove output directories of subdomain collections that no longer exist.
+    // Configured subdomains clean (or incrementally reuse) their own output.
+    let subdomains_root = paths.root.join("dist-subdomains");
+    if subdomains_root.exists() {
+        let configured: HashSet<PathBuf> = config
+            .subdomain_collections()
+            .iter()
+            .map(|c| paths.subdomain_output(&c.name))
+            .collect();
+        for entry in fs::read_dir(&subdomains_root)?.filter_map(|e| e.ok()) {
+            if !configured.contains(&entry.path()) {
+                if entry.path().is_dir() {
+                    fs::remove_dir_all(entry.path())?;
+                } else {
+                    fs::remove_file(entry.path())?;
+                }
+            }
         }
     }
 
@@ -330,6 +427,76 @@ fn build_subdomain_sites(
     Ok(results)
 }
 
+/// Where a content item comes from.
+enum ItemSource {
+    /// A markdown file.
+    File(PathBuf),
+    /// An entry of a data-backed collection, identified as `<data file>#<slug>`.
+    Entry {
+        path: PathBuf,
+        hash: String,
+        frontmatter: Box<Frontmatter>,
+        body: String,
+    },
+}
+
+/// Items of a data-backed collection: one per entry of the list at data key `key`.
+fn data_item_sources(
+    collection: &CollectionConfig,
+    key: &str,
+    data: &serde_json::Value,
+    paths: &ResolvedPaths,
+) -> Result<Vec<ItemSource>> {
+    let file = crate::data::file_for_key(&paths.data_dir, paths.shared.data_dir.as_deref(), key);
+    let entries = crate::data::lookup(data, key)
+        .ok_or_else(|| PageError::Data {
+            path: file.clone(),
+            message: format!(
+                "collection '{}' reads data '{key}', which does not exist",
+                collection.name
+            ),
+        })?
+        .as_array()
+        .ok_or_else(|| PageError::Data {
+            path: file.clone(),
+            message: format!(
+                "collection '{}' reads data '{key}', which is not a list",
+                collection.name
+            ),
+        })?;
+
+    let fields = content::EntryFields {
+        slug: collection.slug_field.as_deref().unwrap_or("slug"),
+        title: collection.title_field.as_deref().unwrap_or("title"),
+        body: collection.body_field.as_deref(),
+    };
+    let mut seen = HashSet::new();
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let (frontmatter, body) =
+                content::parse_data_entry(entry, &fields).map_err(|message| PageError::Data {
+                    path: file.clone(),
+                    message: format!("entry {}: {message}", i + 1),
+                })?;
+            let slug = frontmatter.slug.clone().unwrap_or_default();
+            if !seen.insert(slug.clone()) {
+                return Err(PageError::Data {
+                    path: file.clone(),
+                    message: format!("entry {}: duplicate slug '{slug}'", i + 1),
+                });
+            }
+            Ok(ItemSource::Entry {
+                path: PathBuf::from(format!("{}#{slug}", file.display())),
+                hash: cache::hash_json(entry),
+                frontmatter: Box::new(frontmatter),
+                body,
+            })
+        })
+        .collect()
+}
+
 /// Inner build pipeline. This is the actual 14-step build.
 ///
 /// `subdomain_rewrites_override`: if `Some`, used instead of computing from config.
@@ -343,14 +510,38 @@ fn build_site_inner(
     let start = Instant::now();
     let mut step_timings: Vec<(String, f64)> = Vec::new();
 
-    // Step 1: Clean output directory
+    // Step 1: Prepare a staging directory for the build. The output directory
+    // keeps serving the previous build until the staging directory replaces
+    // it; incremental builds restore unchanged pages from it.
     let step_start = Instant::now();
-    if paths.output.exists() {
-        fs::remove_dir_all(&paths.output)?;
-    }
-    fs::create_dir_all(&paths.output)?;
+    let config_hash = {
+        let mut fp = cache::Fingerprint::new();
+        fp.str(env!("CARGO_PKG_VERSION"));
+        fp.str(&cache::hash_json(config));
+        fp.str(&cache::hash_json(&subdomain_rewrites_override));
+        fp.str(&cache::hash_json(&opts.cross_site_urls));
+        if config.images.is_some() {
+            fp.str(&cache::dir_fingerprint(
+                &paths.static_dir,
+                images::IMAGE_EXTENSIONS,
+            ));
+        }
+        fp.finish()
+    };
+    let mut build_cache = cache::BuildCache::begin(
+        &paths.root,
+        &paths.output,
+        config_hash.clone(),
+        opts.incremental,
+    )?;
+    let output = paths.output.clone();
+    let staged_paths = ResolvedPaths {
+        output: build_cache.staging().to_path_buf(),
+        ..paths.clone()
+    };
+    let paths = &staged_paths;
     step_timings.push((
-        "Clean output".to_string(),
+        "Prepare staging output".to_string(),
         step_start.elapsed().as_secs_f64() * 1000.0,
     ));
 
@@ -384,7 +575,15 @@ fn build_site_inner(
 
     // Step 2: Load templates (collection-aware)
     let step_start = Instant::now();
-    let tera = templates::load_templates(&paths.templates, &config.collections)?;
+    let mut tera = templates::load_templates(
+        &paths.templates,
+        paths.shared.templates.as_deref(),
+        &config.collections,
+    )?;
+    tera.register_filter("date", templates::DateFilter::new(config.timezone()));
+    let mut template_dirs = vec![paths.templates.as_path()];
+    template_dirs.extend(paths.shared.templates.as_deref());
+    let template_graph = cache::TemplateGraph::new(&tera, &template_dirs);
     step_timings.push((
         "Load templates".to_string(),
         step_start.elapsed().as_secs_f64() * 1000.0,
@@ -401,7 +600,17 @@ fn build_site_inner(
 
     // Step 2.5: Load data files
     let step_start = Instant::now();
-    let data = crate::data::load_data_dir(&paths.data_dir)?;
+    let sidebars: Vec<&str> = config
+        .collections
+        .iter()
+        .filter(|c| c.nested)
+        .map(|c| c.name.as_str())
+        .collect();
+    let data = crate::data::load_layered_data(
+        &paths.data_dir,
+        paths.shared.data_dir.as_deref(),
+        &sidebars,
+    )?;
     step_timings.push((
         "Load data files".to_string(),
         step_start.elapsed().as_secs_f64() * 1000.0,
@@ -411,10 +620,107 @@ fn build_site_inner(
     let configured_langs = config.configured_lang_codes();
     let is_multilingual = config.is_multilingual();
     let default_lang = &config.site.language;
+    // Scheduled items are live once `publish_at` has passed, until `expires_at`
+    let tz = config.timezone();
+    let as_of = opts.as_of.map_or_else(chrono::Utc::now, |t| t.start(tz));
+
+    // Step 2.6: Copy static files (with optional minification and fingerprinting).
+    // Runs before rendering so templates can resolve fingerprinted URLs with `asset_url()`.
+    let step_start = Instant::now();
+    let mut static_count = 0;
+    // manifest: maps "/static/foo.css" → "/static/foo.<hash8>.css" (only when fingerprinting)
+    let mut asset_manifest: HashMap<String, String> = HashMap::new();
+    // Fingerprinted stylesheets are hashed after their url() references are rewritten
+    let mut pending_css: Vec<(String, PathBuf, String)> = Vec::new();
+    for (static_root, entry) in static_sources(paths) {
+        let rel = entry
+            .path()
+            .strip_prefix(static_root)
+            .unwrap_or(entry.path());
+        let dest = paths.output.join("static").join(rel);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let ext = entry
+            .path()
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        let is_css = ext == "css";
+        let is_js = ext == "js";
+        // Record in manifest using Unix-style paths
+        let orig_url = format!("/static/{}", rel.to_string_lossy().replace('\\', "/"));
+
+        if (config.build.minify && (is_css || is_js)) || config.build.fingerprint {
+            let content = fs::read(entry.path())?;
+            let processed: Vec<u8> = if config.build.minify && is_css {
+                minify_css(&content).into_bytes()
+            } else if config.build.minify && is_js {
+                minify_js(&content).into_bytes()
+            } else {
+                content
+            };
+
+            if config.build.fingerprint && is_css {
+                let css = String::from_utf8_lossy(&processed).into_owned();
+                pending_css.push((orig_url, dest, css));
+            } else {
+                fs::write(&dest, &processed)?;
+                if config.build.fingerprint {
+                    let fp_url = write_fingerprinted(&dest, &orig_url, &processed)?;
+                    asset_manifest.insert(orig_url, fp_url);
+                }
+            }
+        } else {
+            fs::copy(entry.path(), &dest)?;
+        }
+        static_count += 1;
+    }
+
+    // Hash stylesheets once their references point at fingerprinted files.
+    // Repeat until stable so stylesheets importing other stylesheets settle.
+    for _ in 0..=pending_css.len() {
+        let mut changed = false;
+        for (orig_url, _, css) in &pending_css {
+            let rewritten = assets::rewrite_css_urls(css, orig_url, &asset_manifest);
+            let file_name = &orig_url[orig_url.rfind('/').map_or(0, |i| i + 1)..];
+            let fp_name = assets::fingerprinted_name(file_name, &fnv_hash8(rewritten.as_bytes()));
+            let fp_url = format!("{}{fp_name}", &orig_url[..orig_url.len() - file_name.len()]);
+            if asset_manifest.get(orig_url) != Some(&fp_url) {
+                asset_manifest.insert(orig_url.clone(), fp_url);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    for (orig_url, dest, css) in &pending_css {
+        let rewritten = assets::rewrite_css_urls(css, orig_url, &asset_manifest);
+        fs::write(dest, &rewritten)?;
+        write_fingerprinted(dest, orig_url, rewritten.as_bytes())?;
+    }
+
+    // Write asset manifest if fingerprinting is on
+    if config.build.fingerprint && !asset_manifest.is_empty() {
+        let manifest_json =
+            serde_json::to_string_pretty(&asset_manifest).unwrap_or_else(|_| "{}".to_string());
+        fs::write(paths.output.join("asset-manifest.json"), manifest_json)?;
+    }
+    let asset_manifest_hash = cache::hash_json(&asset_manifest);
+    tera.register_function("asset_url", assets::AssetUrl::new(asset_manifest.clone()));
+
+    step_timings.push((
+        "Copy static files".to_string(),
+        step_start.elapsed().as_secs_f64() * 1000.0,
+    ));
 
     // Step 3: Process each collection
     let step_start = Instant::now();
     let mut all_collections: HashMap<String, Vec<ContentItem>> = HashMap::new();
+    let mut item_inputs: HashMap<PathBuf, ItemInputs> = HashMap::new();
 
     // Pre-compute shortcode site context (identical for every page)
     let sc_site = serde_json::json!({
@@ -430,35 +736,87 @@ fn build_site_inner(
         })),
     });
 
+    // Schema violations and missing bundle files from every collection,
+    // reported together
+    let mut schema_violations: Vec<String> = Vec::new();
+    let mut missing_resources: Vec<String> = Vec::new();
+
     for collection in &config.collections {
         let collection_dir = paths.content.join(&collection.directory);
         let mut items = Vec::new();
-
-        if !collection_dir.exists() {
+        // Patterns were checked when the config was loaded
+        let schema = content::schema::Schema::new(&collection.fields).map_err(PageError::Build)?;
+        let permalink = collection
+            .permalink
+            .as_deref()
+            .map(permalinks::Permalink::parse)
+            .transpose()
+            .map_err(PageError::Build)?;
+
+        let sources: Vec<ItemSource> = if let Some(key) = &collection.data {
+            data_item_sources(collection, key, &data, paths)?
+        } else if collection_dir.exists() {
+            WalkDir::new(&collection_dir)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| {
+                    e.path().extension().is_some_and(|ext| ext == "md")
+                        && !content::is_section_index(e.path())
+                })
+                .map(|e| ItemSource::File(e.into_path()))
+                .collect()
+        } else {
             tracing::warn!(
                 "Content directory '{}' for collection '{}' does not exist",
                 collection_dir.display(),
                 collection.name
             );
-        }
-        if collection_dir.exists() {
-            let entries: Vec<_> = WalkDir::new(&collection_dir)
-                .into_iter()
-                .filter_map(|e| e.ok())
-                .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
-                .collect();
-
-            let results: Vec<std::result::Result<Option<ContentItem>, PageError>> = entries
+            Vec::new()
+        };
+        if !sources.is_empty() {
+            let violations = std::sync::Mutex::new(Vec::new());
+            let missing = std::sync::Mutex::new(Vec::new());
+            type ProcessedItem = (ContentItem, ItemInputs);
+            let results: Vec<std::result::Result<Option<ProcessedItem>, PageError>> = sources
                 .par_iter()
-                .map(|entry| {
-                    let path = entry.path();
+                .map(|source| {
+                    let (path, source_hash, fm, raw_body) = match source {
+                        ItemSource::File(path) => {
+                            let source_hash = cache::hash_bytes(&fs::read(path)?);
+                            let (fm, raw_body) = content::parse_content_file(path)?;
+                            (path.as_path(), source_hash, fm, raw_body)
+                        }
+                        ItemSource::Entry {
+                            path,
+                            hash,
+                            frontmatter,
+                            body,
+                        } => (
+                            path.as_path(),
+                            hash.clone(),
+                            (**frontmatter).clone(),
+                            body.clone(),
+                        ),
+                    };
                     let rel = path.strip_prefix(&collection_dir).unwrap_or(path);
 
-                    let (fm, raw_body) = content::parse_content_file(path)?;
-
                     if fm.draft && !opts.include_drafts {
                         return Ok(None);
                     }
+                    if !fm.is_live_at(as_of, tz) {
+                        return Ok(None);
+                    }
+
+                    // A bundle's `index.md` stands for its directory
+                    let bundle = match source {
+                        ItemSource::File(_) => bundles::page_path(rel, &configured_langs),
+                        ItemSource::Entry { .. } => None,
+                    };
+                    let resources = match (&bundle, path.parent()) {
+                        (Some(_), Some(dir)) => bundles::resources(dir),
+                        _ => Vec::new(),
+                    };
+                    let rel = bundle.as_deref().unwrap_or(rel);
 
                     let file_lang = if is_multilingual {
                         content::extract_lang_from_filename(path, &configured_langs)
@@ -475,32 +833,129 @@ fn build_site_inner(
 
                     let mut fm = fm;
                     if fm.date.is_none() && collection.has_date {
-                        fm.date = parse_date_from_filename(path);
+                        fm.date = parse_date_from_filename(rel).map(Timestamp::Date);
+                    }
+                    if !schema.is_empty() {
+                        schema.apply_defaults(&mut fm.extra);
+                        let problems = schema.validate(&fm.extra);
+                        if !problems.is_empty() {
+                            let file = path.strip_prefix(&paths.root).unwrap_or(path);
+                            violations.lock().expect("violations lock poisoned").extend(
+                                problems
+                                    .into_iter()
+                                    .map(|p| format!("{}: {p}", file.display())),
+                            );
+                        }
                     }
 
-                    let sc_page = serde_json::json!({
-                        "title": fm.title,
-                        "slug": &slug,
-                        "collection": &collection.name,
-                        "tags": &fm.tags,
-                    });
-                    let expanded_body =
-                        shortcode_registry.expand(&raw_body, path, &sc_page, &sc_site)?;
-                    let excerpt = content::extract_excerpt(&expanded_body);
-                    let html_input = if config.build.math {
-                        math::render_math(&expanded_body)
-                    } else {
-                        expanded_body
+                    // Shortcode names are only needed for cache keys; a malformed
+                    // call is reported by `expand` below.
+                    let shortcodes: Vec<String> =
+                        if raw_body.contains("{{<") || raw_body.contains("{{%") {
+                            let mut names: Vec<String> =
+                                crate::shortcodes::parser::parse_shortcodes(&raw_body, path)
+                                    .map(|calls| calls.into_iter().map(|c| c.name).collect())
+                                    .unwrap_or_default();
+                            names.sort();
+                            names.dedup();
+                            names
+                        } else {
+                            Vec::new()
+                        };
+                    let base_url = match &permalink {
+                        Some(permalink) => permalink
+                            .expand(&permalinks::Page {
+                                slug: &slug,
+                                lang: &lang,
+                                frontmatter: &fm,
+                                tz,
+                            })
+                            .map_err(|e| {
+                                let file = path.strip_prefix(&paths.root).unwrap_or(path);
+                                PageError::Build(format!("{}: {e}", file.display()))
+                            })?,
+                        None => build_url(&collection.url_prefix, &slug),
                     };
-                    let (html_body, toc) = markdown::markdown_to_html(&html_input);
-
-                    let base_url = build_url(&collection.url_prefix, &slug);
-                    let url = if lang != *default_lang {
+                    let url = if lang != *default_lang
+                        && !permalink.as_ref().is_some_and(|p| p.has_lang())
+                    {
                         format!("/{lang}{base_url}")
                     } else {
                         base_url
                     };
-                    let (excerpt_html, _) = markdown::markdown_to_html(&excerpt);
+                    let url = config.build.url_style.page_url(url);
+
+                    // Bundle files resolve against the page URL; a missing one
+                    // fails the build once every item has been checked
+                    let mut missing_here: Vec<String> = Vec::new();
+                    if bundle.is_some() {
+                        if let Some(image) = &fm.image {
+                            match bundles::resolve(image, &url, &resources) {
+                                Ok(Some(resolved)) => fm.image = Some(resolved),
+                                Ok(None) => {}
+                                Err(reference) => missing_here.push(reference.to_string()),
+                            }
+                        }
+                    }
+                    let bundle_fingerprint = match (&bundle, path.parent()) {
+                        (Some(_), Some(dir)) => bundles::fingerprint(dir, &resources),
+                        _ => String::new(),
+                    };
+
+                    let fragment_key = cache::Fingerprint::new()
+                        .str(&config_hash)
+                        .str(&path.to_string_lossy())
+                        .str(&source_hash)
+                        .str(&cache::shortcode_fingerprint(&shortcodes_dir, &shortcodes))
+                        .str(&bundle_fingerprint)
+                        .finish();
+
+                    let fragment = match build_cache.fragment(&fragment_key) {
+                        Some(fragment) => fragment,
+                        None => {
+                            let sc_page = serde_json::json!({
+                                "title": fm.title,
+                                "slug": &slug,
+                                "collection": &collection.name,
+                                "tags": &fm.tags,
+                            });
+                            let expanded_body =
+                                shortcode_registry.expand(&raw_body, path, &sc_page, &sc_site)?;
+                            let markdown_body = shortcode_registry
+                                .expand_markdown(&raw_body, path, &sc_page, &sc_site)?;
+                            let excerpt = content::extract_excerpt(&expanded_body);
+                            let html_input = if config.build.math {
+                                math::render_math(&expanded_body)
+                            } else {
+                                expanded_body
+                            };
+                            let (mut html_body, toc) = markdown::markdown_to_html(&html_input);
+                            let (mut excerpt_html, _) = markdown::markdown_to_html(&excerpt);
+                            let mut markdown_body = markdown_body;
+                            if bundle.is_some() {
+                                let (html, unknown) =
+                                    bundles::rewrite_html(&html_body, &url, &resources);
+                                html_body = html;
+                                missing_here.extend(unknown);
+                                excerpt_html =
+                                    bundles::rewrite_html(&excerpt_html, &url, &resources).0;
+                                markdown_body =
+                                    bundles::rewrite_markdown(&markdown_body, &url, &resources);
+                            }
+                            let fragment = cache::Fragment {
+                                excerpt,
+                                html_body,
+                                markdown_body,
+                                excerpt_html,
+                                toc,
+                            };
+                            if missing_here.is_empty() {
+                                build_cache.store_fragment(&fragment_key, &fragment)?;
+                            }
+                            fragment
+                        }
+                    };
+
                     let word_count = raw_body.split_whitespace().count();
                     let reading_time = if word_count == 0 {
                         0
@@ -508,34 +963,60 @@ fn build_site_inner(
                         (word_count / 238).max(1)
                     };
 
-                    Ok(Some(ContentItem {
+                    if !missing_here.is_empty() {
+                        let file = path.strip_prefix(&paths.root).unwrap_or(path);
+                        missing.lock().expect("missing lock poisoned").extend(
+                            missing_here
+                                .into_iter()
+                                .map(|r| format!("{}: no bundle file `{r}`", file.display())),
+                        );
+                    }
+                    let resources = match path.parent() {
+                        Some(dir) if bundle.is_some() => resources
+                            .into_iter()
+                            .map(|r| (dir.join(&r), format!("{}/{r}", url.trim_end_matches('/'))))
+                            .collect(),
+                        _ => Vec::new(),
+                    };
+                    let item = ContentItem {
                         frontmatter: fm,
                         raw_body,
-                        html_body,
+                        markdown_body: fragment.markdown_body,
+                        html_body: fragment.html_body,
                         source_path: path.to_path_buf(),
                         slug,
                         collection: collection.name.clone(),
                         url,
                         lang,
-                        excerpt,
-                        toc,
+                        excerpt: fragment.excerpt,
+                        toc: fragment.toc,
                         word_count,
                         reading_time,
-                        excerpt_html,
-                    }))
+                        excerpt_html: fragment.excerpt_html,
+                    };
+                    let inputs = ItemInputs {
+                        source_hash,
+                        fragment_key,
+                        shortcodes,
+                        resources,
+                    };
+                    Ok(Some((item, inputs)))
                 })
                 .collect();
 
             for result in results {
-                if let Some(item) = result? {
+                if let Some((item, inputs)) = result? {
+                    item_inputs.insert(item.source_path.clone(), inputs);
                     items.push(item);
                 }
             }
+            schema_violations.extend(violations.into_inner().expect("violations lock poisoned"));
+            missing_resources.extend(missing.into_inner().expect("missing lock poisoned"));
         }
 
         // Sort: date-based collections by date desc, others by weight then title
         if collection.has_date {
-            items.sort_by(|a, b| b.frontmatter.date.cmp(&a.frontmatter.date));
+            items.sort_by_key(|item| std::cmp::Reverse(item.frontmatter.date_key(tz)));
         } else {
             items.sort_by(|a, b| match (a.frontmatter.weight, b.frontmatter.weight) {
                 (Some(wa), Some(wb)) => wa
@@ -550,16 +1031,48 @@ fn build_site_inner(
         all_collections.insert(collection.name.clone(), items);
     }
 
+    if !schema_violations.is_empty() {
+        schema_violations.sort();
+        return Err(PageError::Build(format!(
+            "{} frontmatter schema violation(s):\n  {}",
+            schema_violations.len(),
+            schema_violations.join("\n  ")
+        )));
+    }
+    if !missing_resources.is_empty() {
+        missing_resources.sort();
+        return Err(PageError::Build(format!(
+            "{} missing page bundle file(s):\n  {}",
+            missing_resources.len(),
+            missing_resources.join("\n  ")
+        )));
+    }
+
     // Detect URL collisions: if two content items resolve to the same URL, that's an error.
     {
-        let mut url_map: HashMap<&str, &std::path::Path> = HashMap::new();
+        let mut url_map: HashMap<&str, &ContentItem> = HashMap::new();
         for items in all_collections.values() {
             for item in items {
-                if let Some(existing) = url_map.insert(&item.url, &item.source_path) {
+                if let Some(existing) = url_map.insert(&item.url, item) {
+                    // Point at the permalink pattern when one produced the URL
+                    let patterns: Vec<String> = [&existing.collection, &item.collection]
+                        .into_iter()
+                        .collect::<std::collections::BTreeSet<_>>()
+                        .into_iter()
+                        .filter_map(|name| {
+                            let c = config.collections.iter().find(|c| &c.name == name)?;
+                            Some(format!("'{}' of {name}", c.permalink.as_deref()?))
+                        })
+                        .collect();
+                    let hint = if patterns.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" (permalink {})", patterns.join(", "))
+                    };
                     return Err(PageError::Build(format!(
-                        "URL collision: '{}' is claimed by both '{}' and '{}'",
+                        "URL collision: '{}' is claimed by both '{}' and '{}'{hint}",
                         item.url,
-                        existing.display(),
+                        existing.source_path.display(),
                         item.source_path.display()
                     )));
                 }
@@ -567,11 +1080,153 @@ fn build_site_inner(
         }
     }
 
+    // Resolve author profiles once content credits authors; until then
+    // `data/authors` is free to hold anything else
+    let author_registry = {
+        let items: Vec<&ContentItem> = all_collections.values().flatten().collect();
+        if items
+            .iter()
+            .any(|item| !item.frontmatter.authors.is_empty())
+        {
+            let registry = authors::load(&data, &paths.data_dir)?;
+            authors::check(&items, &registry, &paths.root)?;
+            registry
+        } else {
+            authors::Registry::new()
+        }
+    };
+
+    // Related pages of every item, keyed by URL
+    let related_by_url: HashMap<String, Vec<related::RelatedLink>> = {
+        let items: Vec<&ContentItem> = config
+            .collections
+            .iter()
+            .flat_map(|c| all_collections.get(&c.name).into_iter().flatten())
+            .collect();
+        let docs: Vec<related::Doc> = items
+            .iter()
+            .map(|item| related::Doc {
+                url: &item.url,
+                slug: &item.slug,
+                collection: &item.collection,
+                lang: &item.lang,
+                frontmatter: &item.frontmatter,
+                body: &item.raw_body,
+            })
+            .collect();
+        let unknown = related::unknown_overrides(&docs);
+        if !unknown.is_empty() {
+            let mut lines: Vec<String> = unknown
+                .iter()
+                .map(|(i, target)| {
+                    let path = &items[*i].source_path;
+                    let file = path.strip_prefix(&paths.root).unwrap_or(path);
+                    format!("{}: no page matches `{target}`", file.display())
+                })
+                .collect();
+            lines.sort();
+            return Err(PageError::Build(format!(
+                "{} unknown related page(s):\n  {}",
+                lines.len(),
+                lines.join("\n  ")
+            )));
+        }
+        items
+            .iter()
+            .map(|item| item.url.clone())
+            .zip(related::compute(&docs, config))
+            .collect()
+    };
+
     step_timings.push((
         "Process collections".to_string(),
         step_start.elapsed().as_secs_f64() * 1000.0,
     ));
 
+    // Step 3b: Copy page bundle files next to their pages
+    let bundle_files: Vec<(PathBuf, String)> = item_inputs
+        .values()
+        .flat_map(|inputs| inputs.resources.iter().cloned())
+        .collect();
+    for (source, url) in &bundle_files {
+        let dest = paths.output.join(url.trim_start_matches('/'));
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(source, &dest)?;
+    }
+
+    // Step 3c: Process images (resize, WebP, srcset, placeholders). Runs
+    // before rendering so templates can read them with `image_meta()`.
+    let step_start = Instant::now();
+    let image_cache = image_cache::ImageCache::open(&paths.root, &output);
+    let image_manifest = if let Some(ref images_config) = config.images {
+        if !images_config.widths.is_empty() {
+            let mut manifest = images::process_images(paths, images_config, &image_cache)?;
+            manifest.extend(images::process_bundle_images(
+                &bundle_files,
+                &paths.output,
+                images_config,
+                &image_cache,
+            ));
+            manifest
+        } else {
+            HashMap::new()
+        }
+    } else {
+        HashMap::new()
+    };
+    image_cache.finish()?;
+    tera.register_function("image_meta", images::ImageMeta::new(image_manifest.clone()));
+
+    step_timings.push((
+        "Process images".to_string(),
+        step_start.elapsed().as_secs_f64() * 1000.0,
+    ));
+
+    // Step 3d: Draw social cards for pages without an image. Each card becomes
+    // its page's `image` before rendering, so meta tags and JSON-LD use it.
+    let step_start = Instant::now();
+    if let Some(og_config) = &config.og {
+        let renderer = og::Renderer::new(og_config, &paths.static_dir)?;
+        let mut pending: Vec<(String, String, og::Card)> = Vec::new();
+        for collection in &config.collections {
+            let Some(items) = all_collections.get_mut(&collection.name) else {
+                continue;
+            };
+            for item in items
+                .iter_mut()
+                .filter(|item| item.frontmatter.image.is_none())
+            {
+                let url = og::card_url(&item.url);
+                let card = og::Card {
+                    title: item.frontmatter.title.clone(),
+                    label: collection.label.clone(),
+                    site_title: config.title_for_lang(&item.lang).to_string(),
+                };
+                let rel = url.trim_start_matches('/').to_string();
+                let fingerprint = cache::Fingerprint::new()
+                    .str(&renderer.fingerprint)
+                    .str(&cache::hash_json(&card))
+                    .finish();
+                if !build_cache.restore_step(&rel, &fingerprint) {
+                    pending.push((rel, fingerprint, card));
+                }
+                item.frontmatter.image = Some(url);
+            }
+        }
+        pending
+            .par_iter()
+            .try_for_each(|(rel, _, card)| renderer.write(card, &paths.output.join(rel)))?;
+        for (rel, fingerprint, _) in pending {
+            build_cache.record_step(&rel, fingerprint, vec![rel.clone()]);
+        }
+    }
+    step_timings.push((
+        "Generate social cards".to_string(),
+        step_start.elapsed().as_secs_f64() * 1000.0,
+    ));
+
     // Build translation map: (collection, slug) → Vec<TranslationLink>
     // Sort each translations vec by language order (default lang first, then alphabetical)
     // so the language switcher renders in a deterministic, predictable order.
@@ -620,8 +1275,27 @@ fn build_site_inner(
         i18n_cache.insert(lang.clone(), (lang_prefix, default_lang.to_string(), t));
     }
 
+    // Fingerprint each template's closure together with the data it reads, so
+    // per-page cache checks don't re-hash `data`. The asset manifest is part of
+    // every fingerprint: pages reference assets via `asset_url()` and post-processing.
+    let template_fps: HashMap<&str, String> = tera
+        .get_template_names()
+        .filter_map(|name| {
+            let closure = template_graph.closure(name)?;
+            let fp = cache::Fingerprint::new()
+                .str(&closure.hash)
+                .str(&closure.data.fingerprint(&data))
+                .str(&asset_manifest_hash)
+                .finish();
+            Some((name, fp))
+        })
+        .collect();
+    let mut pages_reused: usize = 0;
+    let mut pages_rendered: usize = 0;
+    let mut reused_outputs: HashSet<String> = HashSet::new();
+
     // Pre-serialize an empty nav for non-nested collections
-    let empty_nav: Vec<NavSection> = Vec::new();
+    let empty_nav: Vec<nav::NavSection> = Vec::new();
     let empty_nav_value = serde_json::to_value(&empty_nav).unwrap_or_default();
 
     // Collect nav data per collection so it can be passed to collection index templates later.
@@ -634,10 +1308,21 @@ fn build_site_inner(
             // Only build nav for nested collections (e.g., docs with sidebar).
             // Non-nested collections (posts, pages) get an empty nav — their templates
             // don't use it, and building/cloning a 10k-item nav per page is O(n²).
-            let nav_by_lang: HashMap<&str, serde_json::Value>;
-            let nav_slug_index: HashMap<&str, HashMap<&str, (usize, usize)>>;
+            let mut nav_by_lang: HashMap<&str, serde_json::Value> = HashMap::new();
+            let mut nav_hash_by_lang: HashMap<&str, String> = HashMap::new();
+            let mut nav_placements: HashMap<&str, HashMap<String, nav::Placement>> = HashMap::new();
 
             if collection.nested {
+                let sections = nav::Sections::load(
+                    &paths.content.join(&collection.directory),
+                    &configured_langs,
+                )?;
+                let nav_file = nav::load_override(
+                    &paths.data_dir,
+                    paths.shared.data_dir.as_deref(),
+                    &collection.name,
+                )?;
+
                 // Group items by language
                 let mut items_by_lang: HashMap<&str, Vec<&ContentItem>> = HashMap::new();
                 for item in items {
@@ -647,56 +1332,37 @@ fn build_site_inner(
                         .push(item);
                 }
 
-                let mut by_lang = HashMap::new();
-                let mut slug_idx = HashMap::new();
-
                 for (&lang, lang_items) in &items_by_lang {
-                    let mut sections: Vec<NavSection> = Vec::new();
-                    let mut section_map: HashMap<String, usize> = HashMap::new();
-                    let mut si: HashMap<&str, (usize, usize)> = HashMap::new();
-
-                    for item in lang_items {
-                        let (section_name, section_label) = if let Some(pos) = item.slug.find('/') {
-                            let name = &item.slug[..pos];
-                            let label = title_case(name);
-                            (name.to_string(), label)
-                        } else {
-                            (String::new(), String::new())
-                        };
-
-                        let nav_item = NavItem {
-                            title: item.frontmatter.title.clone(),
-                            url: item.url.clone(),
-                            active: false,
-                        };
-
-                        let section_idx = if let Some(&idx) = section_map.get(&section_name) {
-                            sections[idx].items.push(nav_item);
-                            idx
-                        } else {
-                            let idx = sections.len();
-                            section_map.insert(section_name.clone(), idx);
-                            sections.push(NavSection {
-                                name: section_name,
-                                label: section_label,
-                                items: vec![nav_item],
-                            });
-                            idx
-                        };
-                        let item_idx = sections[section_idx].items.len() - 1;
-                        si.insert(&item.slug, (section_idx, item_idx));
-                    }
+                    let root = nav::NavLink {
+                        title: collection.label.clone(),
+                        url: format!(
+                            "{}{}/",
+                            lang_prefix_for(lang, default_lang),
+                            collection.url_prefix
+                        ),
+                    };
+                    let lang_nav = nav::build(
+                        lang_items,
+                        items,
+                        &sections,
+                        nav_file.as_ref().map(|(_, entries)| entries.as_slice()),
+                        lang,
+                        root,
+                    )
+                    .map_err(|message| match &nav_file {
+                        Some((path, _)) => PageError::Data {
+                            path: path.clone(),
+                            message,
+                        },
+                        None => PageError::Build(message),
+                    })?;
 
                     // Pre-serialize to serde_json::Value so per-item clone is cheap
-                    by_lang.insert(lang, serde_json::to_value(&sections).unwrap_or_default());
-                    slug_idx.insert(lang, si);
+                    let nav_value = serde_json::to_value(&lang_nav.sections).unwrap_or_default();
+                    nav_hash_by_lang.insert(lang, cache::hash_json(&nav_value));
+                    nav_by_lang.insert(lang, nav_value);
+                    nav_placements.insert(lang, lang_nav.placements);
                 }
-
-                nav_by_lang = by_lang;
-                nav_slug_index = slug_idx;
-            } else {
-                nav_by_lang = HashMap::new();
-                nav_slug_index = HashMap::new();
             }
 
             // Cache nav for collection index rendering later (Steps 4b/4b-extra)
@@ -708,7 +1374,8 @@ fn build_site_inner(
                 collection_nav_cache.insert(collection.name.clone(), owned);
             }
 
-            let render_results: Vec<std::result::Result<(PathBuf, String), PageError>> = items
+            type RenderedPage = (PathBuf, Option<String>, Option<cache::PageRecord>);
+            let render_results: Vec<std::result::Result<RenderedPage, PageError>> = items
                 .par_iter()
                 .map(|item| {
                     let site_ctx_for_item =
@@ -718,34 +1385,13 @@ fn build_site_inner(
                                 .expect("default language missing from site context cache")
                         });
 
-                    let mut ctx = build_page_context(site_ctx_for_item, item, &data);
-
-                    if collection.nested {
-                        if let Some(base_nav) = nav_by_lang.get(item.lang.as_str()) {
-                            let mut nav = base_nav.clone();
-                            if let Some(si) = nav_slug_index.get(item.lang.as_str()) {
-                                if let Some(&(sec_idx, item_idx)) = si.get(item.slug.as_str()) {
-                                    if let Some(sections) = nav.as_array_mut() {
-                                        if let Some(section) = sections.get_mut(sec_idx) {
-                                            if let Some(items_arr) = section
-                                                .get_mut("items")
-                                                .and_then(|i| i.as_array_mut())
-                                            {
-                                                if let Some(nav_item) = items_arr.get_mut(item_idx)
-                                                {
-                                                    nav_item["active"] =
-                                                        serde_json::Value::Bool(true);
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            ctx.insert("nav", &nav);
-                        }
-                    } else {
-                        ctx.insert("nav", &empty_nav_value);
-                    }
+                    let mut ctx = build_page_context(
+                        site_ctx_for_item,
+                        item,
+                        authors::resolve(&author_registry, item, default_lang),
+                        related_by_url.get(&item.url).cloned().unwrap_or_default(),
+                        &data,
+                    );
                     ctx.insert("lang", &item.lang);
                     if let Some(cached_i18n) = i18n_cache.get(item.lang.as_str()) {
                         insert_i18n_context_cached(&mut ctx, cached_i18n);
@@ -753,6 +1399,7 @@ fn build_site_inner(
                         insert_i18n_context(&mut ctx, &item.lang, default_lang, &data);
                     }
                     insert_build_flags(&mut ctx, config);
+                    ctx.insert("taxonomies", &taxonomies::page_terms(config, item));
 
                     let empty_translations: Vec<TranslationLink> = Vec::new();
                     let translations = translation_map
@@ -767,6 +1414,73 @@ fn build_site_inner(
                         .template
                         .as_deref()
                         .unwrap_or(&collection.default_template);
+                    let output_path =
+                        url_to_output_path(&paths.output, &item.url, config.build.url_style);
+
+                    // Index pages are re-rendered as homepage/collection indexes
+                    // below, so they are never restored from the cache.
+                    let record = (item.slug != "index").then(|| {
+                        let inputs = &item_inputs[&item.source_path];
+                        let closure = template_graph.closure(template_name);
+                        let fingerprint = cache::Fingerprint::new()
+                            .str(&inputs.fragment_key)
+                            .str(template_name)
+                            .str(template_fps.get(template_name).map_or("", |s| s.as_str()))
+                            .str(
+                                nav_hash_by_lang
+                                    .get(item.lang.as_str())
+                                    .map_or("", |s| s.as_str()),
+                            )
+                            .str(&page_context_fingerprint(&ctx))
+                            .finish();
+                        cache::PageRecord {
+                            source: cache::rel_output(&paths.root, &item.source_path),
+                            source_hash: inputs.source_hash.clone(),
+                            templates: closure.map(|c| c.names.clone()).unwrap_or_default(),
+                            data: closure.map(|c| c.data.names()).unwrap_or_default(),
+                            shortcodes: inputs.shortcodes.clone(),
+                            fingerprint,
+                            outputs: vec![
+                                cache::rel_output(&paths.output, &output_path),
+                                cache::rel_output(
+                                    &paths.output,
+                                    &url_to_md_path(
+                                        &paths.output,
+                                        &item.url,
+                                        config.build.url_style,
+                                    ),
+                                ),
+                            ],
+                        }
+                    });
+                    if let Some(record) = &record {
+                        if build_cache.restore_page(&record.outputs[0], &record.fingerprint) {
+                            return Ok((output_path, None, Some(record.clone())));
+                        }
+                    }
+
+                    // Breadcrumbs and prev/next follow the sidebar; pages outside
+                    // it (or outside nested collections) get none
+                    let placement = nav_placements
+                        .get(item.lang.as_str())
+                        .and_then(|placements| placements.get(&item.slug));
+                    match nav_by_lang.get(item.lang.as_str()) {
+                        Some(base_nav) => {
+                            let mut nav = base_nav.clone();
+                            if let Some(placement) = placement {
+                                nav::mark_active(&mut nav, placement);
+                            }
+                            ctx.insert("nav", &nav);
+                        }
+                        None => ctx.insert("nav", &empty_nav_value),
+                    }
+                    ctx.insert(
+                        "breadcrumbs",
+                        placement.map_or(&[][..], |p| p.breadcrumbs.as_slice()),
+                    );
+                    ctx.insert("prev", &placement.and_then(|p| p.prev.as_ref()));
+                    ctx.insert("next", &placement.and_then(|p| p.next.as_ref()));
+
                     let html = tera.render(template_name, &ctx).map_err(|e| {
                         use std::error::Error as _;
                         let mut source_chain = String::new();
@@ -778,17 +1492,25 @@ fn build_site_inner(
                         PageError::Build(format!("rendering '{}': {e}{source_chain}", item.slug))
                     })?;
 
-                    let output_path = url_to_output_path(&paths.output, &item.url);
-                    Ok((output_path, html))
+                    Ok((output_path, Some(html), record))
                 })
                 .collect();
 
             for result in render_results {
-                let (output_path, html) = result?;
-                if let Some(parent) = output_path.parent() {
-                    fs::create_dir_all(parent)?;
+                let (output_path, html, record) = result?;
+                if let Some(html) = html {
+                    if let Some(parent) = output_path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(&output_path, html)?;
+                    pages_rendered += 1;
+                } else if let Some(record) = &record {
+                    reused_outputs.extend(record.outputs.iter().cloned());
+                    pages_reused += 1;
+                }
+                if let Some(record) = record {
+                    build_cache.record_page(record.outputs[0].clone(), record);
                 }
-                fs::write(&output_path, html)?;
             }
         }
     }
@@ -921,6 +1643,8 @@ fn build_site_inner(
                 ),
                 slug: homepage.slug.clone(),
                 tags: homepage.frontmatter.tags.clone(),
+                authors: Vec::new(),
+                related: Vec::new(),
                 url: index_page_url,
                 collection: homepage.collection.clone(),
                 robots: homepage.frontmatter.robots.clone(),
@@ -953,6 +1677,8 @@ fn build_site_inner(
                 ),
                 slug: col_index.slug.clone(),
                 tags: col_index.frontmatter.tags.clone(),
+                authors: Vec::new(),
+                related: Vec::new(),
                 url: index_page_url,
                 collection: col_index.collection.clone(),
                 robots: col_index.frontmatter.robots.clone(),
@@ -972,6 +1698,8 @@ fn build_site_inner(
                 image: None,
                 slug: "index".to_string(),
                 tags: Vec::new(),
+                authors: Vec::new(),
+                related: Vec::new(),
                 url: index_page_url,
                 collection: String::new(),
                 robots: None,
@@ -1028,7 +1756,7 @@ fn build_site_inner(
             } else {
                 target
             };
-            generate_redirect_html(&target_url)
+            redirects::stub_html(&target_url)
         } else {
             tera.render("index.html", &index_ctx)
                 .map_err(|e| PageError::Build(format!("rendering index ({lang}): {e}")))?
@@ -1169,6 +1897,8 @@ fn build_site_inner(
                             ),
                             slug: url_prefix_trimmed.to_string(),
                             tags: ci.frontmatter.tags.clone(),
+                            authors: Vec::new(),
+                            related: Vec::new(),
                             url: page_url(page_num),
                             collection: c.name.clone(),
                             robots: ci.frontmatter.robots.clone(),
@@ -1188,6 +1918,8 @@ fn build_site_inner(
                             image: None,
                             slug: String::new(),
                             tags: Vec::new(),
+                            authors: Vec::new(),
+                            related: Vec::new(),
                             url: page_url(page_num),
                             collection: String::new(),
                             robots: None,
@@ -1321,7 +2053,7 @@ fn build_site_inner(
                         } else {
                             target.to_string()
                         };
-                        let redirect_html = generate_redirect_html(&target_url);
+                        let redirect_html = redirects::stub_html(&target_url);
                         let out_dir = if *lang == *default_lang {
                             paths.output.join(url_prefix_trimmed)
                         } else {
@@ -1349,6 +2081,8 @@ fn build_site_inner(
                         ),
                         slug: url_prefix_trimmed.to_string(),
                         tags: ci.frontmatter.tags.clone(),
+                        authors: Vec::new(),
+                        related: Vec::new(),
                         url: collection_url.clone(),
                         collection: c.name.clone(),
                         robots: ci.frontmatter.robots.clone(),
@@ -1368,6 +2102,8 @@ fn build_site_inner(
                         image: None,
                         slug: url_prefix_trimmed.to_string(),
                         tags: Vec::new(),
+                        authors: Vec::new(),
+                        related: Vec::new(),
                         url: collection_url.clone(),
                         collection: c.name.clone(),
                         robots: None,
@@ -1409,7 +2145,7 @@ fn build_site_inner(
         let md_content = format!(
             "{}\n\n{}",
             content::generate_frontmatter(&homepage.frontmatter),
-            homepage.raw_body
+            homepage.markdown_body
         );
         if homepage.lang == *default_lang {
             fs::write(paths.output.join("index.md"), md_content)?;
@@ -1450,6 +2186,8 @@ fn build_site_inner(
                     image: None,
                     slug: "404".to_string(),
                     tags: Vec::new(),
+                    authors: Vec::new(),
+                    related: Vec::new(),
                     url: format!("{lang_prefix}/404"),
                     collection: String::new(),
                     robots: Some("noindex".to_string()),
@@ -1476,7 +2214,8 @@ fn build_site_inner(
 
     // Step 4c: Generate tag pages
     // Collect all tags per language from all collections
-    let mut tag_page_urls: Vec<String> = Vec::new();
+    let mut listing_urls: Vec<String> = Vec::new();
+    let tag_feeds = config.feed.as_ref().is_some_and(|f| f.tags);
     if tera.get_template("tags.html").is_ok() && tera.get_template("tag.html").is_ok() {
         for lang in &config.all_languages() {
             let lang_prefix = if *lang == *default_lang {
@@ -1486,11 +2225,21 @@ fn build_site_inner(
             };
             let tags_base_url = format!("{lang_prefix}/tags");
 
-            // Gather all tags and their items for this language
+            // Gather all tags and their items for this language, and the items
+            // of feed collections for per-tag feeds
             let mut tag_map: HashMap<String, Vec<ItemSummary>> = HashMap::new();
+            let mut tag_feed_items: HashMap<String, Vec<&ContentItem>> = HashMap::new();
             for c in &config.collections {
                 if let Some(items) = all_collections.get(&c.name) {
                     for item in items.iter().filter(|i| i.lang == *lang) {
+                        if tag_feeds && c.has_rss {
+                            for tag in &item.frontmatter.tags {
+                                tag_feed_items
+                                    .entry(tag.to_lowercase())
+                                    .or_default()
+                                    .push(item);
+                            }
+                        }
                         let summary = ItemSummary {
                             title: item.frontmatter.title.clone(),
                             date: item.frontmatter.date.map(|d| d.to_string()),
@@ -1559,6 +2308,8 @@ fn build_site_inner(
                     image: None,
                     slug: "tags".to_string(),
                     tags: Vec::new(),
+                    authors: Vec::new(),
+                    related: Vec::new(),
                     url: format!("{tags_base_url}/"),
                     collection: String::new(),
                     robots: None,
@@ -1575,7 +2326,7 @@ fn build_site_inner(
             let tags_dir = paths.output.join(tags_base_url.trim_start_matches('/'));
             fs::create_dir_all(&tags_dir)?;
             fs::write(tags_dir.join("index.html"), tags_html)?;
-            tag_page_urls.push(format!("{tags_base_url}/"));
+            listing_urls.push(format!("{tags_base_url}/"));
 
             // Generate individual tag pages
             for (tag, items) in &sorted_tags {
@@ -1590,6 +2341,31 @@ fn build_site_inner(
                 tag_ctx.insert("tag_name", tag);
                 tag_ctx.insert("items", items);
                 tag_ctx.insert("tags_url", &format!("{tags_base_url}/"));
+                let tag_dir = paths.output.join(tag_url.trim_matches('/'));
+                let feeds = match tag_feed_items.get(tag) {
+                    Some(feed_items) => {
+                        let title = format!("{} — {tag}", config.title_for_lang(lang));
+                        let dir_url =
+                            format!("{}{tag_url}", config.site.base_url.trim_end_matches('/'));
+                        let channel = feed::Channel {
+                            title: &title,
+                            link: &dir_url,
+                            dir_url: &dir_url,
+                            description: config.description_for_lang(lang),
+                            language: lang,
+                        };
+                        feed::write_feeds(
+                            &tag_dir,
+                            &tag_url,
+                            config,
+                            &channel,
+                            feed_items,
+                            &author_registry,
+                        )?
+                    }
+                    None => Vec::new(),
+                };
+                tag_ctx.insert("feeds", &feeds);
                 tag_ctx.insert("translations", &Vec::<TranslationLink>::new());
                 tag_ctx.insert(
                     "page",
@@ -1602,6 +2378,8 @@ fn build_site_inner(
                         image: None,
                         slug: format!("tags/{tag_slug}"),
                         tags: Vec::new(),
+                        authors: Vec::new(),
+                        related: Vec::new(),
                         url: tag_url.clone(),
                         collection: String::new(),
                         robots: None,
@@ -1615,52 +2393,117 @@ fn build_site_inner(
                 let tag_html = tera
                     .render("tag.html", &tag_ctx)
                     .map_err(|e| PageError::Build(format!("rendering tag '{tag}': {e}")))?;
-                let tag_dir = paths.output.join(format!(
-                    "{}/{tag_slug}",
-                    tags_base_url.trim_start_matches('/')
-                ));
                 fs::create_dir_all(&tag_dir)?;
                 fs::write(tag_dir.join("index.html"), tag_html)?;
-                tag_page_urls.push(tag_url);
+                listing_urls.push(tag_url);
             }
         }
     }
 
+    // Step 4d: Custom taxonomy pages and author pages
+    if !config.taxonomies.is_empty() || !author_registry.is_empty() {
+        let items: Vec<&ContentItem> = config
+            .collections
+            .iter()
+            .flat_map(|c| all_collections.get(&c.name).into_iter().flatten())
+            .collect();
+        listing_urls.extend(taxonomies::render(
+            &tera,
+            config,
+            &items,
+            &data,
+            &paths.output,
+        )?);
+        listing_urls.extend(authors::render(
+            &tera,
+            config,
+            &author_registry,
+            &items,
+            &data,
+            &paths.output,
+        )?);
+    }
+
     step_timings.push((
         "Render indexes".to_string(),
         step_start.elapsed().as_secs_f64() * 1000.0,
     ));
 
-    // Step 5: Generate RSS feed(s)
+    // Fingerprint a set of items for the derived-output steps below. The
+    // fragment key covers each item's source, path and shortcodes.
+    let items_fingerprint = |fp: &mut cache::Fingerprint, items: &[&ContentItem]| {
+        for item in items {
+            fp.str(&item.url);
+            fp.str(
+                item_inputs
+                    .get(&item.source_path)
+                    .map_or("", |i| i.fragment_key.as_str()),
+            );
+        }
+    };
+    let all_items: Vec<&ContentItem> = config
+        .collections
+        .iter()
+        .flat_map(|c| all_collections.get(&c.name).into_iter().flatten())
+        .collect();
+    let mut all_items_fp = cache::Fingerprint::new();
+    items_fingerprint(&mut all_items_fp, &all_items);
+    let all_items_fp = all_items_fp.finish();
+
+    // Step 5: Generate feeds (RSS, Atom, JSON Feed)
     let step_start = Instant::now();
-    // Default language feed at /feed.xml
-    let default_rss_items: Vec<&ContentItem> = config
+    let rss_items: Vec<&ContentItem> = config
         .collections
         .iter()
         .filter(|c| c.has_rss)
         .flat_map(|c| all_collections.get(&c.name).into_iter().flatten())
-        .filter(|item| item.lang == *default_lang)
         .collect();
-    let rss = feed::generate_rss(config, &default_rss_items)?;
-    fs::write(paths.output.join("feed.xml"), rss)?;
-
-    // Per-language RSS feeds
-    if is_multilingual {
-        for lang in config.languages.keys() {
-            let lang_rss_items: Vec<&ContentItem> = config
-                .collections
+    let mut rss_fp = cache::Fingerprint::new();
+    items_fingerprint(&mut rss_fp, &rss_items);
+    rss_fp.str(&cache::hash_json(&author_registry));
+    let rss_fp = rss_fp.finish();
+    if !build_cache.restore_step("rss", &rss_fp) {
+        let mut outputs = Vec::new();
+        let base = config.site.base_url.trim_end_matches('/');
+        // Default language feeds at the root, per-language feeds under /{lang}/
+        for lang in config.all_languages() {
+            let lang_rss_items: Vec<&ContentItem> = rss_items
                 .iter()
-                .filter(|c| c.has_rss)
-                .flat_map(|c| all_collections.get(&c.name).into_iter().flatten())
-                .filter(|item| item.lang == *lang)
+                .filter(|item| item.lang == lang)
+                .copied()
                 .collect();
-            if !lang_rss_items.is_empty() {
-                let lang_rss = feed::generate_rss(config, &lang_rss_items)?;
-                let lang_dir = paths.output.join(lang);
-                fs::create_dir_all(&lang_dir)?;
-                fs::write(lang_dir.join("feed.xml"), lang_rss)?;
+            let is_default = lang == *default_lang;
+            if !is_default && (!is_multilingual || lang_rss_items.is_empty()) {
+                continue;
             }
+            let url_dir = format!("{}/", lang_prefix_for(&lang, default_lang));
+            let dir_url = format!("{base}{url_dir}");
+            let channel = feed::Channel {
+                title: config.title_for_lang(&lang),
+                link: if is_default {
+                    &config.site.base_url
+                } else {
+                    &dir_url
+                },
+                dir_url: &dir_url,
+                description: config.description_for_lang(&lang),
+                language: &lang,
+            };
+            let written = feed::write_feeds(
+                &paths.output.join(url_dir.trim_start_matches('/')),
+                &url_dir,
+                config,
+                &channel,
+                &lang_rss_items,
+                &author_registry,
+            )?;
+            outputs.extend(
+                written
+                    .into_iter()
+                    .map(|f| f.url.trim_start_matches('/').to_string()),
+            );
         }
+        build_cache.record_step("rss", rss_fp, outputs);
     }
 
     step_timings.push((
@@ -1670,10 +2513,34 @@ fn build_site_inner(
 
     // Step 6: Generate sitemap (all items, all languages)
     let step_start = Instant::now();
-    let all_items: Vec<&ContentItem> = all_collections.values().flatten().collect();
-    let sitemap_xml =
-        sitemap::generate_sitemap(config, &all_items, &translation_map, &tag_page_urls)?;
-    fs::write(paths.output.join("sitemap.xml"), sitemap_xml)?;
+    let mut image_manifest_keys: Vec<&String> = image_manifest.keys().collect();
+    image_manifest_keys.sort();
+    let sitemap_fp = cache::Fingerprint::new()
+        .str(&all_items_fp)
+        .str(&cache::hash_json(&listing_urls))
+        .str(&cache::hash_json(&image_manifest_keys))
+        .finish();
+    if !build_cache.restore_step("sitemap", &sitemap_fp) {
+        let files = sitemap::generate_sitemap(
+            config,
+            &all_items,
+            &translation_map,
+            &listing_urls,
+            &image_manifest,
+        )?;
+        let mut outputs = Vec::with_capacity(files.len());
+        for file in files {
+            fs::write(paths.output.join(&file.name), file.xml)?;
+            outputs.push(file.name);
+        }
+        build_cache.record_step("sitemap", sitemap_fp, outputs);
+    }
+    // Large sites get a sitemap index; robots.txt points at whichever exists
+    let sitemap_file = if paths.output.join("sitemap_index.xml").exists() {
+        "sitemap_index.xml"
+    } else {
+        "sitemap.xml"
+    };
     step_timings.push((
         "Generate sitemap".to_string(),
         step_start.elapsed().as_secs_f64() * 1000.0,
@@ -1681,51 +2548,64 @@ fn build_site_inner(
 
     // Step 7: Generate discovery files (robots.txt, llms.txt, llms-full.txt)
     let step_start = Instant::now();
-    let robots = discovery::generate_robots_txt(config);
-    fs::write(paths.output.join("robots.txt"), robots)?;
+    let discovery_fp = cache::Fingerprint::new()
+        .str(&all_items_fp)
+        .str(sitemap_file)
+        .finish();
+    if !build_cache.restore_step("discovery", &discovery_fp) {
+        let mut outputs: Vec<String> = ["robots.txt", "llms.txt", "llms-full.txt"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let robots = discovery::generate_robots_txt(config, sitemap_file);
+        fs::write(paths.output.join("robots.txt"), robots)?;
 
-    // Default language discovery files
-    let default_discovery_collections: Vec<(String, Vec<&ContentItem>)> = config
-        .collections
-        .iter()
-        .map(|c| {
-            let items: Vec<&ContentItem> = all_collections
-                .get(&c.name)
-                .into_iter()
-                .flatten()
-                .filter(|item| item.lang == *default_lang)
-                .collect();
-            (c.label.clone(), items)
-        })
-        .collect();
-    let llms_txt = discovery::generate_llms_txt(config, &default_discovery_collections);
-    fs::write(paths.output.join("llms.txt"), llms_txt)?;
-    let llms_full = discovery::generate_llms_full_txt(config, &default_discovery_collections);
-    fs::write(paths.output.join("llms-full.txt"), llms_full)?;
-
-    // Per-language discovery files
-    if is_multilingual {
-        for lang in config.languages.keys() {
-            let lang_collections: Vec<(String, Vec<&ContentItem>)> = config
-                .collections
-                .iter()
-                .map(|c| {
-                    let items: Vec<&ContentItem> = all_collections
-                        .get(&c.name)
-                        .into_iter()
-                        .flatten()
-                        .filter(|item| item.lang == *lang)
-                        .collect();
-                    (c.label.clone(), items)
-                })
-                .collect();
-            let lang_dir = paths.output.join(lang);
-            fs::create_dir_all(&lang_dir)?;
-            let llms = discovery::generate_llms_txt(config, &lang_collections);
-            fs::write(lang_dir.join("llms.txt"), llms)?;
-            let llms_f = discovery::generate_llms_full_txt(config, &lang_collections);
-            fs::write(lang_dir.join("llms-full.txt"), llms_f)?;
+        // Default language discovery files
+        let default_discovery_collections: Vec<(String, Vec<&ContentItem>)> = config
+            .collections
+            .iter()
+            .map(|c| {
+                let items: Vec<&ContentItem> = all_collections
+                    .get(&c.name)
+                    .into_iter()
+                    .flatten()
+                    .filter(|item| item.lang == *default_lang)
+                    .collect();
+                (c.label.clone(), items)
+            })
+            .collect();
+        let llms_txt = discovery::generate_llms_txt(config, &default_discovery_collections);
+        fs::write(paths.output.join("llms.txt"), llms_txt)?;
+        let llms_full = discovery::generate_llms_full_txt(config, &default_discovery_collections);
+        fs::write(paths.output.join("llms-full.txt"), llms_full)?;
+
+        // Per-language discovery files
+        if is_multilingual {
+            for lang in config.languages.keys() {
+                let lang_collections: Vec<(String, Vec<&ContentItem>)> = config
+                    .collections
+                    .iter()
+                    .map(|c| {
+                        let items: Vec<&ContentItem> = all_collections
+                            .get(&c.name)
+                            .into_iter()
+                            .flatten()
+                            .filter(|item| item.lang == *lang)
+                            .collect();
+                        (c.label.clone(), items)
+                    })
+                    .collect();
+                let lang_dir = paths.output.join(lang);
+                fs::create_dir_all(&lang_dir)?;
+                let llms = discovery::generate_llms_txt(config, &lang_collections);
+                fs::write(lang_dir.join("llms.txt"), llms)?;
+                let llms_f = discovery::generate_llms_full_txt(config, &lang_collections);
+                fs::write(lang_dir.join("llms-full.txt"), llms_f)?;
+                outputs.push(format!("{lang}/llms.txt"));
+                outputs.push(format!("{lang}/llms-full.txt"));
+            }
         }
+        build_cache.record_step("discovery", discovery_fp, outputs);
     }
 
     step_timings.push((
@@ -1739,14 +2619,24 @@ fn build_site_inner(
         if let Some(items) = all_collections.get(&collection.name) {
             let md_results: Vec<(PathBuf, String)> = items
                 .par_iter()
-                .map(|item| {
-                    let md_path = url_to_md_path(&paths.output, &item.url);
+                .filter_map(|item| {
+                    let md_path = url_to_md_path(&paths.output, &item.url, config.build.url_style);
+                    // Restored together with the page's HTML
+                    if reused_outputs.contains(&cache::rel_output(&paths.output, &md_path)) {
+                        return None;
+                    }
+                    // Computed related pages replace the explicit list
+                    let mut fm = item.frontmatter.clone();
+                    fm.related = related_by_url
+                        .get(&item.url)
+                        .map(|links| links.iter().map(|l| l.url.clone()).collect())
+                        .unwrap_or_default();
                     let md_content = format!(
                         "{}\n\n{}",
-                        content::generate_frontmatter(&item.frontmatter),
-                        item.raw_body
+                        content::generate_frontmatter(&fm),
+                        item.markdown_body
                     );
-                    (md_path, md_content)
+                    Some((md_path, md_content))
                 })
                 .collect();
 
@@ -1766,150 +2656,94 @@ fn build_site_inner(
 
     // Step 9: Generate search index
     let step_start = Instant::now();
-    let all_search_items: Vec<&ContentItem> = all_collections.values().flatten().collect();
-
-    // Root index: default language only
-    let default_search_items: Vec<&ContentItem> = all_search_items
-        .iter()
-        .filter(|i| i.lang == *default_lang)
-        .copied()
-        .collect();
-    let search_json = generate_search_index(&default_search_items, config);
-    fs::write(paths.output.join("search-index.json"), &search_json)?;
-
-    // Per-language indexes for non-default languages
-    if is_multilingual {
-        for lang_code in config.languages.keys() {
-            let lang_items: Vec<&ContentItem> = all_search_items
-                .iter()
-                .filter(|i| i.lang == *lang_code)
-                .copied()
-                .collect();
-            let lang_json = generate_search_index(&lang_items, config);
-            let lang_dir = paths.output.join(lang_code);
-            fs::create_dir_all(&lang_dir)?;
-            fs::write(lang_dir.join("search-index.json"), lang_json)?;
+    if !build_cache.restore_step("search", &all_items_fp) {
+        let mut outputs = vec!["search-index.json".to_string()];
+        // Root index: default language only
+        let default_search_items: Vec<&ContentItem> = all_items
+            .iter()
+            .filter(|i| i.lang == *default_lang)
+            .copied()
+            .collect();
+        let search_json = generate_search_index(&default_search_items, config);
+        fs::write(paths.output.join("search-index.json"), &search_json)?;
+        if let Some(search_config) = &config.search {
+            let written = search::write_index(
+                &paths.output,
+                &default_search_items,
+                config,
+                search_config,
+                default_lang,
+            )?;
+            outputs.extend(written.iter().map(|p| cache::rel_output(&paths.output, p)));
         }
-    }
-
-    step_timings.push((
-        "Generate search index".to_string(),
-        step_start.elapsed().as_secs_f64() * 1000.0,
-    ));
-
-    // Step 10: Copy static files (with optional minification and fingerprinting)
-    let step_start = Instant::now();
-    let mut static_count = 0;
-    // manifest: maps "/static/foo.css" → "/static/foo.<hash8>.css" (only when fingerprinting)
-    let mut asset_manifest: std::collections::HashMap<String, String> =
-        std::collections::HashMap::new();
-    if paths.static_dir.exists() {
-        for entry in WalkDir::new(&paths.static_dir)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().is_file())
-        {
-            let rel = entry
-                .path()
-                .strip_prefix(&paths.static_dir)
-                .unwrap_or(entry.path());
-            let dest = paths.output.join("static").join(rel);
-            if let Some(parent) = dest.parent() {
-                fs::create_dir_all(parent)?;
-            }
 
-            let ext = entry
-                .path()
-                .extension()
-                .and_then(|e| e.to_str())
-                .unwrap_or("")
-                .to_lowercase();
-            let is_css = ext == "css";
-            let is_js = ext == "js";
-
-            if (config.build.minify && (is_css || is_js)) || config.build.fingerprint {
-                let content = fs::read(entry.path())?;
-                let processed: Vec<u8> = if config.build.minify && is_css {
-                    minify_css(&content).into_bytes()
-                } else if config.build.minify && is_js {
-                    minify_js(&content).into_bytes()
-                } else {
-                    content.clone()
-                };
-
-                fs::write(&dest, &processed)?;
-
-                if config.build.fingerprint {
-                    let hash = fnv_hash8(&processed);
-                    // Build fingerprinted name: foo.css → foo.<hash>.css
-                    let stem = entry
-                        .path()
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("");
-                    let fp_name = format!("{stem}.{hash}.{ext}");
-                    let fp_dest = dest
-                        .parent()
-                        .map(|p| p.join(&fp_name))
-                        .unwrap_or_else(|| PathBuf::from(&fp_name));
-                    fs::write(&fp_dest, &processed)?;
-
-                    // Record in manifest using Unix-style paths
-                    let orig_url = format!("/static/{}", rel.to_string_lossy().replace('\\', "/"));
-                    let fp_rel = rel
-                        .parent()
-                        .map(|p| {
-                            let p = p.to_string_lossy();
-                            if p.is_empty() {
-                                fp_name.clone()
-                            } else {
-                                format!("{}/{fp_name}", p.replace('\\', "/"))
-                            }
-                        })
-                        .unwrap_or_else(|| fp_name.clone());
-                    let fp_url = format!("/static/{fp_rel}");
-                    asset_manifest.insert(orig_url, fp_url);
+        // Per-language indexes for non-default languages
+        if is_multilingual {
+            for lang_code in config.languages.keys() {
+                let lang_items: Vec<&ContentItem> = all_items
+                    .iter()
+                    .filter(|i| i.lang == *lang_code)
+                    .copied()
+                    .collect();
+                let lang_json = generate_search_index(&lang_items, config);
+                let lang_dir = paths.output.join(lang_code);
+                fs::create_dir_all(&lang_dir)?;
+                fs::write(lang_dir.join("search-index.json"), lang_json)?;
+                outputs.push(format!("{lang_code}/search-index.json"));
+                if let Some(search_config) = &config.search {
+                    let written = search::write_index(
+                        &lang_dir,
+                        &lang_items,
+                        config,
+                        search_config,
+                        lang_code,
+                    )?;
+                    outputs.extend(written.iter().map(|p| cache::rel_output(&paths.output, p)));
                 }
-            } else {
-                fs::copy(entry.path(), &dest)?;
             }
-            static_count += 1;
         }
-    }
-
-    // Write asset manifest if fingerprinting is on
-    if config.build.fingerprint && !asset_manifest.is_empty() {
-        let manifest_json =
-            serde_json::to_string_pretty(&asset_manifest).unwrap_or_else(|_| "{}".to_string());
-        fs::write(paths.output.join("asset-manifest.json"), manifest_json)?;
+        build_cache.record_step("search", all_items_fp.clone(), outputs);
     }
 
     step_timings.push((
-        "Copy static files".to_string(),
+        "Generate search index".to_string(),
         step_start.elapsed().as_secs_f64() * 1000.0,
     ));
 
-    // Step 11: Process images (resize, WebP, srcset)
+    // Step 10: Write redirect stubs and the host's native redirect file.
+    // Site-level rules belong to the main site, not its subdomain builds.
     let step_start = Instant::now();
-    let image_manifest = if let Some(ref images_config) = config.images {
-        if !images_config.widths.is_empty() {
-            images::process_images(paths, images_config)?
-        } else {
-            HashMap::new()
-        }
-    } else {
-        HashMap::new()
-    };
+    let is_subdomain_build = output.starts_with(paths.root.join("dist-subdomains"));
+    let redirect_rules = data.get("redirects").filter(|_| !is_subdomain_build);
+    let site_redirects = redirects::collect(&all_items, redirect_rules, &paths.data_dir)?;
+    let stubs = redirects::write_stubs(&paths.output, &site_redirects, &config.base_path())?;
+    redirects::write_native(
+        &paths.output,
+        &config.deploy.target,
+        &site_redirects,
+        &config.base_path(),
+    )?;
+    // Stubs are final; post-processing only scans them for links
+    reused_outputs.extend(stubs);
+    if config.build.url_style == UrlStyle::Directory {
+        let stubs = redirects::write_slash_stubs(&paths.output, &all_items, &config.base_path())?;
+        reused_outputs.extend(stubs);
+    }
 
     step_timings.push((
-        "Process images".to_string(),
+        "Write redirects".to_string(),
         step_start.elapsed().as_secs_f64() * 1000.0,
     ));
 
     // Step 12: Post-process all HTML files in a single pass
-    // (image srcset, code copy buttons, subdomain link rewriting, base path rewriting, analytics)
+    // (image srcset, code copy buttons, subdomain link rewriting, fingerprinted assets,
+    // base path rewriting, analytics)
     let step_start = Instant::now();
     let lazy_loading = config.images.as_ref().is_some_and(|img| img.lazy_loading);
+    let image_placeholder = config
+        .images
+        .as_ref()
+        .map_or(ImagePlaceholder::None, |img| img.placeholder);
     let needs_image_rewrite = !image_manifest.is_empty() || lazy_loading;
     let site_base_path = config.base_path();
     let computed_rewrites;
@@ -1923,10 +2757,15 @@ fn build_site_inner(
     let post_ctx = HtmlPostProcessContext {
         image_manifest: &image_manifest,
         lazy_loading,
+        image_placeholder,
         needs_image_rewrite,
         subdomain_rewrites,
+        cross_site_urls: &opts.cross_site_urls,
+        site_base_url: &config.site.base_url,
+        asset_manifest: &asset_manifest,
         base_path: &site_base_path,
         analytics: config.analytics.as_ref(),
+        unchanged: &reused_outputs,
     };
     let link_check = post_process_html_files(&paths.output, &post_ctx)?;
     step_timings.push((
@@ -1938,10 +2777,15 @@ fn build_site_inner(
     let known_generated = [
         "robots.txt",
         "sitemap.xml",
+        "sitemap_index.xml",
         "feed.xml",
+        "atom.xml",
+        "feed.json",
         "llms.txt",
         "llms-full.txt",
         "search-index.json",
+        "search/index.json",
+        "search/search.js",
         "index.html",
         "404.html",
         "asset-manifest.json",
@@ -1959,16 +2803,26 @@ fn build_site_inner(
         .map(|(name, items)| (name.clone(), items.len()))
         .collect();
 
-    let data_files_count = crate::data::count_data_files(&paths.data_dir);
+    let data_files_count = crate::data::count_data_files(&paths.data_dir)
+        + paths
+            .shared
+            .data_dir
+            .as_deref()
+            .map_or(0, crate::data::count_data_files);
     let stats = BuildStats {
         items_built,
         static_files_copied: static_count,
         public_files_copied: public_count,
         data_files_loaded: data_files_count,
+        pages_reused,
+        pages_rendered,
         duration_ms: start.elapsed().as_millis() as u64,
         step_timings,
     };
 
+    build_cache.keep_fragments(item_inputs.into_values().map(|i| i.fragment_key));
+    build_cache.finish()?;
+
     Ok(BuildResult {
         collections: all_collections,
         stats,
@@ -1977,21 +2831,73 @@ fn build_site_inner(
     })
 }
 
+/// Write the fingerprinted copy of a static file next to `dest` and return its URL
+/// (e.g. `/static/app.css` → `/static/app.<hash8>.css`).
+fn write_fingerprinted(dest: &Path, orig_url: &str, content: &[u8]) -> Result<String> {
+    let file_name = &orig_url[orig_url.rfind('/').map_or(0, |i| i + 1)..];
+    let fp_name = assets::fingerprinted_name(file_name, &fnv_hash8(content));
+    let fp_dest = dest
+        .parent()
+        .map(|p| p.join(&fp_name))
+        .unwrap_or_else(|| PathBuf::from(&fp_name));
+    fs::write(&fp_dest, content)?;
+    Ok(format!(
+        "{}{fp_name}",
+        &orig_url[..orig_url.len() - file_name.len()]
+    ))
+}
+
+/// Static files to copy: the site's `static/` directory plus any workspace
+/// shared static files the site does not override.
+fn static_sources(paths: &ResolvedPaths) -> Vec<(&Path, walkdir::DirEntry)> {
+    let mut roots = vec![paths.static_dir.as_path()];
+    roots.extend(paths.shared.static_dir.as_deref());
+
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    for root in roots {
+        if !root.exists() {
+            continue;
+        }
+        for entry in WalkDir::new(root)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let rel = entry.path().strip_prefix(root).unwrap_or(entry.path());
+            if seen.insert(rel.to_path_buf()) {
+                files.push((root, entry));
+            }
+        }
+    }
+    files
+}
+
 /// All config needed for the unified HTML post-processing pass.
 struct HtmlPostProcessContext<'a> {
     image_manifest: &'a HashMap<String, images::ProcessedImage>,
     lazy_loading: bool,
+    image_placeholder: ImagePlaceholder,
     needs_image_rewrite: bool,
     subdomain_rewrites: &'a HashMap<String, String>,
+    /// Workspace site base URLs for `@site:` links (empty outside workspaces).
+    cross_site_urls: &'a HashMap<String, String>,
+    site_base_url: &'a str,
+    /// Fingerprinted asset URLs (empty unless `build.fingerprint` is on).
+    asset_manifest: &'a HashMap<String, String>,
     base_path: &'a str,
     analytics: Option<&'a AnalyticsSection>,
+    /// Output files restored from the previous build. They were already
+    /// post-processed and are only scanned for links.
+    unchanged: &'a HashSet<String>,
 }
 
 /// Walk all `.html` files once, apply all post-processing transforms in memory, write once.
 /// Also extracts internal links for validation, eliminating a separate file walk.
 ///
-/// Consolidates image srcset rewriting, code copy button injection, base path
-/// URL rewriting, analytics injection, and link extraction into a single pass.
+/// Consolidates image srcset rewriting, code copy button injection, fingerprinted
+/// asset URLs, base path URL rewriting, analytics injection, and link extraction
+/// into a single pass.
 fn post_process_html_files(
     output_dir: &Path,
     ctx: &HtmlPostProcessContext,
@@ -2010,79 +2916,112 @@ fn post_process_html_files(
         .filter(|e| e.path().extension().is_some_and(|ext| ext == "html"))
         .collect();
 
-    // Per-file result: (link_count, broken_links) or error
-    let results: Vec<std::result::Result<(usize, Vec<links::BrokenLink>), PageError>> =
-        html_entries
-            .par_iter()
-            .map(|entry| {
-                let original = fs::read_to_string(entry.path()).map_err(PageError::from)?;
+    // Per-file result: (link_count, broken_links, cross_site_links) or error
+    type FileLinks = (usize, Vec<links::BrokenLink>, Vec<links::CrossSiteLink>);
+    let results: Vec<std::result::Result<FileLinks, PageError>> = html_entries
+        .par_iter()
+        .map(|entry| {
+            let original = fs::read_to_string(entry.path()).map_err(PageError::from)?;
+            let rel_path = entry
+                .path()
+                .strip_prefix(output_dir)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+
+            let mut html = original.clone();
+            let transform = !ctx.unchanged.contains(&rel_path);
+
+            // 1. Image srcset rewrite
+            if transform && ctx.needs_image_rewrite && html.contains("<img ") {
+                html = images::rewrite_html_images(
+                    &html,
+                    ctx.image_manifest,
+                    ctx.lazy_loading,
+                    ctx.image_placeholder,
+                );
+            }
 
-                let mut html = original.clone();
+            // 2. Code copy button injection
+            if transform && html.contains("<pre") {
+                html = code_copy::inject_code_copy(&html);
+            }
 
-                // 1. Image srcset rewrite
-                if ctx.needs_image_rewrite && html.contains("<img ") {
-                    html = images::rewrite_html_images(&html, ctx.image_manifest, ctx.lazy_loading);
-                }
+            // 3. Cross-subdomain link rewriting
+            if transform && !ctx.subdomain_rewrites.is_empty() {
+                html = links::rewrite_subdomain_links(&html, ctx.subdomain_rewrites);
+            }
 
-                // 2. Code copy button injection
-                if html.contains("<pre") {
-                    html = code_copy::inject_code_copy(&html);
-                }
+            // 3b. Cross-site (`@site:`) link resolution
+            if transform && !ctx.cross_site_urls.is_empty() {
+                html = links::rewrite_cross_site_links(&html, ctx.cross_site_urls);
+            }
 
-                // 3. Cross-subdomain link rewriting
-                if !ctx.subdomain_rewrites.is_empty() {
-                    html = links::rewrite_subdomain_links(&html, ctx.subdomain_rewrites);
-                }
+            // 3c. Fingerprinted asset URLs (before base path, which then prefixes them)
+            if transform && !ctx.asset_manifest.is_empty() {
+                html = assets::rewrite_html_assets(&html, ctx.asset_manifest, ctx.base_path);
+            }
 
-                // 4. Base path URL rewriting
-                if !ctx.base_path.is_empty() {
-                    html = base_path::rewrite_html_urls(&html, ctx.base_path);
-                }
+            // 4. Base path URL rewriting
+            if transform && !ctx.base_path.is_empty() {
+                html = base_path::rewrite_html_urls(&html, ctx.base_path);
+            }
 
-                // 5. Analytics injection
-                if let Some(analytics_config) = ctx.analytics {
-                    html = analytics::inject_analytics(&html, analytics_config);
-                }
+            // 5. Analytics injection
+            if let Some(analytics_config) = ctx.analytics.filter(|_| transform) {
+                html = analytics::inject_analytics(&html, analytics_config);
+            }
 
-                // Only write if something changed
-                if html != original {
-                    fs::write(entry.path(), &html).map_err(PageError::from)?;
-                }
+            // Only write if something changed
+            if html != original {
+                fs::write(entry.path(), &html).map_err(PageError::from)?;
+            }
 
-                // 6. Extract internal links from final HTML for validation
-                let internal_links = links::extract_internal_links(&html);
-                let link_count = internal_links.len();
-                let rel_path = entry
-                    .path()
-                    .strip_prefix(output_dir)
-                    .unwrap_or(entry.path())
-                    .to_string_lossy()
-                    .replace('\\', "/");
-
-                let broken: Vec<links::BrokenLink> = internal_links
+            // 6. Extract internal links from final HTML for validation
+            let internal_links = links::extract_internal_links(&html);
+            let link_count = internal_links.len();
+
+            let broken: Vec<links::BrokenLink> = internal_links
+                .into_iter()
+                .filter(|href| !valid_urls.contains(href.as_str()))
+                .map(|href| links::BrokenLink {
+                    source_file: rel_path.clone(),
+                    href,
+                })
+                .collect();
+
+            // 7. Collect links into other workspace sites (checked after all sites build)
+            let cross_site: Vec<links::CrossSiteLink> = if ctx.cross_site_urls.is_empty() {
+                Vec::new()
+            } else {
+                links::extract_cross_site_links(&html, ctx.cross_site_urls, ctx.site_base_url)
                     .into_iter()
-                    .filter(|href| !valid_urls.contains(href.as_str()))
-                    .map(|href| links::BrokenLink {
+                    .map(|(site, path)| links::CrossSiteLink {
                         source_file: rel_path.clone(),
-                        href,
+                        site,
+                        path,
                     })
-                    .collect();
+                    .collect()
+            };
 
-                Ok((link_count, broken))
-            })
-            .collect();
+            Ok((link_count, broken, cross_site))
+        })
+        .collect();
 
     let mut all_broken = Vec::new();
+    let mut all_cross_site = Vec::new();
     let mut total_checked = 0;
     for result in results {
-        let (count, broken) = result?;
+        let (count, broken, cross_site) = result?;
         total_checked += count;
         all_broken.extend(broken);
+        all_cross_site.extend(cross_site);
     }
 
     Ok(links::LinkCheckResult {
         total_links_checked: total_checked,
         broken_links: all_broken,
+        cross_site_links: all_cross_site,
     })
 }
 
@@ -2178,12 +3117,7 @@ fn build_url(url_prefix: &str, slug: &str) -> String {
 
 /// FNV-1a hash → first 8 hex chars, used for cache-busting fingerprints.
 fn fnv_hash8(data: &[u8]) -> String {
-    let mut hash: u64 = 14695981039346656037;
-    for &byte in data {
-        hash ^= byte as u64;
-        hash = hash.wrapping_mul(1099511628211);
-    }
-    format!("{hash:016x}")[..8].to_string()
+    cache::hash_bytes(data)[..8].to_string()
 }
 
 /// Minify CSS: strip comments and collapse whitespace around syntax characters.
@@ -2282,14 +3216,30 @@ fn minify_js(raw: &[u8]) -> String {
     result
 }
 
-fn url_to_output_path(output_dir: &Path, url: &str) -> std::path::PathBuf {
+fn url_to_output_path(output_dir: &Path, url: &str, style: UrlStyle) -> std::path::PathBuf {
     let clean = url.trim_matches('/');
-    output_dir.join(format!("{clean}.html"))
+    match style {
+        UrlStyle::File => output_dir.join(format!("{clean}.html")),
+        UrlStyle::Directory => output_dir.join(clean).join("index.html"),
+    }
 }
 
-fn url_to_md_path(output_dir: &Path, url: &str) -> std::path::PathBuf {
+fn url_to_md_path(output_dir: &Path, url: &str, style: UrlStyle) -> std::path::PathBuf {
     let clean = url.trim_matches('/');
-    output_dir.join(format!("{clean}.md"))
+    match style {
+        UrlStyle::File => output_dir.join(format!("{clean}.md")),
+        UrlStyle::Directory => output_dir.join(clean).join("index.md"),
+    }
+}
+
+/// URL of the markdown copy of the page at `url`: `/posts/hello.md`, or
+/// `/posts/hello/index.md` for a directory URL.
+pub(crate) fn markdown_url(url: &str) -> String {
+    if url.ends_with('/') {
+        format!("{url}index.md")
+    } else {
+        format!("{url}.md")
+    }
 }
 
 /// Generate a markdown listing for a collection index page.
@@ -2310,28 +3260,11 @@ fn generate_collection_index_md(label: &str, items: &[ItemSummary]) -> String {
     md
 }
 
-/// Generate an HTML redirect page (meta refresh + JS redirect).
-fn generate_redirect_html(target_url: &str) -> String {
-    format!(
-        r#"<!DOCTYPE html>
-<html>
-<head>
-<meta charset="utf-8">
-<meta http-equiv="refresh" content="0; url={target_url}">
-<link rel="canonical" href="{target_url}">
-<title>Redirecting…</title>
-<script>window.location.replace("{target_url}");</script>
-</head>
-<body>
-<p>Redirecting to <a href="{target_url}">{target_url}</a>…</p>
-</body>
-</html>"#
-    )
-}
-
 fn build_page_context(
     site: &SiteContext,
     item: &ContentItem,
+    authors: Vec<authors::AuthorContext>,
+    related: Vec<related::RelatedLink>,
     data: &serde_json::Value,
 ) -> tera::Context {
     let mut ctx = tera::Context::new();
@@ -2348,6 +3281,8 @@ fn build_page_context(
             image: absolutize_image(item.frontmatter.image.as_deref(), &site.base_url),
             slug: item.slug.clone(),
             tags: item.frontmatter.tags.clone(),
+            authors,
+            related,
             url: item.url.clone(),
             collection: item.collection.clone(),
             robots: item.frontmatter.robots.clone(),
@@ -2361,6 +3296,19 @@ fn build_page_context(
     ctx
 }
 
+/// Fingerprint a page's render context, excluding `data` and `nav` (which are
+/// fingerprinted once per template and once per language respectively).
+/// `page.related` carries the related pages' links, not their bodies, so a
+/// page is re-rendered only when its related list changes.
+fn page_context_fingerprint(ctx: &tera::Context) -> String {
+    let mut json = ctx.clone().into_json();
+    if let Some(obj) = json.as_object_mut() {
+        obj.remove("data");
+        obj.remove("nav");
+    }
+    cache::hash_json(&json)
+}
+
 /// Ensure an image URL is absolute by prepending `base_url` if it's a relative path.
 /// Absolute URLs (starting with `http`) are returned unchanged.
 fn absolutize_image(image: Option<&str>, base_url: &str) -> Option<String> {
@@ -2430,6 +3378,8 @@ fn ui_strings_for_lang(lang: &str, data: &serde_json::Value) -> serde_json::Valu
         "resources": "Resources",
         "previous": "Previous",
         "next": "Next",
+        "breadcrumb": "Breadcrumb",
+        "doc_pagination": "Documentation pagination",
         "on_this_page": "On this page",
         "search_docs": "Search docs\u{2026}",
         "search_documentation": "Search documentation",
@@ -2560,6 +3510,7 @@ mod tests {
                 base_url: "https://example.com".into(),
                 language: "en".into(),
                 author: "Author".into(),
+                timezone: None,
             },
             collections: vec![CollectionConfig::preset_posts()],
             build: BuildSection::default(),
@@ -2569,6 +3520,11 @@ mod tests {
             analytics: None,
             trust: None,
             contact: None,
+            og: None,
+            search: None,
+            related: None,
+            feed: None,
+            taxonomies: Vec::new(),
         }
     }
 
@@ -2880,25 +3836,25 @@ mod tests {
 
     #[test]
     fn test_url_to_output_path_basic() {
-        let p = url_to_output_path(Path::new("/out"), "/posts/hello");
+        let p = url_to_output_path(Path::new("/out"), "/posts/hello", UrlStyle::File);
         assert_eq!(p, PathBuf::from("/out/posts/hello.html"));
     }
 
     #[test]
     fn test_url_to_output_path_strips_slashes() {
-        let p = url_to_output_path(Path::new("/out"), "/about/");
+        let p = url_to_output_path(Path::new("/out"), "/about/", UrlStyle::File);
         assert_eq!(p, PathBuf::from("/out/about.html"));
     }
 
     #[test]
     fn test_url_to_md_path_basic() {
-        let p = url_to_md_path(Path::new("/out"), "/docs/setup");
+        let p = url_to_md_path(Path::new("/out"), "/docs/setup", UrlStyle::File);
         assert_eq!(p, PathBuf::from("/out/docs/setup.md"));
     }
 
     #[test]
     fn test_url_to_md_path_strips_slashes() {
-        let p = url_to_md_path(Path::new("/out"), "/about/");
+        let p = url_to_md_path(Path::new("/out"), "/about/", UrlStyle::File);
         assert_eq!(p, PathBuf::from("/out/about.md"));
     }
 
@@ -3161,11 +4117,12 @@ mod tests {
             frontmatter: Frontmatter {
                 title: "Hello".into(),
                 description: Some("A post".into()),
-                date: Some(chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()),
+                date: Some(chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap().into()),
                 tags: vec!["rust".into()],
                 ..Frontmatter::default()
             },
             raw_body: "body".into(),
+            markdown_body: "body".into(),
             html_body: "<p>body</p>".into(),
             source_path: PathBuf::from("content/posts/hello.md"),
             slug: "hello".into(),
@@ -3198,6 +4155,7 @@ mod tests {
                 ..Frontmatter::default()
             },
             raw_body: "body".into(),
+            markdown_body: "body".into(),
             html_body: "<p>body</p>".into(),
             source_path: PathBuf::from("content/pages/about.md"),
             slug: "about".into(),
@@ -3236,6 +4194,8 @@ mod tests {
             static_files_copied: 3,
             public_files_copied: 0,
             data_files_loaded: 0,
+            pages_reused: 0,
+            pages_rendered: 0,
             duration_ms: 1500,
             step_timings: vec![],
         };
@@ -3254,6 +4214,8 @@ mod tests {
             static_files_copied: 0,
             public_files_copied: 2,
             data_files_loaded: 4,
+            pages_reused: 0,
+            pages_rendered: 0,
             duration_ms: 250,
             step_timings: vec![],
         };
@@ -3262,6 +4224,22 @@ mod tests {
         assert!(display.contains("4 data files loaded"));
     }
 
+    #[test]
+    fn test_build_stats_human_display_reused_pages() {
+        let stats = BuildStats {
+            items_built: HashMap::new(),
+            static_files_copied: 0,
+            public_files_copied: 0,
+            data_files_loaded: 0,
+            pages_reused: 40,
+            pages_rendered: 2,
+            duration_ms: 100,
+            step_timings: vec![],
+        };
+        let display = stats.human_display();
+        assert!(display.contains("2 pages re-rendered, 40 reused"));
+    }
+
     #[test]
     fn test_build_stats_human_display_with_step_timings() {
         let stats = BuildStats {
@@ -3269,6 +4247,8 @@ mod tests {
             static_files_copied: 0,
             public_files_copied: 0,
             data_files_loaded: 0,
+            pages_reused: 0,
+            pages_rendered: 0,
             duration_ms: 100,
             step_timings: vec![("Fast step".into(), 0.5), ("Slow step".into(), 15.3)],
         };
@@ -3347,14 +4327,15 @@ mod tests {
             frontmatter: Frontmatter {
                 title: "My Post".into(),
                 description: Some("A description".into()),
-                date: Some(chrono::NaiveDate::from_ymd_opt(2025, 6, 1).unwrap()),
-                updated: Some(chrono::NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()),
+                date: Some(chrono::NaiveDate::from_ymd_opt(2025, 6, 1).unwrap().into()),
+                updated: Some(chrono::NaiveDate::from_ymd_opt(2025, 6, 15).unwrap().into()),
                 image: Some("/static/hero.png".into()),
                 tags: vec!["rust".into(), "web".into()],
                 robots: Some("noindex".into()),
                 ..Frontmatter::default()
             },
             raw_body: "Some body text here for testing".into(),
+            markdown_body: "Some body text here for testing".into(),
             html_body: "<p>Some body text here for testing</p>".into(),
             source_path: PathBuf::from("content/posts/my-post.md"),
             slug: "my-post".into(),
@@ -3368,7 +4349,7 @@ mod tests {
             excerpt_html: "<p>Some body</p>".into(),
         };
 
-        let ctx = build_page_context(&site, &item, &data);
+        let ctx = build_page_context(&site, &item, Vec::new(), Vec::new(), &data);
         let json = ctx.into_json();
         let page = json.get("page").unwrap();
         assert_eq!(page["title"], "My Post");
@@ -3397,6 +4378,7 @@ mod tests {
                 ..Frontmatter::default()
             },
             raw_body: String::new(),
+            markdown_body: String::new(),
             html_body: String::new(),
             source_path: PathBuf::from("content/pages/test.md"),
             slug: "test".into(),
@@ -3410,7 +4392,7 @@ mod tests {
             excerpt_html: String::new(),
         };
 
-        let ctx = build_page_context(&site, &item, &data);
+        let ctx = build_page_context(&site, &item, Vec::new(), Vec::new(), &data);
         let json = ctx.into_json();
         let page = json.get("page").unwrap();
         assert!(page["image"].is_null());
@@ -3573,46 +4555,6 @@ mod tests {
         assert_eq!(json["next_url"], "/posts/page/3/");
     }
 
-    // ── NavItem / NavSection serialization ──────────────────────────────
-
-    #[test]
-    fn test_nav_item_serialization() {
-        let item = NavItem {
-            title: "Getting Started".into(),
-            url: "/docs/getting-started".into(),
-            active: true,
-        };
-        let json = serde_json::to_value(&item).unwrap();
-        assert_eq!(json["title"], "Getting Started");
-        assert_eq!(json["url"], "/docs/getting-started");
-        assert_eq!(json["active"], true);
-    }
-
-    #[test]
-    fn test_nav_section_serialization() {
-        let section = NavSection {
-            name: "guides".into(),
-            label: "Guides".into(),
-            items: vec![
-                NavItem {
-                    title: "Setup".into(),
-                    url: "/docs/guides/setup".into(),
-                    active: false,
-                },
-                NavItem {
-                    title: "Config".into(),
-                    url: "/docs/guides/config".into(),
-                    active: true,
-                },
-            ],
-        };
-        let json = serde_json::to_value(&section).unwrap();
-        assert_eq!(json["name"], "guides");
-        assert_eq!(json["label"], "Guides");
-        assert_eq!(json["items"].as_array().unwrap().len(), 2);
-        assert_eq!(json["items"][1]["active"], true);
-    }
-
     // ── CollectionContext / ItemSummary serialization ────────────────────
 
     #[test]
@@ -3653,6 +4595,8 @@ mod tests {
             image: Some("https://example.com/img.png".into()),
             slug: "my-page".into(),
             tags: vec!["tag1".into()],
+            authors: Vec::new(),
+            related: Vec::new(),
             url: "/my-page".into(),
             collection: "pages".into(),
             robots: Some("noindex".into()),
@@ -3691,6 +4635,8 @@ mod tests {
             image: None,
             slug: String::new(),
             tags: Vec::new(),
+            authors: Vec::new(),
+            related: Vec::new(),
             url: "/".into(),
             collection: String::new(),
             robots: None,
@@ -3761,6 +4707,7 @@ mod tests {
                 ..Frontmatter::default()
             },
             raw_body: "body".into(),
+            markdown_body: "body".into(),
             html_body: "<p>body</p>".into(),
             source_path: PathBuf::from("content/posts/post.md"),
             slug: "post".into(),
@@ -3780,6 +4727,7 @@ mod tests {
                 ..Frontmatter::default()
             },
             raw_body: "body".into(),
+            markdown_body: "body".into(),
             html_body: "<p>body</p>".into(),
             source_path: PathBuf::from("content/pages/page.md"),
             slug: "page".into(),
@@ -3849,13 +4797,13 @@ mod tests {
 
     #[test]
     fn test_url_to_output_path_with_lang_prefix() {
-        let p = url_to_output_path(Path::new("/out"), "/es/posts/hello");
+        let p = url_to_output_path(Path::new("/out"), "/es/posts/hello", UrlStyle::File);
         assert_eq!(p, PathBuf::from("/out/es/posts/hello.html"));
     }
 
     #[test]
     fn test_url_to_md_path_with_lang_prefix() {
-        let p = url_to_md_path(Path::new("/out"), "/fr/docs/setup");
+        let p = url_to_md_path(Path::new("/out"), "/fr/docs/setup", UrlStyle::File);
         assert_eq!(p, PathBuf::from("/out/fr/docs/setup.md"));
     }
 
@@ -3921,6 +4869,8 @@ mod tests {
             "resources",
             "previous",
             "next",
+            "breadcrumb",
+            "doc_pagination",
             "on_this_page",
             "search_docs",
             "search_documentation",
@@ -0,0 +1,890 @@
+//! Sidebar navigation for nested collections such as docs.
+//!
+//! The tree follows the collection's directories: every directory is a
+//! section, and pages and sections sort by `weight`, then title. A section
+//! without a weight of its own sorts with its lowest-weighted page, and a page
+//! whose slug names a directory (`guides.md` next to `guides/`) becomes that
+//! section's link. An `_index.md` in a directory describes its section:
+//!
+//! ```yaml
+//! ---
+//! title: Guides
+//! weight: 2
+//! description: Step-by-step walkthroughs
+//! collapsed: true
+//! ---
+//! ```
+//!
+//! `data/nav/<collection>.yaml` replaces the order entirely. Each entry is a
+//! page slug, a directory (standing for its section, ordered as above) or a
+//! group with its own title and entries:
+//!
+//! ```yaml
+//! - getting-started
+//! - title: Guides
+//!   collapsed: true
+//!   items:
+//!     - guides/setup
+//!     - guides/deploy
+//! - page: reference/cli
+//!   title: CLI
+//! ```
+//!
+//! Pages the file leaves out are still built, but not listed. Every listed
+//! page gets `breadcrumbs` and `prev`/`next` links in nav order.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use super::title_case;
+use crate::content::{self, ContentItem};
+use crate::data;
+use crate::error::{PageError, Result};
+
+/// A page or nested section of the sidebar.
+#[derive(Debug, Serialize, Clone)]
+pub struct NavItem {
+    pub title: String,
+    /// Empty for a section without a page of its own.
+    pub url: String,
+    /// Whether this is the page being rendered.
+    pub active: bool,
+    pub description: Option<String>,
+    /// Whether the section starts closed.
+    pub collapsed: bool,
+    /// Whether to show the section open: not collapsed, or around the active page.
+    pub open: bool,
+    /// Entries of a section; empty for a page.
+    pub items: Vec<NavItem>,
+}
+
+/// A top-level group of the sidebar. Top-level pages share a group with an
+/// empty `name` and `label`.
+#[derive(Debug, Serialize, Clone)]
+pub struct NavSection {
+    /// Directory of the section, e.g. `guides`.
+    pub name: String,
+    pub label: String,
+    pub url: String,
+    pub active: bool,
+    pub description: Option<String>,
+    pub collapsed: bool,
+    pub open: bool,
+    pub items: Vec<NavItem>,
+}
+
+/// A link in `breadcrumbs`, `prev` or `next`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NavLink {
+    pub title: String,
+    pub url: String,
+}
+
+/// Where a page sits in the sidebar.
+#[derive(Debug, Clone, Default)]
+pub struct Placement {
+    /// Index of the top-level section, then of each entry in `items` down to the page.
+    path: Vec<usize>,
+    /// The collection, the sections around the page and the page itself.
+    pub breadcrumbs: Vec<NavLink>,
+    pub prev: Option<NavLink>,
+    pub next: Option<NavLink>,
+}
+
+/// The sidebar of a collection in one language.
+#[derive(Debug, Default)]
+pub struct Nav {
+    pub sections: Vec<NavSection>,
+    /// Placement of every listed page, by slug.
+    pub placements: HashMap<String, Placement>,
+}
+
+/// Frontmatter of a section's `_index.md`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SectionMeta {
+    pub title: Option<String>,
+    pub weight: Option<i32>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub collapsed: bool,
+}
+
+/// The `_index.md` files of a collection, by directory and language.
+#[derive(Debug, Default)]
+pub struct Sections(HashMap<String, HashMap<String, SectionMeta>>);
+
+impl Sections {
+    /// Read every `_index.md` under `collection_dir`; `_index.es.md`
+    /// describes a section in Spanish.
+    pub fn load(collection_dir: &Path, configured_langs: &HashSet<&str>) -> Result<Self> {
+        let mut sections: HashMap<String, HashMap<String, SectionMeta>> = HashMap::new();
+        if !collection_dir.exists() {
+            return Ok(Self(sections));
+        }
+        for entry in WalkDir::new(collection_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| content::is_section_index(e.path()))
+        {
+            let path = entry.path();
+            let raw = fs::read_to_string(path)?;
+            let meta = match content::split_frontmatter(&raw) {
+                Some((fm, _)) if !fm.is_empty() => {
+                    serde_yaml_ng::from_str(fm).map_err(|e| PageError::Content {
+                        path: path.to_path_buf(),
+                        message: format!("invalid section frontmatter: {e}"),
+                    })?
+                }
+                _ => SectionMeta::default(),
+            };
+            let dir = path
+                .parent()
+                .and_then(|p| p.strip_prefix(collection_dir).ok())
+                .map(|rel| {
+                    rel.components()
+                        .map(|c| c.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/")
+                })
+                .unwrap_or_default();
+            let lang = content::extract_lang_from_filename(path, configured_langs);
+            sections
+                .entry(dir)
+                .or_default()
+                .insert(lang.unwrap_or_default(), meta);
+        }
+        Ok(Self(sections))
+    }
+
+    /// The metadata of `dir` in `lang`, else from its untranslated `_index.md`.
+    fn get(&self, dir: &str, lang: &str) -> Option<&SectionMeta> {
+        let by_lang = self.0.get(dir)?;
+        by_lang.get(lang).or_else(|| by_lang.get(""))
+    }
+}
+
+/// An entry of `data/nav/<collection>.yaml`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Entry {
+    /// A page slug, or a directory standing for its section.
+    Slug(String),
+    Group(Group),
+}
+
+/// A page under its own title, or a section with explicit entries.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Group {
+    /// Slug of the page the entry links to.
+    page: Option<String>,
+    /// Directory whose `_index.md` and pages fill in what the group leaves out.
+    section: Option<String>,
+    title: Option<String>,
+    description: Option<String>,
+    collapsed: Option<bool>,
+    items: Option<Vec<Entry>>,
+}
+
+/// Read `data/nav/<collection>.{yaml,yml,json,toml}` from the site's data
+/// directory or the workspace's shared one, if there is such a file.
+pub fn load_override(
+    data_dir: &Path,
+    shared_dir: Option<&Path>,
+    collection: &str,
+) -> Result<Option<(PathBuf, Vec<Entry>)>> {
+    let path = data::file_for_key(data_dir, shared_dir, &format!("nav/{collection}"));
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let entries =
+        serde_json::from_value(data::parse_data_file(&path)?).map_err(|e| PageError::Data {
+            path: path.clone(),
+            message: format!("invalid nav entries: {e}"),
+        })?;
+    Ok(Some((path, entries)))
+}
+
+/// Build the sidebar of one language of a collection from its `items`, in
+/// collection order. `all_items` holds every language, so the nav file may
+/// name pages that are not translated into this one.
+pub fn build(
+    items: &[&ContentItem],
+    all_items: &[ContentItem],
+    sections: &Sections,
+    entries: Option<&[Entry]>,
+    lang: &str,
+    root: NavLink,
+) -> std::result::Result<Nav, String> {
+    let tree = Tree::new(items, sections, lang);
+    let nodes = match entries {
+        Some(entries) => {
+            let mut known: HashSet<&str> = HashSet::new();
+            for item in all_items {
+                let mut slug = item.slug.as_str();
+                known.insert(slug);
+                while let Some((dir, _)) = slug.rsplit_once('/') {
+                    known.insert(dir);
+                    slug = dir;
+                }
+            }
+            tree.entries(entries, &known, &mut HashSet::new())?
+        }
+        None => tree.children(""),
+    };
+    Ok(finish(nodes, root))
+}
+
+/// Mark the page at `placement` active in a serialized nav and open the
+/// sections around it.
+pub fn mark_active(nav: &mut serde_json::Value, placement: &Placement) {
+    if placement.path.is_empty() {
+        return;
+    }
+    let mut node = nav;
+    for (depth, &i) in placement.path.iter().enumerate() {
+        let list = if depth == 0 {
+            node.as_array_mut()
+        } else {
+            node.get_mut("items").and_then(|items| items.as_array_mut())
+        };
+        let Some(next) = list.and_then(|list| list.get_mut(i)) else {
+            return;
+        };
+        next["open"] = serde_json::Value::Bool(true);
+        node = next;
+    }
+    node["active"] = serde_json::Value::Bool(true);
+}
+
+/// A page or section before it is laid out.
+struct Node<'a> {
+    title: String,
+    url: String,
+    /// Directory of a section; `None` for a page.
+    dir: Option<String>,
+    /// The page the node links to.
+    slug: Option<&'a str>,
+    weight: Option<i32>,
+    description: Option<String>,
+    collapsed: bool,
+    children: Vec<Node<'a>>,
+}
+
+struct Tree<'a, 's> {
+    pages: HashMap<&'a str, &'a ContentItem>,
+    /// Pages and subdirectories directly in each directory.
+    dirs: HashMap<&'a str, (Vec<&'a ContentItem>, BTreeSet<&'a str>)>,
+    sections: &'s Sections,
+    lang: &'s str,
+}
+
+impl<'a, 's> Tree<'a, 's> {
+    fn new(items: &[&'a ContentItem], sections: &'s Sections, lang: &'s str) -> Self {
+        let mut pages = HashMap::new();
+        let mut dirs: HashMap<&'a str, (Vec<&'a ContentItem>, BTreeSet<&'a str>)> = HashMap::new();
+        for &item in items {
+            pages.insert(item.slug.as_str(), item);
+            let mut dir = item.slug.rsplit_once('/').map_or("", |(dir, _)| dir);
+            dirs.entry(dir).or_default().0.push(item);
+            while !dir.is_empty() {
+                let parent = dir.rsplit_once('/').map_or("", |(parent, _)| parent);
+                dirs.entry(parent).or_default().1.insert(dir);
+                dir = parent;
+            }
+        }
+        Self {
+            pages,
+            dirs,
+            sections,
+            lang,
+        }
+    }
+
+    fn page(&self, item: &'a ContentItem) -> Node<'a> {
+        Node {
+            title: item.frontmatter.title.clone(),
+            url: item.url.clone(),
+            dir: None,
+            slug: Some(item.slug.as_str()),
+            weight: item.frontmatter.weight,
+            description: None,
+            collapsed: false,
+            children: Vec::new(),
+        }
+    }
+
+    /// The section of `dir` with its pages and subsections in order.
+    fn section(&self, dir: &str) -> Node<'a> {
+        let meta = self.sections.get(dir, self.lang);
+        let page = self.pages.get(dir).copied();
+        let children = self.children(dir);
+        Node {
+            title: meta
+                .and_then(|m| m.title.clone())
+                .or_else(|| page.map(|p| p.frontmatter.title.clone()))
+                .unwrap_or_else(|| title_case(dir.rsplit('/').next().unwrap_or(dir))),
+            url: page.map(|p| p.url.clone()).unwrap_or_default(),
+            dir: Some(dir.to_string()),
+            slug: page.map(|p| p.slug.as_str()),
+            weight: meta
+                .and_then(|m| m.weight)
+                .or_else(|| page.and_then(|p| p.frontmatter.weight))
+                .or_else(|| children.iter().filter_map(|c| c.weight).min()),
+            description: meta.and_then(|m| m.description.clone()),
+            collapsed: meta.is_some_and(|m| m.collapsed),
+            children,
+        }
+    }
+
+    /// Pages and subsections of `dir`, by weight then title.
+    fn children(&self, dir: &str) -> Vec<Node<'a>> {
+        let Some((pages, subdirs)) = self.dirs.get(dir) else {
+            return Vec::new();
+        };
+        let mut nodes: Vec<Node<'a>> = pages
+            .iter()
+            .filter(|p| !self.dirs.contains_key(p.slug.as_str()))
+            .map(|p| self.page(p))
+            .chain(subdirs.iter().map(|d| self.section(d)))
+            .collect();
+        nodes.sort_by(by_weight);
+        nodes
+    }
+
+    /// Nodes for nav file entries, in the file's order.
+    fn entries(
+        &self,
+        entries: &[Entry],
+        known: &HashSet<&str>,
+        seen: &mut HashSet<String>,
+    ) -> std::result::Result<Vec<Node<'a>>, String> {
+        let mut nodes = Vec::new();
+        for entry in entries {
+            let node = match entry {
+                Entry::Slug(slug) if self.dirs.contains_key(slug.as_str()) => {
+                    Some(self.section(slug))
+                }
+                Entry::Slug(slug) => self.listed_page(slug, known, seen)?.map(|p| self.page(p)),
+                Entry::Group(group) => self.group(group, known, seen)?,
+            };
+            nodes.extend(node);
+        }
+        Ok(nodes)
+    }
+
+    /// The page `slug` names, or `None` if it exists only in other languages.
+    fn listed_page(
+        &self,
+        slug: &str,
+        known: &HashSet<&str>,
+        seen: &mut HashSet<String>,
+    ) -> std::result::Result<Option<&'a ContentItem>, String> {
+        if !known.contains(slug) {
+            return Err(format!("no page or section '{slug}'"));
+        }
+        if !seen.insert(slug.to_string()) {
+            return Err(format!("'{slug}' is listed more than once"));
+        }
+        Ok(self.pages.get(slug).copied())
+    }
+
+    fn group(
+        &self,
+        group: &Group,
+        known: &HashSet<&str>,
+        seen: &mut HashSet<String>,
+    ) -> std::result::Result<Option<Node<'a>>, String> {
+        let page = match &group.page {
+            Some(slug) => match self.listed_page(slug, known, seen)? {
+                Some(page) => Some(page),
+                None => return Ok(None),
+            },
+            None => None,
+        };
+        let dir = group.section.as_deref();
+        if let Some(dir) = dir {
+            if !known.contains(dir) {
+                return Err(format!("no page or section '{dir}'"));
+            }
+        }
+
+        // A titled page
+        if group.items.is_none() && dir.is_none() {
+            let Some(page) = page else {
+                return Err("a nav entry needs a page, a section or items".into());
+            };
+            let mut node = self.page(page);
+            if let Some(title) = &group.title {
+                node.title = title.clone();
+            }
+            node.description = group.description.clone();
+            return Ok(Some(node));
+        }
+
+        let meta = dir.and_then(|d| self.sections.get(d, self.lang));
+        let page = page.or_else(|| dir.and_then(|d| self.pages.get(d).copied()));
+        let title = group
+            .title
+            .clone()
+            .or_else(|| meta.and_then(|m| m.title.clone()))
+            .or_else(|| page.map(|p| p.frontmatter.title.clone()))
+            .or_else(|| dir.map(|d| title_case(d.rsplit('/').next().unwrap_or(d))))
+            .ok_or("a nav group needs a title or a section")?;
+        let children = match &group.items {
+            Some(items) => self.entries(items, known, seen)?,
+            None => dir.map(|d| self.children(d)).unwrap_or_default(),
+        };
+        // Nothing of the group is in this language
+        if children.is_empty() && page.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Node {
+            url: page.map(|p| p.url.clone()).unwrap_or_default(),
+            dir: Some(
+                dir.map(String::from)
+                    .unwrap_or_else(|| content::slug_from_title(&title)),
+            ),
+            slug: page.map(|p| p.slug.as_str()),
+            weight: None,
+            description: group
+                .description
+                .clone()
+                .or_else(|| meta.and_then(|m| m.description.clone())),
+            collapsed: group
+                .collapsed
+                .unwrap_or_else(|| meta.is_some_and(|m| m.collapsed)),
+            title,
+            children,
+        }))
+    }
+}
+
+fn by_weight(a: &Node<'_>, b: &Node<'_>) -> Ordering {
+    match (a.weight, b.weight) {
+        (Some(wa), Some(wb)) => wa.cmp(&wb).then_with(|| a.title.cmp(&b.title)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.title.cmp(&b.title),
+    }
+}
+
+/// A listed page, in nav order.
+struct Visit<'a> {
+    slug: &'a str,
+    path: Vec<usize>,
+    breadcrumbs: Vec<NavLink>,
+}
+
+/// Lay out the top-level nodes as sections, grouping consecutive top-level
+/// pages, and place every listed page.
+fn finish(nodes: Vec<Node<'_>>, root: NavLink) -> Nav {
+    let mut sections: Vec<NavSection> = Vec::new();
+    let mut visits = Vec::new();
+    for node in nodes {
+        let mut crumbs = vec![root.clone()];
+        match node.dir.clone() {
+            Some(name) => {
+                let mut path = vec![sections.len()];
+                let item = lay_out(node, &mut path, &mut crumbs, &mut visits);
+                sections.push(NavSection {
+                    name,
+                    label: item.title,
+                    url: item.url,
+                    active: false,
+                    description: item.description,
+                    collapsed: item.collapsed,
+                    open: item.open,
+                    items: item.items,
+                });
+            }
+            None => {
+                if !sections.last().is_some_and(|s| s.name.is_empty()) {
+                    sections.push(NavSection {
+                        name: String::new(),
+                        label: String::new(),
+                        url: String::new(),
+                        active: false,
+                        description: None,
+                        collapsed: false,
+                        open: true,
+                        items: Vec::new(),
+                    });
+                }
+                let index = sections.len() - 1;
+                let group = &mut sections[index];
+                let mut path = vec![index, group.items.len()];
+                let item = lay_out(node, &mut path, &mut crumbs, &mut visits);
+                group.items.push(item);
+            }
+        }
+    }
+
+    let links: Vec<Option<NavLink>> = visits
+        .iter()
+        .map(|v| v.breadcrumbs.last().cloned())
+        .collect();
+    let placements = visits
+        .into_iter()
+        .enumerate()
+        .map(|(i, visit)| {
+            let placement = Placement {
+                path: visit.path,
+                breadcrumbs: visit.breadcrumbs,
+                prev: i.checked_sub(1).and_then(|p| links[p].clone()),
+                next: links.get(i + 1).cloned().flatten(),
+            };
+            (visit.slug.to_string(), placement)
+        })
+        .collect();
+    Nav {
+        sections,
+        placements,
+    }
+}
+
+fn lay_out<'a>(
+    node: Node<'a>,
+    path: &mut Vec<usize>,
+    crumbs: &mut Vec<NavLink>,
+    visits: &mut Vec<Visit<'a>>,
+) -> NavItem {
+    let link = NavLink {
+        title: node.title.clone(),
+        url: node.url.clone(),
+    };
+    crumbs.push(link);
+    if let Some(slug) = node.slug {
+        visits.push(Visit {
+            slug,
+            path: path.clone(),
+            breadcrumbs: crumbs.clone(),
+        });
+    }
+    let items = node
+        .children
+        .into_iter()
+        .enumerate()
+        .map(|(i, child)| {
+            path.push(i);
+            let item = lay_out(child, path, crumbs, visits);
+            path.pop();
+            item
+        })
+        .collect();
+    crumbs.pop();
+    NavItem {
+        title: node.title,
+        url: node.url,
+        active: false,
+        description: node.description,
+        collapsed: node.collapsed,
+        open: !node.collapsed,
+        items,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::Frontmatter;
+
+    fn item(slug: &str, title: &str, weight: Option<i32>) -> ContentItem {
+        ContentItem {
+            frontmatter: Frontmatter {
+                title: title.into(),
+                weight,
+                ..Default::default()
+            },
+            raw_body: String::new(),
+            markdown_body: String::new(),
+            html_body: String::new(),
+            source_path: PathBuf::from(format!("content/docs/{slug}.md")),
+            slug: slug.into(),
+            collection: "docs".into(),
+            url: format!("/docs/{slug}"),
+            lang: "en".into(),
+            excerpt: String::new(),
+            toc: Vec::new(),
+            word_count: 0,
+            reading_time: 0,
+            excerpt_html: String::new(),
+        }
+    }
+
+    fn root() -> NavLink {
+        NavLink {
+            title: "Docs".into(),
+            url: "/docs/".into(),
+        }
+    }
+
+    fn sections(entries: &[(&str, &str)]) -> Sections {
+        Sections(
+            entries
+                .iter()
+                .map(|(dir, yaml)| {
+                    let meta = serde_yaml_ng::from_str(yaml).unwrap();
+                    (dir.to_string(), HashMap::from([(String::new(), meta)]))
+                })
+                .collect(),
+        )
+    }
+
+    fn nav(items: &[ContentItem], sections: &Sections, entries: Option<&str>) -> Nav {
+        let refs: Vec<&ContentItem> = items.iter().collect();
+        let entries: Option<Vec<Entry>> =
+            entries.map(|yaml| serde_yaml_ng::from_str(yaml).unwrap());
+        build(&refs, items, sections, entries.as_deref(), "en", root()).unwrap()
+    }
+
+    fn titles(items: &[NavItem]) -> Vec<&str> {
+        items.iter().map(|i| i.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_nav_item_serialization() {
+        let item = NavItem {
+            title: "Getting Started".into(),
+            url: "/docs/getting-started".into(),
+            active: true,
+            description: None,
+            collapsed: false,
+            open: true,
+            items: Vec::new(),
+        };
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(json["title"], "Getting Started");
+        assert_eq!(json["url"], "/docs/getting-started");
+        assert_eq!(json["active"], true);
+        assert!(json["items"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_nav_section_serialization() {
+        let page = |title: &str, active: bool| NavItem {
+            title: title.into(),
+            url: format!("/docs/guides/{}", title.to_lowercase()),
+            active,
+            description: None,
+            collapsed: false,
+            open: true,
+            items: Vec::new(),
+        };
+        let section = NavSection {
+            name: "guides".into(),
+            label: "Guides".into(),
+            url: String::new(),
+            active: false,
+            description: Some("How-tos".into()),
+            collapsed: true,
+            open: false,
+            items: vec![page("Setup", false), page("Config", true)],
+        };
+        let json = serde_json::to_value(&section).unwrap();
+        assert_eq!(json["name"], "guides");
+        assert_eq!(json["label"], "Guides");
+        assert_eq!(json["collapsed"], true);
+        assert_eq!(json["items"].as_array().unwrap().len(), 2);
+        assert_eq!(json["items"][1]["active"], true);
+    }
+
+    #[test]
+    fn test_nested_sections_follow_directories() {
+        let items = vec![
+            item("intro", "Intro", Some(1)),
+            item("guides/setup", "Setup", None),
+            item("guides/deploy/netlify", "Netlify", None),
+            item("guides/deploy/aws", "AWS", None),
+        ];
+        let nav = nav(&items, &Sections::default(), None);
+
+        assert_eq!(nav.sections.len(), 2);
+        assert_eq!(nav.sections[0].name, "");
+        assert_eq!(titles(&nav.sections[0].items), ["Intro"]);
+        let guides = &nav.sections[1];
+        assert_eq!(
+            (guides.name.as_str(), guides.label.as_str()),
+            ("guides", "Guides")
+        );
+        assert_eq!(titles(&guides.items), ["Deploy", "Setup"]);
+        assert_eq!(titles(&guides.items[0].items), ["AWS", "Netlify"]);
+        assert_eq!(guides.items[0].url, "");
+    }
+
+    #[test]
+    fn test_section_index_metadata_orders_and_renames() {
+        let items = vec![
+            item("intro", "Intro", Some(1)),
+            item("api/client", "Client", None),
+            item("guides/setup", "Setup", Some(5)),
+        ];
+        let sections = sections(&[
+            ("api", "title: API Reference\nweight: 10\ncollapsed: true"),
+            ("guides", "description: Walkthroughs"),
+        ]);
+        let nav = nav(&items, &sections, None);
+
+        // Guides sorts by its page's weight, the API section by its own
+        let labels: Vec<&str> = nav.sections.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, ["", "Guides", "API Reference"]);
+        assert_eq!(nav.sections[1].description.as_deref(), Some("Walkthroughs"));
+        assert!(nav.sections[2].collapsed);
+        assert!(!nav.sections[2].open);
+    }
+
+    #[test]
+    fn test_page_named_like_directory_links_section() {
+        let items = vec![
+            item("guides", "Guides Overview", None),
+            item("guides/setup", "Setup", None),
+        ];
+        let nav = nav(&items, &Sections::default(), None);
+
+        assert_eq!(nav.sections.len(), 1);
+        assert_eq!(nav.sections[0].label, "Guides Overview");
+        assert_eq!(nav.sections[0].url, "/docs/guides");
+        assert_eq!(titles(&nav.sections[0].items), ["Setup"]);
+        assert_eq!(
+            nav.placements["guides/setup"].prev.as_ref().unwrap().url,
+            "/docs/guides"
+        );
+    }
+
+    #[test]
+    fn test_breadcrumbs_and_prev_next_follow_nav_order() {
+        let items = vec![
+            item("intro", "Intro", Some(1)),
+            item("guides/setup", "Setup", Some(2)),
+            item("guides/deploy/aws", "AWS", Some(3)),
+        ];
+        let nav = nav(&items, &Sections::default(), None);
+
+        let aws = &nav.placements["guides/deploy/aws"];
+        let crumbs: Vec<&str> = aws.breadcrumbs.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(crumbs, ["Docs", "Guides", "Deploy", "AWS"]);
+        assert_eq!(aws.prev.as_ref().unwrap().title, "Setup");
+        assert!(aws.next.is_none());
+        assert_eq!(
+            nav.placements["intro"].next.as_ref().unwrap().title,
+            "Setup"
+        );
+        assert!(nav.placements["intro"].prev.is_none());
+        let intro_crumbs = &nav.placements["intro"].breadcrumbs;
+        assert_eq!(intro_crumbs.len(), 2);
+    }
+
+    #[test]
+    fn test_mark_active_opens_sections() {
+        let items = vec![
+            item("intro", "Intro", Some(1)),
+            item("guides/deploy/aws", "AWS", None),
+        ];
+        let sections = sections(&[
+            ("guides", "collapsed: true"),
+            ("guides/deploy", "collapsed: true"),
+        ]);
+        let nav = nav(&items, &sections, None);
+        let mut value = serde_json::to_value(&nav.sections).unwrap();
+        mark_active(&mut value, &nav.placements["guides/deploy/aws"]);
+
+        let guides = &value[1];
+        assert_eq!(guides["open"], true);
+        assert_eq!(guides["items"][0]["open"], true);
+        assert_eq!(guides["items"][0]["items"][0]["active"], true);
+        assert_eq!(value[0]["items"][0]["active"], false);
+    }
+
+    #[test]
+    fn test_override_sets_order_and_groups() {
+        let items = vec![
+            item("intro", "Intro", Some(1)),
+            item("faq", "FAQ", Some(2)),
+            item("guides/setup", "Setup", None),
+            item("guides/deploy", "Deploy", None),
+            item("reference/cli", "CLI", None),
+            item("reference/config", "Config", None),
+        ];
+        let nav = nav(
+            &items,
+            &Sections::default(),
+            Some(
+                "- faq\n\
+                 - title: Start Here\n  items:\n    - guides/deploy\n    - intro\n\
+                 - reference\n\
+                 - page: guides/setup\n  title: Setting Up\n",
+            ),
+        );
+
+        let labels: Vec<&str> = nav.sections.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, ["", "Start Here", "Reference", ""]);
+        assert_eq!(nav.sections[1].name, "start-here");
+        assert_eq!(titles(&nav.sections[1].items), ["Deploy", "Intro"]);
+        assert_eq!(titles(&nav.sections[2].items), ["CLI", "Config"]);
+        assert_eq!(titles(&nav.sections[3].items), ["Setting Up"]);
+
+        let order: Vec<&str> = ["faq", "guides/deploy", "intro", "reference/cli"]
+            .iter()
+            .map(|slug| nav.placements[*slug].next.as_ref().unwrap().title.as_str())
+            .collect();
+        assert_eq!(order, ["Deploy", "Intro", "CLI", "Config"]);
+    }
+
+    #[test]
+    fn test_override_rejects_unknown_and_repeated_pages() {
+        let items = vec![item("intro", "Intro", None)];
+        let refs: Vec<&ContentItem> = items.iter().collect();
+        let build_with = |yaml: &str| {
+            let entries: Vec<Entry> = serde_yaml_ng::from_str(yaml).unwrap();
+            build(
+                &refs,
+                &items,
+                &Sections::default(),
+                Some(&entries),
+                "en",
+                root(),
+            )
+        };
+        assert!(build_with("- missing\n").unwrap_err().contains("'missing'"));
+        assert!(build_with("- intro\n- page: intro\n")
+            .unwrap_err()
+            .contains("more than once"));
+        assert!(build_with("- description: lonely\n")
+            .unwrap_err()
+            .contains("needs a page"));
+    }
+
+    #[test]
+    fn test_load_sections_by_language() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path();
+        fs::create_dir_all(dir.join("guides/deploy")).unwrap();
+        fs::write(
+            dir.join("guides/_index.md"),
+            "---\ntitle: Guides\nweight: 2\n---\n",
+        )
+        .unwrap();
+        fs::write(dir.join("guides/_index.es.md"), "---\ntitle: Guías\n---\n").unwrap();
+        fs::write(dir.join("guides/deploy/_index.md"), "").unwrap();
+
+        let sections = Sections::load(dir, &HashSet::from(["en", "es"])).unwrap();
+        assert_eq!(
+            sections.get("guides", "en").unwrap().title.as_deref(),
+            Some("Guides")
+        );
+        assert_eq!(
+            sections.get("guides", "es").unwrap().title.as_deref(),
+            Some("Guías")
+        );
+        assert!(sections.get("guides/deploy", "en").unwrap().title.is_none());
+        assert!(sections.get("reference", "en").is_none());
+
+        fs::write(dir.join("guides/_index.md"), "---\nweight: high\n---\n").unwrap();
+        assert!(Sections::load(dir, &HashSet::new()).is_err());
+    }
+}
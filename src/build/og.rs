@@ -0,0 +1,388 @@
+//! Generated Open Graph cards.
+//!
+//! With `[og]` configured, every page without an `image` gets a 1200×630 PNG
+//! at `/og/<url>.png` showing its title, its collection's label and the site
+//! title over a background colour or an image from `static/`. The card
+//! becomes the page's `image`, so the `og:image` and `twitter:image` tags and
+//! the JSON-LD pick it up like any other image. Text is set in the bundled
+//! DejaVu Sans Bold.
+
+use std::fs;
+use std::path::Path;
+
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+use image::imageops::{self, FilterType};
+use image::{ImageFormat, Rgba, RgbaImage};
+use serde::Serialize;
+
+use super::cache::{self, Fingerprint};
+use crate::config::OgSection;
+use crate::error::{PageError, Result};
+
+pub const WIDTH: u32 = 1200;
+pub const HEIGHT: u32 = 630;
+
+/// Bump when the card layout changes so cached cards are redrawn.
+const LAYOUT_VERSION: u32 = 1;
+
+const MARGIN: f32 = 80.0;
+const LABEL_SIZE: f32 = 30.0;
+const TITLE_SIZE: f32 = 64.0;
+const TITLE_MAX_LINES: usize = 4;
+const SITE_TITLE_SIZE: f32 = 34.0;
+const LOGO_HEIGHT: u32 = 56;
+const LOGO_GAP: f32 = 24.0;
+/// Opacity of the label and site title relative to the title.
+const SECONDARY_ALPHA: u8 = 200;
+
+static FONT: &[u8] = include_bytes!("fonts/DejaVuSans-Bold.ttf");
+
+/// URL of the card for the page at `page_url`: `/posts/hello` → `/og/posts/hello.png`.
+pub fn card_url(page_url: &str) -> String {
+    let path = page_url.trim_matches('/');
+    let path = if path.is_empty() { "index" } else { path };
+    format!("/og/{path}.png")
+}
+
+/// Text drawn on one card.
+#[derive(Debug, Clone, Serialize)]
+pub struct Card {
+    pub title: String,
+    pub label: String,
+    pub site_title: String,
+}
+
+/// Draws cards with one `[og]` configuration.
+pub struct Renderer {
+    font: FontRef<'static>,
+    /// Background colour with the background image drawn over it.
+    canvas: RgbaImage,
+    logo: Option<RgbaImage>,
+    text_color: Rgba<u8>,
+    /// Fingerprint of everything but a card's text: two cards with the same
+    /// text and fingerprint are identical.
+    pub fingerprint: String,
+}
+
+impl Renderer {
+    pub fn new(config: &OgSection, static_dir: &Path) -> Result<Self> {
+        let font = FontRef::try_from_slice(FONT)
+            .map_err(|e| PageError::Build(format!("failed to load the card font: {e}")))?;
+        let mut fingerprint = Fingerprint::new();
+        fingerprint
+            .str(&LAYOUT_VERSION.to_string())
+            .str(&cache::hash_json(config));
+
+        let mut canvas =
+            RgbaImage::from_pixel(WIDTH, HEIGHT, parse_color(&config.background_color));
+        if let Some(background) = &config.background {
+            let (img, hash) = load_static(static_dir, background, "background")?;
+            fingerprint.str(&hash);
+            let cover = img.resize_to_fill(WIDTH, HEIGHT, FilterType::Lanczos3);
+            imageops::overlay(&mut canvas, &cover.to_rgba8(), 0, 0);
+        }
+        let logo = match &config.logo {
+            Some(logo) => {
+                let (img, hash) = load_static(static_dir, logo, "logo")?;
+                fingerprint.str(&hash);
+                Some(
+                    img.resize(WIDTH / 3, LOGO_HEIGHT, FilterType::Lanczos3)
+                        .to_rgba8(),
+                )
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            font,
+            canvas,
+            logo,
+            text_color: parse_color(&config.text_color),
+            fingerprint: fingerprint.finish(),
+        })
+    }
+
+    /// Draw a card.
+    pub fn render(&self, card: &Card) -> RgbaImage {
+        let mut img = self.canvas.clone();
+        let text_width = WIDTH as f32 - 2.0 * MARGIN;
+        let secondary = Rgba([
+            self.text_color[0],
+            self.text_color[1],
+            self.text_color[2],
+            SECONDARY_ALPHA,
+        ]);
+
+        // Top row: logo and collection label, centred on each other
+        let label_height = self.line_height(LABEL_SIZE);
+        let row_height = self
+            .logo
+            .as_ref()
+            .map_or(label_height, |logo| label_height.max(logo.height() as f32));
+        let mut label_x = MARGIN;
+        if let Some(logo) = &self.logo {
+            let y = MARGIN + (row_height - logo.height() as f32) / 2.0;
+            imageops::overlay(&mut img, logo, MARGIN as i64, y as i64);
+            label_x += logo.width() as f32 + LOGO_GAP;
+        }
+        let label_y = MARGIN + (row_height - label_height) / 2.0;
+        self.draw_line(
+            &mut img,
+            &card.label,
+            label_x,
+            label_y,
+            LABEL_SIZE,
+            secondary,
+        );
+
+        // Title, wrapped below the top row
+        let title_height = self.line_height(TITLE_SIZE);
+        let mut y = MARGIN + row_height + 40.0;
+        for line in self.wrap(&card.title, TITLE_SIZE, text_width, TITLE_MAX_LINES) {
+            self.draw_line(&mut img, &line, MARGIN, y, TITLE_SIZE, self.text_color);
+            y += title_height;
+        }
+
+        // Site title along the bottom edge
+        let site_y = HEIGHT as f32 - MARGIN - self.line_height(SITE_TITLE_SIZE);
+        self.draw_line(
+            &mut img,
+            &card.site_title,
+            MARGIN,
+            site_y,
+            SITE_TITLE_SIZE,
+            secondary,
+        );
+
+        img
+    }
+
+    /// Draw a card and write it as a PNG to `dest`.
+    pub fn write(&self, card: &Card, dest: &Path) -> Result<()> {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.render(card)
+            .save_with_format(dest, ImageFormat::Png)
+            .map_err(|e| {
+                PageError::Build(format!("failed to write card '{}': {e}", dest.display()))
+            })
+    }
+
+    fn line_height(&self, size: f32) -> f32 {
+        self.font.as_scaled(PxScale::from(size)).height()
+    }
+
+    fn text_width(&self, text: &str, size: f32) -> f32 {
+        let font = self.font.as_scaled(PxScale::from(size));
+        let mut width = 0.0;
+        let mut prev = None;
+        for c in text.chars() {
+            let id = font.glyph_id(c);
+            if let Some(prev) = prev {
+                width += font.kern(prev, id);
+            }
+            width += font.h_advance(id);
+            prev = Some(id);
+        }
+        width
+    }
+
+    /// Break `text` into lines no wider than `max_width`, ending the last
+    /// line with an ellipsis when it doesn't fit in `max_lines`. A single
+    /// word wider than a line gets a line of its own.
+    fn wrap(&self, text: &str, size: f32, max_width: f32, max_lines: usize) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        let mut current = String::new();
+        for word in text.split_whitespace() {
+            let candidate = if current.is_empty() {
+                word.to_string()
+            } else {
+                format!("{current} {word}")
+            };
+            if current.is_empty() || self.text_width(&candidate, size) <= max_width {
+                current = candidate;
+            } else {
+                lines.push(std::mem::replace(&mut current, word.to_string()));
+            }
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+
+        if lines.len() > max_lines {
+            lines.truncate(max_lines);
+            if let Some(last) = lines.last_mut() {
+                while !last.is_empty() && self.text_width(&format!("{last}…"), size) > max_width {
+                    match last.rfind(' ') {
+                        Some(space) => last.truncate(space),
+                        None => {
+                            last.pop();
+                        }
+                    }
+                }
+                let kept = last.trim_end_matches([' ', ',', '.', ';', ':']).len();
+                last.truncate(kept);
+                last.push('…');
+            }
+        }
+        lines
+    }
+
+    /// Draw one line of text whose line box starts at `top`.
+    fn draw_line(
+        &self,
+        img: &mut RgbaImage,
+        text: &str,
+        x: f32,
+        top: f32,
+        size: f32,
+        color: Rgba<u8>,
+    ) {
+        let scale = PxScale::from(size);
+        let font = self.font.as_scaled(scale);
+        let baseline = top + font.ascent();
+        let mut caret = x;
+        let mut prev = None;
+        for c in text.chars() {
+            let id = font.glyph_id(c);
+            if let Some(prev) = prev {
+                caret += font.kern(prev, id);
+            }
+            let glyph = id.with_scale_and_position(scale, point(caret, baseline));
+            caret += font.h_advance(id);
+            prev = Some(id);
+
+            let Some(outlined) = self.font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outlined.px_bounds();
+            outlined.draw(|gx, gy, coverage| {
+                let px = bounds.min.x as i64 + i64::from(gx);
+                let py = bounds.min.y as i64 + i64::from(gy);
+                if px < 0 || py < 0 || px >= i64::from(WIDTH) || py >= i64::from(HEIGHT) {
+                    return;
+                }
+                blend(img.get_pixel_mut(px as u32, py as u32), color, coverage);
+            });
+        }
+    }
+}
+
+/// Blend `color` over an opaque pixel at `coverage` (0–1).
+fn blend(pixel: &mut Rgba<u8>, color: Rgba<u8>, coverage: f32) {
+    let alpha = coverage.clamp(0.0, 1.0) * f32::from(color[3]) / 255.0;
+    for i in 0..3 {
+        let mixed = f32::from(pixel[i]) * (1.0 - alpha) + f32::from(color[i]) * alpha;
+        pixel[i] = mixed.round() as u8;
+    }
+    pixel[3] = 255;
+}
+
+/// Parse a `#rrggbb` colour. The config is validated on load, so anything
+/// else falls back to black.
+fn parse_color(hex: &str) -> Rgba<u8> {
+    let channel = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+            .unwrap_or(0)
+    };
+    Rgba([channel(1), channel(3), channel(5), 255])
+}
+
+/// Load an image named by a `/static/...` URL (or a path inside `static/`)
+/// along with its content hash.
+fn load_static(
+    static_dir: &Path,
+    reference: &str,
+    what: &str,
+) -> Result<(image::DynamicImage, String)> {
+    let rel = reference.trim_start_matches('/');
+    let rel = rel.strip_prefix("static/").unwrap_or(rel);
+    let path = static_dir.join(rel);
+    let bytes = fs::read(&path)
+        .map_err(|_| PageError::Build(format!("[og] {what} '{reference}' not found in static/")))?;
+    let img = image::load_from_memory(&bytes)
+        .map_err(|e| PageError::Build(format!("[og] {what} '{reference}': {e}")))?;
+    Ok((img, cache::hash_bytes(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> OgSection {
+        toml::from_str("background_color = \"#102030\"").unwrap()
+    }
+
+    #[test]
+    fn test_card_url() {
+        assert_eq!(card_url("/posts/hello"), "/og/posts/hello.png");
+        assert_eq!(card_url("/es/docs/setup/"), "/og/es/docs/setup.png");
+        assert_eq!(card_url("/"), "/og/index.png");
+    }
+
+    #[test]
+    fn test_wrap_breaks_lines_and_truncates() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let renderer = Renderer::new(&config(), tmp.path()).unwrap();
+        let width = renderer.text_width("aaaa bbbb", 40.0) + 1.0;
+        assert_eq!(
+            renderer.wrap("aaaa bbbb cccc", 40.0, width, 3),
+            vec!["aaaa bbbb", "cccc"]
+        );
+        let lines = renderer.wrap("aaaa bbbb cccc dddd eeee ffff", 40.0, width, 2);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "aaaa bbbb");
+        assert!(lines[1].ends_with('…'));
+        assert!(renderer.text_width(&lines[1], 40.0) <= width);
+    }
+
+    #[test]
+    fn test_render_draws_text_over_background() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let renderer = Renderer::new(&config(), tmp.path()).unwrap();
+        let card = Card {
+            title: "Hello, World".into(),
+            label: "Posts".into(),
+            site_title: "My Site".into(),
+        };
+        let img = renderer.render(&card);
+        assert_eq!(img.dimensions(), (WIDTH, HEIGHT));
+        assert_eq!(*img.get_pixel(0, 0), Rgba([0x10, 0x20, 0x30, 255]));
+        // White title text lands somewhere in the title area
+        let title_area = (MARGIN as u32..WIDTH / 2).flat_map(|x| (200..260).map(move |y| (x, y)));
+        assert!(title_area
+            .into_iter()
+            .any(|(x, y)| *img.get_pixel(x, y) == Rgba([255, 255, 255, 255])));
+    }
+
+    #[test]
+    fn test_background_and_logo_from_static() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let bg = RgbaImage::from_pixel(600, 315, Rgba([200, 0, 0, 255]));
+        bg.save(tmp.path().join("bg.png")).unwrap();
+        let logo = RgbaImage::from_pixel(112, 112, Rgba([0, 0, 200, 255]));
+        logo.save(tmp.path().join("logo.png")).unwrap();
+        let config: OgSection =
+            toml::from_str("background = \"/static/bg.png\"\nlogo = \"logo.png\"").unwrap();
+
+        let renderer = Renderer::new(&config, tmp.path()).unwrap();
+        let img = renderer.render(&Card {
+            title: String::new(),
+            label: String::new(),
+            site_title: String::new(),
+        });
+        assert_eq!(
+            *img.get_pixel(WIDTH - 1, HEIGHT - 1),
+            Rgba([200, 0, 0, 255])
+        );
+        assert_eq!(*img.get_pixel(100, 100), Rgba([0, 0, 200, 255]));
+
+        let missing: OgSection = toml::from_str("logo = \"/static/nope.png\"").unwrap();
+        let err = Renderer::new(&missing, tmp.path()).err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("logo '/static/nope.png' not found"));
+    }
+}
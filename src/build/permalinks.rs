@@ -0,0 +1,249 @@
+//! Permalink patterns: a collection's `permalink` decides the URL of each of
+//! its items instead of `{url_prefix}/{slug}`.
+//!
+//! ```toml
+//! [[collections]]
+//! name = "posts"
+//! permalink = "/:year/:month/:slug"
+//! ```
+//!
+//! Tokens are `:year`, `:month` and `:day` of the item's date (in
+//! `site.timezone`), `:slug` (the last part of the slug), `:section` (the
+//! directories of a nested item, empty at the top level), `:lang` and the name
+//! of any frontmatter field, standard or `extra`. Field values are slugified;
+//! a list stands for its first entry. Empty parts collapse, so
+//! `/:section/:slug` gives `/guides/setup` and `/setup`. A trailing slash is
+//! only allowed with `build.url_style = "directory"`, which gives every page
+//! URL one; the expansion itself leaves it to `UrlStyle::page_url`.
+
+use chrono_tz::Tz;
+
+use crate::content::{self, Frontmatter};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(String),
+    Token(String),
+}
+
+/// A parsed permalink pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Permalink {
+    parts: Vec<Part>,
+}
+
+/// What a pattern is expanded for.
+pub struct Page<'a> {
+    pub slug: &'a str,
+    pub lang: &'a str,
+    pub frontmatter: &'a Frontmatter,
+    pub tz: Tz,
+}
+
+impl Permalink {
+    /// Parse a pattern: a path starting with `/`, with `:name` tokens.
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        if !pattern.starts_with('/') {
+            return Err(format!("permalink '{pattern}' must start with '/'"));
+        }
+        if pattern.contains(['?', '#']) {
+            return Err(format!(
+                "permalink '{pattern}' must not contain a query or fragment"
+            ));
+        }
+        let mut parts = Vec::new();
+        let mut rest = pattern;
+        while let Some(start) = rest.find(':') {
+            if start > 0 {
+                parts.push(Part::Literal(rest[..start].to_string()));
+            }
+            let after = &rest[start + 1..];
+            let len = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            if len == 0 {
+                return Err(format!(
+                    "permalink '{pattern}': ':' must be followed by a token name"
+                ));
+            }
+            parts.push(Part::Token(after[..len].to_string()));
+            rest = &after[len..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest.to_string()));
+        }
+        Ok(Self { parts })
+    }
+
+    /// Whether the pattern places the language itself; otherwise items in
+    /// other languages get the usual `/{lang}` prefix.
+    pub fn has_lang(&self) -> bool {
+        self.parts.contains(&Part::Token("lang".into()))
+    }
+
+    /// The URL of `page`, or why the pattern cannot be filled in for it.
+    pub fn expand(&self, page: &Page<'_>) -> Result<String, String> {
+        let (section, slug) = page.slug.rsplit_once('/').unwrap_or(("", page.slug));
+        let mut fields: Option<serde_json::Value> = None;
+        let mut url = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => url.push_str(text),
+                Part::Token(name) => {
+                    let value = match name.as_str() {
+                        "year" | "month" | "day" => {
+                            let date = page
+                                .frontmatter
+                                .date
+                                .ok_or_else(|| format!("permalink token :{name} needs a date"))?
+                                .date_in(page.tz);
+                            match name.as_str() {
+                                "year" => date.format("%Y"),
+                                "month" => date.format("%m"),
+                                _ => date.format("%d"),
+                            }
+                            .to_string()
+                        }
+                        "slug" => slug.to_string(),
+                        "section" => section.to_string(),
+                        "lang" => page.lang.to_string(),
+                        _ => {
+                            let fields = fields.get_or_insert_with(|| {
+                                serde_json::to_value(page.frontmatter).unwrap_or_default()
+                            });
+                            field_value(fields, name)?
+                        }
+                    };
+                    url.push_str(&value);
+                }
+            }
+        }
+
+        let segments: Vec<&str> = url.split('/').filter(|s| !s.is_empty()).collect();
+        if segments.is_empty() {
+            return Err("permalink expands to an empty URL".into());
+        }
+        Ok(format!("/{}", segments.join("/")))
+    }
+}
+
+/// The slugified value of frontmatter field `name`, standard or `extra`.
+fn field_value(fields: &serde_json::Value, name: &str) -> Result<String, String> {
+    let value = fields
+        .get(name)
+        .filter(|v| !v.is_null())
+        .or_else(|| fields.get("extra").and_then(|extra| extra.get(name)))
+        .ok_or_else(|| format!("permalink token :{name} has no `{name}` field to fill it"))?;
+    let value = match value {
+        serde_json::Value::Array(list) => list
+            .first()
+            .ok_or_else(|| format!("permalink token :{name}: `{name}` is empty"))?,
+        other => other,
+    };
+    match value {
+        serde_json::Value::String(s) => Ok(content::slug_from_title(s)),
+        serde_json::Value::Number(n) => Ok(n.to_string()),
+        serde_json::Value::Bool(b) => Ok(b.to_string()),
+        _ => Err(format!(
+            "permalink token :{name}: `{name}` is not a single value"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frontmatter(yaml: &str) -> Frontmatter {
+        serde_yaml_ng::from_str(yaml).unwrap()
+    }
+
+    fn expand(pattern: &str, slug: &str, fm: &Frontmatter) -> Result<String, String> {
+        Permalink::parse(pattern).unwrap().expand(&Page {
+            slug,
+            lang: "en",
+            frontmatter: fm,
+            tz: chrono_tz::UTC,
+        })
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_patterns() {
+        assert!(Permalink::parse("/:year/:slug").is_ok());
+        assert!(Permalink::parse(":slug")
+            .unwrap_err()
+            .contains("start with '/'"));
+        assert!(Permalink::parse("/posts/:/x")
+            .unwrap_err()
+            .contains("token name"));
+        assert!(Permalink::parse("/:slug?x=1").is_err());
+        assert!(Permalink::parse("/:lang/:slug").unwrap().has_lang());
+        assert!(!Permalink::parse("/:language/:slug").unwrap().has_lang());
+    }
+
+    #[test]
+    fn test_expand_date_parts() {
+        let fm = frontmatter("title: Hello\ndate: 2025-03-07");
+        assert_eq!(
+            expand("/:year/:month/:day/:slug/", "hello", &fm).unwrap(),
+            "/2025/03/07/hello"
+        );
+        assert_eq!(
+            expand("/blog/:year-:month/:slug", "hello", &fm).unwrap(),
+            "/blog/2025-03/hello"
+        );
+        let undated = frontmatter("title: Hello");
+        assert!(expand("/:year/:slug", "hello", &undated)
+            .unwrap_err()
+            .contains("needs a date"));
+    }
+
+    #[test]
+    fn test_expand_date_uses_site_timezone() {
+        let fm = frontmatter("title: Late\ndate: 2025-12-31T23:30:00Z");
+        let url = Permalink::parse("/:year/:slug")
+            .unwrap()
+            .expand(&Page {
+                slug: "late",
+                lang: "en",
+                frontmatter: &fm,
+                tz: chrono_tz::Europe::Berlin,
+            })
+            .unwrap();
+        assert_eq!(url, "/2026/late");
+    }
+
+    #[test]
+    fn test_expand_section_and_slug() {
+        let fm = frontmatter("title: Setup");
+        assert_eq!(
+            expand("/:section/:slug", "guides/install/setup", &fm).unwrap(),
+            "/guides/install/setup"
+        );
+        assert_eq!(expand("/:section/:slug", "setup", &fm).unwrap(), "/setup");
+        assert_eq!(
+            expand("/docs/:lang/:slug", "setup", &fm).unwrap(),
+            "/docs/en/setup"
+        );
+    }
+
+    #[test]
+    fn test_expand_frontmatter_fields() {
+        let fm =
+            frontmatter("title: Hello World\ncategories: [Rust Tips, cli]\nextra:\n  version: 2\n");
+        assert_eq!(
+            expand("/:categories/:title", "hello", &fm).unwrap(),
+            "/rust-tips/hello-world"
+        );
+        assert_eq!(
+            expand("/v:version/:slug", "hello", &fm).unwrap(),
+            "/v2/hello"
+        );
+        assert!(expand("/:series/:slug", "hello", &fm)
+            .unwrap_err()
+            .contains("no `series` field"));
+        assert!(expand("/:section", "hello", &fm)
+            .unwrap_err()
+            .contains("empty URL"));
+    }
+}
@@ -0,0 +1,393 @@
+//! Redirects from old URLs: `aliases` in page frontmatter and site-level
+//! rules in `data/redirects.yaml`.
+//!
+//! Every redirect gets a meta-refresh HTML stub at its old path, which works
+//! on any host. Cloudflare Pages and Netlify also get a `_redirects` file so
+//! they answer with a real 301.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::config::DeployTarget;
+use crate::content::ContentItem;
+use crate::error::{PageError, Result};
+
+/// A permanent redirect from an old path.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Redirect {
+    /// Root-relative old path, e.g. `/old/path`.
+    pub from: String,
+    /// Root-relative path or absolute URL.
+    pub to: String,
+}
+
+/// Collect redirects from item aliases and the `redirects` data file
+/// (`data["redirects"]`, a list of `{from, to}` rules). Each old path may only
+/// be declared once.
+pub fn collect(
+    items: &[&ContentItem],
+    rules: Option<&serde_json::Value>,
+    data_dir: &Path,
+) -> Result<Vec<Redirect>> {
+    let mut redirects = Vec::new();
+    let mut declared_by: HashMap<String, String> = HashMap::new();
+    let mut add = |redirect: Redirect, source: String| -> Result<()> {
+        if let Some(existing) = declared_by.insert(redirect.from.clone(), source.clone()) {
+            return Err(PageError::Build(format!(
+                "redirect from '{}' is declared by both {existing} and {source}",
+                redirect.from
+            )));
+        }
+        redirects.push(redirect);
+        Ok(())
+    };
+
+    for item in items {
+        for alias in &item.frontmatter.aliases {
+            validate_from(alias).map_err(|message| PageError::Content {
+                path: item.source_path.clone(),
+                message: format!("alias {message}"),
+            })?;
+            add(
+                Redirect {
+                    from: alias.clone(),
+                    to: item.url.clone(),
+                },
+                format!("'{}'", item.source_path.display()),
+            )?;
+        }
+    }
+
+    if let Some(rules) = rules {
+        let path = crate::data::file_for_key(data_dir, None, "redirects");
+        let rules: Vec<Redirect> =
+            serde_json::from_value(rules.clone()).map_err(|e| PageError::Data {
+                path: path.clone(),
+                message: format!("expected a list of {{from, to}} redirects: {e}"),
+            })?;
+        for rule in rules {
+            validate_from(&rule.from)
+                .and_then(|()| validate_to(&rule.to))
+                .map_err(|message| PageError::Data {
+                    path: path.clone(),
+                    message: format!("redirect {message}"),
+                })?;
+            add(rule, format!("'{}'", path.display()))?;
+        }
+    }
+
+    Ok(redirects)
+}
+
+/// Write an HTML stub for every redirect and return the stubs' paths relative
+/// to `output_dir`. Fails if an old path is already served by a page or file.
+pub fn write_stubs(
+    output_dir: &Path,
+    redirects: &[Redirect],
+    base_path: &str,
+) -> Result<Vec<String>> {
+    let mut written = Vec::new();
+    for redirect in redirects {
+        let rel = stub_path(&redirect.from);
+        let dest = output_dir.join(&rel);
+        let shadowed = redirect.from.strip_suffix(".html").is_none().then(|| {
+            output_dir
+                .join(redirect.from.trim_matches('/'))
+                .join("index.html")
+        });
+        if dest.exists() || shadowed.is_some_and(|p| p.exists()) {
+            return Err(PageError::Build(format!(
+                "redirect from '{}' conflicts with an existing page or file",
+                redirect.from
+            )));
+        }
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&dest, stub_html(&target_url(&redirect.to, base_path)))?;
+        written.push(rel);
+    }
+    Ok(written)
+}
+
+/// Write a stub at the slashless form of every directory-style page URL,
+/// `/posts/hello` → `/posts/hello/`, for hosts that serve `posts/hello.html`
+/// at `/posts/hello` and for old links to the `.html` file. Paths already
+/// taken by another page or file are left alone. Returns the stubs' paths
+/// relative to `output_dir`.
+pub fn write_slash_stubs(
+    output_dir: &Path,
+    items: &[&ContentItem],
+    base_path: &str,
+) -> Result<Vec<String>> {
+    let mut written = Vec::new();
+    for item in items {
+        let Some(from) = item.url.strip_suffix('/').filter(|f| !f.is_empty()) else {
+            continue;
+        };
+        let rel = stub_path(from);
+        let dest = output_dir.join(&rel);
+        if dest.exists() {
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&dest, stub_html(&target_url(&item.url, base_path)))?;
+        written.push(rel);
+    }
+    Ok(written)
+}
+
+/// Write the host's native redirect file, if the deploy target has one.
+/// Rules from a `_redirects` copied from `public/` are kept and take precedence.
+pub fn write_native(
+    output_dir: &Path,
+    target: &DeployTarget,
+    redirects: &[Redirect],
+    base_path: &str,
+) -> Result<()> {
+    match target {
+        DeployTarget::Cloudflare | DeployTarget::Netlify if !redirects.is_empty() => {
+            let path = output_dir.join("_redirects");
+            let mut content = fs::read_to_string(&path).unwrap_or_default();
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
+            }
+            content.push_str(&redirects_file(redirects, base_path));
+            fs::write(path, content)?;
+            Ok(())
+        }
+        // GitHub Pages has no redirect file; the HTML stubs are all it gets
+        _ => Ok(()),
+    }
+}
+
+/// `_redirects` rules (Cloudflare Pages and Netlify format).
+pub fn redirects_file(redirects: &[Redirect], base_path: &str) -> String {
+    redirects
+        .iter()
+        .map(|r| {
+            format!(
+                "{base_path}{} {} 301\n",
+                r.from,
+                target_url(&r.to, base_path)
+            )
+        })
+        .collect()
+}
+
+/// Generate an HTML redirect page (meta refresh + JS redirect).
+pub fn stub_html(target_url: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta http-equiv="refresh" content="0; url={target_url}">
+<link rel="canonical" href="{target_url}">
+<title>Redirecting…</title>
+<script>window.location.replace("{target_url}");</script>
+</head>
+<body>
+<p>Redirecting to <a href="{target_url}">{target_url}</a>…</p>
+</body>
+</html>"#
+    )
+}
+
+/// The target of a page generated by [`stub_html`], or `None` for any other page.
+pub fn stub_target(html: &str) -> Option<&str> {
+    if !html.starts_with("<!DOCTYPE html>") || !html.contains("<title>Redirecting…</title>") {
+        return None;
+    }
+    let start = html.find("<meta http-equiv=\"refresh\" content=\"0; url=")?;
+    let rest = &html[start + "<meta http-equiv=\"refresh\" content=\"0; url=".len()..];
+    rest.find('"').map(|end| &rest[..end])
+}
+
+/// Output path of the stub for an old path: `/a/b` → `a/b.html`,
+/// `/a/b/` → `a/b/index.html`.
+fn stub_path(from: &str) -> String {
+    let rel = from.trim_start_matches('/');
+    if rel.ends_with(".html") {
+        rel.to_string()
+    } else if rel.ends_with('/') {
+        format!("{rel}index.html")
+    } else {
+        format!("{rel}.html")
+    }
+}
+
+fn target_url(to: &str, base_path: &str) -> String {
+    if to.starts_with('/') {
+        format!("{base_path}{to}")
+    } else {
+        to.to_string()
+    }
+}
+
+fn validate_from(from: &str) -> std::result::Result<(), String> {
+    if !from.starts_with('/') || from == "/" {
+        return Err(format!(
+            "'{from}' must be a path starting with '/' other than the site root"
+        ));
+    }
+    if from.split('/').any(|seg| seg == "..") || from.contains(['?', '#']) {
+        return Err(format!("'{from}' must be a plain path"));
+    }
+    validate_chars(from)
+}
+
+fn validate_to(to: &str) -> std::result::Result<(), String> {
+    if to.is_empty() {
+        return Err("target must not be empty".into());
+    }
+    validate_chars(to)
+}
+
+fn validate_chars(url: &str) -> std::result::Result<(), String> {
+    if url.contains(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>')) {
+        return Err(format!(
+            "'{url}' must not contain whitespace, quotes or angle brackets"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::Frontmatter;
+    use std::path::PathBuf;
+
+    fn item(url: &str, aliases: &[&str]) -> ContentItem {
+        ContentItem {
+            frontmatter: Frontmatter {
+                title: "T".into(),
+                aliases: aliases.iter().map(|a| a.to_string()).collect(),
+                ..Default::default()
+            },
+            raw_body: String::new(),
+            markdown_body: String::new(),
+            html_body: String::new(),
+            source_path: PathBuf::from(format!("content{url}.md")),
+            slug: String::new(),
+            collection: "docs".into(),
+            url: url.into(),
+            lang: "en".into(),
+            excerpt: String::new(),
+            toc: Vec::new(),
+            word_count: 0,
+            reading_time: 0,
+            excerpt_html: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_collect_aliases_and_rules() {
+        let a = item("/docs/setup", &["/guides/setup", "/install/"]);
+        let rules = serde_json::json!([{ "from": "/blog", "to": "https://blog.example.com/" }]);
+        let redirects = collect(&[&a], Some(&rules), Path::new("data")).unwrap();
+        assert_eq!(redirects.len(), 3);
+        assert_eq!(redirects[0].to, "/docs/setup");
+        assert_eq!(redirects[2].from, "/blog");
+    }
+
+    #[test]
+    fn test_collect_rejects_duplicates() {
+        let a = item("/docs/a", &["/old"]);
+        let b = item("/docs/b", &["/old"]);
+        let err = collect(&[&a, &b], None, Path::new("data")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("redirect from '/old' is declared by both"));
+    }
+
+    #[test]
+    fn test_collect_rejects_invalid_paths() {
+        let a = item("/docs/a", &["old"]);
+        assert!(collect(&[&a], None, Path::new("data")).is_err());
+        let rules = serde_json::json!([{ "from": "/a b", "to": "/b" }]);
+        assert!(collect(&[], Some(&rules), Path::new("data")).is_err());
+        let rules = serde_json::json!({ "from": "/a", "to": "/b" });
+        let err = collect(&[], Some(&rules), Path::new("data")).unwrap_err();
+        assert!(err.to_string().contains("expected a list"));
+    }
+
+    #[test]
+    fn test_stub_path() {
+        assert_eq!(stub_path("/a/b"), "a/b.html");
+        assert_eq!(stub_path("/a/b/"), "a/b/index.html");
+        assert_eq!(stub_path("/a/b.html"), "a/b.html");
+    }
+
+    #[test]
+    fn test_write_stubs_and_conflicts() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let redirects = vec![Redirect {
+            from: "/old/page".into(),
+            to: "/new".into(),
+        }];
+        let written = write_stubs(tmp.path(), &redirects, "/base").unwrap();
+        assert_eq!(written, vec!["old/page.html".to_string()]);
+        let html = fs::read_to_string(tmp.path().join("old/page.html")).unwrap();
+        assert_eq!(stub_target(&html), Some("/base/new"));
+
+        // A second stub at the same path conflicts with the first
+        assert!(write_stubs(tmp.path(), &redirects, "").is_err());
+    }
+
+    #[test]
+    fn test_write_slash_stubs() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("docs")).unwrap();
+        fs::write(tmp.path().join("docs/taken.html"), "page").unwrap();
+        let items = [
+            item("/docs/setup/", &[]),
+            item("/docs/taken/", &[]),
+            item("/docs/file-style", &[]),
+            item("/", &[]),
+        ];
+        let refs: Vec<&ContentItem> = items.iter().collect();
+        let written = write_slash_stubs(tmp.path(), &refs, "/base").unwrap();
+        assert_eq!(written, vec!["docs/setup.html".to_string()]);
+        let html = fs::read_to_string(tmp.path().join("docs/setup.html")).unwrap();
+        assert_eq!(stub_target(&html), Some("/base/docs/setup/"));
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("docs/taken.html")).unwrap(),
+            "page"
+        );
+    }
+
+    #[test]
+    fn test_stub_target_ignores_other_pages() {
+        assert_eq!(stub_target("<!DOCTYPE html><html></html>"), None);
+        assert_eq!(
+            stub_target(&stub_html("https://x.dev/")),
+            Some("https://x.dev/")
+        );
+    }
+
+    #[test]
+    fn test_native_file_per_target() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let redirects = vec![Redirect {
+            from: "/old".into(),
+            to: "/new".into(),
+        }];
+        write_native(tmp.path(), &DeployTarget::GithubPages, &redirects, "").unwrap();
+        assert!(!tmp.path().join("_redirects").exists());
+
+        fs::write(tmp.path().join("_redirects"), "/custom /x 302").unwrap();
+        write_native(tmp.path(), &DeployTarget::Netlify, &redirects, "").unwrap();
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("_redirects")).unwrap(),
+            "/custom /x 302\n/old /new 301\n"
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! Related content, computed for every page at build time.
+//!
+//! Pages listed in a page's `related` frontmatter come first, in order. With
+//! a `[related]` section, the rest of the list is ranked by shared tags and
+//! taxonomy terms plus the term-frequency similarity of the markdown bodies.
+//! Candidates are pages of listed collections in the same language. Ranking
+//! compares every page with every other, so it is opt-in.
+
+use std::collections::{HashMap, HashSet};
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+use super::taxonomies;
+use crate::config::SiteConfig;
+use crate::content::Frontmatter;
+
+/// Pages scoring below this are not related.
+const MIN_SCORE: f64 = 0.2;
+
+/// Words too common to say anything about a page's topic.
+const STOPWORDS: &[&str] = &[
+    "about", "after", "all", "also", "and", "any", "are", "because", "been", "before", "but",
+    "can", "could", "does", "each", "for", "from", "has", "have", "how", "into", "its", "just",
+    "may", "more", "most", "not", "now", "only", "other", "our", "out", "over", "should", "some",
+    "such", "than", "that", "the", "their", "them", "then", "there", "these", "they", "this",
+    "use", "using", "was", "way", "were", "what", "when", "which", "while", "who", "will", "with",
+    "would", "you", "your",
+];
+
+/// A page as seen by the ranking.
+pub struct Doc<'a> {
+    pub url: &'a str,
+    pub slug: &'a str,
+    pub collection: &'a str,
+    pub lang: &'a str,
+    pub frontmatter: &'a Frontmatter,
+    pub body: &'a str,
+}
+
+/// A related page, as exposed to templates, `.md` copies and MCP.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RelatedLink {
+    pub title: String,
+    pub url: String,
+    pub collection: String,
+    pub date: Option<String>,
+    pub description: Option<String>,
+}
+
+impl RelatedLink {
+    fn new(doc: &Doc) -> Self {
+        Self {
+            title: doc.frontmatter.title.clone(),
+            url: doc.url.to_string(),
+            collection: doc.collection.to_string(),
+            date: doc.frontmatter.date.map(|d| d.to_string()),
+            description: doc.frontmatter.description.clone(),
+        }
+    }
+}
+
+/// Index of the page an explicit `related` entry names: a URL path, a
+/// `collection/slug` pair or a bare slug (same collection first). Only pages
+/// in `lang` are considered.
+fn find(docs: &[Doc], target: &str, collection: &str, lang: &str) -> Option<usize> {
+    let matching =
+        |pred: &dyn Fn(&Doc) -> bool| docs.iter().position(|d| d.lang == lang && pred(d));
+    if target.starts_with('/') {
+        let target = target.trim_end_matches('/');
+        return matching(&|d| d.url.trim_end_matches('/') == target);
+    }
+    if let Some((coll, slug)) = target.split_once('/') {
+        return matching(&|d| d.collection == coll && d.slug == slug);
+    }
+    matching(&|d| d.collection == collection && d.slug == target)
+        .or_else(|| matching(&|d| d.slug == target))
+}
+
+/// Explicit `related` entries that match no page in any language, as
+/// `(doc index, entry)` pairs.
+pub fn unknown_overrides<'a>(docs: &'a [Doc]) -> Vec<(usize, &'a str)> {
+    let langs: HashSet<&str> = docs.iter().map(|d| d.lang).collect();
+    docs.iter()
+        .enumerate()
+        .flat_map(|(i, doc)| {
+            doc.frontmatter
+                .related
+                .iter()
+                .filter(|target| {
+                    !langs
+                        .iter()
+                        .any(|lang| find(docs, target, doc.collection, lang).is_some())
+                })
+                .map(move |target| (i, target.as_str()))
+        })
+        .collect()
+}
+
+/// Tag and taxonomy terms of a page, by slug.
+fn terms(doc: &Doc, config: &SiteConfig) -> HashSet<String> {
+    let tags = doc
+        .frontmatter
+        .tags
+        .iter()
+        .map(|t| format!("tags:{}", slug::slugify(t)));
+    let taxonomy_terms = config.taxonomies.iter().flat_map(|t| {
+        taxonomies::item_terms(doc.frontmatter, &t.name)
+            .into_iter()
+            .map(move |term| format!("{}:{}", t.name, slug::slugify(term)))
+    });
+    tags.chain(taxonomy_terms).collect()
+}
+
+/// L2-normalized term frequencies of a markdown body.
+fn term_vector(body: &str) -> HashMap<String, f64> {
+    let mut tf: HashMap<String, f64> = HashMap::new();
+    for word in body
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3 && !w.chars().all(|c| c.is_ascii_digit()))
+    {
+        let word = word.to_lowercase();
+        if !STOPWORDS.contains(&word.as_str()) {
+            *tf.entry(word).or_default() += 1.0;
+        }
+    }
+    let norm = tf.values().map(|v| v * v).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        tf.values_mut().for_each(|v| *v /= norm);
+    }
+    tf
+}
+
+fn cosine(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> f64 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    small
+        .iter()
+        .filter_map(|(word, x)| large.get(word).map(|y| x * y))
+        .sum()
+}
+
+/// Pages named in a doc's `related` frontmatter, in order.
+fn explicit(docs: &[Doc], i: usize) -> Vec<usize> {
+    let doc = &docs[i];
+    let mut picked: Vec<usize> = Vec::new();
+    for target in &doc.frontmatter.related {
+        if let Some(j) = find(docs, target, doc.collection, doc.lang) {
+            if j != i && !picked.contains(&j) {
+                picked.push(j);
+            }
+        }
+    }
+    picked
+}
+
+/// Related pages of every doc, in the order of `docs`.
+pub fn compute(docs: &[Doc], config: &SiteConfig) -> Vec<Vec<RelatedLink>> {
+    let links = |picked: Vec<usize>| -> Vec<RelatedLink> {
+        picked
+            .into_iter()
+            .map(|j| RelatedLink::new(&docs[j]))
+            .collect()
+    };
+    let Some(section) = &config.related else {
+        return (0..docs.len()).map(|i| links(explicit(docs, i))).collect();
+    };
+
+    let tz = config.timezone();
+    let listed: HashSet<&str> = config
+        .collections
+        .iter()
+        .filter(|c| c.listed)
+        .map(|c| c.name.as_str())
+        .collect();
+    let terms: Vec<HashSet<String>> = docs.iter().map(|d| terms(d, config)).collect();
+    let vectors: Vec<HashMap<String, f64>> = docs.par_iter().map(|d| term_vector(d.body)).collect();
+
+    (0..docs.len())
+        .into_par_iter()
+        .map(|i| {
+            let doc = &docs[i];
+            let mut picked = explicit(docs, i);
+            let room = section.limit.saturating_sub(picked.len());
+            if room == 0 {
+                return links(picked);
+            }
+
+            let mut ranked: Vec<(f64, usize)> = (0..docs.len())
+                .filter(|&j| {
+                    j != i
+                        && docs[j].lang == doc.lang
+                        && docs[j].slug != "index"
+                        && listed.contains(docs[j].collection)
+                        && !picked.contains(&j)
+                })
+                .map(|j| {
+                    let shared = terms[i].intersection(&terms[j]).count() as f64;
+                    (shared + cosine(&vectors[i], &vectors[j]), j)
+                })
+                .filter(|(score, _)| *score >= MIN_SCORE)
+                .collect();
+            ranked.sort_by(|(sa, a), (sb, b)| {
+                sb.total_cmp(sa)
+                    .then_with(|| {
+                        let date = |doc: &Doc| doc.frontmatter.date_key(tz);
+                        date(&docs[*b]).cmp(&date(&docs[*a]))
+                    })
+                    .then_with(|| docs[*a].url.cmp(docs[*b].url))
+            });
+            picked.extend(ranked.into_iter().take(room).map(|(_, j)| j));
+            links(picked)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SiteConfig {
+        toml::from_str(
+            r#"
+[site]
+title = "T"
+
+[[collections]]
+name = "posts"
+label = "Posts"
+directory = "posts"
+default_template = "post.html"
+listed = true
+
+[[collections]]
+name = "pages"
+label = "Pages"
+directory = "pages"
+default_template = "page.html"
+
+[related]
+
+[[taxonomies]]
+name = "series"
+"#,
+        )
+        .unwrap()
+    }
+
+    fn fm(yaml: &str) -> Frontmatter {
+        serde_yaml_ng::from_str(yaml).unwrap()
+    }
+
+    fn doc<'a>(url: &'a str, lang: &'a str, fm: &'a Frontmatter, body: &'a str) -> Doc<'a> {
+        let (collection, slug) = url.trim_start_matches('/').split_once('/').unwrap();
+        Doc {
+            url,
+            slug,
+            collection,
+            lang,
+            frontmatter: fm,
+            body,
+        }
+    }
+
+    fn urls(links: &[RelatedLink]) -> Vec<&str> {
+        links.iter().map(|l| l.url.as_str()).collect()
+    }
+
+    #[test]
+    fn test_ranks_by_terms_then_similarity() {
+        let config = config();
+        let a = fm("title: A\ntags: [rust]\nseries: Intro");
+        let b = fm("title: B\ntags: [Rust]\nseries: intro");
+        let c = fm("title: C\ntags: [rust]");
+        let d = fm("title: D");
+        let e = fm("title: E");
+        let docs = vec![
+            doc("/posts/a", "en", &a, "borrow checker lifetimes"),
+            doc("/posts/b", "en", &b, "gardening tomatoes"),
+            doc("/posts/c", "en", &c, "baking bread"),
+            doc("/posts/d", "en", &d, "borrow checker lifetimes explained"),
+            doc("/posts/e", "en", &e, "sailing boats"),
+        ];
+        let related = compute(&docs, &config);
+        // Two shared terms, one shared term, then body similarity alone
+        assert_eq!(urls(&related[0]), vec!["/posts/b", "/posts/c", "/posts/d"]);
+        assert!(related[4].is_empty());
+    }
+
+    #[test]
+    fn test_explicit_related_first_and_scope() {
+        let config = config();
+        let a = fm("title: A\ntags: [rust]\nrelated: [pages/about, /posts/c/, missing]");
+        let b = fm("title: B\ntags: [rust]");
+        let c = fm("title: C");
+        let about = fm("title: About\ntags: [rust]");
+        let es = fm("title: B es\ntags: [rust]");
+        let docs = vec![
+            doc("/posts/a", "en", &a, ""),
+            doc("/posts/b", "en", &b, ""),
+            doc("/posts/c", "en", &c, ""),
+            doc("/pages/about", "en", &about, ""),
+            doc("/posts/b-es", "es", &es, ""),
+        ];
+        let related = compute(&docs, &config);
+        // Explicit entries may point outside listed collections; ranked
+        // candidates are same-language pages of listed collections only
+        assert_eq!(
+            urls(&related[0]),
+            vec!["/pages/about", "/posts/c", "/posts/b"]
+        );
+        assert_eq!(related[0][0].title, "About");
+        assert_eq!(unknown_overrides(&docs), vec![(0, "missing")]);
+    }
+
+    #[test]
+    fn test_without_section_only_explicit() {
+        let mut config = config();
+        config.related = None;
+        let a = fm("title: A\ntags: [rust]\nrelated: [c]");
+        let b = fm("title: B\ntags: [rust]");
+        let c = fm("title: C");
+        let docs = vec![
+            doc("/posts/a", "en", &a, "borrow checker"),
+            doc("/posts/b", "en", &b, "borrow checker"),
+            doc("/posts/c", "en", &c, ""),
+        ];
+        let related = compute(&docs, &config);
+        assert_eq!(urls(&related[0]), vec!["/posts/c"]);
+        assert!(related[1].is_empty());
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut config = config();
+        config.related.as_mut().unwrap().limit = 1;
+        let a = fm("title: A\ntags: [rust]");
+        let b = fm("title: B\ntags: [rust]");
+        let c = fm("title: C\ntags: [rust]\nrelated: [a, b]");
+        let docs = vec![
+            doc("/posts/a", "en", &a, ""),
+            doc("/posts/b", "en", &b, ""),
+            doc("/posts/c", "en", &c, ""),
+        ];
+        let related = compute(&docs, &config);
+        assert_eq!(related[0].len(), 1);
+        // Explicit entries are kept beyond the limit
+        assert_eq!(urls(&related[2]), vec!["/posts/a", "/posts/b"]);
+    }
+
+    #[test]
+    fn test_term_vector_skips_stopwords_and_numbers() {
+        let tf = term_vector("The cache and the CACHE in 2025 with it");
+        assert_eq!(tf.len(), 1);
+        assert!((tf["cache"] - 1.0).abs() < 1e-9);
+    }
+}
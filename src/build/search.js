@@ -0,0 +1,177 @@
+/* seite full-text search client.
+ *
+ * Include it from any template:
+ *
+ *   <input type="search" data-seite-search>
+ *   <div data-seite-search-results></div>
+ *   <script src="{{ site.base_path }}{{ lang_prefix }}/search/search.js" data-base-path="{{ site.base_path }}" defer></script>
+ *
+ * Results link to the best matching section of each page. `window.seiteSearch(query)`
+ * returns a promise of the same results for custom UIs.
+ */
+(function () {
+  var script = document.currentScript;
+  var dir = script.src.replace(/[^/]*$/, '');
+  var basePath = script.getAttribute('data-base-path') || '';
+  var manifest = null;
+  var shards = {};
+
+  function fetchJson(url) {
+    return fetch(url).then(function (r) { return r.ok ? r.json() : null; });
+  }
+
+  function loadManifest() {
+    if (!manifest) manifest = fetchJson(dir + 'index.json');
+    return manifest;
+  }
+
+  function loadShard(m, prefix) {
+    var file = m.shards[prefix];
+    if (!file) return Promise.resolve(null);
+    if (!shards[prefix]) shards[prefix] = fetchJson(dir + 'shards/' + file);
+    return shards[prefix];
+  }
+
+  // Mirrors the tokenizer in src/build/search.rs
+  function words(text) {
+    return text.toLowerCase().split(/[^\p{L}\p{N}]+/u).filter(function (w) {
+      var len = Array.from(w).length;
+      return len >= 2 && len <= 40;
+    });
+  }
+
+  function prefix(word, len) {
+    return Array.from(word).slice(0, len).join('');
+  }
+
+  // Stems a query word stands for, with how strongly it matches them. The last
+  // word of the query is still being typed, so it also matches longer words.
+  function stemsFor(m, word, partial) {
+    return loadShard(m, prefix(word, m.prefix_length)).then(function (shard) {
+      var stems = {};
+      if (!shard) return stems;
+      if (shard.words[word]) stems[shard.words[word]] = 1;
+      if (partial) {
+        var found = 0;
+        for (var w in shard.words) {
+          if (found >= 20) break;
+          if (w !== word && w.indexOf(word) === 0 && !(shard.words[w] in stems)) {
+            stems[shard.words[w]] = 0.5;
+            found++;
+          }
+        }
+      }
+      return stems;
+    });
+  }
+
+  function postings(m, stem) {
+    return loadShard(m, prefix(stem, m.prefix_length)).then(function (shard) {
+      return (shard && shard.terms[stem]) || [];
+    });
+  }
+
+  function search(query, limit) {
+    limit = limit || 10;
+    var qwords = words(query);
+    if (!qwords.length) return Promise.resolve([]);
+    return loadManifest().then(function (m) {
+      if (!m) return [];
+      return Promise.all(qwords.map(function (word, i) {
+        return stemsFor(m, word, i === qwords.length - 1).then(function (stems) {
+          return Promise.all(Object.keys(stems).map(function (stem) {
+            return postings(m, stem).then(function (list) {
+              return list.map(function (p) { return [p[0], p[1] * stems[stem]]; });
+            });
+          }));
+        });
+      })).then(function (perWord) {
+        // Sections matching more of the query words rank first
+        var scores = {};
+        var matched = {};
+        perWord.forEach(function (lists, i) {
+          lists.forEach(function (list) {
+            list.forEach(function (p) {
+              scores[p[0]] = (scores[p[0]] || 0) + p[1];
+              matched[p[0]] = matched[p[0]] || {};
+              matched[p[0]][i] = true;
+            });
+          });
+        });
+        var ranked = Object.keys(scores).map(function (s) {
+          return { section: +s, score: scores[s] * Object.keys(matched[s]).length };
+        }).sort(function (a, b) { return b.score - a.score; });
+
+        // Best section per page
+        var seen = {};
+        var results = [];
+        for (var i = 0; i < ranked.length && results.length < limit; i++) {
+          var sec = m.sections[ranked[i].section];
+          if (seen[sec[0]]) continue;
+          seen[sec[0]] = true;
+          var doc = m.docs[sec[0]];
+          results.push({
+            title: doc.title,
+            heading: sec[2] ? sec[1] : null,
+            url: doc.url + (sec[2] ? '#' + sec[2] : ''),
+            collection: doc.collection,
+            description: doc.description || null,
+            date: doc.date || null,
+            score: ranked[i].score
+          });
+        }
+        return results;
+      });
+    });
+  }
+
+  window.seiteSearch = search;
+
+  function render(container, results, noResults) {
+    container.textContent = '';
+    if (!results.length) {
+      var none = document.createElement('div');
+      none.className = 'no-results';
+      none.textContent = noResults;
+      container.appendChild(none);
+      return;
+    }
+    results.forEach(function (r) {
+      var a = document.createElement('a');
+      a.href = basePath + r.url;
+      var strong = document.createElement('strong');
+      strong.textContent = r.title;
+      a.appendChild(strong);
+      var meta = [r.heading, r.collection, r.date].filter(Boolean).join(' · ');
+      if (meta) {
+        var div = document.createElement('div');
+        div.className = 'result-meta';
+        div.textContent = meta;
+        a.appendChild(div);
+      }
+      container.appendChild(a);
+    });
+  }
+
+  function attach() {
+    var container = document.querySelector('[data-seite-search-results]');
+    document.querySelectorAll('[data-seite-search]').forEach(function (input) {
+      var pending = 0;
+      input.addEventListener('input', function () {
+        var q = input.value.trim();
+        var id = ++pending;
+        if (!container) return;
+        if (!q) { container.textContent = ''; return; }
+        search(q, 8).then(function (results) {
+          if (id === pending) render(container, results, input.getAttribute('data-no-results') || 'No results');
+        });
+      });
+    });
+  }
+
+  if (document.readyState === 'loading') {
+    document.addEventListener('DOMContentLoaded', attach);
+  } else {
+    attach();
+  }
+})();
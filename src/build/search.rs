@@ -0,0 +1,491 @@
+//! Full-text search index.
+//!
+//! Each page of a listed collection is split into sections at the headings of
+//! its rendered body: the text before the first heading belongs to the page
+//! title, every other section to the heading (and anchor) from `page.toc` that
+//! opens it. Words are lowercased, optionally stemmed in the index language and
+//! weighted per section with BM25, so the browser only sums precomputed weights.
+//!
+//! The index is written to `search/` in the language's output directory:
+//!
+//! - `search/index.json` — pages, sections and the list of shards
+//! - `search/shards/<prefix>.json` — for the words starting with `prefix`, the
+//!   stem each word reduces to (`words`) and, for the stems starting with
+//!   `prefix`, their `[section, weight]` postings (`terms`)
+//! - `search/search.js` — a small client that queries the index
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::Serialize;
+
+use crate::config::{SearchSection, SiteConfig};
+use crate::content::ContentItem;
+use crate::error::Result;
+
+/// Browser client, written next to the index.
+pub const SEARCH_JS: &str = include_str!("search.js");
+
+/// BM25 term-frequency saturation.
+const K1: f64 = 1.2;
+/// BM25 length normalization.
+const B: f64 = 0.75;
+/// Times a page title counts towards its first section.
+const TITLE_BOOST: usize = 3;
+/// Times a heading counts towards its section.
+const HEADING_BOOST: usize = 2;
+/// Words longer than this are hashes, URLs or noise.
+const MAX_WORD_LEN: usize = 40;
+
+/// A page in the index.
+#[derive(Debug, Serialize)]
+struct IndexDoc<'a> {
+    title: &'a str,
+    url: &'a str,
+    collection: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+}
+
+/// `search/index.json`.
+#[derive(Debug, Serialize)]
+struct Manifest<'a> {
+    version: u32,
+    lang: &'a str,
+    prefix_length: usize,
+    docs: Vec<IndexDoc<'a>>,
+    /// `[doc, heading, anchor]`; the anchor is empty for a page's first section.
+    sections: Vec<(usize, String, String)>,
+    /// Shard prefix → file name in `shards/`.
+    shards: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Shard {
+    words: BTreeMap<String, String>,
+    terms: BTreeMap<String, Vec<(usize, f64)>>,
+}
+
+/// A section of a page, as plain text.
+#[derive(Debug, PartialEq)]
+struct Section {
+    heading: String,
+    anchor: String,
+    text: String,
+}
+
+/// Snowball stemmer for a language code such as `en` or `pt-BR`. `None` for
+/// languages without one.
+fn stemmer_for(lang: &str) -> Option<Stemmer> {
+    let code = lang.split(['-', '_']).next().unwrap_or(lang);
+    let algorithm = match code.to_ascii_lowercase().as_str() {
+        "ar" => Algorithm::Arabic,
+        "da" => Algorithm::Danish,
+        "de" => Algorithm::German,
+        "el" => Algorithm::Greek,
+        "en" => Algorithm::English,
+        "es" => Algorithm::Spanish,
+        "fi" => Algorithm::Finnish,
+        "fr" => Algorithm::French,
+        "hu" => Algorithm::Hungarian,
+        "it" => Algorithm::Italian,
+        "nl" => Algorithm::Dutch,
+        "no" | "nb" | "nn" => Algorithm::Norwegian,
+        "pt" => Algorithm::Portuguese,
+        "ro" => Algorithm::Romanian,
+        "ru" => Algorithm::Russian,
+        "sv" => Algorithm::Swedish,
+        "ta" => Algorithm::Tamil,
+        "tr" => Algorithm::Turkish,
+        _ => return None,
+    };
+    Some(Stemmer::create(algorithm))
+}
+
+/// Lowercased words of at least two characters. Mirrors the tokenizer in
+/// `search.js`.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| {
+            let len = w.chars().count();
+            (2..=MAX_WORD_LEN).contains(&len)
+        })
+        .map(str::to_lowercase)
+}
+
+/// Shard of a word or stem: its first `len` characters.
+fn prefix(word: &str, len: usize) -> String {
+    word.chars().take(len).collect()
+}
+
+/// File name of a shard. Prefixes outside `[a-z0-9]` are hex-encoded so every
+/// file name is safe on every host.
+fn shard_file(prefix: &str) -> String {
+    if prefix
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    {
+        format!("{prefix}.json")
+    } else {
+        let hex: String = prefix.bytes().map(|b| format!("{b:02x}")).collect();
+        format!("x{hex}.json")
+    }
+}
+
+/// Plain text of an HTML fragment: tags dropped, `<script>`/`<style>` bodies
+/// skipped and common entities decoded.
+fn html_to_text(html: &str) -> String {
+    static SKIP: OnceLock<Regex> = OnceLock::new();
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    let skip =
+        SKIP.get_or_init(|| Regex::new(r"(?is)<(script|style)\b.*?</(script|style)>").unwrap());
+    let tag = TAG.get_or_init(|| Regex::new(r"(?s)<[^>]*>").unwrap());
+    let text = skip.replace_all(html, " ");
+    let text = tag.replace_all(&text, " ");
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Split a page's rendered body at its headings. The first section carries
+/// the page title and no anchor; the others take their heading text from the
+/// matching `toc` entry.
+fn sections(item: &ContentItem) -> Vec<Section> {
+    static HEADING: OnceLock<Regex> = OnceLock::new();
+    let heading = HEADING.get_or_init(|| {
+        Regex::new(r#"(?s)<h([1-6])[^>]*\bid="([^"]*)"[^>]*>(.*?)</h[1-6]>"#).unwrap()
+    });
+
+    let html = &item.html_body;
+    let mut out = vec![Section {
+        heading: item.frontmatter.title.clone(),
+        anchor: String::new(),
+        text: String::new(),
+    }];
+    let mut last = 0;
+    for caps in heading.captures_iter(html) {
+        let whole = caps.get(0).unwrap();
+        out.last_mut().unwrap().text = html_to_text(&html[last..whole.start()]);
+        let anchor = caps[2].to_string();
+        let text = item
+            .toc
+            .iter()
+            .find(|e| e.id == anchor)
+            .map(|e| e.text.clone())
+            .unwrap_or_else(|| html_to_text(&caps[3]).trim().to_string());
+        out.push(Section {
+            heading: text,
+            anchor,
+            text: String::new(),
+        });
+        last = whole.end();
+    }
+    out.last_mut().unwrap().text = html_to_text(&html[last..]);
+    out
+}
+
+/// Write the full-text index of `items` (pages in `lang`) into `dir/search/`
+/// and return the files written. Only items from `listed: true` collections
+/// are indexed.
+pub fn write_index(
+    dir: &Path,
+    items: &[&ContentItem],
+    config: &SiteConfig,
+    search: &SearchSection,
+    lang: &str,
+) -> Result<Vec<PathBuf>> {
+    let listed: HashSet<&str> = config
+        .collections
+        .iter()
+        .filter(|c| c.listed)
+        .map(|c| c.name.as_str())
+        .collect();
+    let stemmer = search.stemming.then(|| stemmer_for(lang)).flatten();
+    let stem = |word: &str| match &stemmer {
+        Some(s) => s.stem(word).into_owned(),
+        None => word.to_string(),
+    };
+
+    let mut docs = Vec::new();
+    let mut section_refs = Vec::new();
+    // Per section: stem → term frequency, and the section's length in words
+    let mut section_terms: Vec<HashMap<String, usize>> = Vec::new();
+    let mut section_lens: Vec<usize> = Vec::new();
+    let mut word_stems: BTreeMap<String, String> = BTreeMap::new();
+
+    for item in items
+        .iter()
+        .filter(|item| listed.contains(item.collection.as_str()))
+    {
+        let doc = docs.len();
+        docs.push(IndexDoc {
+            title: &item.frontmatter.title,
+            url: &item.url,
+            collection: &item.collection,
+            description: item.frontmatter.description.as_deref(),
+            date: item.frontmatter.date.map(|d| d.to_string()),
+        });
+        for (i, section) in sections(item).into_iter().enumerate() {
+            let boost = if i == 0 { TITLE_BOOST } else { HEADING_BOOST };
+            let mut tf: HashMap<String, usize> = HashMap::new();
+            let mut len = 0;
+            let weighted = words(&section.heading)
+                .map(|w| (w, boost))
+                .chain(words(&section.text).map(|w| (w, 1)));
+            for (word, count) in weighted {
+                let stemmed = word_stems
+                    .entry(word)
+                    .or_insert_with_key(|w| stem(w))
+                    .clone();
+                *tf.entry(stemmed).or_default() += count;
+                len += count;
+            }
+            if len == 0 && i > 0 {
+                continue;
+            }
+            section_refs.push((doc, section.heading, section.anchor));
+            section_terms.push(tf);
+            section_lens.push(len);
+        }
+    }
+
+    // BM25 weight of every (stem, section) pair
+    let n = section_terms.len() as f64;
+    let avg_len = (section_lens.iter().sum::<usize>() as f64 / n.max(1.0)).max(1.0);
+    let mut df: HashMap<&str, usize> = HashMap::new();
+    for tf in &section_terms {
+        for term in tf.keys() {
+            *df.entry(term).or_default() += 1;
+        }
+    }
+    let mut shards: BTreeMap<String, Shard> = BTreeMap::new();
+    for (section, tf) in section_terms.iter().enumerate() {
+        let len_norm = 1.0 - B + B * section_lens[section] as f64 / avg_len;
+        for (term, &freq) in tf {
+            let df = df[term.as_str()] as f64;
+            let idf = (1.0 + (n - df + 0.5) / (df + 0.5)).ln();
+            let freq = freq as f64;
+            let weight = idf * freq * (K1 + 1.0) / (freq + K1 * len_norm);
+            shards
+                .entry(prefix(term, search.prefix_length))
+                .or_default()
+                .terms
+                .entry(term.clone())
+                .or_default()
+                .push((section, (weight * 1000.0).round() / 1000.0));
+        }
+    }
+    for (word, stemmed) in word_stems {
+        shards
+            .entry(prefix(&word, search.prefix_length))
+            .or_default()
+            .words
+            .insert(word, stemmed);
+    }
+
+    let search_dir = dir.join("search");
+    let shards_dir = search_dir.join("shards");
+    fs::create_dir_all(&shards_dir)?;
+    let mut written = Vec::new();
+    let mut shard_files = BTreeMap::new();
+    for (prefix, shard) in &shards {
+        let file = shard_file(prefix);
+        let path = shards_dir.join(&file);
+        fs::write(&path, serde_json::to_string(shard).unwrap_or_default())?;
+        written.push(path);
+        shard_files.insert(prefix.clone(), file);
+    }
+
+    let manifest = Manifest {
+        version: 1,
+        lang,
+        prefix_length: search.prefix_length,
+        docs,
+        sections: section_refs,
+        shards: shard_files,
+    };
+    let manifest_path = search_dir.join("index.json");
+    fs::write(
+        &manifest_path,
+        serde_json::to_string(&manifest).unwrap_or_default(),
+    )?;
+    written.push(manifest_path);
+
+    let js_path = search_dir.join("search.js");
+    fs::write(&js_path, SEARCH_JS)?;
+    written.push(js_path);
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build::markdown::markdown_to_html;
+    use crate::content::Frontmatter;
+
+    fn config() -> SiteConfig {
+        toml::from_str(
+            r#"
+[site]
+title = "Test"
+
+[[collections]]
+name = "docs"
+label = "Docs"
+directory = "docs"
+listed = true
+url_prefix = "/docs"
+default_template = "doc.html"
+
+[[collections]]
+name = "pages"
+label = "Pages"
+directory = "pages"
+default_template = "page.html"
+"#,
+        )
+        .unwrap()
+    }
+
+    fn item(collection: &str, slug: &str, title: &str, body: &str) -> ContentItem {
+        let (html_body, toc) = markdown_to_html(body);
+        ContentItem {
+            frontmatter: Frontmatter {
+                title: title.into(),
+                ..Default::default()
+            },
+            raw_body: body.into(),
+            markdown_body: body.into(),
+            html_body,
+            source_path: PathBuf::from(format!("content/{collection}/{slug}.md")),
+            slug: slug.into(),
+            collection: collection.into(),
+            url: format!("/{collection}/{slug}"),
+            lang: "en".into(),
+            excerpt: String::new(),
+            toc,
+            word_count: 0,
+            reading_time: 0,
+            excerpt_html: String::new(),
+        }
+    }
+
+    fn read(path: &Path) -> serde_json::Value {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_words() {
+        let w: Vec<String> = words("Hello, World! a x2 café-crème").collect();
+        assert_eq!(w, ["hello", "world", "x2", "café", "crème"]);
+    }
+
+    #[test]
+    fn test_shard_file() {
+        assert_eq!(shard_file("ru"), "ru.json");
+        assert_eq!(shard_file("0x"), "0x.json");
+        assert_eq!(shard_file("ca"), "ca.json");
+        assert_eq!(shard_file("é"), "xc3a9.json");
+    }
+
+    #[test]
+    fn test_stemmer_for() {
+        assert!(stemmer_for("en").is_some());
+        assert!(stemmer_for("pt-BR").is_some());
+        assert!(stemmer_for("ja").is_none());
+        assert_eq!(stemmer_for("en").unwrap().stem("running"), "run");
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        let text = html_to_text("<p>a &amp; b</p><script>var x = 1;</script><em>c</em>");
+        assert!(text.contains("a & b"));
+        assert!(text.contains('c'));
+        assert!(!text.contains("var"));
+    }
+
+    #[test]
+    fn test_sections_follow_toc() {
+        let page = item(
+            "docs",
+            "setup",
+            "Setup",
+            "Intro text.\n\n## Install `seite`\n\nRun the installer.\n\n### Verify\n\nCheck it.\n",
+        );
+        let sections = sections(&page);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].heading, "Setup");
+        assert_eq!(sections[0].anchor, "");
+        assert!(sections[0].text.contains("Intro text."));
+        assert_eq!(sections[1].heading, page.toc[0].text);
+        assert_eq!(sections[1].anchor, page.toc[0].id);
+        assert!(sections[1].text.contains("Run the installer."));
+        assert!(!sections[1].text.contains("Check it."));
+        assert_eq!(sections[2].anchor, "verify");
+    }
+
+    #[test]
+    fn test_write_index() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = config();
+        let search: SearchSection = toml::from_str("").unwrap();
+        let a = item(
+            "docs",
+            "deploy",
+            "Deploying",
+            "Ship it.\n\n## Running the server\n\nThe server runs on port 3000.\n",
+        );
+        let b = item("docs", "themes", "Themes", "Pick a theme.\n");
+        let hidden = item("pages", "about", "About", "The server is hidden.\n");
+        let items = vec![&a, &b, &hidden];
+        let written = write_index(tmp.path(), &items, &config, &search, "en").unwrap();
+        assert!(written.contains(&tmp.path().join("search/index.json")));
+        assert!(tmp.path().join("search/search.js").exists());
+
+        let manifest = read(&tmp.path().join("search/index.json"));
+        assert_eq!(manifest["docs"].as_array().unwrap().len(), 2);
+        assert_eq!(manifest["sections"][1][1], "Running the server");
+        assert_eq!(manifest["sections"][1][2], "running-the-server");
+        assert_eq!(manifest["shards"]["se"], "se.json");
+
+        // Surface words map to their stem, which holds the postings
+        let ru = read(&tmp.path().join("search/shards/ru.json"));
+        assert_eq!(ru["words"]["running"], "run");
+        assert_eq!(ru["words"]["runs"], "run");
+        let postings = ru["terms"]["run"].as_array().unwrap();
+        assert_eq!(postings.len(), 1);
+        assert_eq!(postings[0][0], 1);
+
+        // Pages of unlisted collections are not indexed
+        let se = read(&tmp.path().join("search/shards/se.json"));
+        let server = se["terms"]["server"].as_array().unwrap();
+        assert!(server.iter().all(|p| p[0] == 1));
+    }
+
+    #[test]
+    fn test_write_index_ranks_rare_terms_higher() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = config();
+        let search: SearchSection = toml::from_str("stemming = false").unwrap();
+        let a = item("docs", "a", "Alpha", "common words here and rare\n");
+        let b = item("docs", "b", "Beta", "common words here\n");
+        let items = vec![&a, &b];
+        write_index(tmp.path(), &items, &config, &search, "en").unwrap();
+        let weight = |shard: &str, term: &str| {
+            read(&tmp.path().join(format!("search/shards/{shard}.json")))["terms"][term][0][1]
+                .as_f64()
+                .unwrap()
+        };
+        assert!(weight("ra", "rare") > weight("co", "common"));
+    }
+}
@@ -5,95 +5,293 @@ use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Writer;
 
 use crate::config::SiteConfig;
-use crate::content::ContentItem;
+use crate::content::{ChangeFreq, ContentItem};
 use crate::error::{PageError, Result};
 
+use super::images::{self, ProcessedImage};
 /// A translation link used in the sitemap's xhtml:link alternates.
 use super::TranslationLink;
 
+/// Most URLs a single sitemap file may hold (sitemaps.org protocol limit).
+const MAX_URLS: usize = 50_000;
+/// Largest uncompressed size of a single sitemap file, in bytes.
+const MAX_BYTES: usize = 50 * 1024 * 1024;
+/// Most `<image:image>` entries Google reads per URL.
+const MAX_IMAGES_PER_URL: usize = 1_000;
+
+/// One file of the generated sitemap, named relative to the output directory.
+pub(crate) struct SitemapFile {
+    pub name: String,
+    pub xml: String,
+}
+
+/// One `<url>` entry.
+struct UrlEntry {
+    loc: String,
+    lastmod: Option<String>,
+    changefreq: Option<ChangeFreq>,
+    priority: Option<f32>,
+    /// `(hreflang, href)` pairs for `<xhtml:link rel="alternate">`.
+    alternates: Vec<(String, String)>,
+    /// Absolute image URLs for `<image:image>`.
+    images: Vec<String>,
+}
+
+impl UrlEntry {
+    fn new(loc: String) -> Self {
+        Self {
+            loc,
+            lastmod: None,
+            changefreq: None,
+            priority: None,
+            alternates: Vec::new(),
+            images: Vec::new(),
+        }
+    }
+}
+
+/// Whether a page belongs in the sitemap: pages marked `robots: noindex` or
+/// `sitemap: { exclude: true }` are left out.
+pub(crate) fn is_listed(item: &ContentItem) -> bool {
+    let noindex = item
+        .frontmatter
+        .robots
+        .as_deref()
+        .is_some_and(|r| r.to_ascii_lowercase().contains("noindex"));
+    let excluded = item.frontmatter.sitemap.as_ref().is_some_and(|s| s.exclude);
+    !noindex && !excluded
+}
+
+/// Generate the sitemap. Small sites get a single `sitemap.xml`; once the
+/// URLs exceed the protocol limits they are split into `sitemap-N.xml` files
+/// listed by a `sitemap_index.xml`.
 pub(crate) fn generate_sitemap(
     config: &SiteConfig,
     items: &[&ContentItem],
     translation_map: &HashMap<(String, String), Vec<TranslationLink>>,
     extra_urls: &[String],
-) -> Result<String> {
-    let mut writer = Writer::new(Cursor::new(Vec::new()));
+    image_manifest: &HashMap<String, ProcessedImage>,
+) -> Result<Vec<SitemapFile>> {
+    let entries = url_entries(config, items, translation_map, extra_urls, image_manifest);
+    split_sitemap(config, &entries, MAX_URLS, MAX_BYTES)
+}
+
+fn url_entries(
+    config: &SiteConfig,
+    items: &[&ContentItem],
+    translation_map: &HashMap<(String, String), Vec<TranslationLink>>,
+    extra_urls: &[String],
+    image_manifest: &HashMap<String, ProcessedImage>,
+) -> Vec<UrlEntry> {
     let base = config.site.base_url.trim_end_matches('/');
     let is_multilingual = config.is_multilingual();
-
-    write(
-        &mut writer,
-        Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)),
-    )?;
-
-    let mut urlset = BytesStart::new("urlset");
-    urlset.push_attribute(("xmlns", "http://www.sitemaps.org/schemas/sitemap/0.9"));
-    if is_multilingual {
-        urlset.push_attribute(("xmlns:xhtml", "http://www.w3.org/1999/xhtml"));
-    }
-    write(&mut writer, Event::Start(urlset))?;
+    let mut entries = Vec::new();
 
     // Index page(s)
     if is_multilingual {
         let all_langs = config.all_languages();
-        // Default language index
-        write_url_with_alternates(
-            &mut writer,
-            &format!("{base}/"),
-            None,
-            &all_langs,
-            base,
-            &config.site.language,
-        )?;
-        // Non-default language indices
+        let mut alternates: Vec<(String, String)> = all_langs
+            .iter()
+            .map(|lang| {
+                let href = if *lang == config.site.language {
+                    format!("{base}/")
+                } else {
+                    format!("{base}/{lang}/")
+                };
+                (lang.clone(), href)
+            })
+            .collect();
+        alternates.push(("x-default".to_string(), format!("{base}/")));
+
+        // Default language index, then non-default language indices
+        let mut entry = UrlEntry::new(format!("{base}/"));
+        entry.alternates = alternates.clone();
+        entries.push(entry);
         for lang in config.languages.keys() {
-            write_url_with_alternates(
-                &mut writer,
-                &format!("{base}/{lang}/"),
-                None,
-                &all_langs,
-                base,
-                &config.site.language,
-            )?;
+            let mut entry = UrlEntry::new(format!("{base}/{lang}/"));
+            entry.alternates = alternates.clone();
+            entries.push(entry);
         }
     } else {
-        write_url(&mut writer, &format!("{base}/"), None)?;
+        entries.push(UrlEntry::new(format!("{base}/")));
     }
 
-    for item in items {
-        let loc = format!("{}{}", base, item.url);
-        let lastmod = item
+    for item in items.iter().filter(|item| is_listed(item)) {
+        let mut entry = UrlEntry::new(format!("{}{}", base, item.url));
+        entry.lastmod = item
             .frontmatter
             .updated
             .or(item.frontmatter.date)
             .map(|d| d.to_string());
-
+        if let Some(ref options) = item.frontmatter.sitemap {
+            entry.changefreq = options.changefreq;
+            entry.priority = options.priority;
+        }
         if is_multilingual {
             let key = (item.collection.clone(), item.slug.clone());
             if let Some(translations) = translation_map.get(&key) {
                 if translations.len() > 1 {
-                    write_url_with_translations(&mut writer, &loc, lastmod, translations, base)?;
-                } else {
-                    write_url(&mut writer, &loc, lastmod)?;
+                    entry.alternates = translations
+                        .iter()
+                        .map(|t| (t.lang.clone(), format!("{base}{}", t.url)))
+                        .collect();
                 }
-            } else {
-                write_url(&mut writer, &loc, lastmod)?;
             }
-        } else {
-            write_url(&mut writer, &loc, lastmod)?;
         }
+        entry.images = page_images(item, image_manifest)
+            .into_iter()
+            .map(|path| format!("{base}{path}"))
+            .collect();
+        entries.push(entry);
     }
 
     // Extra URLs (e.g. tag pages) — simple entries with no lastmod
     for url in extra_urls {
-        let loc = format!("{base}{url}");
-        write_url(&mut writer, &loc, None)?;
+        entries.push(UrlEntry::new(format!("{base}{url}")));
     }
 
-    write(&mut writer, Event::End(BytesEnd::new("urlset")))?;
+    entries
+}
 
-    let bytes = writer.into_inner().into_inner();
-    String::from_utf8(bytes).map_err(|e| PageError::Build(format!("Sitemap encoding error: {e}")))
+/// Processed images shown on a page: its frontmatter `image` and every
+/// `<img>` in the body, as root-relative paths found in the image manifest.
+fn page_images(
+    item: &ContentItem,
+    image_manifest: &HashMap<String, ProcessedImage>,
+) -> Vec<String> {
+    if image_manifest.is_empty() {
+        return Vec::new();
+    }
+    let mut paths = Vec::new();
+    if let Some(ref image) = item.frontmatter.image {
+        if image.starts_with('/') {
+            paths.push(image.clone());
+        } else if !image.contains("://") {
+            paths.push(format!("/{image}"));
+        }
+    }
+    let mut remaining = item.html_body.as_str();
+    while let Some(start) = remaining.find("<img ") {
+        let tag = &remaining[start..];
+        let end = tag.find('>').map_or(tag.len(), |e| e + 1);
+        if let Some(src) = images::extract_attr(&tag[..end], "src") {
+            paths.push(src);
+        }
+        remaining = &tag[end..];
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    paths.retain(|p| image_manifest.contains_key(p) && seen.insert(p.clone()));
+    paths.truncate(MAX_IMAGES_PER_URL);
+    paths
+}
+
+/// Write the entries as one `sitemap.xml`, or as `sitemap-N.xml` files plus a
+/// `sitemap_index.xml` when they don't fit within `max_urls` / `max_bytes`.
+fn split_sitemap(
+    config: &SiteConfig,
+    entries: &[UrlEntry],
+    max_urls: usize,
+    max_bytes: usize,
+) -> Result<Vec<SitemapFile>> {
+    let is_multilingual = config.is_multilingual();
+    let fragments = entries
+        .iter()
+        .map(url_fragment)
+        .collect::<Result<Vec<String>>>()?;
+
+    let overhead = urlset_xml(is_multilingual, true, std::iter::empty()).len();
+    let mut chunks: Vec<std::ops::Range<usize>> = Vec::new();
+    let mut start = 0;
+    let mut bytes = overhead;
+    for (i, fragment) in fragments.iter().enumerate() {
+        if i > start && (i - start == max_urls || bytes + fragment.len() > max_bytes) {
+            chunks.push(start..i);
+            start = i;
+            bytes = overhead;
+        }
+        bytes += fragment.len();
+    }
+    chunks.push(start..fragments.len());
+
+    let file = |range: std::ops::Range<usize>, name: String| {
+        let has_images = entries[range.clone()].iter().any(|e| !e.images.is_empty());
+        SitemapFile {
+            name,
+            xml: urlset_xml(
+                is_multilingual,
+                has_images,
+                fragments[range].iter().map(String::as_str),
+            ),
+        }
+    };
+
+    if chunks.len() == 1 {
+        return Ok(vec![file(chunks.remove(0), "sitemap.xml".to_string())]);
+    }
+
+    let base = config.site.base_url.trim_end_matches('/');
+    let mut files = Vec::with_capacity(chunks.len() + 1);
+    let mut part_urls = Vec::with_capacity(chunks.len());
+    for (n, range) in chunks.into_iter().enumerate() {
+        let name = format!("sitemap-{}.xml", n + 1);
+        part_urls.push(format!("{base}/{name}"));
+        files.push(file(range, name));
+    }
+    files.push(SitemapFile {
+        name: "sitemap_index.xml".to_string(),
+        xml: generate_sitemap_index(&part_urls)?,
+    });
+    Ok(files)
+}
+
+/// Wrap `<url>` fragments in a `<urlset>` document.
+fn urlset_xml<'a>(
+    is_multilingual: bool,
+    has_images: bool,
+    fragments: impl Iterator<Item = &'a str>,
+) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\"",
+    );
+    if is_multilingual {
+        xml.push_str(" xmlns:xhtml=\"http://www.w3.org/1999/xhtml\"");
+    }
+    if has_images {
+        xml.push_str(" xmlns:image=\"http://www.google.com/schemas/sitemap-image/1.1\"");
+    }
+    xml.push('>');
+    for fragment in fragments {
+        xml.push_str(fragment);
+    }
+    xml.push_str("</urlset>");
+    xml
+}
+
+/// Generate a sitemap index (`<sitemapindex>`) listing the given sitemap URLs.
+/// Used for split sitemaps and by workspaces to tie every site's sitemap together.
+pub fn generate_sitemap_index(sitemap_urls: &[String]) -> Result<String> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+
+    write(
+        &mut writer,
+        Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)),
+    )?;
+
+    let mut index = BytesStart::new("sitemapindex");
+    index.push_attribute(("xmlns", "http://www.sitemaps.org/schemas/sitemap/0.9"));
+    write(&mut writer, Event::Start(index))?;
+
+    for url in sitemap_urls {
+        write(&mut writer, Event::Start(BytesStart::new("sitemap")))?;
+        write_text_element(&mut writer, "loc", url)?;
+        write(&mut writer, Event::End(BytesEnd::new("sitemap")))?;
+    }
+
+    write(&mut writer, Event::End(BytesEnd::new("sitemapindex")))?;
+
+    into_string(writer)
 }
 
 fn write(writer: &mut Writer<Cursor<Vec<u8>>>, event: Event<'_>) -> Result<()> {
@@ -102,89 +300,45 @@ fn write(writer: &mut Writer<Cursor<Vec<u8>>>, event: Event<'_>) -> Result<()> {
         .map_err(|e| PageError::Build(format!("Sitemap write error: {e}")))
 }
 
-fn write_url(
-    writer: &mut Writer<Cursor<Vec<u8>>>,
-    loc: &str,
-    lastmod: Option<String>,
-) -> Result<()> {
-    write(writer, Event::Start(BytesStart::new("url")))?;
-    write(writer, Event::Start(BytesStart::new("loc")))?;
-    write(writer, Event::Text(BytesText::new(loc)))?;
-    write(writer, Event::End(BytesEnd::new("loc")))?;
-    if let Some(date) = lastmod {
-        write(writer, Event::Start(BytesStart::new("lastmod")))?;
-        write(writer, Event::Text(BytesText::new(&date)))?;
-        write(writer, Event::End(BytesEnd::new("lastmod")))?;
-    }
-    write(writer, Event::End(BytesEnd::new("url")))
+fn write_text_element(writer: &mut Writer<Cursor<Vec<u8>>>, name: &str, text: &str) -> Result<()> {
+    write(writer, Event::Start(BytesStart::new(name)))?;
+    write(writer, Event::Text(BytesText::new(text)))?;
+    write(writer, Event::End(BytesEnd::new(name)))
 }
 
-fn write_url_with_translations(
-    writer: &mut Writer<Cursor<Vec<u8>>>,
-    loc: &str,
-    lastmod: Option<String>,
-    translations: &[TranslationLink],
-    base: &str,
-) -> Result<()> {
-    write(writer, Event::Start(BytesStart::new("url")))?;
-    write(writer, Event::Start(BytesStart::new("loc")))?;
-    write(writer, Event::Text(BytesText::new(loc)))?;
-    write(writer, Event::End(BytesEnd::new("loc")))?;
-    if let Some(date) = lastmod {
-        write(writer, Event::Start(BytesStart::new("lastmod")))?;
-        write(writer, Event::Text(BytesText::new(&date)))?;
-        write(writer, Event::End(BytesEnd::new("lastmod")))?;
-    }
-    for t in translations {
-        let mut link = BytesStart::new("xhtml:link");
-        link.push_attribute(("rel", "alternate"));
-        link.push_attribute(("hreflang", t.lang.as_str()));
-        let href = format!("{base}{}", t.url);
-        link.push_attribute(("href", href.as_str()));
-        write(writer, Event::Empty(link))?;
-    }
-    write(writer, Event::End(BytesEnd::new("url")))
+fn into_string(writer: Writer<Cursor<Vec<u8>>>) -> Result<String> {
+    let bytes = writer.into_inner().into_inner();
+    String::from_utf8(bytes).map_err(|e| PageError::Build(format!("Sitemap encoding error: {e}")))
 }
 
-/// Write a URL entry with hreflang alternates for index pages.
-fn write_url_with_alternates(
-    writer: &mut Writer<Cursor<Vec<u8>>>,
-    loc: &str,
-    lastmod: Option<String>,
-    all_langs: &[String],
-    base: &str,
-    default_lang: &str,
-) -> Result<()> {
-    write(writer, Event::Start(BytesStart::new("url")))?;
-    write(writer, Event::Start(BytesStart::new("loc")))?;
-    write(writer, Event::Text(BytesText::new(loc)))?;
-    write(writer, Event::End(BytesEnd::new("loc")))?;
-    if let Some(date) = lastmod {
-        write(writer, Event::Start(BytesStart::new("lastmod")))?;
-        write(writer, Event::Text(BytesText::new(&date)))?;
-        write(writer, Event::End(BytesEnd::new("lastmod")))?;
-    }
-    for lang in all_langs {
+/// Serialize one `<url>` element.
+fn url_fragment(entry: &UrlEntry) -> Result<String> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+    write(&mut writer, Event::Start(BytesStart::new("url")))?;
+    write_text_element(&mut writer, "loc", &entry.loc)?;
+    if let Some(ref date) = entry.lastmod {
+        write_text_element(&mut writer, "lastmod", date)?;
+    }
+    if let Some(changefreq) = entry.changefreq {
+        write_text_element(&mut writer, "changefreq", changefreq.as_str())?;
+    }
+    if let Some(priority) = entry.priority {
+        write_text_element(&mut writer, "priority", &priority.to_string())?;
+    }
+    for (lang, href) in &entry.alternates {
         let mut link = BytesStart::new("xhtml:link");
         link.push_attribute(("rel", "alternate"));
         link.push_attribute(("hreflang", lang.as_str()));
-        let href = if lang == default_lang {
-            format!("{base}/")
-        } else {
-            format!("{base}/{lang}/")
-        };
         link.push_attribute(("href", href.as_str()));
-        write(writer, Event::Empty(link))?;
+        write(&mut writer, Event::Empty(link))?;
     }
-    // x-default
-    let mut xdefault = BytesStart::new("xhtml:link");
-    xdefault.push_attribute(("rel", "alternate"));
-    xdefault.push_attribute(("hreflang", "x-default"));
-    let xdefault_href = format!("{base}/");
-    xdefault.push_attribute(("href", xdefault_href.as_str()));
-    write(writer, Event::Empty(xdefault))?;
-
-    write(writer, Event::End(BytesEnd::new("url")))
+    for image in &entry.images {
+        write(&mut writer, Event::Start(BytesStart::new("image:image")))?;
+        write_text_element(&mut writer, "image:loc", image)?;
+        write(&mut writer, Event::End(BytesEnd::new("image:image")))?;
+    }
+    write(&mut writer, Event::End(BytesEnd::new("url")))?;
+    into_string(writer)
 }
 
 #[cfg(test)]
@@ -200,6 +354,7 @@ mod tests {
                 base_url: base_url.into(),
                 language: "en".into(),
                 author: "".into(),
+                timezone: None,
             },
             collections: vec![],
             build: Default::default(),
@@ -209,6 +364,11 @@ mod tests {
             analytics: None,
             trust: None,
             contact: None,
+            og: None,
+            search: None,
+            related: None,
+            feed: None,
+            taxonomies: Vec::new(),
         }
     }
 
@@ -222,13 +382,21 @@ mod tests {
                 image: None,
                 slug: None,
                 tags: vec![],
+                authors: Vec::new(),
                 draft: false,
                 template: None,
                 robots: None,
                 weight: None,
+                related: Vec::new(),
+                aliases: Vec::new(),
+                sitemap: None,
+                publish_at: None,
+                expires_at: None,
+                terms: Default::default(),
                 extra: Default::default(),
             },
             raw_body: String::new(),
+            markdown_body: String::new(),
             html_body: String::new(),
             source_path: std::path::PathBuf::from("test.md"),
             slug: slug.into(),
@@ -243,13 +411,39 @@ mod tests {
         }
     }
 
+    fn sitemap_xml(
+        config: &SiteConfig,
+        items: &[&ContentItem],
+        translation_map: &HashMap<(String, String), Vec<TranslationLink>>,
+        extra_urls: &[String],
+    ) -> String {
+        let mut files =
+            generate_sitemap(config, items, translation_map, extra_urls, &HashMap::new()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "sitemap.xml");
+        files.remove(0).xml
+    }
+
+    fn processed_image(rel_path: &str) -> ProcessedImage {
+        ProcessedImage {
+            rel_path: rel_path.into(),
+            srcset_entries: vec![],
+            webp_entries: vec![],
+            avif_entries: vec![],
+            original_width: 800,
+            original_height: 600,
+            placeholder: String::new(),
+            color: String::new(),
+        }
+    }
+
     #[test]
     fn test_generate_sitemap_basic() {
         let config = test_config("https://example.com");
         let item = test_item("hello", "/posts/hello", "posts");
         let items: Vec<&ContentItem> = vec![&item];
         let translation_map = HashMap::new();
-        let result = generate_sitemap(&config, &items, &translation_map, &[]).unwrap();
+        let result = sitemap_xml(&config, &items, &translation_map, &[]);
         assert!(result.contains("<urlset"));
         assert!(result.contains("https://example.com/"));
         assert!(result.contains("https://example.com/posts/hello"));
@@ -260,7 +454,7 @@ mod tests {
         let config = test_config("https://example.com/");
         let items: Vec<&ContentItem> = vec![];
         let translation_map = HashMap::new();
-        let result = generate_sitemap(&config, &items, &translation_map, &[]).unwrap();
+        let result = sitemap_xml(&config, &items, &translation_map, &[]);
         assert!(result.contains("https://example.com/"));
         assert!(!result.contains("https://example.com//"));
     }
@@ -269,10 +463,10 @@ mod tests {
     fn test_generate_sitemap_with_lastmod() {
         let config = test_config("https://example.com");
         let mut item = test_item("hello", "/posts/hello", "posts");
-        item.frontmatter.date = Some(chrono::NaiveDate::from_ymd_opt(2025, 6, 15).unwrap());
+        item.frontmatter.date = Some(chrono::NaiveDate::from_ymd_opt(2025, 6, 15).unwrap().into());
         let items: Vec<&ContentItem> = vec![&item];
         let translation_map = HashMap::new();
-        let result = generate_sitemap(&config, &items, &translation_map, &[]).unwrap();
+        let result = sitemap_xml(&config, &items, &translation_map, &[]);
         assert!(result.contains("<lastmod>2025-06-15</lastmod>"));
     }
 
@@ -282,7 +476,7 @@ mod tests {
         let items: Vec<&ContentItem> = vec![];
         let translation_map = HashMap::new();
         let extra = vec!["/tags/".to_string(), "/tags/rust/".to_string()];
-        let result = generate_sitemap(&config, &items, &translation_map, &extra).unwrap();
+        let result = sitemap_xml(&config, &items, &translation_map, &extra);
         assert!(result.contains("https://example.com/tags/"));
         assert!(result.contains("https://example.com/tags/rust/"));
     }
@@ -292,7 +486,7 @@ mod tests {
         let config = test_config("https://example.com");
         let items: Vec<&ContentItem> = vec![];
         let translation_map = HashMap::new();
-        let result = generate_sitemap(&config, &items, &translation_map, &[]).unwrap();
+        let result = sitemap_xml(&config, &items, &translation_map, &[]);
         assert!(result.contains("<urlset"));
         assert!(result.contains("</urlset>"));
         assert!(result.contains("https://example.com/"));
@@ -311,7 +505,7 @@ mod tests {
         let item = test_item("hello", "/posts/hello", "posts");
         let items: Vec<&ContentItem> = vec![&item];
         let translation_map = HashMap::new();
-        let result = generate_sitemap(&config, &items, &translation_map, &[]).unwrap();
+        let result = sitemap_xml(&config, &items, &translation_map, &[]);
         assert!(result.contains("xmlns:xhtml"));
         assert!(result.contains("hreflang"));
         assert!(result.contains("https://example.com/es/"));
@@ -345,7 +539,7 @@ mod tests {
                 },
             ],
         );
-        let result = generate_sitemap(&config, &items, &translation_map, &[]).unwrap();
+        let result = sitemap_xml(&config, &items, &translation_map, &[]);
         assert!(result.contains("hreflang=\"en\""));
         assert!(result.contains("hreflang=\"es\""));
         assert!(result.contains("/posts/hello"));
@@ -360,9 +554,152 @@ mod tests {
         let item3 = test_item("about", "/about", "pages");
         let items: Vec<&ContentItem> = vec![&item1, &item2, &item3];
         let translation_map = HashMap::new();
-        let result = generate_sitemap(&config, &items, &translation_map, &[]).unwrap();
+        let result = sitemap_xml(&config, &items, &translation_map, &[]);
         assert!(result.contains("/posts/first"));
         assert!(result.contains("/posts/second"));
         assert!(result.contains("/about"));
     }
+
+    #[test]
+    fn test_generate_sitemap_index() {
+        let urls = vec![
+            "https://example.com/sitemap.xml".to_string(),
+            "https://docs.example.com/sitemap.xml".to_string(),
+        ];
+        let result = generate_sitemap_index(&urls).unwrap();
+        assert!(
+            result.contains("<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">")
+        );
+        assert!(result.contains("<sitemap><loc>https://example.com/sitemap.xml</loc></sitemap>"));
+        assert!(result.contains("<loc>https://docs.example.com/sitemap.xml</loc>"));
+    }
+
+    #[test]
+    fn test_generate_sitemap_skips_noindex_and_excluded() {
+        let config = test_config("https://example.com");
+        let listed = test_item("listed", "/posts/listed", "posts");
+        let mut hidden = test_item("hidden", "/posts/hidden", "posts");
+        hidden.frontmatter.robots = Some("NoIndex, follow".into());
+        let mut excluded = test_item("excluded", "/posts/excluded", "posts");
+        excluded.frontmatter.sitemap = Some(crate::content::SitemapOptions {
+            exclude: true,
+            ..Default::default()
+        });
+        let items: Vec<&ContentItem> = vec![&listed, &hidden, &excluded];
+        let result = sitemap_xml(&config, &items, &HashMap::new(), &[]);
+        assert!(result.contains("/posts/listed"));
+        assert!(!result.contains("/posts/hidden"));
+        assert!(!result.contains("/posts/excluded"));
+    }
+
+    #[test]
+    fn test_generate_sitemap_priority_and_changefreq() {
+        let config = test_config("https://example.com");
+        let mut item = test_item("hello", "/posts/hello", "posts");
+        item.frontmatter.date = Some(chrono::NaiveDate::from_ymd_opt(2025, 6, 15).unwrap().into());
+        item.frontmatter.sitemap = Some(crate::content::SitemapOptions {
+            exclude: false,
+            priority: Some(0.8),
+            changefreq: Some(ChangeFreq::Weekly),
+        });
+        let items: Vec<&ContentItem> = vec![&item];
+        let result = sitemap_xml(&config, &items, &HashMap::new(), &[]);
+        assert!(result.contains(
+            "<lastmod>2025-06-15</lastmod><changefreq>weekly</changefreq><priority>0.8</priority>"
+        ));
+    }
+
+    #[test]
+    fn test_sitemap_options_reject_out_of_range_priority() {
+        let err = serde_yaml_ng::from_str::<Frontmatter>("title: X\nsitemap:\n  priority: 1.5")
+            .unwrap_err();
+        assert!(err.to_string().contains("between 0.0 and 1.0"));
+        let fm: Frontmatter =
+            serde_yaml_ng::from_str("title: X\nsitemap:\n  changefreq: daily").unwrap();
+        assert_eq!(fm.sitemap.unwrap().changefreq, Some(ChangeFreq::Daily));
+    }
+
+    #[test]
+    fn test_generate_sitemap_images_from_manifest() {
+        let config = test_config("https://example.com");
+        let mut item = test_item("hello", "/posts/hello", "posts");
+        item.frontmatter.image = Some("/static/cover.jpg".into());
+        item.html_body = concat!(
+            "<p><img src=\"/static/photo.jpg\" alt=\"\"></p>",
+            "<p><img src=\"/static/cover.jpg\" alt=\"\"></p>",
+            "<p><img src=\"https://cdn.example.com/x.png\" alt=\"\"></p>",
+        )
+        .into();
+        let mut manifest = HashMap::new();
+        manifest.insert(
+            "/static/photo.jpg".to_string(),
+            processed_image("photo.jpg"),
+        );
+        manifest.insert(
+            "/static/cover.jpg".to_string(),
+            processed_image("cover.jpg"),
+        );
+        let items: Vec<&ContentItem> = vec![&item];
+        let files = generate_sitemap(&config, &items, &HashMap::new(), &[], &manifest).unwrap();
+        let xml = &files[0].xml;
+        assert!(xml.contains("xmlns:image=\"http://www.google.com/schemas/sitemap-image/1.1\""));
+        assert!(xml.contains(concat!(
+            "<image:image><image:loc>https://example.com/static/cover.jpg</image:loc></image:image>",
+            "<image:image><image:loc>https://example.com/static/photo.jpg</image:loc></image:image>",
+        )));
+        assert!(!xml.contains("cdn.example.com"));
+    }
+
+    #[test]
+    fn test_generate_sitemap_without_images_omits_namespace() {
+        let config = test_config("https://example.com");
+        let result = sitemap_xml(&config, &[], &HashMap::new(), &[]);
+        assert!(!result.contains("xmlns:image"));
+    }
+
+    #[test]
+    fn test_split_sitemap_by_url_count() {
+        let config = test_config("https://example.com");
+        let items: Vec<ContentItem> = (0..4)
+            .map(|i| test_item(&format!("p{i}"), &format!("/posts/p{i}"), "posts"))
+            .collect();
+        let refs: Vec<&ContentItem> = items.iter().collect();
+        let entries = url_entries(&config, &refs, &HashMap::new(), &[], &HashMap::new());
+        let files = split_sitemap(&config, &entries, 2, MAX_BYTES).unwrap();
+
+        // Home page + 4 posts = 5 URLs → 3 parts and an index
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "sitemap-1.xml",
+                "sitemap-2.xml",
+                "sitemap-3.xml",
+                "sitemap_index.xml"
+            ]
+        );
+        assert_eq!(files[0].xml.matches("<url>").count(), 2);
+        assert_eq!(files[2].xml.matches("<url>").count(), 1);
+        assert!(files[2].xml.contains("/posts/p3"));
+        assert!(files[3]
+            .xml
+            .contains("<loc>https://example.com/sitemap-3.xml</loc>"));
+    }
+
+    #[test]
+    fn test_split_sitemap_by_size() {
+        let config = test_config("https://example.com");
+        let items: Vec<ContentItem> = (0..3)
+            .map(|i| test_item(&format!("p{i}"), &format!("/posts/p{i}"), "posts"))
+            .collect();
+        let refs: Vec<&ContentItem> = items.iter().collect();
+        let entries = url_entries(&config, &refs, &HashMap::new(), &[], &HashMap::new());
+        let single = split_sitemap(&config, &entries, MAX_URLS, MAX_BYTES).unwrap();
+        assert_eq!(single.len(), 1);
+
+        let limit = single[0].xml.len() - 1;
+        let files = split_sitemap(&config, &entries, MAX_URLS, limit).unwrap();
+        assert_eq!(files.len(), 3);
+        assert!(files[..2].iter().all(|f| f.xml.len() <= limit));
+    }
 }
@@ -0,0 +1,397 @@
+//! Custom taxonomies declared as `[[taxonomies]]` in `seite.toml`.
+//!
+//! Items list their terms under a frontmatter key named after the taxonomy.
+//! For every language, each taxonomy gets an index of its terms at
+//! `/{prefix}/` and a page per term at `/{prefix}/{term}/`, optionally
+//! paginated and with an RSS feed.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use chrono_tz::Tz;
+use serde::Serialize;
+
+use super::{
+    feed, insert_build_flags, insert_i18n_context, lang_prefix_for, write_index_page, ItemSummary,
+    PageContext, PaginationContext, SiteContext, TranslationLink,
+};
+use crate::config::{SiteConfig, TaxonomyConfig};
+use crate::content::{ContentItem, Frontmatter};
+use crate::error::{PageError, Result};
+
+/// A link to a term, as exposed to templates.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TermLink {
+    pub name: String,
+    pub slug: String,
+    pub url: String,
+}
+
+#[derive(Serialize)]
+struct TaxonomyContext<'a> {
+    name: &'a str,
+    label: String,
+    url: String,
+}
+
+#[derive(Serialize)]
+struct TermContext<'a> {
+    name: &'a str,
+    slug: &'a str,
+    url: String,
+    count: usize,
+    feed_url: Option<String>,
+}
+
+/// One term of a taxonomy and the items filed under it.
+struct Term<'a> {
+    /// Display name, as first written in frontmatter.
+    name: String,
+    slug: String,
+    items: Vec<&'a ContentItem>,
+}
+
+/// Terms an item lists for a taxonomy: a string or a list under the
+/// top-level frontmatter key, or under `extra` for data-backed items.
+pub fn item_terms(fm: &Frontmatter, taxonomy: &str) -> Vec<String> {
+    let value = match fm.terms.get(taxonomy).or_else(|| fm.extra.get(taxonomy)) {
+        Some(value) => value,
+        None => return Vec::new(),
+    };
+    let scalar = |v: &serde_yaml_ng::Value| match v {
+        serde_yaml_ng::Value::String(s) => Some(s.trim().to_string()),
+        serde_yaml_ng::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    };
+    let terms: Vec<String> = match value {
+        serde_yaml_ng::Value::Sequence(items) => items.iter().filter_map(scalar).collect(),
+        other => scalar(other).into_iter().collect(),
+    };
+    terms.into_iter().filter(|t| !t.is_empty()).collect()
+}
+
+/// URL of a term page for a language.
+fn term_url(taxonomy: &TaxonomyConfig, slug: &str, lang: &str, default_lang: &str) -> String {
+    format!(
+        "{}/{}/{slug}/",
+        lang_prefix_for(lang, default_lang),
+        taxonomy.url_prefix()
+    )
+}
+
+/// The `taxonomies` template context of an item: its linked terms, keyed by
+/// taxonomy name. Every taxonomy has an entry, empty if the item has no terms.
+pub fn page_terms(config: &SiteConfig, item: &ContentItem) -> BTreeMap<String, Vec<TermLink>> {
+    config
+        .taxonomies
+        .iter()
+        .map(|t| {
+            let mut links: Vec<TermLink> = Vec::new();
+            for name in item_terms(&item.frontmatter, &t.name) {
+                let slug = slug::slugify(&name);
+                if links.iter().any(|l| l.slug == slug) {
+                    continue;
+                }
+                links.push(TermLink {
+                    url: term_url(t, &slug, &item.lang, &config.site.language),
+                    name,
+                    slug,
+                });
+            }
+            (t.name.clone(), links)
+        })
+        .collect()
+}
+
+/// Group items by term, matching terms by slug. Terms are sorted by slug and
+/// their items newest first.
+fn group_terms<'a>(items: &[&'a ContentItem], taxonomy: &str, tz: Tz) -> Vec<Term<'a>> {
+    let mut terms: BTreeMap<String, Term<'a>> = BTreeMap::new();
+    for item in items {
+        for name in item_terms(&item.frontmatter, taxonomy) {
+            let slug = slug::slugify(&name);
+            if slug.is_empty() {
+                continue;
+            }
+            let term = terms.entry(slug.clone()).or_insert_with(|| Term {
+                name,
+                slug,
+                items: Vec::new(),
+            });
+            if !term.items.iter().any(|i| i.url == item.url) {
+                term.items.push(item);
+            }
+        }
+    }
+    let mut terms: Vec<Term<'a>> = terms.into_values().collect();
+    for term in &mut terms {
+        term.items
+            .sort_by_key(|item| std::cmp::Reverse(item.frontmatter.date_key(tz)));
+    }
+    terms
+}
+
+/// Render the index and term pages (and term feeds) of every taxonomy in every
+/// language. Returns the URLs of the rendered pages, for the sitemap.
+pub(super) fn render(
+    tera: &tera::Tera,
+    config: &SiteConfig,
+    items: &[&ContentItem],
+    data: &serde_json::Value,
+    output_dir: &Path,
+) -> Result<Vec<String>> {
+    let default_lang = &config.site.language;
+    let mut urls = Vec::new();
+    for lang in &config.all_languages() {
+        let lang_items: Vec<&ContentItem> = items
+            .iter()
+            .filter(|item| item.lang == *lang)
+            .copied()
+            .collect();
+        let site_ctx = SiteContext::for_lang(config, lang);
+        let base_ctx = || {
+            let mut ctx = tera::Context::new();
+            ctx.insert("site", &site_ctx);
+            ctx.insert("data", data);
+            ctx.insert("lang", lang);
+            insert_i18n_context(&mut ctx, lang, default_lang, data);
+            insert_build_flags(&mut ctx, config);
+            ctx.insert("translations", &Vec::<TranslationLink>::new());
+            ctx
+        };
+
+        for taxonomy in &config.taxonomies {
+            let terms = group_terms(&lang_items, &taxonomy.name, config.timezone());
+            if terms.is_empty() {
+                continue;
+            }
+            let index_url = format!(
+                "{}/{}/",
+                lang_prefix_for(lang, default_lang),
+                taxonomy.url_prefix()
+            );
+            let taxonomy_ctx = TaxonomyContext {
+                name: &taxonomy.name,
+                label: taxonomy.label(),
+                url: index_url.clone(),
+            };
+            let feed_url = |term: &Term| {
+                taxonomy.feed.then(|| {
+                    format!(
+                        "{}feed.xml",
+                        term_url(taxonomy, &term.slug, lang, default_lang)
+                    )
+                })
+            };
+
+            // Index of all terms
+            let term_ctxs: Vec<TermContext> = terms
+                .iter()
+                .map(|term| TermContext {
+                    name: &term.name,
+                    slug: &term.slug,
+                    url: term_url(taxonomy, &term.slug, lang, default_lang),
+                    count: term.items.len(),
+                    feed_url: feed_url(term),
+                })
+                .collect();
+            let mut ctx = base_ctx();
+            ctx.insert("taxonomy", &taxonomy_ctx);
+            ctx.insert("terms", &term_ctxs);
+            ctx.insert(
+                "page",
+                &PageContext::listing(
+                    taxonomy.label(),
+                    taxonomy.url_prefix().to_string(),
+                    index_url.clone(),
+                ),
+            );
+            let html = tera
+                .render(taxonomy.template(), &ctx)
+                .map_err(|e| PageError::Build(format!("rendering {} index: {e}", taxonomy.name)))?;
+            write_index_page(output_dir, &index_url, html)?;
+            urls.push(index_url.clone());
+
+            // One page (or a run of pages) per term
+            for (term, term_ctx) in terms.iter().zip(&term_ctxs) {
+                let summaries: Vec<ItemSummary> = term
+                    .items
+                    .iter()
+                    .map(|item| ItemSummary::from_item(item))
+                    .collect();
+                let page_size = taxonomy.paginate.unwrap_or(summaries.len()).max(1);
+                let total_pages = summaries.len().div_ceil(page_size);
+                let page_url = |n: usize| {
+                    if n == 1 {
+                        term_ctx.url.clone()
+                    } else {
+                        format!("{}page/{n}/", term_ctx.url)
+                    }
+                };
+                for (idx, chunk) in summaries.chunks(page_size).enumerate() {
+                    let page_num = idx + 1;
+                    let url = page_url(page_num);
+                    let mut ctx = base_ctx();
+                    ctx.insert("taxonomy", &taxonomy_ctx);
+                    ctx.insert("term", term_ctx);
+                    ctx.insert("items", chunk);
+                    if taxonomy.paginate.is_some() {
+                        ctx.insert(
+                            "pagination",
+                            &PaginationContext {
+                                current_page: page_num,
+                                total_pages,
+                                prev_url: (page_num > 1).then(|| page_url(page_num - 1)),
+                                next_url: (page_num < total_pages).then(|| page_url(page_num + 1)),
+                                base_url: term_ctx.url.trim_end_matches('/').to_string(),
+                            },
+                        );
+                    }
+                    ctx.insert(
+                        "page",
+                        &PageContext::listing(
+                            format!("{}: {}", taxonomy.label(), term.name),
+                            format!("{}/{}", taxonomy.url_prefix(), term.slug),
+                            url.clone(),
+                        ),
+                    );
+                    let html = tera.render(taxonomy.term_template(), &ctx).map_err(|e| {
+                        PageError::Build(format!(
+                            "rendering {} term '{}': {e}",
+                            taxonomy.name, term.name
+                        ))
+                    })?;
+                    write_index_page(output_dir, &url, html)?;
+                    urls.push(url);
+                }
+
+                if let Some(feed_url) = &term_ctx.feed_url {
+                    let title = format!(
+                        "{} — {}: {}",
+                        config.title_for_lang(lang),
+                        taxonomy.label(),
+                        term.name
+                    );
+                    let link = format!(
+                        "{}{}",
+                        config.site.base_url.trim_end_matches('/'),
+                        term_ctx.url
+                    );
+                    let rss = feed::generate_channel_rss(config, &title, &link, &term.items)?;
+                    fs::write(output_dir.join(feed_url.trim_start_matches('/')), rss)?;
+                }
+            }
+        }
+    }
+    Ok(urls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn item(url: &str, lang: &str, yaml: &str) -> ContentItem {
+        let frontmatter: Frontmatter = serde_yaml_ng::from_str(yaml).unwrap();
+        ContentItem {
+            frontmatter,
+            raw_body: String::new(),
+            markdown_body: String::new(),
+            html_body: String::new(),
+            source_path: PathBuf::from(format!("content{url}.md")),
+            slug: url.rsplit('/').next().unwrap_or_default().into(),
+            collection: "posts".into(),
+            url: url.into(),
+            lang: lang.into(),
+            excerpt: String::new(),
+            toc: Vec::new(),
+            word_count: 0,
+            reading_time: 0,
+            excerpt_html: String::new(),
+        }
+    }
+
+    fn config() -> SiteConfig {
+        toml::from_str(
+            r#"
+collections = []
+
+[site]
+title = "T"
+language = "en"
+
+[languages.fr]
+
+[[taxonomies]]
+name = "categories"
+
+[[taxonomies]]
+name = "series"
+url_prefix = "/in-series"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_item_terms_from_string_list_and_extra() {
+        let a = item(
+            "/posts/a",
+            "en",
+            "title: A\ncategories: [Rust, ' Web ', '']",
+        );
+        assert_eq!(
+            item_terms(&a.frontmatter, "categories"),
+            vec!["Rust", "Web"]
+        );
+        let b = item("/posts/b", "en", "title: B\nseries: Getting Started");
+        assert_eq!(
+            item_terms(&b.frontmatter, "series"),
+            vec!["Getting Started"]
+        );
+        let c = item("/posts/c", "en", "title: C\nextra:\n  categories: rust");
+        assert_eq!(item_terms(&c.frontmatter, "categories"), vec!["rust"]);
+        assert!(item_terms(&c.frontmatter, "series").is_empty());
+    }
+
+    #[test]
+    fn test_page_terms_links_per_language() {
+        let config = config();
+        let a = item(
+            "/fr/posts/a",
+            "fr",
+            "title: A\ncategories: [Rust, rust]\nseries: Intro",
+        );
+        let terms = page_terms(&config, &a);
+        assert_eq!(
+            terms["categories"],
+            vec![TermLink {
+                name: "Rust".into(),
+                slug: "rust".into(),
+                url: "/fr/categories/rust/".into(),
+            }]
+        );
+        assert_eq!(terms["series"][0].url, "/fr/in-series/intro/");
+    }
+
+    #[test]
+    fn test_group_terms_merges_by_slug_newest_first() {
+        let a = item(
+            "/posts/a",
+            "en",
+            "title: A\ndate: 2025-01-01\ncategories: [Rust]",
+        );
+        let b = item(
+            "/posts/b",
+            "en",
+            "title: B\ndate: 2025-02-01\ncategories: [rust, Web]",
+        );
+        let terms = group_terms(&[&a, &b], "categories", chrono_tz::UTC);
+        assert_eq!(terms.len(), 2);
+        assert_eq!(terms[0].name, "Rust");
+        let urls: Vec<&str> = terms[0].items.iter().map(|i| i.url.as_str()).collect();
+        assert_eq!(urls, vec!["/posts/b", "/posts/a"]);
+        assert_eq!(terms[1].slug, "web");
+    }
+}
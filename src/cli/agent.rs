@@ -442,7 +442,10 @@ fn scan_collection_content(
     for entry in WalkDir::new(&collection_dir)
         .into_iter()
         .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+        .filter(|e| {
+            e.path().extension().is_some_and(|ext| ext == "md")
+                && !content::is_section_index(e.path())
+        })
     {
         let path = entry.path();
         if let Ok((fm, _body)) = content::parse_content_file(path) {
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use clap::Args;
 
 use crate::build::{self, links, BuildOptions};
 use crate::config::SiteConfig;
+use crate::content::Timestamp;
 use crate::meta;
 use crate::output::{human, CommandOutput};
 use crate::workspace;
@@ -17,10 +19,24 @@ pub struct BuildArgs {
     /// Treat broken internal links as build errors
     #[arg(long)]
     pub strict: bool,
+
+    /// Ignore the build cache and render every page from scratch
+    #[arg(long)]
+    pub full: bool,
+
+    /// Delete the processed-image cache (.seite/cache/images) before building
+    #[arg(long)]
+    pub clean_cache: bool,
+
+    /// Build the site as it will be at this date or RFC 3339 datetime, to
+    /// preview scheduled (`publish_at`) and expiring (`expires_at`) content
+    #[arg(long, value_name = "DATETIME")]
+    pub as_of: Option<Timestamp>,
 }
 
 pub fn run(args: &BuildArgs, site_filter: Option<&str>) -> anyhow::Result<()> {
     let cwd = std::env::current_dir()?;
+    let as_of = args.as_of;
 
     // Nudge if project config is outdated
     if cwd.join("seite.toml").exists() && meta::needs_upgrade(&cwd) {
@@ -38,10 +54,21 @@ pub fn run(args: &BuildArgs, site_filter: Option<&str>) -> anyhow::Result<()> {
     // Check for workspace context
     if let Some(ws_root) = workspace::find_workspace_root(&cwd) {
         let ws_config = workspace::WorkspaceConfig::load(&ws_root.join("seite-workspace.toml"))?;
+        if args.clean_cache {
+            for site in ws_config
+                .sites
+                .iter()
+                .filter(|s| site_filter.is_none_or(|name| s.name == name))
+            {
+                build::image_cache::clean(&ws_root.join(&site.path))?;
+            }
+        }
 
         let opts = workspace::build::WorkspaceBuildOptions {
             include_drafts: args.drafts,
+            as_of,
             strict: args.strict,
+            incremental: !args.full,
             site_filter: site_filter.map(String::from),
         };
 
@@ -56,9 +83,15 @@ pub fn run(args: &BuildArgs, site_filter: Option<&str>) -> anyhow::Result<()> {
 
     let config = SiteConfig::load(&PathBuf::from("seite.toml"))?;
     let paths = config.resolve_paths(&cwd);
+    if args.clean_cache {
+        build::image_cache::clean(&paths.root)?;
+    }
 
     let opts = BuildOptions {
         include_drafts: args.drafts,
+        as_of,
+        incremental: !args.full,
+        cross_site_urls: HashMap::new(),
     };
 
     let result = build::build_site(&config, &paths, &opts)?;
@@ -198,6 +198,9 @@ pub fn run(args: &DeployArgs, site_filter: Option<&str>) -> anyhow::Result<()> {
         };
         let opts = BuildOptions {
             include_drafts: false,
+            as_of: None,
+            incremental: false,
+            cross_site_urls: HashMap::new(),
         };
         let result = build::build_site(&build_config, &paths, &opts)?;
         human::success(&result.stats.human_display());
@@ -131,7 +131,10 @@ pub fn run(args: &InitArgs) -> anyhow::Result<()> {
     fs::create_dir_all(root.join(".seite"))?;
 
     // Write .gitignore
-    fs::write(root.join(".gitignore"), "/dist\n/dist-subdomains\n")?;
+    fs::write(
+        root.join(".gitignore"),
+        "/dist\n/dist-subdomains\n/.seite/cache\n",
+    )?;
 
     // Generate seite.toml
     let target = match deploy_target.as_str() {
@@ -146,6 +149,7 @@ pub fn run(args: &InitArgs) -> anyhow::Result<()> {
             base_url: "http://localhost:3000".into(),
             language: "en".into(),
             author: String::new(),
+            timezone: None,
         },
         collections: collections.clone(),
         build: Default::default(),
@@ -161,6 +165,11 @@ pub fn run(args: &InitArgs) -> anyhow::Result<()> {
         analytics: None,
         trust: None,
         contact: None,
+        og: None,
+        search: None,
+        related: None,
+        feed: None,
+        taxonomies: Vec::new(),
     };
 
     // If trust collection is included, run trust center scaffolding
@@ -242,7 +251,7 @@ pub fn run(args: &InitArgs) -> anyhow::Result<()> {
         let today = chrono::Local::now().format("%Y-%m-%d").to_string();
         let fm = content::Frontmatter {
             title: "Hello World".into(),
-            date: Some(chrono::Local::now().date_naive()),
+            date: Some(chrono::Local::now().date_naive().into()),
             description: Some("Welcome to your new site!".into()),
             tags: vec!["intro".into()],
             draft: false,
@@ -263,7 +272,7 @@ pub fn run(args: &InitArgs) -> anyhow::Result<()> {
         let today = chrono::Local::now().format("%Y-%m-%d").to_string();
         let fm = content::Frontmatter {
             title: "v0.1.0".into(),
-            date: Some(chrono::Local::now().date_naive()),
+            date: Some(chrono::Local::now().date_naive().into()),
             description: Some("Initial release".into()),
             tags: vec!["new".into()],
             draft: false,
@@ -829,6 +838,9 @@ fn generate_claude_md(
     md.push_str("```bash\n");
     md.push_str("seite build                              # Build the site\n");
     md.push_str("seite build --drafts                     # Build including draft content\n");
+    md.push_str(
+        "seite build --as-of 2026-01-01           # Preview scheduled content as of a date\n",
+    );
     md.push_str("seite serve                              # Dev server with live reload + REPL\n");
     md.push_str("seite serve --port 8080                  # Use a specific port\n");
     for c in collections {
@@ -839,6 +851,7 @@ fn generate_claude_md(
     }
     md.push_str("seite new post \"Title\" --tags rust,web   # Create with tags\n");
     md.push_str("seite new post \"Title\" --draft           # Create as draft\n");
+    md.push_str("seite publish content/posts/<file>.md    # Clear draft, stamp today's date\n");
     md.push_str("seite new post \"Title\" --lang es         # Create translation (needs [languages.es] in config)\n");
     md.push_str("seite collection list                    # List site collections\n");
     md.push_str("seite collection add <preset>            # Add a preset collection (posts, docs, pages, changelog, roadmap, trust)\n");
@@ -900,6 +913,7 @@ fn generate_claude_md(
             md.push_str(
                 "- Supports nested directories (e.g., `section/slug.md` → `/docs/section/slug`)\n",
             );
+            md.push_str("- Sidebar sections: `_index.md` in a directory sets `title`, `weight`, `description`, `collapsed`; `data/nav/<collection>.yaml` sets the order explicitly\n");
         }
         if c.has_rss {
             md.push_str("- Included in RSS feed (`/feed.xml`)\n");
@@ -940,7 +954,7 @@ fn generate_claude_md(
     md.push_str("---\n");
     md.push_str("title: \"Post Title\"\n");
     if collections.iter().any(|c| c.has_date) {
-        md.push_str("date: 2025-01-15        # required for dated collections\n");
+        md.push_str("date: 2025-01-15        # required for dated collections (or 2025-01-15T09:30:00+01:00)\n");
     }
     md.push_str(
         "description: \"Optional\"  # page description — used in meta/OG/Twitter/JSON-LD\n",
@@ -953,9 +967,12 @@ fn generate_claude_md(
     md.push_str("  - tag1\n");
     md.push_str("  - tag2\n");
     md.push_str("draft: true              # optional, hides from default build\n");
+    md.push_str("publish_at: 2026-03-01T09:00:00+01:00  # optional, hidden until then\n");
+    md.push_str("expires_at: 2026-04-01   # optional, hidden from then on\n");
     md.push_str("slug: custom-slug        # optional, overrides auto-generated slug\n");
     md.push_str("template: custom.html    # optional, overrides collection default template\n");
     md.push_str("robots: noindex          # optional, per-page <meta name=\"robots\">\n");
+    md.push_str("sitemap: {priority: 0.8} # optional, also exclude: true / changefreq: weekly\n");
     md.push_str(
         "weight: 1                # optional, sort order for non-date collections (lower first)\n",
     );
@@ -1154,6 +1171,7 @@ fn generate_claude_md(
     md.push_str("- Pagination: add `paginate = 10` to a `[[collections]]` block in `seite.toml` to generate `/posts/`, `/posts/page/2/`, etc.\n");
     md.push_str("  Use `{% if pagination %}<nav>...</nav>{% endif %}` in templates; variables: `pagination.current_page`, `pagination.total_pages`, `pagination.prev_url`, `pagination.next_url`\n");
     md.push_str("- Search is always enabled: `dist/search-index.json` is generated every build. All bundled themes include a search box wired to it. No config needed.\n");
+    md.push_str("- Full-text search: add `[search]` to `seite.toml` to also write a sharded, BM25-ranked index of page bodies to `dist/search/`; include `/search/search.js` in `base.html` to query it\n");
     md.push_str("- Custom theme: `seite theme create \"your design description\"` generates `templates/base.html` with Claude (requires Claude Code)\n");
     md.push_str("- Deploy auto-commits and pushes before deploying. On non-main branches, it auto-uses preview mode. Disable with `auto_commit = false` in `[deploy]` or `--no-commit` flag\n\n");
 
@@ -6,6 +6,7 @@ pub mod deploy;
 pub mod init;
 pub mod mcp;
 pub mod new;
+pub mod publish;
 pub mod self_update;
 pub mod serve;
 pub mod theme;
@@ -54,6 +55,9 @@ pub enum Command {
     /// Create new content
     New(new::NewArgs),
 
+    /// Publish a draft or scheduled content file
+    Publish(publish::PublishArgs),
+
     /// Build the site
     Build(build::BuildArgs),
 
@@ -47,6 +47,13 @@ pub fn run(args: &NewArgs) -> anyhow::Result<()> {
             )
         })?;
 
+    if let Some(key) = &collection.data {
+        anyhow::bail!(
+            "collection '{}' is built from data '{key}'; add an entry to that data file instead",
+            collection.name
+        );
+    }
+
     let slug = content::slug_from_title(&args.title);
     let tags_vec: Vec<String> = args
         .tags
@@ -55,16 +62,26 @@ pub fn run(args: &NewArgs) -> anyhow::Result<()> {
         .unwrap_or_default();
 
     let date = if collection.has_date {
-        Some(chrono::Local::now().date_naive())
+        Some(chrono::Local::now().date_naive().into())
     } else {
         None
     };
 
+    // Pre-fill required schema fields so the new file passes validation
+    // once the placeholders are filled in.
+    let extra = collection
+        .fields
+        .iter()
+        .filter(|(_, field)| field.required)
+        .map(|(name, field)| (name.clone(), content::schema::placeholder(field)))
+        .collect();
+
     let fm = Frontmatter {
         title: args.title.clone(),
         date,
         tags: tags_vec,
         draft: args.draft,
+        extra,
         ..Default::default()
     };
 
@@ -0,0 +1,185 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use clap::Args;
+
+use crate::config::SiteConfig;
+use crate::output::human;
+
+#[derive(Args)]
+pub struct PublishArgs {
+    /// Content file to publish (e.g., content/posts/2025-01-15-hello.md)
+    pub path: PathBuf,
+
+    /// Publication date to stamp (defaults to today)
+    #[arg(long, value_name = "YYYY-MM-DD")]
+    pub date: Option<NaiveDate>,
+}
+
+/// Publish a content file: drop `draft` and any pending `publish_at`, set
+/// `date`, and move a date-prefixed file (or bundle directory) to the new date.
+pub fn run(args: &PublishArgs) -> anyhow::Result<()> {
+    let path = &args.path;
+    let raw = fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("cannot read {}: {e}", path.display()))?;
+    let date = args
+        .date
+        .unwrap_or_else(|| chrono::Local::now().date_naive());
+
+    // The file to rename: the bundle directory for `index.md`, else the file
+    let is_bundle = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .is_some_and(|stem| stem == "index" || stem.starts_with("index."));
+    let target = if is_bundle {
+        path.parent().unwrap_or(path)
+    } else {
+        path.as_path()
+    };
+    let renamed = redate_file_name(target, date);
+    if let Some(ref new_target) = renamed {
+        if new_target != target && new_target.exists() {
+            anyhow::bail!(
+                "cannot rename to {}: it already exists",
+                new_target.display()
+            );
+        }
+    }
+
+    let stamp_date = renamed.is_some() || collection_has_date(path);
+    let updated = publish_frontmatter(&raw, stamp_date.then_some(date))
+        .ok_or_else(|| anyhow::anyhow!("{} has no frontmatter", path.display()))?;
+    fs::write(path, updated)?;
+
+    match renamed {
+        Some(new_target) if new_target != target => {
+            fs::rename(target, &new_target)?;
+            human::success(&format!(
+                "Published {} -> {}",
+                target.display(),
+                new_target.display()
+            ));
+        }
+        _ => human::success(&format!("Published {}", path.display())),
+    }
+    Ok(())
+}
+
+/// Whether the file belongs to a dated collection of the site in the current directory.
+fn collection_has_date(path: &Path) -> bool {
+    let Ok(config) = SiteConfig::load(&PathBuf::from("seite.toml")) else {
+        return false;
+    };
+    let Ok(cwd) = std::env::current_dir() else {
+        return false;
+    };
+    let paths = config.resolve_paths(&cwd);
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        cwd.join(path)
+    };
+    config
+        .collections
+        .iter()
+        .any(|c| c.has_date && path.starts_with(paths.content.join(&c.directory)))
+}
+
+/// The path with its `YYYY-MM-DD-` file name prefix replaced by `date`, or
+/// `None` when the name has no date prefix.
+fn redate_file_name(path: &Path, date: NaiveDate) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    let prefix = name.get(..11)?;
+    if !prefix.ends_with('-') || NaiveDate::parse_from_str(&prefix[..10], "%Y-%m-%d").is_err() {
+        return None;
+    }
+    let new_name = format!("{}-{}", date.format("%Y-%m-%d"), &name[11..]);
+    Some(path.with_file_name(new_name))
+}
+
+/// Rewrite the frontmatter of `raw` for publishing, keeping every other line
+/// as written: remove `draft` and `publish_at`, and set `date` when given.
+/// Returns `None` when `raw` has no frontmatter.
+fn publish_frontmatter(raw: &str, date: Option<NaiveDate>) -> Option<String> {
+    let rest = raw.trim_start().strip_prefix("---")?;
+    let end = rest.find("\n---")?;
+    let (fm, body) = (&rest[..end], &rest[end + 1..]);
+
+    let is_key = |line: &str, key: &str| {
+        line.strip_prefix(key)
+            .is_some_and(|after| after.starts_with(':'))
+    };
+    let mut lines: Vec<String> = fm
+        .lines()
+        .filter(|line| !is_key(line, "draft") && !is_key(line, "publish_at"))
+        .map(String::from)
+        .collect();
+
+    if let Some(date) = date {
+        let date_line = format!("date: {}", date.format("%Y-%m-%d"));
+        if let Some(line) = lines.iter_mut().find(|l| is_key(l, "date")) {
+            *line = date_line;
+        } else {
+            let at = lines
+                .iter()
+                .position(|l| is_key(l, "title"))
+                .map_or(lines.len(), |i| i + 1);
+            lines.insert(at, date_line);
+        }
+    }
+
+    Some(format!("---{}\n{body}", lines.join("\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_publish_frontmatter_clears_draft_and_sets_date() {
+        let raw = "---\ntitle: Hello\ndate: 2024-01-01\ndraft: true\npublish_at: 2030-01-01\ntags:\n  - draft\n---\n\nBody with draft: text.\n";
+        let out = publish_frontmatter(raw, Some(date("2025-06-01"))).unwrap();
+        assert_eq!(
+            out,
+            "---\ntitle: Hello\ndate: 2025-06-01\ntags:\n  - draft\n---\n\nBody with draft: text.\n"
+        );
+    }
+
+    #[test]
+    fn test_publish_frontmatter_inserts_date_after_title() {
+        let raw = "---\ntitle: Hello\ndraft: true\n---\nBody\n";
+        let out = publish_frontmatter(raw, Some(date("2025-06-01"))).unwrap();
+        assert_eq!(out, "---\ntitle: Hello\ndate: 2025-06-01\n---\nBody\n");
+    }
+
+    #[test]
+    fn test_publish_frontmatter_without_date() {
+        let raw = "---\ntitle: About\ndraft: true\n---\nBody\n";
+        let out = publish_frontmatter(raw, None).unwrap();
+        assert_eq!(out, "---\ntitle: About\n---\nBody\n");
+        assert!(publish_frontmatter("No frontmatter", None).is_none());
+    }
+
+    #[test]
+    fn test_redate_file_name() {
+        let new = date("2025-06-01");
+        assert_eq!(
+            redate_file_name(Path::new("content/posts/2024-01-01-hello.es.md"), new),
+            Some(PathBuf::from("content/posts/2025-06-01-hello.es.md"))
+        );
+        assert_eq!(
+            redate_file_name(Path::new("content/posts/2024-01-01-trip"), new),
+            Some(PathBuf::from("content/posts/2025-06-01-trip"))
+        );
+        assert_eq!(
+            redate_file_name(Path::new("content/pages/about.md"), new),
+            None
+        );
+        assert_eq!(redate_file_name(Path::new("2024-99-01-bad.md"), new), None);
+    }
+}
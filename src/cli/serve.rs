@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::io::{self, BufRead, Write};
 use std::path::PathBuf;
 
@@ -37,7 +38,9 @@ pub fn run(args: &ServeArgs, site_filter: Option<&str>) -> anyhow::Result<()> {
             human::info("Building workspace...");
             let build_opts = workspace::build::WorkspaceBuildOptions {
                 include_drafts: true,
+                as_of: None,
                 strict: false,
+                incremental: true,
                 site_filter: site_filter.map(String::from),
             };
             workspace::build::build_workspace(&ws_config, &ws_root, &build_opts)?;
@@ -51,15 +54,20 @@ pub fn run(args: &ServeArgs, site_filter: Option<&str>) -> anyhow::Result<()> {
             let ws_site = ws_config
                 .find_site(site_name)
                 .ok_or_else(|| anyhow::anyhow!("unknown site '{site_name}' in workspace"))?;
-            let (config, paths) = workspace::load_site_in_workspace(&ws_root, ws_site)?;
-            let handle = server::start(&config, &paths, port, true, auto_increment)?;
+            let (config, paths) = workspace::load_site_in_workspace(&ws_config, &ws_root, ws_site)?;
+            let load_config: server::ConfigLoader = {
+                let (ws_config, ws_root, ws_site) =
+                    (ws_config.clone(), ws_root.clone(), ws_site.clone());
+                Box::new(move || workspace::load_site_in_workspace(&ws_config, &ws_root, &ws_site))
+            };
+            let handle = server::start(&config, &paths, load_config, port, true, auto_increment)?;
 
             human::info(&format!(
                 "Serving site '{site_name}'. Type \"help\" for commands, \"stop\" to quit (port {})",
                 handle.port()
             ));
 
-            run_repl(&config, &paths, &handle)?;
+            run_repl(&handle)?;
             return Ok(());
         }
 
@@ -126,6 +134,9 @@ pub fn run(args: &ServeArgs, site_filter: Option<&str>) -> anyhow::Result<()> {
         human::info("Building site...");
         let opts = BuildOptions {
             include_drafts: true,
+            as_of: None,
+            incremental: true,
+            cross_site_urls: HashMap::new(),
         };
         let result = build::build_site(&config, &paths, &opts)?;
         human::success(&result.stats.human_display());
@@ -133,23 +144,27 @@ pub fn run(args: &ServeArgs, site_filter: Option<&str>) -> anyhow::Result<()> {
 
     let port = args.port.unwrap_or(DEFAULT_PORT);
     let auto_increment = args.port.is_none();
-    let handle = server::start(&config, &paths, port, true, auto_increment)?;
+    let load_config: server::ConfigLoader = {
+        let root = cwd.clone();
+        Box::new(move || {
+            let config = SiteConfig::load(&root.join("seite.toml"))?;
+            let paths = config.resolve_paths(&root);
+            Ok((config, paths))
+        })
+    };
+    let handle = server::start(&config, &paths, load_config, port, true, auto_increment)?;
 
     human::info(&format!(
         "Type \"help\" for commands, \"stop\" to quit (server on port {})",
         handle.port()
     ));
 
-    run_repl(&config, &paths, &handle)?;
+    run_repl(&handle)?;
 
     Ok(())
 }
 
-fn run_repl(
-    config: &SiteConfig,
-    paths: &crate::config::ResolvedPaths,
-    handle: &server::ServerHandle,
-) -> anyhow::Result<()> {
+fn run_repl(handle: &server::ServerHandle) -> anyhow::Result<()> {
     let stdin = io::stdin();
     let reader = stdin.lock();
     print_prompt();
@@ -165,7 +180,9 @@ fn run_repl(
             continue;
         }
 
-        match dispatch(&line, config, paths) {
+        // Picks up `seite.toml` changes reloaded by the server
+        let site = handle.site();
+        match dispatch(&line, &site.config, &site.paths) {
             LoopAction::Continue => {}
             LoopAction::Stop => {
                 handle.stop();
@@ -212,7 +229,12 @@ fn dispatch(line: &str, config: &SiteConfig, paths: &crate::config::ResolvedPath
 
         "build" => {
             let include_drafts = args.iter().any(|a| a == "--drafts");
-            let opts = BuildOptions { include_drafts };
+            let opts = BuildOptions {
+                include_drafts,
+                as_of: None,
+                incremental: true,
+                cross_site_urls: HashMap::new(),
+            };
             match build::build_site(config, paths, &opts) {
                 Ok(result) => human::success(&result.stats.human_display()),
                 Err(e) => human::error(&format!("Build failed: {e}")),
@@ -292,6 +314,9 @@ fn dispatch(line: &str, config: &SiteConfig, paths: &crate::config::ResolvedPath
                                 human::info("Rebuilding site...");
                                 let opts = BuildOptions {
                                     include_drafts: true,
+                                    as_of: None,
+                                    incremental: true,
+                                    cross_site_urls: HashMap::new(),
                                 };
                                 match build::build_site(config, paths, &opts) {
                                     Ok(result) => human::success(&result.stats.human_display()),
@@ -375,7 +400,7 @@ fn cmd_new(
 
     let slug = content::slug_from_title(title);
     let date = if collection.has_date {
-        Some(chrono::Local::now().date_naive())
+        Some(chrono::Local::now().date_naive().into())
     } else {
         None
     };
@@ -133,6 +133,11 @@ const fn upgrade_steps() -> &'static [UpgradeStep] {
             label: ".gitignore dist-subdomains/ entry",
             check: check_gitignore_dist_subdomains,
         },
+        UpgradeStep {
+            introduced_in: (0, 4, 5),
+            label: ".gitignore .seite/cache/ entry (incremental build cache)",
+            check: check_gitignore_build_cache,
+        },
     ]
 }
 
@@ -796,6 +801,29 @@ fn check_gitignore_dist_subdomains(root: &Path) -> Vec<UpgradeAction> {
     }]
 }
 
+/// Ensure .gitignore excludes the incremental build cache in .seite/cache/.
+fn check_gitignore_build_cache(root: &Path) -> Vec<UpgradeAction> {
+    let path = root.join(".gitignore");
+    if !path.exists() {
+        return vec![];
+    }
+
+    let content = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(_) => return vec![],
+    };
+
+    if content.contains(".seite/cache") {
+        return vec![];
+    }
+
+    vec![UpgradeAction::Append {
+        path,
+        content: "\n/.seite/cache\n".to_string(),
+        description: ".gitignore (added .seite/cache/)".into(),
+    }]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
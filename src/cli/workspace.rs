@@ -100,6 +100,7 @@ name = "{name}"
 # unified_sitemap = true
 # cross_site_links = true
 # unified_search = false
+# output_dir = "dist"
 "#
     );
     fs::write(&ws_file, config_content)?;
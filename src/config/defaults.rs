@@ -38,6 +38,22 @@ pub fn image_quality() -> u8 {
     80
 }
 
+pub fn og_background_color() -> String {
+    "#111827".to_string()
+}
+
+pub fn og_text_color() -> String {
+    "#ffffff".to_string()
+}
+
+pub fn search_prefix_length() -> usize {
+    2
+}
+
+pub fn related_limit() -> usize {
+    5
+}
+
 pub fn avif_quality() -> u8 {
     70
 }
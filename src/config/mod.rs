@@ -19,12 +19,27 @@ pub struct SiteConfig {
     pub languages: BTreeMap<String, LanguageConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub images: Option<ImageSection>,
+    /// Generated Open Graph cards for pages without an `image`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub og: Option<OgSection>,
+    /// Full-text search index, sharded under `/search/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<SearchSection>,
+    /// Related pages ranked by shared terms and text similarity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub related: Option<RelatedSection>,
+    /// Feed content and per-tag feeds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feed: Option<FeedSection>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub analytics: Option<AnalyticsSection>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub trust: Option<TrustSection>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub contact: Option<ContactSection>,
+    /// Custom taxonomies such as categories or series, declared as `[[taxonomies]]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub taxonomies: Vec<TaxonomyConfig>,
 }
 
 /// Per-language overrides for site metadata.
@@ -45,6 +60,9 @@ pub struct CollectionConfig {
     pub has_date: bool,
     #[serde(default)]
     pub has_rss: bool,
+    /// Feed formats a `has_rss` collection's items appear in. Default: `["rss"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feeds: Vec<FeedFormat>,
     #[serde(default)]
     pub listed: bool,
     #[serde(default)]
@@ -70,6 +88,106 @@ pub struct CollectionConfig {
     /// Only used when `subdomain` is set. Falls back to the global `deploy.project`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub deploy_project: Option<String>,
+    /// Typed schema for custom frontmatter fields (the `extra` map), keyed by
+    /// field name. Declared as `[collections.fields]`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, FieldSchema>,
+    /// Build items from the entries of a data file instead of markdown files.
+    /// Value is the data key of a list, e.g. `"integrations"` for
+    /// `data/integrations.yaml` or `"catalogue/integrations"` for a nested file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    /// Entry field holding each item's slug. Default: `"slug"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug_field: Option<String>,
+    /// Entry field holding each item's title. Default: `"title"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_field: Option<String>,
+    /// Entry field holding each item's markdown body. Without it, items have
+    /// an empty body and templates render from `page.extra`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_field: Option<String>,
+    /// URL pattern for the collection's items, e.g. `"/:year/:month/:slug"`.
+    /// Tokens: `:year`, `:month`, `:day`, `:slug`, `:section`, `:lang` and any
+    /// frontmatter field. Default: `{url_prefix}/{slug}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permalink: Option<String>,
+}
+
+/// Syndication format of a feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedFormat {
+    /// RSS 2.0, written to `feed.xml`.
+    Rss,
+    /// Atom 1.0, written to `atom.xml`.
+    Atom,
+    /// JSON Feed 1.1, written to `feed.json`.
+    Json,
+}
+
+impl FeedFormat {
+    pub const ALL: [FeedFormat; 3] = [FeedFormat::Rss, FeedFormat::Atom, FeedFormat::Json];
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "feed.xml",
+            FeedFormat::Atom => "atom.xml",
+            FeedFormat::Json => "feed.json",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml",
+            FeedFormat::Atom => "application/atom+xml",
+            FeedFormat::Json => "application/feed+json",
+        }
+    }
+}
+
+/// Value type of a custom frontmatter field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    #[serde(alias = "int")]
+    Integer,
+    #[serde(alias = "number")]
+    Float,
+    #[serde(alias = "boolean")]
+    Bool,
+    /// `YYYY-MM-DD` string.
+    Date,
+    /// List of strings.
+    List,
+}
+
+/// Schema of one custom frontmatter field:
+///
+/// ```toml
+/// [collections.fields]
+/// version = { type = "string", required = true, pattern = '^\d+\.\d+\.\d+$' }
+/// type = { type = "string", enum = ["added", "changed", "fixed"] }
+/// breaking = { type = "bool", default = false }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldSchema {
+    #[serde(rename = "type")]
+    pub field_type: FieldType,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub required: bool,
+    /// Allowed values for a string field, or for each item of a list field.
+    #[serde(default, rename = "enum", skip_serializing_if = "Vec::is_empty")]
+    pub allowed: Vec<String>,
+    /// Value used when the field is missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    /// Regular expression a string field must match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 impl CollectionConfig {
@@ -80,6 +198,7 @@ impl CollectionConfig {
             directory: "posts".into(),
             has_date: true,
             has_rss: true,
+            feeds: Vec::new(),
             listed: true,
             url_prefix: "/posts".into(),
             nested: false,
@@ -88,6 +207,12 @@ impl CollectionConfig {
             subdomain: None,
             subdomain_base_url: None,
             deploy_project: None,
+            fields: BTreeMap::new(),
+            data: None,
+            slug_field: None,
+            title_field: None,
+            body_field: None,
+            permalink: None,
         }
     }
 
@@ -98,6 +223,7 @@ impl CollectionConfig {
             directory: "docs".into(),
             has_date: false,
             has_rss: false,
+            feeds: Vec::new(),
             listed: true,
             url_prefix: "/docs".into(),
             nested: true,
@@ -106,6 +232,12 @@ impl CollectionConfig {
             subdomain: None,
             subdomain_base_url: None,
             deploy_project: None,
+            fields: BTreeMap::new(),
+            data: None,
+            slug_field: None,
+            title_field: None,
+            body_field: None,
+            permalink: None,
         }
     }
 
@@ -116,6 +248,7 @@ impl CollectionConfig {
             directory: "pages".into(),
             has_date: false,
             has_rss: false,
+            feeds: Vec::new(),
             listed: false,
             url_prefix: "".into(),
             nested: false,
@@ -124,6 +257,12 @@ impl CollectionConfig {
             subdomain: None,
             subdomain_base_url: None,
             deploy_project: None,
+            fields: BTreeMap::new(),
+            data: None,
+            slug_field: None,
+            title_field: None,
+            body_field: None,
+            permalink: None,
         }
     }
 
@@ -134,6 +273,7 @@ impl CollectionConfig {
             directory: "changelog".into(),
             has_date: true,
             has_rss: true,
+            feeds: Vec::new(),
             listed: true,
             url_prefix: "/changelog".into(),
             nested: false,
@@ -142,6 +282,12 @@ impl CollectionConfig {
             subdomain: None,
             subdomain_base_url: None,
             deploy_project: None,
+            fields: BTreeMap::new(),
+            data: None,
+            slug_field: None,
+            title_field: None,
+            body_field: None,
+            permalink: None,
         }
     }
 
@@ -152,6 +298,7 @@ impl CollectionConfig {
             directory: "roadmap".into(),
             has_date: false,
             has_rss: false,
+            feeds: Vec::new(),
             listed: true,
             url_prefix: "/roadmap".into(),
             nested: false,
@@ -160,6 +307,12 @@ impl CollectionConfig {
             subdomain: None,
             subdomain_base_url: None,
             deploy_project: None,
+            fields: BTreeMap::new(),
+            data: None,
+            slug_field: None,
+            title_field: None,
+            body_field: None,
+            permalink: None,
         }
     }
 
@@ -170,6 +323,7 @@ impl CollectionConfig {
             directory: "trust".into(),
             has_date: false,
             has_rss: false,
+            feeds: Vec::new(),
             listed: true,
             url_prefix: "/trust".into(),
             nested: true,
@@ -178,6 +332,23 @@ impl CollectionConfig {
             subdomain: None,
             subdomain_base_url: None,
             deploy_project: None,
+            fields: BTreeMap::new(),
+            data: None,
+            slug_field: None,
+            title_field: None,
+            body_field: None,
+            permalink: None,
+        }
+    }
+
+    /// Feed formats this collection's items appear in; none unless `has_rss`.
+    pub fn feed_formats(&self) -> &[FeedFormat] {
+        if !self.has_rss {
+            &[]
+        } else if self.feeds.is_empty() {
+            &[FeedFormat::Rss]
+        } else {
+            &self.feeds
         }
     }
 
@@ -194,6 +365,68 @@ impl CollectionConfig {
     }
 }
 
+/// A custom taxonomy. Items list their terms under a frontmatter key of the
+/// same name, e.g. `categories: [rust, web]`:
+///
+/// ```toml
+/// [[taxonomies]]
+/// name = "categories"
+/// paginate = 10
+/// feed = true
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxonomyConfig {
+    pub name: String,
+    /// Display name. Default: the name, capitalized.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// URL prefix of the term pages. Default: `/{name}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url_prefix: Option<String>,
+    /// Template listing all terms. Default: `taxonomy.html`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Template listing the items of one term. Default: `taxonomy-term.html`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub term_template: Option<String>,
+    /// Number of items per term page. None means no pagination.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paginate: Option<usize>,
+    /// Write an RSS feed per term at `{url_prefix}/{term}/feed.xml`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub feed: bool,
+}
+
+impl TaxonomyConfig {
+    pub fn label(&self) -> String {
+        self.label.clone().unwrap_or_else(|| {
+            let mut chars = self.name.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+    }
+
+    /// URL prefix without surrounding slashes, e.g. `categories`.
+    pub fn url_prefix(&self) -> &str {
+        self.url_prefix
+            .as_deref()
+            .unwrap_or(&self.name)
+            .trim_matches('/')
+    }
+
+    pub fn template(&self) -> &str {
+        self.template.as_deref().unwrap_or("taxonomy.html")
+    }
+
+    pub fn term_template(&self) -> &str {
+        self.term_template
+            .as_deref()
+            .unwrap_or("taxonomy-term.html")
+    }
+}
+
 /// Find a collection by name, supporting singular→plural normalization.
 pub fn find_collection<'a>(
     name: &str,
@@ -219,6 +452,10 @@ pub struct SiteSection {
     pub language: String,
     #[serde(default)]
     pub author: String,
+    /// IANA timezone (e.g. `"Europe/Berlin"`) for date-only frontmatter
+    /// values and the `date` template filter. Defaults to UTC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -244,6 +481,41 @@ pub struct BuildSection {
     /// Enable math/LaTeX rendering ($inline$ and $$display$$ blocks). Default: false.
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub math: bool,
+    /// How page URLs map to output files. Default: `file`.
+    #[serde(default, skip_serializing_if = "UrlStyle::is_file")]
+    pub url_style: UrlStyle,
+}
+
+/// How page URLs map to output files.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UrlStyle {
+    /// `/posts/hello` written as `posts/hello.html`, for hosts that serve
+    /// clean URLs from `.html` files.
+    #[default]
+    File,
+    /// `/posts/hello/` written as `posts/hello/index.html`, for hosts that
+    /// only serve directory indexes (S3 website hosting, plain nginx).
+    Directory,
+}
+
+impl UrlStyle {
+    fn is_file(&self) -> bool {
+        *self == UrlStyle::File
+    }
+
+    /// The URL of a content page in this style, from its clean form
+    /// (`/posts/hello`). A directory-style `index` page is its directory.
+    pub fn page_url(self, url: String) -> String {
+        match self {
+            UrlStyle::File => url,
+            UrlStyle::Directory => match url.strip_suffix("index") {
+                Some(dir) if dir.ends_with('/') => dir.to_string(),
+                _ if url.ends_with('/') => url,
+                _ => format!("{url}/"),
+            },
+        }
+    }
 }
 
 impl Default for BuildSection {
@@ -258,6 +530,7 @@ impl Default for BuildSection {
             minify: false,
             fingerprint: false,
             math: false,
+            url_style: UrlStyle::File,
         }
     }
 }
@@ -318,6 +591,79 @@ pub struct ImageSection {
     /// AVIF quality (1-100). Default: 70 (AVIF compresses better than WebP, so lower is OK).
     #[serde(default = "defaults::avif_quality")]
     pub avif_quality: u8,
+    /// What processed `<img>` tags show while loading: "none", "color" or "blur". Default: "none".
+    #[serde(default)]
+    pub placeholder: ImagePlaceholder,
+}
+
+/// Social cards: a 1200×630 PNG per page without an `image`, written to
+/// `/og/<url>.png` and used as the page's `image`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OgSection {
+    /// Background image in `static/`, e.g. "/static/og-background.png". Scaled
+    /// to cover the card. Default: a plain `background_color` fill.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    /// Logo in `static/`, drawn next to the collection label.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo: Option<String>,
+    /// Fill colour behind the text, `#rrggbb`. Default: "#111827".
+    #[serde(default = "defaults::og_background_color")]
+    pub background_color: String,
+    /// Text colour, `#rrggbb`. Default: "#ffffff".
+    #[serde(default = "defaults::og_text_color")]
+    pub text_color: String,
+}
+
+/// What feeds carry, beyond the per-collection `feeds` formats.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeedSection {
+    /// Put each item's full rendered HTML in the feed, with absolute URLs,
+    /// instead of only its description. Default: false.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub full_content: bool,
+    /// Write feeds per tag at `/tags/{tag}/`, linked from the tag pages.
+    /// Default: false.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tags: bool,
+}
+
+/// Full-text search: every page of a listed collection is split into
+/// sections at its headings and indexed with BM25 weights. The index is
+/// written to `/search/`, sharded by term prefix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchSection {
+    /// Leading characters of a word that pick its shard (1-4). Longer prefixes
+    /// mean more, smaller shards. Default: 2.
+    #[serde(default = "defaults::search_prefix_length")]
+    pub prefix_length: usize,
+    /// Reduce words to their stem in the page language, so "running" finds
+    /// "runs". Default: true.
+    #[serde(default = "defaults::bool_true")]
+    pub stemming: bool,
+}
+
+/// Ranked related content: each page's `page.related` is filled up with the
+/// pages sharing the most tags, taxonomy terms and words with it. Without
+/// this section only the explicit `related` frontmatter entries are listed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedSection {
+    /// Maximum number of pages in `page.related`; explicit entries beyond it
+    /// are still listed. Default: 5.
+    #[serde(default = "defaults::related_limit")]
+    pub limit: usize,
+}
+
+/// Inline loading placeholder for processed images.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImagePlaceholder {
+    #[default]
+    None,
+    /// The image's dominant colour as a background colour.
+    Color,
+    /// A tiny blurred preview over the dominant colour.
+    Blur,
 }
 
 impl Default for ImageSection {
@@ -329,6 +675,7 @@ impl Default for ImageSection {
             webp: true,
             avif: false,
             avif_quality: defaults::avif_quality(),
+            placeholder: ImagePlaceholder::None,
         }
     }
 }
@@ -417,6 +764,17 @@ pub struct ResolvedPaths {
     pub static_dir: PathBuf,
     pub data_dir: PathBuf,
     pub public_dir: PathBuf,
+    /// Workspace-level shared directories (only set inside a workspace).
+    pub shared: SharedPaths,
+}
+
+/// Directories shared by every site in a workspace. Site-local files always
+/// take precedence over shared ones.
+#[derive(Clone, Default)]
+pub struct SharedPaths {
+    pub data_dir: Option<PathBuf>,
+    pub static_dir: Option<PathBuf>,
+    pub templates: Option<PathBuf>,
 }
 
 impl ResolvedPaths {
@@ -485,6 +843,13 @@ impl SiteConfig {
                 message: e.to_string(),
             })?;
         config.validate_subdomains()?;
+        config.validate_fields()?;
+        config.validate_data_sources()?;
+        config.validate_permalinks()?;
+        config.validate_taxonomies()?;
+        config.validate_og()?;
+        config.validate_search()?;
+        config.validate_timezone()?;
         Ok(config)
     }
 
@@ -529,6 +894,198 @@ impl SiteConfig {
         Ok(())
     }
 
+    /// Validate `[collections.fields]` schemas: patterns must compile, enums
+    /// only apply to string and list fields, and defaults must match the schema.
+    fn validate_fields(&self) -> Result<()> {
+        for c in &self.collections {
+            let schema = crate::content::schema::Schema::new(&c.fields).map_err(|message| {
+                PageError::ConfigInvalid {
+                    message: format!("collection '{}': {message}", c.name),
+                }
+            })?;
+            for (name, field) in &c.fields {
+                if !field.allowed.is_empty()
+                    && !matches!(field.field_type, FieldType::String | FieldType::List)
+                {
+                    return Err(PageError::ConfigInvalid {
+                        message: format!(
+                            "collection '{}': field '{name}' has an enum but is not a string or list",
+                            c.name
+                        ),
+                    });
+                }
+                if let Some(default) = &field.default {
+                    let value = serde_yaml_ng::to_value(default).unwrap_or_default();
+                    if let Some(problem) = schema.check(name, field, &value) {
+                        return Err(PageError::ConfigInvalid {
+                            message: format!("collection '{}': default of {problem}", c.name),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Collection `permalink` patterns must parse. A trailing slash needs
+    /// directory-style output: file-style pages would not answer at `…/slug/`.
+    fn validate_permalinks(&self) -> Result<()> {
+        for c in &self.collections {
+            if let Some(pattern) = &c.permalink {
+                let invalid = |message: String| PageError::ConfigInvalid {
+                    message: format!("collection '{}': {message}", c.name),
+                };
+                crate::build::permalinks::Permalink::parse(pattern).map_err(invalid)?;
+                if pattern.len() > 1 && pattern.ends_with('/') && self.build.url_style.is_file() {
+                    return Err(invalid(format!(
+                        "permalink '{pattern}' ends with '/', but pages are written as \
+                         {{url}}.html; set url_style = \"directory\" under [build] to write \
+                         {{url}}/index.html, or drop the trailing slash"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Entry field options only apply to data-backed collections.
+    fn validate_data_sources(&self) -> Result<()> {
+        for c in &self.collections {
+            if c.data.is_none()
+                && (c.slug_field.is_some() || c.title_field.is_some() || c.body_field.is_some())
+            {
+                return Err(PageError::ConfigInvalid {
+                    message: format!(
+                        "collection '{}': slug_field, title_field and body_field require data to be set",
+                        c.name
+                    ),
+                });
+            }
+            if c.data.is_some() && c.nested {
+                return Err(PageError::ConfigInvalid {
+                    message: format!(
+                        "collection '{}': a data-backed collection cannot be nested",
+                        c.name
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Taxonomy names must be plain frontmatter keys that don't clash with a
+    /// standard field, and each taxonomy needs its own URL prefix.
+    fn validate_taxonomies(&self) -> Result<()> {
+        let mut prefixes = std::collections::HashSet::new();
+        for (i, t) in self.taxonomies.iter().enumerate() {
+            let invalid = |message: String| PageError::ConfigInvalid {
+                message: format!("taxonomy '{}': {message}", t.name),
+            };
+            if t.name.is_empty()
+                || !t
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            {
+                return Err(invalid(
+                    "name must only contain lowercase letters, digits and underscores".into(),
+                ));
+            }
+            if crate::content::FRONTMATTER_KEYS.contains(&t.name.as_str()) || t.name == "extra" {
+                return Err(invalid(format!(
+                    "name clashes with the standard frontmatter field `{}`",
+                    t.name
+                )));
+            }
+            if self.taxonomies[..i].iter().any(|o| o.name == t.name) {
+                return Err(invalid("declared more than once".into()));
+            }
+            let prefix = t.url_prefix();
+            if prefix.is_empty()
+                || prefix == "tags"
+                || prefix == "authors"
+                || !prefixes.insert(prefix)
+            {
+                return Err(invalid(format!(
+                    "url_prefix '/{prefix}' is empty or already in use"
+                )));
+            }
+            if t.paginate == Some(0) {
+                return Err(invalid("paginate must be at least 1".into()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Social card colours must be `#rrggbb`.
+    fn validate_og(&self) -> Result<()> {
+        let Some(og) = &self.og else {
+            return Ok(());
+        };
+        for (name, value) in [
+            ("background_color", &og.background_color),
+            ("text_color", &og.text_color),
+        ] {
+            let valid = value.len() == 7
+                && value.starts_with('#')
+                && value[1..].chars().all(|c| c.is_ascii_hexdigit());
+            if !valid {
+                return Err(PageError::ConfigInvalid {
+                    message: format!("[og] {name} '{value}' must be a #rrggbb colour"),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The search shard prefix must be 1-4 characters.
+    fn validate_search(&self) -> Result<()> {
+        match &self.search {
+            Some(search) if !(1..=4).contains(&search.prefix_length) => {
+                Err(PageError::ConfigInvalid {
+                    message: format!(
+                        "[search] prefix_length {} must be between 1 and 4",
+                        search.prefix_length
+                    ),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// `site.timezone` must be an IANA timezone name.
+    fn validate_timezone(&self) -> Result<()> {
+        match &self.site.timezone {
+            Some(tz) if tz.parse::<chrono_tz::Tz>().is_err() => Err(PageError::ConfigInvalid {
+                message: format!(
+                    "site.timezone '{tz}' is not an IANA timezone name (e.g. \"Europe/Berlin\")"
+                ),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// The site's timezone (`site.timezone`), UTC when unset.
+    pub fn timezone(&self) -> chrono_tz::Tz {
+        self.site
+            .timezone
+            .as_deref()
+            .and_then(|tz| tz.parse().ok())
+            .unwrap_or(chrono_tz::UTC)
+    }
+
+    /// Feed formats any collection asks for, in [`FeedFormat::ALL`] order.
+    pub fn feed_formats(&self) -> Vec<FeedFormat> {
+        FeedFormat::ALL
+            .into_iter()
+            .filter(|f| {
+                self.collections
+                    .iter()
+                    .any(|c| c.feed_formats().contains(f))
+            })
+            .collect()
+    }
+
     /// Returns collections that have a subdomain configured.
     pub fn subdomain_collections(&self) -> Vec<&CollectionConfig> {
         self.collections
@@ -672,6 +1229,7 @@ impl SiteConfig {
             static_dir: project_root.join(&self.build.static_dir),
             data_dir: project_root.join(&self.build.data_dir),
             public_dir: project_root.join(&self.build.public_dir),
+            shared: SharedPaths::default(),
         }
     }
 }
@@ -688,6 +1246,7 @@ mod tests {
                 base_url: base_url.into(),
                 language: "en".into(),
                 author: "".into(),
+                timezone: None,
             },
             collections,
             build: BuildSection::default(),
@@ -697,6 +1256,11 @@ mod tests {
             analytics: None,
             trust: None,
             contact: None,
+            og: None,
+            search: None,
+            related: None,
+            feed: None,
+            taxonomies: Vec::new(),
         }
     }
 
@@ -846,6 +1410,179 @@ mod tests {
         assert!(config.validate_subdomains().is_ok());
     }
 
+    #[test]
+    fn test_validate_fields() {
+        let field = |toml: &str| -> FieldSchema { toml::from_str(toml).unwrap() };
+        let mut posts = posts_collection();
+        posts.fields.insert(
+            "kind".into(),
+            field("type = \"string\"\nenum = [\"a\", \"b\"]\ndefault = \"a\""),
+        );
+        let config = make_config("https://example.com", vec![posts.clone()]);
+        assert!(config.validate_fields().is_ok());
+
+        posts.fields.insert(
+            "featured".into(),
+            field("type = \"bool\"\ndefault = \"yes\""),
+        );
+        let config = make_config("https://example.com", vec![posts.clone()]);
+        let err = config.validate_fields().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("default of field `featured` must be a bool"));
+
+        posts.fields.remove("featured");
+        posts
+            .fields
+            .insert("rank".into(), field("type = \"int\"\nenum = [\"1\"]"));
+        let config = make_config("https://example.com", vec![posts]);
+        let err = config.validate_fields().unwrap_err();
+        assert!(err.to_string().contains("has an enum but is not a string"));
+    }
+
+    #[test]
+    fn test_url_style_page_url() {
+        assert_eq!(
+            UrlStyle::File.page_url("/posts/hello".into()),
+            "/posts/hello"
+        );
+        let dir = UrlStyle::Directory;
+        assert_eq!(dir.page_url("/posts/hello".into()), "/posts/hello/");
+        assert_eq!(dir.page_url("/index".into()), "/");
+        assert_eq!(dir.page_url("/es/docs/index".into()), "/es/docs/");
+        assert_eq!(dir.page_url("/docs/reindex".into()), "/docs/reindex/");
+        let config: SiteConfig = toml::from_str(
+            "[site]\ntitle = \"T\"\n[[collections]]\nname = \"posts\"\nlabel = \"Posts\"\ndirectory = \"posts\"\ndefault_template = \"post.html\"\n[build]\nurl_style = \"directory\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.build.url_style, UrlStyle::Directory);
+    }
+
+    #[test]
+    fn test_validate_permalinks() {
+        let mut posts = posts_collection();
+        posts.permalink = Some("/:year/:month/:slug".into());
+        let config = make_config("https://example.com", vec![posts.clone()]);
+        assert!(config.validate_permalinks().is_ok());
+
+        posts.permalink = Some(":year/:slug".into());
+        let config = make_config("https://example.com", vec![posts.clone()]);
+        let err = config.validate_permalinks().unwrap_err();
+        assert!(err.to_string().contains("collection 'posts'"));
+        assert!(err.to_string().contains("must start with '/'"));
+
+        posts.permalink = Some("/:year/:month/:slug/".into());
+        let mut config = make_config("https://example.com", vec![posts]);
+        let err = config.validate_permalinks().unwrap_err();
+        assert!(err.to_string().contains("ends with '/'"));
+        assert!(err.to_string().contains("url_style = \"directory\""));
+        config.build.url_style = UrlStyle::Directory;
+        assert!(config.validate_permalinks().is_ok());
+    }
+
+    #[test]
+    fn test_validate_data_sources() {
+        let mut posts = posts_collection();
+        posts.body_field = Some("details".into());
+        let config = make_config("https://example.com", vec![posts.clone()]);
+        let err = config.validate_data_sources().unwrap_err();
+        assert!(err.to_string().contains("require data to be set"));
+
+        posts.data = Some("integrations".into());
+        let config = make_config("https://example.com", vec![posts.clone()]);
+        assert!(config.validate_data_sources().is_ok());
+
+        posts.nested = true;
+        let config = make_config("https://example.com", vec![posts]);
+        let err = config.validate_data_sources().unwrap_err();
+        assert!(err.to_string().contains("cannot be nested"));
+    }
+
+    #[test]
+    fn test_validate_taxonomies() {
+        let taxonomy = |toml: &str| -> TaxonomyConfig { toml::from_str(toml).unwrap() };
+        let mut config = make_config("https://example.com", vec![posts_collection()]);
+        config.taxonomies = vec![
+            taxonomy("name = \"categories\""),
+            taxonomy("name = \"series\"\nurl_prefix = \"/in-series/\""),
+        ];
+        assert!(config.validate_taxonomies().is_ok());
+        assert_eq!(config.taxonomies[0].label(), "Categories");
+        assert_eq!(config.taxonomies[1].url_prefix(), "in-series");
+
+        config.taxonomies = vec![taxonomy("name = \"tags\"")];
+        let err = config.validate_taxonomies().unwrap_err();
+        assert!(err.to_string().contains("clashes with the standard"));
+
+        config.taxonomies = vec![
+            taxonomy("name = \"categories\""),
+            taxonomy("name = \"series\"\nurl_prefix = \"categories\""),
+        ];
+        let err = config.validate_taxonomies().unwrap_err();
+        assert!(err.to_string().contains("already in use"));
+
+        config.taxonomies = vec![taxonomy("name = \"Series\"")];
+        assert!(config.validate_taxonomies().is_err());
+    }
+
+    #[test]
+    fn test_validate_og() {
+        let mut config = make_config("https://example.com", vec![posts_collection()]);
+        config.og = Some(toml::from_str("logo = \"/static/logo.png\"").unwrap());
+        assert!(config.validate_og().is_ok());
+        assert_eq!(config.og.as_ref().unwrap().background_color, "#111827");
+
+        config.og = Some(toml::from_str("text_color = \"white\"").unwrap());
+        let err = config.validate_og().unwrap_err();
+        assert!(err.to_string().contains("text_color 'white'"));
+    }
+
+    #[test]
+    fn test_validate_search() {
+        let mut config = make_config("https://example.com", vec![posts_collection()]);
+        config.search = Some(toml::from_str("").unwrap());
+        assert!(config.validate_search().is_ok());
+        let search = config.search.as_ref().unwrap();
+        assert_eq!(search.prefix_length, 2);
+        assert!(search.stemming);
+
+        config.search = Some(toml::from_str("prefix_length = 0").unwrap());
+        let err = config.validate_search().unwrap_err();
+        assert!(err.to_string().contains("prefix_length 0"));
+    }
+
+    #[test]
+    fn test_validate_timezone() {
+        let mut config = make_config("https://example.com", vec![posts_collection()]);
+        assert!(config.validate_timezone().is_ok());
+        assert_eq!(config.timezone(), chrono_tz::UTC);
+
+        config.site.timezone = Some("Europe/Berlin".into());
+        assert!(config.validate_timezone().is_ok());
+        assert_eq!(config.timezone(), chrono_tz::Europe::Berlin);
+
+        config.site.timezone = Some("Mars/Olympus".into());
+        let err = config.validate_timezone().unwrap_err();
+        assert!(err.to_string().contains("Mars/Olympus"));
+    }
+
+    #[test]
+    fn test_feed_formats() {
+        let mut posts = posts_collection();
+        assert_eq!(posts.feed_formats(), &[FeedFormat::Rss]);
+        posts.feeds = vec![FeedFormat::Json, FeedFormat::Atom];
+        let mut docs = CollectionConfig::preset_docs();
+        docs.feeds = vec![FeedFormat::Rss];
+        assert!(docs.feed_formats().is_empty());
+
+        let config = make_config("https://example.com", vec![posts, docs]);
+        assert_eq!(
+            config.feed_formats(),
+            vec![FeedFormat::Atom, FeedFormat::Json]
+        );
+        assert_eq!(FeedFormat::Json.file_name(), "feed.json");
+    }
+
     #[test]
     fn test_subdomain_config_deserialization() {
         let toml = r#"
@@ -882,6 +1619,7 @@ deploy_project = "my-docs"
             static_dir: PathBuf::from("/project/static"),
             data_dir: PathBuf::from("/project/data"),
             public_dir: PathBuf::from("/project/public"),
+            shared: Default::default(),
         };
         assert_eq!(
             paths.subdomain_output("docs"),
@@ -1,7 +1,10 @@
-use std::collections::{HashMap, HashSet};
+pub mod schema;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use chrono::NaiveDate;
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{PageError, Result};
@@ -9,11 +12,12 @@ use crate::error::{PageError, Result};
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Frontmatter {
     pub title: String,
+    /// Publication date, or datetime for exact ordering and feed times.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub date: Option<NaiveDate>,
+    pub date: Option<Timestamp>,
     /// Last-modified date — used in JSON-LD `dateModified` and sitemap `<lastmod>`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub updated: Option<NaiveDate>,
+    pub updated: Option<Timestamp>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Absolute URL or path to a social-preview image (og:image / twitter:image).
@@ -23,8 +27,17 @@ pub struct Frontmatter {
     pub slug: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+    /// Ids of the page's authors in `data/authors.yaml`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
     #[serde(default, skip_serializing_if = "is_false")]
     pub draft: bool,
+    /// Leave the item out of the build until this time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish_at: Option<Timestamp>,
+    /// Leave the item out of the build from this time on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<Timestamp>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub template: Option<String>,
     /// Per-page `<meta name="robots">` value, e.g. `"noindex"` or `"noindex, nofollow"`.
@@ -34,6 +47,20 @@ pub struct Frontmatter {
     /// When unset, items sort after weighted items, alphabetically by title.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub weight: Option<i32>,
+    /// Pages to list first in `page.related`: a slug, `collection/slug` or
+    /// URL path. The rest of the list is computed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<String>,
+    /// Old URL paths that redirect to this page, e.g. `/guides/setup`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Per-page sitemap overrides: `sitemap: { exclude, priority, changefreq }`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sitemap: Option<SitemapOptions>,
+    /// Other top-level keys. Terms of custom taxonomies are read from here,
+    /// e.g. `categories: [rust]`; keys that aren't a taxonomy are ignored.
+    #[serde(flatten, skip_serializing_if = "BTreeMap::is_empty")]
+    pub terms: BTreeMap<String, serde_yaml_ng::Value>,
     /// Arbitrary key-value data passed through to templates as `page.extra`.
     /// Use this for custom per-page data that doesn't fit standard fields.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
@@ -44,10 +71,193 @@ fn is_false(v: &bool) -> bool {
     !v
 }
 
+impl Frontmatter {
+    /// Whether the item is published at `now`: past its `publish_at` and
+    /// before its `expires_at`. Dates start at midnight in `tz`.
+    pub fn is_live_at(&self, now: DateTime<Utc>, tz: Tz) -> bool {
+        let published = self.publish_at.is_none_or(|t| t.start(tz) <= now);
+        let expired = self.expires_at.is_some_and(|t| t.start(tz) <= now);
+        published && !expired
+    }
+
+    /// Sort key for newest-first ordering: `date` as an instant in `tz`.
+    pub fn date_key(&self, tz: Tz) -> Option<DateTime<Utc>> {
+        self.date.map(|d| d.start(tz))
+    }
+}
+
+/// A point in time written in frontmatter or on the command line: a date
+/// (`2025-06-01`, read in the site's timezone) or an RFC 3339 datetime
+/// (`2025-06-01T09:00:00+02:00`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timestamp {
+    Date(NaiveDate),
+    DateTime(DateTime<FixedOffset>),
+}
+
+impl Timestamp {
+    /// When this timestamp begins: a datetime as written, a date at midnight in `tz`.
+    pub fn start(self, tz: Tz) -> DateTime<Utc> {
+        match self {
+            Timestamp::Date(date) => local_time(date, chrono::NaiveTime::MIN, tz).to_utc(),
+            Timestamp::DateTime(dt) => dt.to_utc(),
+        }
+    }
+
+    /// The moment to publish in feeds: a datetime as written, a date at
+    /// 12:00 in `tz` so it falls on the same day for most readers.
+    pub fn instant(self, tz: Tz) -> DateTime<FixedOffset> {
+        match self {
+            Timestamp::Date(date) => {
+                let noon = chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap_or_default();
+                local_time(date, noon, tz)
+            }
+            Timestamp::DateTime(dt) => dt,
+        }
+    }
+
+    /// The calendar date in `tz`; a date-only value is its own date.
+    pub fn date_in(self, tz: Tz) -> NaiveDate {
+        match self {
+            Timestamp::Date(date) => date,
+            Timestamp::DateTime(dt) => dt.with_timezone(&tz).date_naive(),
+        }
+    }
+
+    pub fn date_naive(self) -> NaiveDate {
+        match self {
+            Timestamp::Date(date) => date,
+            Timestamp::DateTime(dt) => dt.date_naive(),
+        }
+    }
+}
+
+impl From<NaiveDate> for Timestamp {
+    fn from(date: NaiveDate) -> Self {
+        Timestamp::Date(date)
+    }
+}
+
+/// `date` at `time` in `tz`. Local times skipped by a DST change fall back to UTC.
+fn local_time(date: NaiveDate, time: chrono::NaiveTime, tz: Tz) -> DateTime<FixedOffset> {
+    let naive = date.and_time(time);
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.fixed_offset())
+        .unwrap_or_else(|| naive.and_utc().fixed_offset())
+}
+
+impl std::str::FromStr for Timestamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+            return Ok(Timestamp::DateTime(dt));
+        }
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map(Timestamp::Date)
+            .map_err(|_| {
+                format!(
+                    "invalid timestamp '{s}': expected a date (2025-06-01) or an RFC 3339 \
+                     datetime with timezone (2025-06-01T09:00:00+02:00)"
+                )
+            })
+    }
+}
+
+impl std::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Timestamp::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            Timestamp::DateTime(dt) => write!(f, "{}", dt.to_rfc3339()),
+        }
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Per-page controls for the page's `sitemap.xml` entry.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct SitemapOptions {
+    /// Leave the page out of the sitemap.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub exclude: bool,
+    /// `<priority>` hint, from 0.0 to 1.0.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_priority",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub priority: Option<f32>,
+    /// `<changefreq>` hint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changefreq: Option<ChangeFreq>,
+}
+
+/// Values of the sitemap `<changefreq>` element.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeFreq {
+    Always,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    Never,
+}
+
+impl ChangeFreq {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeFreq::Always => "always",
+            ChangeFreq::Hourly => "hourly",
+            ChangeFreq::Daily => "daily",
+            ChangeFreq::Weekly => "weekly",
+            ChangeFreq::Monthly => "monthly",
+            ChangeFreq::Yearly => "yearly",
+            ChangeFreq::Never => "never",
+        }
+    }
+}
+
+fn deserialize_priority<'de, D>(deserializer: D) -> std::result::Result<Option<f32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let priority = Option::<f32>::deserialize(deserializer)?;
+    match priority {
+        Some(p) if !(0.0..=1.0).contains(&p) => Err(serde::de::Error::custom(format!(
+            "sitemap priority must be between 0.0 and 1.0, got {p}"
+        ))),
+        _ => Ok(priority),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ContentItem {
     pub frontmatter: Frontmatter,
     pub raw_body: String,
+    /// Body with shortcodes expanded to their markdown renderings, written to
+    /// the `.md` page copies and `llms-full.txt`.
+    pub markdown_body: String,
     pub html_body: String,
     pub source_path: PathBuf,
     pub slug: String,
@@ -81,7 +291,93 @@ pub fn parse_content_file(path: &Path) -> Result<(Frontmatter, String)> {
     Ok((frontmatter, body.to_string()))
 }
 
-fn split_frontmatter(raw: &str) -> Option<(&str, &str)> {
+/// Standard frontmatter keys. Other fields of a data entry go into `extra`.
+pub const FRONTMATTER_KEYS: &[&str] = &[
+    "title",
+    "date",
+    "updated",
+    "description",
+    "image",
+    "slug",
+    "tags",
+    "authors",
+    "draft",
+    "publish_at",
+    "expires_at",
+    "template",
+    "robots",
+    "weight",
+    "aliases",
+    "related",
+    "sitemap",
+];
+
+/// Fields of a data entry that hold an item's slug, title and markdown body.
+pub struct EntryFields<'a> {
+    pub slug: &'a str,
+    pub title: &'a str,
+    pub body: Option<&'a str>,
+}
+
+/// Turn one entry of a data-backed collection into frontmatter and a markdown
+/// body. Standard frontmatter keys are used as-is, the slug is slugified, and
+/// every other field (plus an explicit `extra` map) becomes `page.extra`.
+pub fn parse_data_entry(
+    entry: &serde_json::Value,
+    fields: &EntryFields,
+) -> std::result::Result<(Frontmatter, String), String> {
+    let obj = entry
+        .as_object()
+        .ok_or_else(|| format!("expected a mapping, got {entry}"))?;
+
+    let slug = match obj.get(fields.slug) {
+        Some(serde_json::Value::String(s)) if !s.trim().is_empty() => slug::slugify(s),
+        Some(serde_json::Value::Number(n)) => n.to_string(),
+        _ => return Err(format!("missing `{}` (the slug field)", fields.slug)),
+    };
+    let body = match fields.body.and_then(|f| obj.get(f)) {
+        None | Some(serde_json::Value::Null) => String::new(),
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(other) => {
+            return Err(format!(
+                "`{}` (the body field) must be a string, got {other}",
+                fields.body.unwrap_or_default()
+            ))
+        }
+    };
+
+    let mut fm_obj = serde_json::Map::new();
+    let mut extra = match obj.get("extra") {
+        Some(serde_json::Value::Object(map)) => map.clone(),
+        _ => serde_json::Map::new(),
+    };
+    for (key, value) in obj {
+        if key == fields.slug || Some(key.as_str()) == fields.body || key == "extra" {
+            continue;
+        }
+        let key = if key == fields.title {
+            "title"
+        } else {
+            key.as_str()
+        };
+        if FRONTMATTER_KEYS.contains(&key) {
+            fm_obj.insert(key.to_string(), value.clone());
+        } else {
+            extra.insert(key.to_string(), value.clone());
+        }
+    }
+    if !fm_obj.contains_key("title") {
+        return Err(format!("missing `{}` (the title field)", fields.title));
+    }
+    fm_obj.insert("slug".into(), slug.into());
+    fm_obj.insert("extra".into(), extra.into());
+
+    let fm: Frontmatter =
+        serde_json::from_value(serde_json::Value::Object(fm_obj)).map_err(|e| e.to_string())?;
+    Ok((fm, body))
+}
+
+pub fn split_frontmatter(raw: &str) -> Option<(&str, &str)> {
     let trimmed = raw.trim_start();
     if !trimmed.starts_with("---") {
         return None;
@@ -122,6 +418,16 @@ pub fn extract_lang_from_filename(path: &Path, configured_langs: &HashSet<&str>)
     None
 }
 
+/// Whether `path` is a section's `_index.md` (or a translation such as
+/// `_index.es.md`), which describes a directory rather than being a page.
+pub fn is_section_index(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "md")
+        && path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .is_some_and(|stem| stem == "_index" || stem.starts_with("_index."))
+}
+
 /// Strip a language suffix from a file stem, only if it matches a configured language.
 /// Example: "about.es" → "about" (if "es" is configured)
 /// Example: "2025-01-15-hello.fr" → "2025-01-15-hello" (if "fr" is configured)
@@ -181,8 +487,9 @@ mod tests {
     fn test_generate_and_parse_frontmatter() {
         let fm = Frontmatter {
             title: "Test Post".into(),
-            date: Some(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()),
+            date: Some(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap().into()),
             tags: vec!["rust".into(), "web".into()],
+            authors: Vec::new(),
             draft: false,
             ..Default::default()
         };
@@ -219,6 +526,78 @@ mod tests {
         assert!(generated.contains("draft: true"));
     }
 
+    #[test]
+    fn test_timestamp_parse() {
+        let utc = chrono_tz::UTC;
+        let date: Timestamp = "2025-06-01".parse().unwrap();
+        assert_eq!(date.start(utc).to_rfc3339(), "2025-06-01T00:00:00+00:00");
+        assert_eq!(date.to_string(), "2025-06-01");
+        let dt: Timestamp = "2025-06-01T09:30:00+02:00".parse().unwrap();
+        assert_eq!(dt.start(utc).to_rfc3339(), "2025-06-01T07:30:00+00:00");
+        assert_eq!(dt.to_string(), "2025-06-01T09:30:00+02:00");
+        assert!("2025-06-01 9:30".parse::<Timestamp>().is_err());
+        assert!("next week".parse::<Timestamp>().is_err());
+    }
+
+    #[test]
+    fn test_timestamp_in_timezone() {
+        let berlin = chrono_tz::Europe::Berlin;
+        let date: Timestamp = "2025-06-01".parse().unwrap();
+        assert_eq!(date.start(berlin).to_rfc3339(), "2025-05-31T22:00:00+00:00");
+        assert_eq!(
+            date.instant(berlin).to_rfc3339(),
+            "2025-06-01T12:00:00+02:00"
+        );
+        assert_eq!(
+            date.instant(chrono_tz::UTC).to_rfc2822(),
+            "Sun, 1 Jun 2025 12:00:00 +0000"
+        );
+        // Datetimes keep the offset they were written with
+        let dt: Timestamp = "2025-06-01T09:30:00-05:00".parse().unwrap();
+        assert_eq!(dt.instant(berlin).to_rfc3339(), "2025-06-01T09:30:00-05:00");
+    }
+
+    #[test]
+    fn test_frontmatter_dates_accept_datetimes() {
+        let fm: Frontmatter = serde_yaml_ng::from_str(
+            "title: Release\ndate: 2025-06-01T16:45:00+02:00\nupdated: 2025-06-02",
+        )
+        .unwrap();
+        assert_eq!(
+            fm.date,
+            Some(Timestamp::DateTime(
+                DateTime::parse_from_rfc3339("2025-06-01T16:45:00+02:00").unwrap()
+            ))
+        );
+        assert_eq!(fm.updated.unwrap().to_string(), "2025-06-02");
+
+        // Same-day entries order by time
+        let morning: Frontmatter =
+            serde_yaml_ng::from_str("title: A\ndate: 2025-06-01T09:00:00+02:00").unwrap();
+        let tz = chrono_tz::UTC;
+        assert!(fm.date_key(tz) > morning.date_key(tz));
+        assert!(generate_frontmatter(&fm).contains("date: 2025-06-01T16:45:00+02:00"));
+    }
+
+    #[test]
+    fn test_frontmatter_is_live_at() {
+        let fm: Frontmatter = serde_yaml_ng::from_str(
+            "title: Sale\npublish_at: 2025-06-01T09:00:00Z\nexpires_at: 2025-07-01",
+        )
+        .unwrap();
+        let utc = chrono_tz::UTC;
+        let at = |s: &str| s.parse::<Timestamp>().unwrap().start(utc);
+        assert!(!fm.is_live_at(at("2025-06-01T08:59:59Z"), utc));
+        assert!(fm.is_live_at(at("2025-06-01T09:00:00Z"), utc));
+        assert!(fm.is_live_at(at("2025-06-30T23:59:59Z"), utc));
+        assert!(!fm.is_live_at(at("2025-07-01"), utc));
+        assert!(Frontmatter::default().is_live_at(at("2025-01-01"), utc));
+        // A date expires at midnight in the site's timezone
+        let berlin = chrono_tz::Europe::Berlin;
+        assert!(!fm.is_live_at(at("2025-06-30T22:30:00Z"), berlin));
+        assert!(serde_yaml_ng::from_str::<Frontmatter>("title: X\npublish_at: soon").is_err());
+    }
+
     #[test]
     fn test_extract_lang_from_filename() {
         let langs: HashSet<&str> = ["es", "fr", "de"].into_iter().collect();
@@ -320,16 +699,23 @@ mod tests {
     fn test_generate_frontmatter_with_all_fields() {
         let fm = Frontmatter {
             title: "Full Post".into(),
-            date: Some(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()),
-            updated: Some(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()),
+            date: Some(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap().into()),
+            updated: Some(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap().into()),
             description: Some("A description".into()),
             image: Some("/static/hero.jpg".into()),
             slug: Some("custom-slug".into()),
             tags: vec!["rust".into(), "web".into()],
+            authors: Vec::new(),
             draft: true,
             template: Some("custom.html".into()),
             robots: Some("noindex".into()),
             weight: Some(5),
+            related: Vec::new(),
+            aliases: Vec::new(),
+            sitemap: None,
+            publish_at: None,
+            expires_at: None,
+            terms: Default::default(),
             extra: HashMap::new(),
         };
         let generated = generate_frontmatter(&fm);
@@ -348,6 +734,15 @@ mod tests {
         assert_eq!(extract_lang_from_filename(Path::new("noext"), &langs), None);
     }
 
+    #[test]
+    fn test_is_section_index() {
+        assert!(is_section_index(Path::new("docs/guides/_index.md")));
+        assert!(is_section_index(Path::new("docs/guides/_index.es.md")));
+        assert!(!is_section_index(Path::new("docs/guides/index.md")));
+        assert!(!is_section_index(Path::new("docs/_index-notes.md")));
+        assert!(!is_section_index(Path::new("docs/_index.yaml")));
+    }
+
     #[test]
     fn test_extract_excerpt_more_marker_at_start() {
         assert_eq!(extract_excerpt("<!-- more -->\nrest"), "");
@@ -404,4 +799,50 @@ mod tests {
         // "readme.min" should not strip
         assert_eq!(strip_lang_suffix("readme.min", &langs), "readme.min");
     }
+
+    #[test]
+    fn test_parse_data_entry() {
+        let fields = EntryFields {
+            slug: "id",
+            title: "name",
+            body: Some("details"),
+        };
+        let entry = serde_json::json!({
+            "id": "Stripe Payments",
+            "name": "Stripe",
+            "tags": ["payments"],
+            "logo": "/static/stripe.svg",
+            "details": "Take **payments**.",
+        });
+        let (fm, body) = parse_data_entry(&entry, &fields).unwrap();
+        assert_eq!(fm.title, "Stripe");
+        assert_eq!(fm.slug.as_deref(), Some("stripe-payments"));
+        assert_eq!(fm.tags, vec!["payments"]);
+        assert_eq!(fm.extra["logo"], "/static/stripe.svg");
+        assert!(!fm.extra.contains_key("details"));
+        assert_eq!(body, "Take **payments**.");
+    }
+
+    #[test]
+    fn test_parse_data_entry_errors() {
+        let fields = EntryFields {
+            slug: "code",
+            title: "title",
+            body: None,
+        };
+        let (fm, body) = parse_data_entry(
+            &serde_json::json!({ "code": 404, "title": "Not found" }),
+            &fields,
+        )
+        .unwrap();
+        assert_eq!(fm.slug.as_deref(), Some("404"));
+        assert!(body.is_empty());
+
+        let err = parse_data_entry(&serde_json::json!({ "title": "x" }), &fields).unwrap_err();
+        assert!(err.contains("missing `code`"));
+        let err = parse_data_entry(&serde_json::json!({ "code": "a" }), &fields).unwrap_err();
+        assert!(err.contains("missing `title`"));
+        let err = parse_data_entry(&serde_json::json!(["a"]), &fields).unwrap_err();
+        assert!(err.contains("expected a mapping"));
+    }
 }
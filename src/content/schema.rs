@@ -0,0 +1,343 @@
+//! Validation of custom frontmatter fields against a collection's
+//! `[collections.fields]` schema.
+//!
+//! Schema fields describe keys of the frontmatter `extra` map. Defaults fill
+//! in missing fields before validation; every violation is reported, not just
+//! the first.
+
+use std::collections::{BTreeMap, HashMap};
+
+use regex::Regex;
+use serde_yaml_ng::Value;
+
+use crate::config::{FieldSchema, FieldType};
+
+/// A collection schema with its patterns compiled.
+pub struct Schema<'a> {
+    fields: &'a BTreeMap<String, FieldSchema>,
+    patterns: HashMap<&'a str, Regex>,
+}
+
+impl<'a> Schema<'a> {
+    /// Compile the schema. Fails with a message naming the field whose
+    /// pattern is not a valid regular expression.
+    pub fn new(fields: &'a BTreeMap<String, FieldSchema>) -> Result<Self, String> {
+        let mut patterns = HashMap::new();
+        for (name, field) in fields {
+            if let Some(pattern) = &field.pattern {
+                let re = Regex::new(pattern)
+                    .map_err(|e| format!("field '{name}' has an invalid pattern: {e}"))?;
+                patterns.insert(name.as_str(), re);
+            }
+        }
+        Ok(Self { fields, patterns })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Insert the default of every missing field that has one.
+    pub fn apply_defaults(&self, extra: &mut HashMap<String, Value>) {
+        for (name, field) in self.fields {
+            let Some(default) = &field.default else {
+                continue;
+            };
+            if extra.get(name).is_none_or(Value::is_null) {
+                extra.insert(
+                    name.clone(),
+                    serde_yaml_ng::to_value(default).unwrap_or_default(),
+                );
+            }
+        }
+    }
+
+    /// Check `extra` against the schema and describe every violation.
+    pub fn validate(&self, extra: &HashMap<String, Value>) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (name, field) in self.fields {
+            match extra.get(name) {
+                None | Some(Value::Null) => {
+                    if field.required {
+                        problems.push(format!("missing required field `{name}`"));
+                    }
+                }
+                Some(value) => problems.extend(self.check(name, field, value)),
+            }
+        }
+
+        // Undeclared keys that look like a misspelt field
+        let mut unknown: Vec<&String> = extra
+            .keys()
+            .filter(|k| !self.fields.contains_key(*k))
+            .collect();
+        unknown.sort();
+        for key in unknown {
+            if let Some(suggestion) = self
+                .fields
+                .keys()
+                .find(|name| edit_distance(key, name) <= 2)
+            {
+                problems.push(format!(
+                    "unknown field `{key}` (did you mean `{suggestion}`?)"
+                ));
+            }
+        }
+        problems
+    }
+
+    /// Check one present value against its field schema.
+    pub fn check(&self, name: &str, field: &FieldSchema, value: &Value) -> Option<String> {
+        let got = || serde_json::to_string(value).unwrap_or_default();
+        let type_ok = match field.field_type {
+            FieldType::String => value.is_string(),
+            FieldType::Integer => value.is_i64() || value.is_u64(),
+            FieldType::Float => value.is_number(),
+            FieldType::Bool => value.is_bool(),
+            FieldType::Date => value
+                .as_str()
+                .is_some_and(|s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok()),
+            FieldType::List => value
+                .as_sequence()
+                .is_some_and(|items| items.iter().all(Value::is_string)),
+        };
+        if !type_ok {
+            return Some(format!(
+                "field `{name}` must be {} (got {})",
+                type_description(field.field_type),
+                got()
+            ));
+        }
+
+        if !field.allowed.is_empty() {
+            let values: Vec<&str> = match value {
+                Value::Sequence(items) => items.iter().filter_map(Value::as_str).collect(),
+                _ => value.as_str().into_iter().collect(),
+            };
+            if let Some(bad) = values
+                .iter()
+                .find(|v| !field.allowed.iter().any(|a| a == *v))
+            {
+                return Some(format!(
+                    "field `{name}` must be one of: {} (got \"{bad}\")",
+                    field.allowed.join(", ")
+                ));
+            }
+        }
+
+        if let (Some(re), Some(s)) = (self.patterns.get(name), value.as_str()) {
+            if !re.is_match(s) {
+                return Some(format!(
+                    "field `{name}` does not match pattern `{}` (got {})",
+                    re.as_str(),
+                    got()
+                ));
+            }
+        }
+        None
+    }
+}
+
+/// Starting value for a field in newly created content: its default, else
+/// the first allowed value, else an empty value of its type.
+pub fn placeholder(field: &FieldSchema) -> Value {
+    if let Some(default) = &field.default {
+        return serde_yaml_ng::to_value(default).unwrap_or_default();
+    }
+    if let Some(first) = field.allowed.first() {
+        return match field.field_type {
+            FieldType::List => Value::Sequence(vec![Value::String(first.clone())]),
+            _ => Value::String(first.clone()),
+        };
+    }
+    match field.field_type {
+        FieldType::String => Value::String(String::new()),
+        FieldType::Integer => Value::from(0),
+        FieldType::Float => Value::from(0.0),
+        FieldType::Bool => Value::Bool(false),
+        FieldType::Date => Value::String(chrono::Local::now().format("%Y-%m-%d").to_string()),
+        FieldType::List => Value::Sequence(Vec::new()),
+    }
+}
+
+/// JSON Schema for the `extra` object of a collection, as advertised to MCP clients.
+pub fn json_schema(fields: &BTreeMap<String, FieldSchema>) -> serde_json::Value {
+    let mut properties = serde_json::Map::new();
+    for (name, field) in fields {
+        let mut prop = match field.field_type {
+            FieldType::String => serde_json::json!({ "type": "string" }),
+            FieldType::Integer => serde_json::json!({ "type": "integer" }),
+            FieldType::Float => serde_json::json!({ "type": "number" }),
+            FieldType::Bool => serde_json::json!({ "type": "boolean" }),
+            FieldType::Date => serde_json::json!({ "type": "string", "format": "date" }),
+            FieldType::List => {
+                serde_json::json!({ "type": "array", "items": { "type": "string" } })
+            }
+        };
+        if !field.allowed.is_empty() {
+            let target = if field.field_type == FieldType::List {
+                &mut prop["items"]
+            } else {
+                &mut prop
+            };
+            target["enum"] = serde_json::json!(field.allowed);
+        }
+        if let Some(pattern) = &field.pattern {
+            prop["pattern"] = serde_json::json!(pattern);
+        }
+        if let Some(default) = &field.default {
+            prop["default"] = default.clone();
+        }
+        if let Some(description) = &field.description {
+            prop["description"] = serde_json::json!(description);
+        }
+        properties.insert(name.clone(), prop);
+    }
+
+    let required: Vec<&String> = fields
+        .iter()
+        .filter(|(_, f)| f.required && f.default.is_none())
+        .map(|(name, _)| name)
+        .collect();
+    let mut schema = serde_json::json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = serde_json::json!(required);
+    }
+    schema
+}
+
+fn type_description(field_type: FieldType) -> &'static str {
+    match field_type {
+        FieldType::String => "a string",
+        FieldType::Integer => "an integer",
+        FieldType::Float => "a number",
+        FieldType::Bool => "a bool",
+        FieldType::Date => "a date (YYYY-MM-DD)",
+        FieldType::List => "a list of strings",
+    }
+}
+
+/// Levenshtein distance, used to spot misspelt field names.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr.push((prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1));
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changelog_fields() -> BTreeMap<String, FieldSchema> {
+        let toml = r#"
+version = { type = "string", required = true, pattern = '^\d+\.\d+\.\d+$' }
+type = { type = "string", required = true, enum = ["added", "changed", "fixed"] }
+breaking = { type = "bool", default = false }
+areas = { type = "list", enum = ["cli", "build"] }
+"#;
+        toml::from_str(toml).unwrap()
+    }
+
+    fn extra(yaml: &str) -> HashMap<String, Value> {
+        serde_yaml_ng::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_valid_extra_passes() {
+        let fields = changelog_fields();
+        let schema = Schema::new(&fields).unwrap();
+        let mut e = extra("version: 1.2.3\ntype: fixed\nareas: [cli]");
+        schema.apply_defaults(&mut e);
+        assert_eq!(e["breaking"], Value::Bool(false));
+        assert!(schema.validate(&e).is_empty());
+    }
+
+    #[test]
+    fn test_reports_every_violation() {
+        let fields = changelog_fields();
+        let schema = Schema::new(&fields).unwrap();
+        let e = extra("version: '1.2'\nbreaking: 'yes'\nareas: [cli, docs]");
+        let problems = schema.validate(&e);
+        assert_eq!(problems.len(), 4, "{problems:?}");
+        assert!(problems.contains(&"missing required field `type`".to_string()));
+        assert!(problems
+            .iter()
+            .any(|p| p.starts_with("field `version` does not match")));
+        assert!(problems.contains(&"field `breaking` must be a bool (got \"yes\")".to_string()));
+        assert!(problems
+            .contains(&"field `areas` must be one of: cli, build (got \"docs\")".to_string()));
+    }
+
+    #[test]
+    fn test_misspelt_field_is_reported() {
+        let fields = changelog_fields();
+        let schema = Schema::new(&fields).unwrap();
+        let e = extra("verison: 1.2.3\nversion: 1.2.3\ntype: added\nunrelated: x");
+        assert_eq!(
+            schema.validate(&e),
+            vec!["unknown field `verison` (did you mean `version`?)".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_type_checks() {
+        let fields: BTreeMap<String, FieldSchema> = toml::from_str(
+            r#"
+count = { type = "integer" }
+ratio = { type = "float" }
+released = { type = "date" }
+"#,
+        )
+        .unwrap();
+        let schema = Schema::new(&fields).unwrap();
+        assert!(schema
+            .validate(&extra("count: 3\nratio: 3\nreleased: 2025-01-31"))
+            .is_empty());
+        let problems = schema.validate(&extra("count: 1.5\nratio: x\nreleased: 2025-13-01"));
+        assert_eq!(problems.len(), 3, "{problems:?}");
+    }
+
+    #[test]
+    fn test_invalid_pattern_rejected() {
+        let fields: BTreeMap<String, FieldSchema> =
+            toml::from_str("v = { type = \"string\", pattern = \"(\" }").unwrap();
+        let err = Schema::new(&fields).err().unwrap();
+        assert!(err.contains("field 'v' has an invalid pattern"));
+    }
+
+    #[test]
+    fn test_placeholder_values() {
+        let fields = changelog_fields();
+        assert_eq!(placeholder(&fields["type"]), Value::String("added".into()));
+        assert_eq!(placeholder(&fields["breaking"]), Value::Bool(false));
+        assert_eq!(
+            placeholder(&fields["version"]),
+            Value::String(String::new())
+        );
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema = json_schema(&changelog_fields());
+        assert_eq!(schema["properties"]["type"]["enum"][1], "changed");
+        assert_eq!(schema["properties"]["areas"]["items"]["enum"][0], "cli");
+        assert_eq!(schema["properties"]["breaking"]["default"], false);
+        assert_eq!(schema["required"], serde_json::json!(["type", "version"]));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("version", "version"), 0);
+        assert_eq!(edit_distance("verison", "version"), 2);
+        assert_eq!(edit_distance("tpye", "type"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
@@ -13,6 +13,16 @@ use crate::error::{PageError, Result};
 ///
 /// Returns `Ok(Value::Object(empty))` if the directory doesn't exist.
 pub fn load_data_dir(data_dir: &Path) -> Result<serde_json::Value> {
+    load_data_dir_with_sidebars(data_dir, &[])
+}
+
+/// Like [`load_data_dir`], but `nav/<name>` files for the given collections
+/// are sidebars, read by the build's nav module. When `nav.yaml` holds the
+/// site menu they may sit next to it; they are then not part of `{{ data }}`.
+pub fn load_data_dir_with_sidebars(
+    data_dir: &Path,
+    sidebars: &[&str],
+) -> Result<serde_json::Value> {
     let mut root = serde_json::Map::new();
 
     if !data_dir.exists() {
@@ -71,6 +81,12 @@ pub fn load_data_dir(data_dir: &Path) -> Result<serde_json::Value> {
         files.push((segments, path.to_path_buf()));
     }
 
+    if files.iter().any(|(segments, _)| segments == &["nav"]) {
+        files.retain(|(segments, _)| {
+            !matches!(segments.as_slice(), [dir, name] if dir == "nav" && sidebars.contains(&name.as_str()))
+        });
+    }
+
     // Check for key conflicts (e.g., authors.yaml and authors.json)
     check_conflicts(&files)?;
 
@@ -83,6 +99,67 @@ pub fn load_data_dir(data_dir: &Path) -> Result<serde_json::Value> {
     Ok(serde_json::Value::Object(root))
 }
 
+/// Load a site's data directory layered over an optional shared (workspace)
+/// data directory. Objects are deep-merged; on conflicting keys the site's
+/// value wins. `sidebars` are as for [`load_data_dir_with_sidebars`].
+pub fn load_layered_data(
+    data_dir: &Path,
+    shared_dir: Option<&Path>,
+    sidebars: &[&str],
+) -> Result<serde_json::Value> {
+    let site = load_data_dir_with_sidebars(data_dir, sidebars)?;
+    match shared_dir {
+        Some(shared_dir) => {
+            let mut merged = load_data_dir_with_sidebars(shared_dir, sidebars)?;
+            merge_data(&mut merged, site);
+            Ok(merged)
+        }
+        None => Ok(site),
+    }
+}
+
+/// Deep-merge `overlay` into `base`. Nested objects are merged key by key;
+/// any other value in `overlay` replaces the one in `base`.
+pub fn merge_data(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base_map), serde_json::Value::Object(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(existing) => merge_data(existing, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Look up a value by data key: `catalogue/integrations` or
+/// `catalogue.integrations` → `data.catalogue.integrations`.
+pub fn lookup<'a>(data: &'a serde_json::Value, key: &str) -> Option<&'a serde_json::Value> {
+    key.split(['/', '.'])
+        .filter(|seg| !seg.is_empty())
+        .try_fold(data, |value, seg| value.get(seg))
+}
+
+/// The file a data key was loaded from, for error messages. Checks the site's
+/// data directory, then the shared one; falls back to `<key>.yaml`.
+pub fn file_for_key(data_dir: &Path, shared_dir: Option<&Path>, key: &str) -> PathBuf {
+    let rel: PathBuf = key.split(['/', '.']).filter(|s| !s.is_empty()).collect();
+    let rel = &rel;
+    std::iter::once(data_dir)
+        .chain(shared_dir)
+        .flat_map(|dir| {
+            ["yaml", "yml", "json", "toml"]
+                .iter()
+                .map(move |ext| dir.join(rel).with_extension(ext))
+        })
+        .find(|p| p.is_file())
+        .unwrap_or_else(|| data_dir.join(rel).with_extension("yaml"))
+}
+
 /// Returns the number of data files that would be loaded from the directory.
 pub fn count_data_files(data_dir: &Path) -> usize {
     if !data_dir.exists() {
@@ -106,7 +183,7 @@ pub fn count_data_files(data_dir: &Path) -> usize {
 }
 
 /// Parse a single data file into a `serde_json::Value`.
-fn parse_data_file(path: &Path) -> Result<serde_json::Value> {
+pub fn parse_data_file(path: &Path) -> Result<serde_json::Value> {
     let content = std::fs::read_to_string(path)?;
     let ext = path
         .extension()
@@ -164,6 +241,28 @@ fn check_conflicts(files: &[(Vec<String>, PathBuf)]) -> Result<()> {
         }
         seen.insert(segments.clone(), path);
     }
+    // A file and a directory with the same key, e.g. `nav.yaml` and `nav/main.yaml`
+    for (segments, path) in files {
+        for depth in 1..segments.len() {
+            if let Some(file) = seen.get(&segments[..depth]) {
+                let key = segments[..depth].join(".");
+                let mut message = format!(
+                    "data key conflict: '{}' and '{}' both define data.{key}",
+                    file.display(),
+                    path.display(),
+                );
+                if key == "nav" {
+                    message.push_str(
+                        "; only collection sidebars (data/nav/<collection>.yaml for a nested collection) may sit next to nav.yaml",
+                    );
+                }
+                return Err(PageError::Data {
+                    path: path.to_path_buf(),
+                    message,
+                });
+            }
+        }
+    }
     Ok(())
 }
 
@@ -280,6 +379,25 @@ mod tests {
         assert_eq!(nav_main[0]["title"], "Home");
     }
 
+    #[test]
+    fn test_nav_file_next_to_sidebar_files() {
+        let tmp = TempDir::new().unwrap();
+        let data_dir = tmp.path();
+        std::fs::create_dir_all(data_dir.join("nav")).unwrap();
+        std::fs::write(data_dir.join("nav.yaml"), "- title: Home\n  url: /\n").unwrap();
+        std::fs::write(data_dir.join("nav/docs.yaml"), "- intro\n").unwrap();
+
+        let data = load_data_dir_with_sidebars(data_dir, &["docs"]).unwrap();
+        assert_eq!(data["nav"][0]["title"], "Home");
+
+        // Any other file under nav/ is a conflict, whichever loads first
+        let err = load_data_dir(data_dir).unwrap_err().to_string();
+        assert!(err.contains("both define data.nav"), "{err}");
+        assert!(err.contains("collection sidebars"), "{err}");
+        std::fs::write(data_dir.join("nav/footer.yaml"), "- x\n").unwrap();
+        assert!(load_data_dir_with_sidebars(data_dir, &["docs"]).is_err());
+    }
+
     #[test]
     fn test_conflict_detection() {
         let tmp = TempDir::new().unwrap();
@@ -473,4 +591,71 @@ mod tests {
         let data = load_data_dir(tmp.path()).unwrap();
         assert_eq!(data["config"]["section"]["key"], "val");
     }
+
+    #[test]
+    fn test_merge_data_site_wins_and_deep_merges() {
+        let mut base = serde_json::json!({
+            "site": {"name": "Shared", "links": {"github": "gh", "x": "x"}},
+            "footer": "shared footer"
+        });
+        let overlay = serde_json::json!({
+            "site": {"name": "Blog", "links": {"x": "site-x"}},
+            "extra": [1, 2]
+        });
+        merge_data(&mut base, overlay);
+        assert_eq!(base["site"]["name"], "Blog");
+        assert_eq!(base["site"]["links"]["github"], "gh");
+        assert_eq!(base["site"]["links"]["x"], "site-x");
+        assert_eq!(base["footer"], "shared footer");
+        assert_eq!(base["extra"], serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn test_load_layered_data() {
+        let tmp = TempDir::new().unwrap();
+        let shared = tmp.path().join("shared");
+        let site = tmp.path().join("site");
+        std::fs::create_dir_all(shared.join("nav")).unwrap();
+        std::fs::create_dir_all(&site).unwrap();
+        std::fs::write(shared.join("nav/main.yaml"), "- Home\n").unwrap();
+        std::fs::write(shared.join("company.yaml"), "name: Acme\nphone: 555\n").unwrap();
+        std::fs::write(site.join("company.yaml"), "name: Acme Docs\n").unwrap();
+
+        let data = load_layered_data(&site, Some(&shared), &[]).unwrap();
+        assert_eq!(data["company"]["name"], "Acme Docs");
+        assert_eq!(data["company"]["phone"], 555);
+        assert_eq!(data["nav"]["main"][0], "Home");
+    }
+
+    #[test]
+    fn test_lookup_by_key() {
+        let data = serde_json::json!({ "catalogue": { "integrations": [1, 2] } });
+        assert_eq!(lookup(&data, "catalogue/integrations").unwrap()[1], 2);
+        assert_eq!(lookup(&data, "catalogue.integrations").unwrap()[0], 1);
+        assert!(lookup(&data, "catalogue/missing").is_none());
+    }
+
+    #[test]
+    fn test_file_for_key() {
+        let tmp = TempDir::new().unwrap();
+        let site = tmp.path().join("site");
+        let shared = tmp.path().join("shared");
+        std::fs::create_dir_all(site.join("catalogue")).unwrap();
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::write(site.join("catalogue/integrations.yml"), "[]").unwrap();
+        std::fs::write(shared.join("authors.json"), "[]").unwrap();
+
+        assert_eq!(
+            file_for_key(&site, Some(&shared), "catalogue.integrations"),
+            site.join("catalogue/integrations.yml")
+        );
+        assert_eq!(
+            file_for_key(&site, Some(&shared), "authors"),
+            shared.join("authors.json")
+        );
+        assert_eq!(
+            file_for_key(&site, None, "missing"),
+            site.join("missing.yaml")
+        );
+    }
 }
@@ -688,6 +688,9 @@ pub fn execute_fix(
             human::info("Building site...");
             let opts = crate::build::BuildOptions {
                 include_drafts: false,
+                as_of: None,
+                incremental: false,
+                cross_site_urls: HashMap::new(),
             };
             match crate::build::build_site(config, paths, &opts) {
                 Ok(result) => {
@@ -1622,6 +1625,9 @@ pub fn generate_github_actions_workflow(config: &SiteConfig) -> String {
 on:
   push:
     branches: [main]
+  schedule:
+    # Rebuild hourly so scheduled (publish_at / expires_at) content goes live
+    - cron: "0 * * * *"
   workflow_dispatch:
 
 permissions:
@@ -1679,6 +1685,9 @@ pub fn generate_cloudflare_workflow(config: &SiteConfig) -> String {
 on:
   push:
     branches: [main]
+  schedule:
+    # Rebuild hourly so scheduled (publish_at / expires_at) content goes live
+    - cron: "0 * * * *"
   workflow_dispatch:
 
 jobs:
@@ -1730,6 +1739,9 @@ pub fn generate_netlify_workflow(config: &SiteConfig) -> String {
 on:
   push:
     branches: [main]
+  schedule:
+    # Rebuild hourly so scheduled (publish_at / expires_at) content goes live
+    - cron: "0 * * * *"
   workflow_dispatch:
 
 jobs:
@@ -2098,9 +2110,16 @@ pub fn verify_deployment(url: &str) -> Vec<VerifyResult> {
     let robots_url = format!("{}/robots.txt", url.trim_end_matches('/'));
     results.push(verify_url_reachable(&robots_url, "robots.txt"));
 
-    // Check 3: Check /sitemap.xml exists
+    // Check 3: Check /sitemap.xml exists (or /sitemap_index.xml for split sitemaps)
     let sitemap_url = format!("{}/sitemap.xml", url.trim_end_matches('/'));
-    results.push(verify_url_reachable(&sitemap_url, "sitemap.xml"));
+    let sitemap = verify_url_reachable(&sitemap_url, "sitemap.xml");
+    if sitemap.passed {
+        results.push(sitemap);
+    } else {
+        let index_url = format!("{}/sitemap_index.xml", url.trim_end_matches('/'));
+        let index = verify_url_reachable(&index_url, "sitemap_index.xml");
+        results.push(if index.passed { index } else { sitemap });
+    }
 
     // Check 4: Check /llms.txt exists
     let llms_url = format!("{}/llms.txt", url.trim_end_matches('/'));
@@ -2448,6 +2467,7 @@ mod tests {
                 base_url: "http://localhost:3000".into(),
                 language: "en".into(),
                 author: "".into(),
+                timezone: None,
             },
             collections: vec![],
             build: Default::default(),
@@ -2457,6 +2477,11 @@ mod tests {
             analytics: None,
             trust: None,
             contact: None,
+            og: None,
+            search: None,
+            related: None,
+            feed: None,
+            taxonomies: Vec::new(),
         };
 
         // Override takes precedence
@@ -2481,6 +2506,7 @@ mod tests {
                 base_url: "http://localhost:3000".into(),
                 language: "en".into(),
                 author: "".into(),
+                timezone: None,
             },
             collections: vec![],
             build: Default::default(),
@@ -2490,6 +2516,11 @@ mod tests {
             analytics: None,
             trust: None,
             contact: None,
+            og: None,
+            search: None,
+            related: None,
+            feed: None,
+            taxonomies: Vec::new(),
         };
         let check = check_base_url(&config);
         assert!(!check.passed);
@@ -2510,6 +2541,7 @@ mod tests {
                 base_url: base_url.into(),
                 language: "en".into(),
                 author: "".into(),
+                timezone: None,
             },
             collections: vec![],
             build: Default::default(),
@@ -2519,6 +2551,11 @@ mod tests {
             analytics: None,
             trust: None,
             contact: None,
+            og: None,
+            search: None,
+            related: None,
+            feed: None,
+            taxonomies: Vec::new(),
         }
     }
 
@@ -2642,6 +2679,7 @@ mod tests {
             output: std::path::PathBuf::from("/nonexistent/dist"),
             data_dir: std::path::PathBuf::from("/nonexistent/data"),
             public_dir: std::path::PathBuf::from("/nonexistent/public"),
+            shared: Default::default(),
         };
         let check = check_output_dir(&paths);
         assert!(!check.passed);
@@ -2661,6 +2699,7 @@ mod tests {
             output: dist,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let check = check_output_dir(&paths);
         assert!(!check.passed);
@@ -2681,6 +2720,7 @@ mod tests {
             output: dist,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let check = check_output_dir(&paths);
         assert!(check.passed);
@@ -2700,6 +2740,7 @@ mod tests {
             output: dist,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = test_config("https://example.com");
         let checks = preflight(&config, &paths, "github-pages");
@@ -2715,6 +2756,7 @@ mod tests {
             output: dir.join("dist"),
             data_dir: dir.join("data"),
             public_dir: dir.join("public"),
+            shared: Default::default(),
         }
     }
 
@@ -3638,6 +3680,7 @@ target = "github-pages"
             output: dist,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = test_config("https://example.com");
         let checks = preflight(&config, &paths, "cloudflare");
@@ -3665,6 +3708,7 @@ target = "github-pages"
             output: dist,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = test_config("https://example.com");
         let checks = preflight(&config, &paths, "netlify");
@@ -3691,6 +3735,7 @@ target = "github-pages"
             output: dist,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = test_config("https://example.com");
         let checks = preflight(&config, &paths, "unknown-target");
@@ -3712,6 +3757,7 @@ target = "github-pages"
             output: dist,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let mut config = test_config("https://example.com");
         config.deploy.domain = Some("example.com".into());
@@ -3734,6 +3780,7 @@ target = "github-pages"
             output: dist,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let mut config = test_config("https://example.com");
         config.deploy.domain = Some("mysite.com".into());
@@ -4122,6 +4169,7 @@ target = "github-pages"
             output: std::path::PathBuf::from("/nonexistent/dist"),
             data_dir: std::path::PathBuf::from("/nonexistent/data"),
             public_dir: std::path::PathBuf::from("/nonexistent/public"),
+            shared: Default::default(),
         };
         let check = check_output_dir(&paths);
         assert_eq!(check.name, "Output directory");
@@ -4618,6 +4666,7 @@ target = "github-pages"
             output: dist,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = test_config("https://example.com");
         // No domain configured
@@ -4640,6 +4689,7 @@ target = "github-pages"
             output: dist,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = test_config("https://example.com");
         let checks = preflight(&config, &paths, "netlify");
@@ -4665,6 +4715,7 @@ target = "github-pages"
             output: dist,
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = test_config("https://example.com");
         let checks = preflight(&config, &paths, "github-pages");
@@ -5062,6 +5113,19 @@ project = "old-project"
         assert!(workflow.contains("workflow_dispatch"));
     }
 
+    #[test]
+    fn test_generated_workflows_have_scheduled_rebuild() {
+        let config = test_config("https://example.com");
+        for workflow in [
+            generate_github_actions_workflow(&config),
+            generate_cloudflare_workflow(&config),
+            generate_netlify_workflow(&config),
+        ] {
+            assert!(workflow.contains("  schedule:\n"));
+            assert!(workflow.contains("- cron: \"0 * * * *\""));
+        }
+    }
+
     #[test]
     fn test_generate_netlify_config_has_redirects() {
         let config = test_config("https://example.com");
@@ -5513,6 +5577,7 @@ target = "github-pages"
             output: dist.clone(),
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = test_config("https://example.com");
         // This will fail at the git init step, but .nojekyll should exist
@@ -5534,6 +5599,7 @@ target = "github-pages"
             output: dist.clone(),
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = test_config("https://myblog.com");
         let _ = deploy_github_pages(&config, &paths, Some("https://github.com/user/repo"));
@@ -5557,6 +5623,7 @@ target = "github-pages"
             output: dist.clone(),
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let config = test_config("https://user.github.io");
         let _ = deploy_github_pages(&config, &paths, Some("https://github.com/user/repo"));
@@ -5582,6 +5649,7 @@ target = "github-pages"
             output: dist.clone(),
             data_dir: tmp.path().join("data"),
             public_dir: tmp.path().join("public"),
+            shared: Default::default(),
         };
         let check = check_output_dir(&paths);
         assert!(check.passed);
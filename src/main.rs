@@ -23,6 +23,7 @@ fn main() -> Result<()> {
     match &cli.command {
         Command::Init(args) => seite::cli::init::run(args)?,
         Command::New(args) => seite::cli::new::run(args)?,
+        Command::Publish(args) => seite::cli::publish::run(args)?,
         Command::Build(args) => seite::cli::build::run(args, cli.site.as_deref())?,
         Command::Serve(args) => seite::cli::serve::run(args, cli.site.as_deref())?,
         Command::Deploy(args) => seite::cli::deploy::run(args, cli.site.as_deref())?,
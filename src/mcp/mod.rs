@@ -204,7 +204,7 @@ fn dispatch(
         "ping" => Ok(serde_json::json!({})),
         "resources/list" => resources::list(state),
         "resources/read" => resources::read(state, &request.params),
-        "tools/list" => tools::list(),
+        "tools/list" => tools::list(state),
         "tools/call" => tools::call(state, &request.params),
         _ => Err(JsonRpcError {
             code: METHOD_NOT_FOUND,
@@ -3,11 +3,14 @@
 //! Resources are read-only data items identified by URI. The MCP client
 //! discovers them via `resources/list` and reads them via `resources/read`.
 
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::path::{Path, PathBuf};
 
 use walkdir::WalkDir;
 
 use super::{JsonRpcError, ServerState};
+use crate::build::related::{self, RelatedLink};
 use crate::content;
 
 /// Handle `resources/list` — enumerate all available resources.
@@ -213,7 +216,10 @@ fn read_content_overview(state: &ServerState) -> Result<serde_json::Value, JsonR
             WalkDir::new(&dir)
                 .into_iter()
                 .filter_map(|e| e.ok())
-                .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+                .filter(|e| {
+                    e.path().extension().is_some_and(|ext| ext == "md")
+                        && !content::is_section_index(e.path())
+                })
                 .count()
         } else {
             0
@@ -270,12 +276,16 @@ fn read_collection(
 
     let dir = paths.content.join(&collection.directory);
     let mut items = Vec::new();
+    let related = related_by_path(config, &paths.content);
 
     if dir.exists() {
         for entry in WalkDir::new(&dir)
             .into_iter()
             .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+            .filter(|e| {
+                e.path().extension().is_some_and(|ext| ext == "md")
+                    && !content::is_section_index(e.path())
+            })
         {
             if let Ok((fm, _body)) = content::parse_content_file(entry.path()) {
                 let slug = fm
@@ -296,6 +306,12 @@ fn read_collection(
                     "draft": fm.draft,
                     "description": fm.description,
                     "weight": fm.weight,
+                    "related": related.get(entry.path()).map(|links| {
+                        links
+                            .iter()
+                            .map(|l| serde_json::json!({ "title": l.title, "url": l.url }))
+                            .collect::<Vec<_>>()
+                    }),
                 }));
             }
         }
@@ -330,6 +346,69 @@ fn read_collection(
     }))
 }
 
+/// Related pages of every content file, computed the way the build does.
+fn related_by_path(
+    config: &crate::config::SiteConfig,
+    content_dir: &Path,
+) -> HashMap<PathBuf, Vec<RelatedLink>> {
+    let default_lang = config.site.language.as_str();
+    let langs = config.all_languages();
+    let configured: HashSet<&str> = langs.iter().map(String::as_str).collect();
+    let mut files = Vec::new();
+    for collection in &config.collections {
+        let dir = content_dir.join(&collection.directory);
+        for entry in WalkDir::new(&dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.path().extension().is_some_and(|ext| ext == "md")
+                    && !content::is_section_index(e.path())
+            })
+        {
+            let Ok((fm, body)) = content::parse_content_file(entry.path()) else {
+                continue;
+            };
+            let lang = content::extract_lang_from_filename(entry.path(), &configured)
+                .unwrap_or_else(|| default_lang.to_string());
+            let slug = fm
+                .slug
+                .clone()
+                .unwrap_or_else(|| content::slug_from_title(&fm.title));
+            let prefix = if lang == default_lang {
+                String::new()
+            } else {
+                format!("/{lang}")
+            };
+            let url = format!("{prefix}{}/{slug}", collection.url_prefix);
+            files.push((
+                entry.path().to_path_buf(),
+                collection.name.as_str(),
+                lang,
+                slug,
+                url,
+                fm,
+                body,
+            ));
+        }
+    }
+    let docs: Vec<related::Doc> = files
+        .iter()
+        .map(|(_, collection, lang, slug, url, fm, body)| related::Doc {
+            url,
+            slug,
+            collection,
+            lang,
+            frontmatter: fm,
+            body,
+        })
+        .collect();
+    files
+        .iter()
+        .map(|(path, ..)| path.clone())
+        .zip(related::compute(&docs, config))
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Theme resources
 // ---------------------------------------------------------------------------
@@ -418,7 +497,10 @@ fn read_trust(state: &ServerState) -> Result<serde_json::Value, JsonRpcError> {
         for entry in WalkDir::new(&trust_dir)
             .into_iter()
             .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+            .filter(|e| {
+                e.path().extension().is_some_and(|ext| ext == "md")
+                    && !content::is_section_index(e.path())
+            })
         {
             if let Ok((fm, _body)) = content::parse_content_file(entry.path()) {
                 let rel = entry
@@ -499,6 +581,7 @@ mod tests {
                 base_url: "http://localhost:3000".into(),
                 language: "en".into(),
                 author: "Tester".into(),
+                timezone: None,
             },
             collections,
             build: BuildSection::default(),
@@ -508,6 +591,11 @@ mod tests {
             analytics: None,
             trust: None,
             contact: None,
+            og: None,
+            search: None,
+            related: None,
+            feed: None,
+            taxonomies: Vec::new(),
         }
     }
 
@@ -1220,6 +1308,44 @@ mod tests {
         assert_eq!(items[0]["title"], "Valid");
     }
 
+    #[test]
+    fn test_read_collection_includes_related() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = make_config(vec![
+            CollectionConfig::preset_posts(),
+            CollectionConfig::preset_pages(),
+        ]);
+        config.related = Some(crate::config::RelatedSection { limit: 5 });
+        let state = make_state(tmp.path(), config);
+
+        let posts_dir = tmp.path().join("content").join("posts");
+        write_content(
+            &posts_dir,
+            "a.md",
+            "Alpha",
+            "date: 2026-01-01\ntags: [rust]\nrelated: [about]\n",
+        );
+        write_content(
+            &posts_dir,
+            "b.md",
+            "Beta",
+            "date: 2026-01-02\ntags: [rust]\n",
+        );
+        write_content(&tmp.path().join("content/pages"), "about.md", "About", "");
+
+        let result = read_collection(&state, "posts").unwrap();
+        let text = result["contents"][0]["text"].as_str().unwrap();
+        let items: Vec<serde_json::Value> = serde_json::from_str(text).unwrap();
+        let alpha = items.iter().find(|i| i["title"] == "Alpha").unwrap();
+        assert_eq!(
+            alpha["related"],
+            serde_json::json!([
+                { "title": "About", "url": "/about" },
+                { "title": "Beta", "url": "/posts/beta" },
+            ])
+        );
+    }
+
     // -----------------------------------------------------------------------
     // read_themes() tests
     // -----------------------------------------------------------------------
@@ -3,6 +3,7 @@
 //! Tools are invoked via `tools/call` and return structured results.
 //! Each tool wraps existing seite CLI functionality.
 
+use std::collections::HashMap;
 use std::fs;
 
 use walkdir::WalkDir;
@@ -11,8 +12,11 @@ use super::{JsonRpcError, ServerState};
 use crate::{build, content, themes};
 
 /// Handle `tools/list` — enumerate all available tools with JSON schemas.
-pub fn list() -> Result<serde_json::Value, JsonRpcError> {
-    Ok(serde_json::json!({
+///
+/// The `seite_create_content` schema includes the `[collections.fields]`
+/// schemas of the loaded site, so clients know which custom fields to send.
+pub fn list(state: &ServerState) -> Result<serde_json::Value, JsonRpcError> {
+    let mut result = serde_json::json!({
         "tools": [
             {
                 "name": "seite_build",
@@ -110,7 +114,51 @@ pub fn list() -> Result<serde_json::Value, JsonRpcError> {
                 }
             }
         ]
-    }))
+    });
+
+    if let Some(config) = &state.config {
+        let create = result["tools"]
+            .as_array_mut()
+            .and_then(|tools| {
+                tools
+                    .iter_mut()
+                    .find(|t| t["name"] == "seite_create_content")
+            })
+            .expect("seite_create_content is always listed");
+        add_field_schemas(&mut create["inputSchema"], &config.collections);
+    }
+    Ok(result)
+}
+
+/// Advertise custom frontmatter fields: an `extra` object, plus one
+/// conditional schema per collection that declares `[collections.fields]`.
+fn add_field_schemas(
+    input_schema: &mut serde_json::Value,
+    collections: &[crate::config::CollectionConfig],
+) {
+    let conditions: Vec<serde_json::Value> = collections
+        .iter()
+        .filter(|c| !c.fields.is_empty())
+        .map(|c| {
+            let extra = content::schema::json_schema(&c.fields);
+            let mut then = serde_json::json!({ "properties": { "extra": extra } });
+            if extra.get("required").is_some() {
+                then["required"] = serde_json::json!(["extra"]);
+            }
+            serde_json::json!({
+                "if": { "properties": { "collection": { "const": c.name } } },
+                "then": then
+            })
+        })
+        .collect();
+    if conditions.is_empty() {
+        return;
+    }
+    input_schema["properties"]["extra"] = serde_json::json!({
+        "type": "object",
+        "description": "Custom frontmatter fields, validated against the collection's field schema"
+    });
+    input_schema["allOf"] = serde_json::json!(conditions);
 }
 
 /// Handle `tools/call` — dispatch to the appropriate tool.
@@ -175,7 +223,12 @@ fn call_build(
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
 
-    let opts = build::BuildOptions { include_drafts };
+    let opts = build::BuildOptions {
+        include_drafts,
+        as_of: None,
+        incremental: true,
+        cross_site_urls: HashMap::new(),
+    };
 
     match build::build_site(config, paths, &opts) {
         Ok(result) => {
@@ -252,6 +305,13 @@ fn call_create_content(
             JsonRpcError::invalid_params(format!("Collection not found: {collection_name}"))
         })?;
 
+    if let Some(key) = &collection.data {
+        return Err(JsonRpcError::invalid_params(format!(
+            "Collection '{}' is built from data '{key}'; add an entry to that data file instead",
+            collection.name
+        )));
+    }
+
     let slug = content::slug_from_title(title);
     let tags: Vec<String> = arguments
         .get("tags")
@@ -273,18 +333,43 @@ fn call_create_content(
         .and_then(|v| v.as_str())
         .unwrap_or("Your content here...");
 
+    let extra: HashMap<String, serde_yaml_ng::Value> = arguments
+        .get("extra")
+        .and_then(|v| v.as_object())
+        .map(|obj| {
+            obj.iter()
+                .map(|(k, v)| (k.clone(), serde_yaml_ng::to_value(v).unwrap_or_default()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Check custom fields before writing anything
+    let schema = content::schema::Schema::new(&collection.fields)
+        .map_err(|e| JsonRpcError::internal(format!("Invalid field schema: {e}")))?;
+    let mut with_defaults = extra.clone();
+    schema.apply_defaults(&mut with_defaults);
+    let problems = schema.validate(&with_defaults);
+    if !problems.is_empty() {
+        return Err(JsonRpcError::invalid_params(format!(
+            "Invalid 'extra' for collection '{}': {}",
+            collection.name,
+            problems.join("; ")
+        )));
+    }
+
     // Build frontmatter
     let mut fm = content::Frontmatter {
         title: title.to_string(),
         tags,
         draft,
+        extra,
         ..Default::default()
     };
 
     // Build filename
     let filename = if collection.has_date {
         let date = chrono::Local::now().format("%Y-%m-%d").to_string();
-        fm.date = Some(chrono::Local::now().date_naive());
+        fm.date = Some(chrono::Local::now().date_naive().into());
         format!("{date}-{slug}.md")
     } else {
         format!("{slug}.md")
@@ -361,7 +446,10 @@ fn call_search(
         for entry in WalkDir::new(&dir)
             .into_iter()
             .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+            .filter(|e| {
+                e.path().extension().is_some_and(|ext| ext == "md")
+                    && !content::is_section_index(e.path())
+            })
         {
             if let Ok((fm, body)) = content::parse_content_file(entry.path()) {
                 // Match against title, description, tags, and body
@@ -639,7 +727,12 @@ mod tests {
 
     #[test]
     fn test_list_returns_all_tools() {
-        let result = list().unwrap();
+        let state = ServerState {
+            config: None,
+            paths: None,
+            cwd: std::path::PathBuf::new(),
+        };
+        let result = list(&state).unwrap();
         let tools = result["tools"].as_array().unwrap();
         assert_eq!(tools.len(), 5);
         let names: Vec<&str> = tools.iter().map(|t| t["name"].as_str().unwrap()).collect();
@@ -865,6 +958,83 @@ mod tests {
         assert!(err.message.contains("seite project"));
     }
 
+    /// Helper: a site whose `changelog` collection declares a field schema.
+    fn schema_state(dir: &std::path::Path) -> ServerState {
+        fs::write(
+            dir.join("seite.toml"),
+            r#"[site]
+title = "Test"
+
+[[collections]]
+name = "changelog"
+label = "Changelog"
+directory = "changelog"
+has_date = true
+default_template = "changelog-entry.html"
+
+[collections.fields]
+type = { type = "string", required = true, enum = ["added", "fixed"] }
+breaking = { type = "bool", default = false }
+"#,
+        )
+        .unwrap();
+        let config = crate::config::SiteConfig::load(&dir.join("seite.toml")).unwrap();
+        ServerState {
+            paths: Some(config.resolve_paths(dir)),
+            config: Some(config),
+            cwd: dir.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn test_list_advertises_field_schemas() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let state = schema_state(tmp.path());
+        let result = list(&state).unwrap();
+        let create = result["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|t| t["name"] == "seite_create_content")
+            .unwrap();
+        let schema = &create["inputSchema"];
+        assert_eq!(schema["properties"]["extra"]["type"], "object");
+        let condition = &schema["allOf"][0];
+        assert_eq!(
+            condition["if"]["properties"]["collection"]["const"],
+            "changelog"
+        );
+        let extra = &condition["then"]["properties"]["extra"];
+        assert_eq!(extra["properties"]["type"]["enum"][0], "added");
+        assert_eq!(extra["required"], serde_json::json!(["type"]));
+        assert_eq!(condition["then"]["required"], serde_json::json!(["extra"]));
+    }
+
+    #[test]
+    fn test_call_create_content_validates_extra() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut state = schema_state(tmp.path());
+        let params = serde_json::json!({
+            "name": "seite_create_content",
+            "arguments": { "collection": "changelog", "title": "Fix", "extra": { "type": "removed" } }
+        });
+        let err = call(&mut state, &params).unwrap_err();
+        assert!(err.message.contains("must be one of: added, fixed"));
+
+        let params = serde_json::json!({
+            "name": "seite_create_content",
+            "arguments": { "collection": "changelog", "title": "Fix", "extra": { "type": "fixed" } }
+        });
+        call(&mut state, &params).unwrap();
+        let entry = WalkDir::new(tmp.path().join("content/changelog"))
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .find(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+            .unwrap();
+        let written = fs::read_to_string(entry.path()).unwrap();
+        assert!(written.contains("type: fixed"));
+    }
+
     #[test]
     fn test_lookup_docs_all_topics_have_content() {
         for doc in crate::docs::all() {
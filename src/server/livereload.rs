@@ -0,0 +1,287 @@
+//! Push-based live reload over server-sent events.
+//!
+//! Pages served by the dev server open an `EventSource` on `/__livereload`.
+//! After every rebuild the watcher sends one typed event to all connected
+//! pages: `reload`, `css-update` (stylesheets swapped in place) or
+//! `build-error` (shown as an overlay until the next successful build).
+
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tiny_http::Request;
+
+use crate::error::PageError;
+
+/// Seconds between keep-alive comments, which also detect closed connections.
+const PING_INTERVAL_SECS: u64 = 15;
+
+/// An event pushed to connected pages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiveReloadEvent {
+    /// Reload the page.
+    Reload,
+    /// Only stylesheets changed; `paths` are their URLs relative to the site root.
+    CssUpdate { paths: Vec<String> },
+    /// The rebuild failed.
+    BuildError(BuildErrorInfo),
+}
+
+/// Details of a failed build shown in the browser overlay.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BuildErrorInfo {
+    pub message: String,
+    /// Source file, relative to the project root when possible.
+    pub file: Option<String>,
+    pub line: Option<usize>,
+}
+
+impl BuildErrorInfo {
+    /// Describe a build error, including the messages of its source chain.
+    pub fn from_error(err: &PageError, root: &Path) -> Self {
+        let mut message = err.to_string();
+        let mut source = std::error::Error::source(err);
+        while let Some(cause) = source {
+            let cause_msg = cause.to_string();
+            if !message.contains(&cause_msg) {
+                message.push_str(&format!("\n{cause_msg}"));
+            }
+            source = cause.source();
+        }
+
+        let (file, line) = match err {
+            PageError::Shortcode { path, line, .. } => (Some(path), Some(*line)),
+            // YAML lines count from the opening `---` delimiter
+            PageError::Frontmatter { path, source } => {
+                (Some(path), source.location().map(|l| l.line() + 1))
+            }
+            PageError::Content { path, .. } | PageError::Data { path, .. } => (Some(path), None),
+            _ => (None, None),
+        };
+        let file = file.map(|p| p.strip_prefix(root).unwrap_or(p).display().to_string());
+
+        Self {
+            message,
+            file,
+            line,
+        }
+    }
+}
+
+impl LiveReloadEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            LiveReloadEvent::Reload => "reload",
+            LiveReloadEvent::CssUpdate { .. } => "css-update",
+            LiveReloadEvent::BuildError(_) => "build-error",
+        }
+    }
+
+    /// Format as a server-sent event frame.
+    pub fn to_sse(&self) -> String {
+        let data = match self {
+            LiveReloadEvent::Reload => serde_json::json!({}),
+            LiveReloadEvent::CssUpdate { paths } => serde_json::json!({ "paths": paths }),
+            LiveReloadEvent::BuildError(info) => serde_json::json!(info),
+        };
+        format!("event: {}\ndata: {data}\n\n", self.name())
+    }
+}
+
+/// Connected live reload clients. Shared between the HTTP server thread, which
+/// registers connections, and the watcher thread, which sends events.
+#[derive(Default)]
+pub struct LiveReload {
+    clients: Mutex<Vec<mpsc::Sender<String>>>,
+    /// The last build error, replayed to pages that connect before the next
+    /// successful build.
+    last_error: Mutex<Option<String>>,
+    closed: AtomicBool,
+}
+
+impl LiveReload {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Send an event to every connected page, dropping closed connections.
+    pub fn send(&self, event: &LiveReloadEvent) {
+        let frame = event.to_sse();
+        *self.last_error.lock().expect("live reload lock poisoned") =
+            matches!(event, LiveReloadEvent::BuildError(_)).then(|| frame.clone());
+        self.clients
+            .lock()
+            .expect("live reload lock poisoned")
+            .retain(|client| client.send(frame.clone()).is_ok());
+    }
+
+    /// Number of connected pages.
+    pub fn client_count(&self) -> usize {
+        self.clients
+            .lock()
+            .expect("live reload lock poisoned")
+            .len()
+    }
+
+    /// End all event streams. Called when the server shuts down.
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+    }
+
+    /// Answer a `/__livereload` request with an event stream, on its own thread.
+    pub fn serve(self: &Arc<Self>, request: Request) {
+        let (tx, rx) = mpsc::channel();
+        if let Some(frame) = self
+            .last_error
+            .lock()
+            .expect("live reload lock poisoned")
+            .clone()
+        {
+            let _ = tx.send(frame);
+        }
+        self.clients
+            .lock()
+            .expect("live reload lock poisoned")
+            .push(tx);
+
+        let hub = self.clone();
+        std::thread::spawn(move || {
+            let mut writer = request.into_writer();
+            let _ = hub.stream(&mut writer, &rx);
+        });
+    }
+
+    /// Write the response head, then forward events until the client goes
+    /// away or the server closes.
+    fn stream(&self, writer: &mut impl Write, rx: &mpsc::Receiver<String>) -> std::io::Result<()> {
+        writer.write_all(
+            b"HTTP/1.1 200 OK\r\n\
+              Content-Type: text/event-stream\r\n\
+              Cache-Control: no-cache\r\n\
+              Connection: keep-alive\r\n\r\n\
+              retry: 1000\n\n",
+        )?;
+        writer.flush()?;
+
+        let mut idle_secs = 0;
+        while !self.closed.load(Ordering::Relaxed) {
+            match rx.recv_timeout(Duration::from_secs(1)) {
+                Ok(frame) => {
+                    writer.write_all(frame.as_bytes())?;
+                    idle_secs = 0;
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    idle_secs += 1;
+                    if idle_secs < PING_INTERVAL_SECS {
+                        continue;
+                    }
+                    writer.write_all(b": ping\n\n")?;
+                    idle_secs = 0;
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_event_sse_frames() {
+        assert_eq!(
+            LiveReloadEvent::Reload.to_sse(),
+            "event: reload\ndata: {}\n\n"
+        );
+        let css = LiveReloadEvent::CssUpdate {
+            paths: vec!["/static/main.css".into()],
+        };
+        assert_eq!(
+            css.to_sse(),
+            "event: css-update\ndata: {\"paths\":[\"/static/main.css\"]}\n\n"
+        );
+    }
+
+    #[test]
+    fn test_build_error_info_from_shortcode_error() {
+        let err = PageError::Shortcode {
+            path: PathBuf::from("/site/content/posts/a.md"),
+            line: 7,
+            message: "unknown shortcode `nope`".into(),
+        };
+        let info = BuildErrorInfo::from_error(&err, Path::new("/site"));
+        assert_eq!(info.file.as_deref(), Some("content/posts/a.md"));
+        assert_eq!(info.line, Some(7));
+        assert!(info.message.contains("unknown shortcode `nope`"));
+
+        let frame = LiveReloadEvent::BuildError(info).to_sse();
+        assert!(frame.starts_with("event: build-error\ndata: {"));
+        assert!(frame.contains("\"line\":7"));
+    }
+
+    #[test]
+    fn test_build_error_info_includes_source_chain() {
+        let yaml_err = serde_yaml_ng::from_str::<serde_yaml_ng::Value>("a: [").unwrap_err();
+        let err = PageError::Frontmatter {
+            path: PathBuf::from("/site/content/pages/about.md"),
+            source: yaml_err,
+        };
+        let info = BuildErrorInfo::from_error(&err, Path::new("/site"));
+        assert_eq!(info.file.as_deref(), Some("content/pages/about.md"));
+        assert!(info.line.is_some());
+        // The YAML message is already part of the error's own message
+        assert_eq!(info.message.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_last_error_replayed_until_success() {
+        let hub = LiveReload::new();
+        let error = LiveReloadEvent::BuildError(BuildErrorInfo {
+            message: "boom".into(),
+            file: None,
+            line: None,
+        });
+        hub.send(&error);
+        assert_eq!(
+            hub.last_error.lock().unwrap().as_deref(),
+            Some(error.to_sse().as_str())
+        );
+        hub.send(&LiveReloadEvent::Reload);
+        assert!(hub.last_error.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stream_writes_head_and_events() {
+        let hub = LiveReload::new();
+        let (tx, rx) = mpsc::channel();
+        tx.send(LiveReloadEvent::Reload.to_sse()).unwrap();
+        drop(tx);
+
+        let mut out = Vec::new();
+        hub.stream(&mut out, &rx).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(out.contains("Content-Type: text/event-stream\r\n"));
+        assert!(out.ends_with("event: reload\ndata: {}\n\n"));
+    }
+
+    #[test]
+    fn test_send_drops_disconnected_clients() {
+        let hub = LiveReload::new();
+        let (tx, rx) = mpsc::channel();
+        hub.clients.lock().unwrap().push(tx);
+        let (closed_tx, _) = mpsc::channel();
+        hub.clients.lock().unwrap().push(closed_tx);
+
+        hub.send(&LiveReloadEvent::Reload);
+        assert_eq!(hub.client_count(), 1);
+        assert_eq!(rx.recv().unwrap(), LiveReloadEvent::Reload.to_sse());
+    }
+}
@@ -1,9 +1,10 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::net::TcpStream;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use notify::{RecursiveMode, Watcher};
@@ -15,22 +16,83 @@ use crate::error::{PageError, Result};
 use crate::output::human;
 use crate::output::CommandOutput;
 
+pub mod livereload;
+
+use livereload::{BuildErrorInfo, LiveReload, LiveReloadEvent};
+
 const LIVERELOAD_SCRIPT: &str = r#"<script>
 (function(){
-  var v = "0";
-  setInterval(function(){
-    fetch("/__livereload").then(function(r){ return r.text(); }).then(function(t){
-      if (v !== "0" && t !== v) location.reload();
-      v = t;
-    }).catch(function(){});
-  }, 1000);
+  var overlay = null;
+  function hideError(){
+    if (overlay) { overlay.remove(); overlay = null; }
+  }
+  function showError(err){
+    hideError();
+    overlay = document.createElement("div");
+    overlay.setAttribute("style", "position:fixed;inset:0;z-index:2147483647;overflow:auto;padding:2rem;background:rgba(20,20,20,.94);color:#f5f5f5;font:14px/1.5 ui-monospace,SFMono-Regular,Menlo,monospace");
+    var title = document.createElement("div");
+    title.setAttribute("style", "color:#ff6b6b;font-weight:bold;margin-bottom:1rem");
+    title.textContent = "Build failed" + (err.file ? " \u2014 " + err.file + (err.line ? ":" + err.line : "") : "");
+    var msg = document.createElement("pre");
+    msg.setAttribute("style", "white-space:pre-wrap;margin:0");
+    msg.textContent = err.message;
+    overlay.appendChild(title);
+    overlay.appendChild(msg);
+    document.body.appendChild(overlay);
+  }
+  function swapCss(paths){
+    var links = Array.prototype.slice.call(document.querySelectorAll('link[rel="stylesheet"]'));
+    var matched = links.filter(function(l){
+      var p = new URL(l.href, location.href).pathname;
+      return paths.some(function(c){ return p.slice(-c.length) === c; });
+    });
+    (matched.length ? matched : links).forEach(function(l){
+      var url = new URL(l.href, location.href);
+      url.searchParams.set("livereload", Date.now());
+      var next = l.cloneNode();
+      next.href = url.href;
+      next.onload = function(){ l.remove(); };
+      l.parentNode.insertBefore(next, l.nextSibling);
+    });
+  }
+  var es = new EventSource("/__livereload");
+  es.addEventListener("reload", function(){ location.reload(); });
+  es.addEventListener("css-update", function(e){ hideError(); swapCss(JSON.parse(e.data).paths); });
+  es.addEventListener("build-error", function(e){ showError(JSON.parse(e.data)); });
 })();
 </script>"#;
 
+/// Re-loads the served site's config and paths when `seite.toml` changes.
+pub type ConfigLoader = Box<dyn Fn() -> Result<(SiteConfig, ResolvedPaths)> + Send>;
+
+/// The config and paths of the site being served. Replaced when `seite.toml`
+/// changes and reloads successfully.
+#[derive(Clone)]
+pub struct ServedSite {
+    pub config: SiteConfig,
+    pub paths: ResolvedPaths,
+}
+
+impl ServedSite {
+    /// Subdomain mount points for dev preview (`/docs-preview/` → `dist-subdomains/docs/`).
+    fn subdomain_mounts(&self) -> Vec<(String, PathBuf)> {
+        self.config
+            .subdomain_collections()
+            .iter()
+            .map(|c| {
+                let prefix = format!("{}-preview", c.name);
+                let output = self.paths.subdomain_output(&c.name);
+                (prefix, output)
+            })
+            .collect()
+    }
+}
+
 /// Handle to a running dev server. Drop or call `stop()` to shut down.
 pub struct ServerHandle {
     stop: Arc<AtomicBool>,
     port: u16,
+    site: Arc<RwLock<ServedSite>>,
 }
 
 impl ServerHandle {
@@ -38,6 +100,11 @@ impl ServerHandle {
         self.port
     }
 
+    /// The config currently served, including any reload of `seite.toml`.
+    pub fn site(&self) -> ServedSite {
+        self.site.read().expect("served site lock poisoned").clone()
+    }
+
     pub fn stop(&self) {
         self.stop.store(true, Ordering::Relaxed);
     }
@@ -50,9 +117,12 @@ impl Drop for ServerHandle {
 }
 
 /// Start the dev server in background threads. Returns a handle to stop it.
+///
+/// `load_config` is called to pick up changes to `seite.toml`.
 pub fn start(
     config: &SiteConfig,
     paths: &ResolvedPaths,
+    load_config: ConfigLoader,
     port: u16,
     include_drafts: bool,
     auto_increment: bool,
@@ -78,18 +148,12 @@ pub fn start(
         human::success(&format!("Serving at http://localhost:{actual_port}"));
     }
 
-    // Compute subdomain mount points for dev preview
-    let subdomain_mounts: Vec<(String, PathBuf)> = config
-        .subdomain_collections()
-        .iter()
-        .map(|c| {
-            let prefix = format!("{}-preview", c.name);
-            let output = paths.subdomain_output(&c.name);
-            (prefix, output)
-        })
-        .collect();
+    let site = Arc::new(RwLock::new(ServedSite {
+        config: config.clone(),
+        paths: paths.clone(),
+    }));
 
-    if !subdomain_mounts.is_empty() {
+    if !config.subdomain_collections().is_empty() {
         human::info("Subdomain previews:");
         for c in config.subdomain_collections() {
             let prefix = format!("{}-preview", c.name);
@@ -101,47 +165,40 @@ pub fn start(
     }
 
     let stop = Arc::new(AtomicBool::new(false));
-    let build_version = Arc::new(AtomicU64::new(1));
+    let live_reload = LiveReload::new();
 
     // Spawn file watcher thread
     let watcher_stop = stop.clone();
-    let watcher_version = build_version.clone();
-    let watcher_config = config.clone();
-    let watcher_paths = paths.clone();
+    let watcher_live_reload = live_reload.clone();
+    let watcher_site = site.clone();
     std::thread::spawn(move || {
         watch_and_rebuild(
-            &watcher_config,
-            &watcher_paths,
+            &watcher_site,
+            &load_config,
             include_drafts,
             &watcher_stop,
-            &watcher_version,
+            &watcher_live_reload,
         );
     });
 
     // Spawn HTTP server thread
     let server_stop = stop.clone();
-    let server_paths = paths.clone();
+    let server_site = site.clone();
     std::thread::spawn(move || {
-        run_serve_loop(
-            server,
-            &server_paths,
-            &subdomain_mounts,
-            &build_version,
-            &server_stop,
-        );
+        run_serve_loop(server, &server_site, &live_reload, &server_stop);
     });
 
     Ok(ServerHandle {
         stop,
         port: actual_port,
+        site,
     })
 }
 
 fn run_serve_loop(
     server: Server,
-    paths: &ResolvedPaths,
-    subdomain_mounts: &[(String, PathBuf)],
-    build_version: &AtomicU64,
+    site: &RwLock<ServedSite>,
+    live_reload: &Arc<LiveReload>,
     stop: &AtomicBool,
 ) {
     loop {
@@ -152,15 +209,17 @@ fn run_serve_loop(
             Ok(Some(request)) => {
                 let url_path = request.url().to_string();
 
-                // Live reload polling endpoint
+                // Live reload event stream
                 if url_path == "/__livereload" {
-                    let version = build_version.load(Ordering::Relaxed).to_string();
-                    let header =
-                        Header::from_bytes("Content-Type", "text/plain").expect("valid header");
-                    let _ = request.respond(Response::from_string(version).with_header(header));
+                    live_reload.serve(request);
                     continue;
                 }
 
+                let (output, subdomain_mounts) = {
+                    let site = site.read().expect("served site lock poisoned");
+                    (site.paths.output.clone(), site.subdomain_mounts())
+                };
+
                 // Check subdomain mount points (e.g., /docs-preview/ → dist-subdomains/docs/)
                 let subdomain_file = {
                     let clean_url = url_path.trim_start_matches('/');
@@ -178,6 +237,10 @@ fn run_serve_loop(
                 if let Some(ref path) = subdomain_file {
                     let content = fs::read(path).unwrap_or_default();
                     let mime = guess_mime(path);
+                    if let Some(response) = redirect_response(mime, &content) {
+                        let _ = request.respond(response);
+                        continue;
+                    }
                     let content = if mime == "text/html; charset=utf-8" {
                         inject_livereload(&content)
                     } else {
@@ -188,13 +251,19 @@ fn run_serve_loop(
                     continue;
                 }
 
-                let file_path = resolve_file_path(&paths.output, &url_path);
+                let file_path = resolve_file_path(&output, &url_path);
 
                 if let Some(ref path) = file_path {
                     if path.exists() && path.is_file() {
                         let content = fs::read(path).unwrap_or_default();
                         let mime = guess_mime(path);
 
+                        // Redirect stubs get a real 301, like hosts reading `_redirects`
+                        if let Some(response) = redirect_response(mime, &content) {
+                            let _ = request.respond(response);
+                            continue;
+                        }
+
                         // Inject livereload script into HTML responses
                         let content = if mime == "text/html; charset=utf-8" {
                             inject_livereload(&content)
@@ -215,9 +284,9 @@ fn run_serve_loop(
                     .split('/')
                     .next()
                     .filter(|seg| seg.len() == 2)
-                    .map(|lang| paths.output.join(lang).join("404.html"))
+                    .map(|lang| output.join(lang).join("404.html"))
                     .filter(|p| p.exists());
-                let not_found_path = lang_404_path.unwrap_or_else(|| paths.output.join("404.html"));
+                let not_found_path = lang_404_path.unwrap_or_else(|| output.join("404.html"));
                 if not_found_path.exists() {
                     let content = fs::read(&not_found_path).unwrap_or_default();
                     let content = inject_livereload(&content);
@@ -237,9 +306,21 @@ fn run_serve_loop(
             Err(_) => break,
         }
     }
+    live_reload.close();
 }
 
-fn inject_livereload(html_bytes: &[u8]) -> Vec<u8> {
+/// A 301 response for a redirect stub written by the build, or `None` for any
+/// other file.
+pub(crate) fn redirect_response(mime: &str, content: &[u8]) -> Option<Response<std::io::Empty>> {
+    if mime != "text/html; charset=utf-8" {
+        return None;
+    }
+    let location = crate::build::redirects::stub_target(std::str::from_utf8(content).ok()?)?;
+    let header = Header::from_bytes("Location", location).ok()?;
+    Some(Response::empty(301).with_header(header))
+}
+
+pub(crate) fn inject_livereload(html_bytes: &[u8]) -> Vec<u8> {
     let html = String::from_utf8_lossy(html_bytes);
     if let Some(pos) = html.rfind("</body>") {
         let mut result = String::with_capacity(html.len() + LIVERELOAD_SCRIPT.len() + 1);
@@ -303,11 +384,11 @@ fn guess_mime(path: &Path) -> &'static str {
 }
 
 fn watch_and_rebuild(
-    config: &SiteConfig,
-    paths: &ResolvedPaths,
+    site: &RwLock<ServedSite>,
+    load_config: &ConfigLoader,
     include_drafts: bool,
     stop: &AtomicBool,
-    build_version: &AtomicU64,
+    live_reload: &LiveReload,
 ) {
     let (tx, rx) = mpsc::channel();
 
@@ -320,21 +401,20 @@ fn watch_and_rebuild(
         }
     };
 
-    // Watch content, templates, static, public, and data directories
-    let dirs = [
-        &paths.content,
-        &paths.templates,
-        &paths.static_dir,
-        &paths.public_dir,
-        &paths.data_dir,
-    ];
-    for dir in &dirs {
-        if dir.exists() {
-            if let Err(e) = watcher.watch(dir, RecursiveMode::Recursive) {
-                human::error(&format!("Failed to watch {}: {e}", dir.display()));
-            }
-        }
+    let ServedSite {
+        mut config,
+        mut paths,
+    } = site.read().expect("served site lock poisoned").clone();
+
+    // `seite.toml` is watched through its directory, since editors often save
+    // by replacing the file
+    let root = paths.root.clone();
+    if let Err(e) = watcher.watch(&root, RecursiveMode::NonRecursive) {
+        human::error(&format!("Failed to watch {}: {e}", root.display()));
     }
+    let root_variants = path_variants(std::slice::from_ref(&root));
+    let mut watched = HashSet::new();
+    sync_watches(&mut watcher, &mut watched, &watch_dirs(&paths));
 
     // Debounce: wait for events, then pause briefly to batch rapid changes
     let debounce = Duration::from_millis(200);
@@ -342,19 +422,92 @@ fn watch_and_rebuild(
     while !stop.load(Ordering::Relaxed) {
         // Block until we get an event or timeout (so we can check `stop`)
         match rx.recv_timeout(Duration::from_secs(1)) {
-            Ok(Ok(_event)) => {
+            Ok(Ok(event)) => {
                 // Got a real fs event — drain any additional events within the debounce window
-                while rx.recv_timeout(debounce).is_ok() {}
+                let mut changed = event.paths;
+                while let Ok(next) = rx.recv_timeout(debounce) {
+                    if let Ok(next) = next {
+                        changed.extend(next.paths);
+                    }
+                }
+
+                // Other files next to `seite.toml` (the output dir, the build
+                // cache) are not site inputs
+                let config_changed = changed.iter().any(|p| is_config_file(p, &root_variants));
+                let dirs = watch_dirs(&paths);
+                let dir_variants = path_variants(&dirs);
+                changed.retain(|p| dir_variants.iter().any(|d| p.starts_with(d)));
+                if !config_changed && changed.is_empty() {
+                    continue;
+                }
 
-                human::info("Changes detected, rebuilding...");
-                let opts = BuildOptions { include_drafts };
-                match build::build_site(config, paths, &opts) {
+                // Set when `seite.toml` failed to reload: the edits batched
+                // with it are rebuilt with the last valid config, and the
+                // error stays on screen
+                let mut config_error = None;
+                let mut reloaded = false;
+                if config_changed {
+                    match load_config() {
+                        Ok((new_config, new_paths)) => {
+                            if new_paths.output != paths.output {
+                                human::info(&format!(
+                                    "Output directory is now {}",
+                                    new_paths.output.display()
+                                ));
+                            }
+                            config = new_config;
+                            paths = new_paths;
+                            sync_watches(&mut watcher, &mut watched, &watch_dirs(&paths));
+                            *site.write().expect("served site lock poisoned") = ServedSite {
+                                config: config.clone(),
+                                paths: paths.clone(),
+                            };
+                            reloaded = true;
+                            human::info("Reloaded seite.toml, rebuilding...");
+                        }
+                        Err(e) => {
+                            human::error(&format!("Failed to reload seite.toml: {e}"));
+                            human::info("Still serving with the last valid config");
+                            let mut info = BuildErrorInfo::from_error(&e, &root);
+                            info.file.get_or_insert_with(|| "seite.toml".into());
+                            if changed.is_empty() {
+                                live_reload.send(&LiveReloadEvent::BuildError(info));
+                                continue;
+                            }
+                            config_error = Some(info);
+                        }
+                    }
+                }
+                if !reloaded {
+                    // Directories created since the last build (e.g. a new `data/`)
+                    sync_watches(&mut watcher, &mut watched, &dirs);
+                    human::info("Changes detected, rebuilding...");
+                }
+
+                let opts = BuildOptions {
+                    include_drafts,
+                    as_of: None,
+                    incremental: true,
+                    cross_site_urls: HashMap::new(),
+                };
+                match build::build_site(&config, &paths, &opts) {
                     Ok(result) => {
-                        build_version.fetch_add(1, Ordering::Relaxed);
                         human::success(&result.stats.human_display());
+                        let event = if let Some(info) = config_error {
+                            LiveReloadEvent::BuildError(info)
+                        } else if reloaded {
+                            LiveReloadEvent::Reload
+                        } else {
+                            reload_event(&config, &paths, &changed)
+                        };
+                        live_reload.send(&event);
                     }
                     Err(e) => {
                         human::error(&format!("Rebuild failed: {e}"));
+                        live_reload.send(&LiveReloadEvent::BuildError(BuildErrorInfo::from_error(
+                            &e,
+                            &paths.root,
+                        )));
                     }
                 }
             }
@@ -372,6 +525,113 @@ fn watch_and_rebuild(
     }
 }
 
+/// Site input directories watched for changes: content, templates, static,
+/// public and data, plus shared workspace directories when serving a single
+/// workspace site.
+fn watch_dirs(paths: &ResolvedPaths) -> Vec<PathBuf> {
+    let mut dirs = vec![
+        paths.content.clone(),
+        paths.templates.clone(),
+        paths.static_dir.clone(),
+        paths.public_dir.clone(),
+        paths.data_dir.clone(),
+    ];
+    dirs.extend(paths.shared.data_dir.clone());
+    dirs.extend(paths.shared.static_dir.clone());
+    dirs.extend(paths.shared.templates.clone());
+    dirs
+}
+
+/// Watch the existing directories in `wanted` that are not watched yet, and
+/// stop watching the ones no longer wanted.
+pub(crate) fn sync_watches(
+    watcher: &mut impl Watcher,
+    watched: &mut HashSet<PathBuf>,
+    wanted: &[PathBuf],
+) {
+    let stale: Vec<PathBuf> = watched
+        .iter()
+        .filter(|dir| !wanted.contains(dir) || !dir.exists())
+        .cloned()
+        .collect();
+    for dir in stale {
+        let _ = watcher.unwatch(&dir);
+        watched.remove(&dir);
+    }
+    for dir in wanted {
+        if dir.exists() && !watched.contains(dir) {
+            match watcher.watch(dir, RecursiveMode::Recursive) {
+                Ok(()) => {
+                    watched.insert(dir.clone());
+                }
+                Err(e) => human::error(&format!("Failed to watch {}: {e}", dir.display())),
+            }
+        }
+    }
+}
+
+/// `dirs` plus their canonical forms. Watchers may report canonical paths
+/// (e.g. `/private/var` on macOS).
+pub(crate) fn path_variants(dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut variants = dirs.to_vec();
+    variants.extend(dirs.iter().filter_map(|d| fs::canonicalize(d).ok()));
+    variants
+}
+
+/// Whether `path` is the `seite.toml` in the project root (given with
+/// [`path_variants`]).
+pub(crate) fn is_config_file(path: &Path, roots: &[PathBuf]) -> bool {
+    path.file_name().is_some_and(|n| n == "seite.toml")
+        && path
+            .parent()
+            .is_some_and(|parent| roots.iter().any(|r| r == parent))
+}
+
+/// The event to send after a successful rebuild: `css-update` when only
+/// stylesheets in the static or public directories changed, `reload` otherwise.
+/// Fingerprinted stylesheets get new URLs, so they always reload the page.
+pub(crate) fn reload_event(
+    config: &SiteConfig,
+    paths: &ResolvedPaths,
+    changed: &[PathBuf],
+) -> LiveReloadEvent {
+    if config.build.fingerprint || changed.is_empty() {
+        return LiveReloadEvent::Reload;
+    }
+
+    let mut dirs = vec![(&paths.static_dir, "/static/"), (&paths.public_dir, "/")];
+    dirs.extend(paths.shared.static_dir.iter().map(|dir| (dir, "/static/")));
+    let roots: Vec<(PathBuf, &str)> = dirs
+        .into_iter()
+        .flat_map(|(dir, prefix)| {
+            path_variants(std::slice::from_ref(dir))
+                .into_iter()
+                .map(move |d| (d, prefix))
+        })
+        .collect();
+
+    let mut css_paths = Vec::new();
+    for path in changed {
+        if path.extension().and_then(|e| e.to_str()) != Some("css") {
+            return LiveReloadEvent::Reload;
+        }
+        let url = roots.iter().find_map(|(root, prefix)| {
+            let rel = path.strip_prefix(root).ok()?;
+            Some(format!(
+                "{prefix}{}",
+                rel.to_string_lossy().replace('\\', "/")
+            ))
+        });
+        match url {
+            Some(url) => css_paths.push(url),
+            None => return LiveReloadEvent::Reload,
+        }
+    }
+    css_paths.sort();
+    css_paths.dedup();
+    LiveReloadEvent::CssUpdate { paths: css_paths }
+}
+
 /// Check if a port is available by trying to connect to it.
 /// If the connection succeeds, something is already listening.
 fn port_is_available(port: u16) -> bool {
@@ -424,7 +684,7 @@ mod tests {
     fn test_livereload_script_contains_reload_endpoint() {
         assert!(
             LIVERELOAD_SCRIPT.contains("/__livereload"),
-            "script should poll the /__livereload endpoint"
+            "script should connect to the /__livereload endpoint"
         );
     }
 
@@ -432,7 +692,79 @@ mod tests {
     fn test_livereload_script_calls_location_reload() {
         assert!(
             LIVERELOAD_SCRIPT.contains("location.reload()"),
-            "script should trigger page reload on version change"
+            "script should trigger page reload on a reload event"
+        );
+    }
+
+    #[test]
+    fn test_livereload_script_handles_all_events() {
+        assert!(LIVERELOAD_SCRIPT.contains("new EventSource(\"/__livereload\")"));
+        for event in ["\"reload\"", "\"css-update\"", "\"build-error\""] {
+            assert!(
+                LIVERELOAD_SCRIPT.contains(&format!("addEventListener({event}")),
+                "script should listen for {event}"
+            );
+        }
+    }
+
+    // =========================================================================
+    // reload_event
+    // =========================================================================
+
+    const TEST_CONFIG: &str = "collections = []\n[site]\ntitle = \"T\"\ndescription = \"\"\nbase_url = \"http://localhost\"\nlanguage = \"en\"\nauthor = \"\"\n";
+
+    fn test_served_site() -> Arc<RwLock<ServedSite>> {
+        let config: SiteConfig = toml::from_str(TEST_CONFIG).unwrap();
+        let paths = config.resolve_paths(Path::new("/nonexistent"));
+        Arc::new(RwLock::new(ServedSite { config, paths }))
+    }
+
+    fn reload_test_site(tmp: &TempDir) -> (SiteConfig, ResolvedPaths) {
+        let config: SiteConfig = toml::from_str(TEST_CONFIG).unwrap();
+        let paths = config.resolve_paths(tmp.path());
+        (config, paths)
+    }
+
+    #[test]
+    fn test_reload_event_css_only_changes() {
+        let tmp = TempDir::new().unwrap();
+        let (config, paths) = reload_test_site(&tmp);
+        let changed = vec![
+            paths.static_dir.join("css/main.css"),
+            paths.public_dir.join("print.css"),
+            paths.static_dir.join("css/main.css"),
+        ];
+        assert_eq!(
+            reload_event(&config, &paths, &changed),
+            LiveReloadEvent::CssUpdate {
+                paths: vec!["/print.css".into(), "/static/css/main.css".into()]
+            }
+        );
+    }
+
+    #[test]
+    fn test_reload_event_other_changes_reload() {
+        let tmp = TempDir::new().unwrap();
+        let (mut config, paths) = reload_test_site(&tmp);
+        let css = paths.static_dir.join("main.css");
+
+        // Any non-stylesheet change needs a full reload
+        let changed = vec![css.clone(), paths.content.join("posts/a.md")];
+        assert_eq!(
+            reload_event(&config, &paths, &changed),
+            LiveReloadEvent::Reload
+        );
+        // A stylesheet outside the static directories is a template asset
+        let changed = vec![paths.templates.join("theme.css")];
+        assert_eq!(
+            reload_event(&config, &paths, &changed),
+            LiveReloadEvent::Reload
+        );
+        // Fingerprinted stylesheets change URL
+        config.build.fingerprint = true;
+        assert_eq!(
+            reload_event(&config, &paths, &[css]),
+            LiveReloadEvent::Reload
         );
     }
 
@@ -445,6 +777,7 @@ mod tests {
         let handle = ServerHandle {
             stop: Arc::new(AtomicBool::new(false)),
             port: 3000,
+            site: test_served_site(),
         };
         assert_eq!(handle.port(), 3000);
     }
@@ -455,6 +788,7 @@ mod tests {
         let handle = ServerHandle {
             stop: stop.clone(),
             port: 3000,
+            site: test_served_site(),
         };
         assert!(!stop.load(Ordering::Relaxed), "should start as not stopped");
         handle.stop();
@@ -464,6 +798,173 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_livereload_endpoint_streams_events() {
+        use std::io::{Read, Write};
+
+        let tmp = TempDir::new().unwrap();
+        let (config, paths) = reload_test_site(&tmp);
+        let handle = start(&config, &paths, test_loader(&tmp), 38_400, false, true).unwrap();
+
+        let mut stream = TcpStream::connect(("127.0.0.1", handle.port())).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        stream
+            .write_all(b"GET /__livereload HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+
+        let mut received = String::new();
+        let mut buf = [0u8; 256];
+        while !received.contains("retry: 1000\n\n") {
+            let n = stream.read(&mut buf).unwrap();
+            assert!(n > 0, "stream closed early: {received}");
+            received.push_str(&String::from_utf8_lossy(&buf[..n]));
+        }
+        assert!(received.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(received.contains("Content-Type: text/event-stream"));
+        handle.stop();
+    }
+
+    #[test]
+    fn test_redirect_stub_served_as_301() {
+        use std::io::{Read, Write};
+
+        let tmp = TempDir::new().unwrap();
+        let (config, paths) = reload_test_site(&tmp);
+        fs::create_dir_all(paths.output.join("old")).unwrap();
+        fs::write(
+            paths.output.join("old/page.html"),
+            crate::build::redirects::stub_html("/docs/new"),
+        )
+        .unwrap();
+        let handle = start(&config, &paths, test_loader(&tmp), 38_450, false, true).unwrap();
+
+        let mut stream = TcpStream::connect(("127.0.0.1", handle.port())).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        stream
+            .write_all(b"GET /old/page HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .unwrap();
+        let mut received = String::new();
+        stream.read_to_string(&mut received).unwrap();
+        assert!(received.starts_with("HTTP/1.1 301"), "{received}");
+        assert!(received.contains("Location: /docs/new\r\n"));
+        handle.stop();
+    }
+
+    #[test]
+    fn test_redirect_response_only_for_stubs() {
+        let stub = crate::build::redirects::stub_html("/new");
+        assert!(redirect_response("text/html; charset=utf-8", stub.as_bytes()).is_some());
+        assert!(redirect_response("text/plain; charset=utf-8", stub.as_bytes()).is_none());
+        assert!(redirect_response("text/html; charset=utf-8", b"<html></html>").is_none());
+    }
+
+    fn test_loader(tmp: &TempDir) -> ConfigLoader {
+        let root = tmp.path().to_path_buf();
+        Box::new(move || {
+            let config = SiteConfig::load(&root.join("seite.toml"))?;
+            let paths = config.resolve_paths(&root);
+            Ok((config, paths))
+        })
+    }
+
+    #[test]
+    fn test_config_change_reloads_and_keeps_last_good_config() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("seite.toml"), TEST_CONFIG).unwrap();
+        let (config, paths) = reload_test_site(&tmp);
+        let handle = start(&config, &paths, test_loader(&tmp), 38_500, false, true).unwrap();
+        // Let the watcher register before editing
+        std::thread::sleep(Duration::from_millis(300));
+
+        // An invalid config is reported and the previous one kept
+        fs::write(tmp.path().join("seite.toml"), "[site\n").unwrap();
+        std::thread::sleep(Duration::from_millis(1500));
+        assert_eq!(handle.site().paths.output, tmp.path().join("dist"));
+
+        fs::write(
+            tmp.path().join("seite.toml"),
+            format!("{TEST_CONFIG}[build]\noutput_dir = \"public-out\"\n"),
+        )
+        .unwrap();
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while handle.site().paths.output != tmp.path().join("public-out") {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "config was not reloaded"
+            );
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        handle.stop();
+    }
+
+    #[test]
+    fn test_invalid_config_still_rebuilds_batched_edits() {
+        let tmp = TempDir::new().unwrap();
+        let mut config: SiteConfig = toml::from_str(TEST_CONFIG).unwrap();
+        config.collections = vec![crate::config::CollectionConfig::preset_posts()];
+        fs::write(
+            tmp.path().join("seite.toml"),
+            toml::to_string(&config).unwrap(),
+        )
+        .unwrap();
+        let posts = tmp.path().join("content/posts");
+        fs::create_dir_all(&posts).unwrap();
+        let post = posts.join("2025-01-01-hello.md");
+        fs::write(&post, "---\ntitle: Hello\n---\n\nFirst draft.\n").unwrap();
+        let paths = config.resolve_paths(tmp.path());
+        let opts = BuildOptions {
+            include_drafts: false,
+            as_of: None,
+            incremental: true,
+            cross_site_urls: HashMap::new(),
+        };
+        build::build_site(&config, &paths, &opts).unwrap();
+        let handle = start(&config, &paths, test_loader(&tmp), 38_520, false, true).unwrap();
+        std::thread::sleep(Duration::from_millis(300));
+
+        // Both land in the same debounce window
+        fs::write(tmp.path().join("seite.toml"), "[site\n").unwrap();
+        fs::write(&post, "---\ntitle: Hello\n---\n\nSecond draft.\n").unwrap();
+        let output = paths.output.join("posts/hello.html");
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while !fs::read_to_string(&output)
+            .unwrap_or_default()
+            .contains("Second draft.")
+        {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "post edit was not rebuilt"
+            );
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        assert_eq!(handle.site().paths.output, tmp.path().join("dist"));
+        handle.stop();
+    }
+
+    #[test]
+    fn test_is_config_file() {
+        let roots = vec![PathBuf::from("/site")];
+        assert!(is_config_file(Path::new("/site/seite.toml"), &roots));
+        assert!(!is_config_file(
+            Path::new("/site/content/seite.toml"),
+            &roots
+        ));
+        assert!(!is_config_file(Path::new("/site/seite.toml.swp"), &roots));
+    }
+
+    #[test]
+    fn test_watch_dirs_include_shared_dirs() {
+        let tmp = TempDir::new().unwrap();
+        let (_, mut paths) = reload_test_site(&tmp);
+        assert_eq!(watch_dirs(&paths).len(), 5);
+        paths.shared.data_dir = Some(PathBuf::from("/ws/data"));
+        assert!(watch_dirs(&paths).contains(&PathBuf::from("/ws/data")));
+    }
+
     #[test]
     fn test_server_handle_drop_stops() {
         let stop = Arc::new(AtomicBool::new(false));
@@ -471,6 +972,7 @@ mod tests {
             let _handle = ServerHandle {
                 stop: stop.clone(),
                 port: 3000,
+                site: test_served_site(),
             };
             assert!(!stop.load(Ordering::Relaxed));
         } // handle dropped here
@@ -486,6 +988,7 @@ mod tests {
         let handle = ServerHandle {
             stop: stop.clone(),
             port: 3000,
+            site: test_served_site(),
         };
         handle.stop();
         handle.stop();
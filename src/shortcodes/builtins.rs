@@ -2,6 +2,8 @@
 pub struct BuiltinShortcode {
     pub name: &'static str,
     pub template: &'static str,
+    /// Markdown rendering used in the `.md` page copies and `llms-full.txt`.
+    pub markdown: &'static str,
     /// Whether this shortcode uses body syntax (`{{% name() %}}...{{% end %}}`).
     pub is_body: bool,
 }
@@ -12,31 +14,37 @@ pub fn all() -> Vec<BuiltinShortcode> {
         BuiltinShortcode {
             name: "youtube",
             template: include_str!("builtins/youtube.html"),
+            markdown: include_str!("builtins/youtube.md"),
             is_body: false,
         },
         BuiltinShortcode {
             name: "vimeo",
             template: include_str!("builtins/vimeo.html"),
+            markdown: include_str!("builtins/vimeo.md"),
             is_body: false,
         },
         BuiltinShortcode {
             name: "gist",
             template: include_str!("builtins/gist.html"),
+            markdown: include_str!("builtins/gist.md"),
             is_body: false,
         },
         BuiltinShortcode {
             name: "callout",
             template: include_str!("builtins/callout.html"),
+            markdown: include_str!("builtins/callout.md"),
             is_body: true,
         },
         BuiltinShortcode {
             name: "figure",
             template: include_str!("builtins/figure.html"),
+            markdown: include_str!("builtins/figure.md"),
             is_body: false,
         },
         BuiltinShortcode {
             name: "contact_form",
             template: include_str!("builtins/contact_form.html"),
+            markdown: include_str!("builtins/contact_form.md"),
             is_body: false,
         },
     ]
@@ -15,57 +15,78 @@ use crate::error::{PageError, Result};
 pub struct ShortcodeRegistry {
     tera: tera::Tera,
     known: HashSet<String>,
+    /// Shortcodes with a markdown rendering (`shortcodes/<name>.md`).
+    markdown: HashSet<String>,
 }
 
 impl ShortcodeRegistry {
     /// Create a new registry by loading built-in shortcodes and any user-defined
     /// shortcode templates from the given directory (`templates/shortcodes/`).
+    ///
+    /// A user `.html` template without a matching `.md` drops the built-in
+    /// markdown rendering of the same name, since it may not fit the new
+    /// template's arguments.
     pub fn new(shortcodes_dir: &Path) -> Result<Self> {
         let mut tera = tera::Tera::default();
         tera.autoescape_on(vec![]); // disable auto-escaping for shortcode HTML output
         let mut known = HashSet::new();
+        let mut markdown = HashSet::new();
 
         // Load built-in shortcodes
         for builtin in builtins::all() {
-            let template_name = format!("shortcodes/{}.html", builtin.name);
-            tera.add_raw_template(&template_name, builtin.template)
-                .map_err(|e| {
-                    PageError::Build(format!("built-in shortcode '{}': {e}", builtin.name))
-                })?;
+            for (ext, template) in [("html", builtin.template), ("md", builtin.markdown)] {
+                let template_name = format!("shortcodes/{}.{ext}", builtin.name);
+                tera.add_raw_template(&template_name, template)
+                    .map_err(|e| {
+                        PageError::Build(format!("built-in shortcode '{}': {e}", builtin.name))
+                    })?;
+            }
             known.insert(builtin.name.to_string());
+            markdown.insert(builtin.name.to_string());
         }
 
         // Load user-defined shortcodes (override built-ins with same name)
         if shortcodes_dir.exists() {
+            let mut user_html = HashSet::new();
+            let mut user_md = HashSet::new();
             for entry in std::fs::read_dir(shortcodes_dir)? {
                 let entry = entry?;
                 let path = entry.path();
-                if path.extension().and_then(|e| e.to_str()) == Some("html") {
-                    let name = path
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .ok_or_else(|| {
-                            PageError::Build(format!(
-                                "invalid shortcode filename: {}",
-                                path.display()
-                            ))
-                        })?
-                        .to_string();
-                    let content = std::fs::read_to_string(&path)?;
-                    let template_name = format!("shortcodes/{}.html", name);
-                    tera.add_raw_template(&template_name, &content)
-                        .map_err(|e| {
-                            PageError::Build(format!(
-                                "shortcode template '{}': {e}",
-                                path.display()
-                            ))
-                        })?;
-                    known.insert(name);
+                let ext = match path.extension().and_then(|e| e.to_str()) {
+                    Some(ext @ ("html" | "md")) => ext,
+                    _ => continue,
+                };
+                let name = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .ok_or_else(|| {
+                        PageError::Build(format!("invalid shortcode filename: {}", path.display()))
+                    })?
+                    .to_string();
+                let content = std::fs::read_to_string(&path)?;
+                let template_name = format!("shortcodes/{}.{ext}", name);
+                tera.add_raw_template(&template_name, &content)
+                    .map_err(|e| {
+                        PageError::Build(format!("shortcode template '{}': {e}", path.display()))
+                    })?;
+                if ext == "html" {
+                    user_html.insert(name);
+                } else {
+                    user_md.insert(name);
                 }
             }
+            for name in user_html.difference(&user_md) {
+                markdown.remove(name);
+            }
+            markdown.extend(user_md);
+            known.extend(user_html);
         }
 
-        Ok(Self { tera, known })
+        Ok(Self {
+            tera,
+            known,
+            markdown,
+        })
     }
 
     /// Returns true if there are any registered shortcodes.
@@ -86,6 +107,53 @@ impl ShortcodeRegistry {
         source_path: &Path,
         page_context: &serde_json::Value,
         site_context: &serde_json::Value,
+    ) -> Result<String> {
+        self.expand_with(input, source_path, |call| {
+            let template_name = format!("shortcodes/{}.html", call.name);
+            self.render_shortcode(
+                &template_name,
+                call,
+                source_path,
+                page_context,
+                site_context,
+            )
+        })
+    }
+
+    /// Expand shortcodes to their markdown renderings, for the `.md` page
+    /// copies and `llms-full.txt`.
+    ///
+    /// Shortcodes without a markdown rendering are left as written.
+    pub fn expand_markdown(
+        &self,
+        input: &str,
+        source_path: &Path,
+        page_context: &serde_json::Value,
+        site_context: &serde_json::Value,
+    ) -> Result<String> {
+        self.expand_with(input, source_path, |call| {
+            if !self.markdown.contains(&call.name) {
+                return Ok(input[call.span.0..call.span.1].to_string());
+            }
+            let template_name = format!("shortcodes/{}.md", call.name);
+            let rendered = self.render_shortcode(
+                &template_name,
+                call,
+                source_path,
+                page_context,
+                site_context,
+            )?;
+            Ok(rendered.trim_end().to_string())
+        })
+    }
+
+    /// Validate every shortcode call in `input` and replace each with the
+    /// output of `render`.
+    fn expand_with(
+        &self,
+        input: &str,
+        source_path: &Path,
+        render: impl Fn(&ShortcodeCall) -> Result<String>,
     ) -> Result<String> {
         let calls = parser::parse_shortcodes(input, source_path)?;
 
@@ -113,22 +181,22 @@ impl ShortcodeRegistry {
         // Replace spans back-to-front so byte offsets stay valid
         let mut output = input.to_string();
         for call in calls.iter().rev() {
-            let rendered = self.render_shortcode(call, source_path, page_context, site_context)?;
+            let rendered = render(call)?;
             output.replace_range(call.span.0..call.span.1, &rendered);
         }
 
         Ok(output)
     }
 
-    /// Render a single shortcode call using its Tera template.
+    /// Render a single shortcode call using the named Tera template.
     fn render_shortcode(
         &self,
+        template_name: &str,
         call: &ShortcodeCall,
         source_path: &Path,
         page_context: &serde_json::Value,
         site_context: &serde_json::Value,
     ) -> Result<String> {
-        let template_name = format!("shortcodes/{}.html", call.name);
         let mut ctx = tera::Context::new();
 
         // Insert all named arguments
@@ -146,7 +214,7 @@ impl ShortcodeRegistry {
         ctx.insert("site", site_context);
 
         self.tera
-            .render(&template_name, &ctx)
+            .render(template_name, &ctx)
             .map_err(|e| PageError::Shortcode {
                 path: source_path.to_path_buf(),
                 line: call.line,
@@ -400,6 +468,50 @@ mod tests {
         assert!(!registry.is_empty());
     }
 
+    #[test]
+    fn test_expand_markdown_builtins() {
+        let registry = test_registry();
+        let (page, site) = empty_contexts();
+        let input = "Intro\n\n{{< youtube(id=\"abc\", title=\"Demo\") >}}\n\n{{% callout(type=\"tip\") %}}\nUse **this**.\n\nAnd that.\n{{% end %}}\n\n{{< figure(src=\"/static/a.png\", alt=\"A\", caption=\"Cap\") >}}\n\nOutro";
+        let result = registry
+            .expand_markdown(input, &PathBuf::from("test.md"), &page, &site)
+            .unwrap();
+        assert_eq!(
+            result,
+            "Intro\n\n[Demo](https://www.youtube.com/watch?v=abc)\n\n> **Tip**\n>\n> Use **this**.\n>\n> And that.\n\n![A](/static/a.png)\n\n*Cap*\n\nOutro"
+        );
+    }
+
+    #[test]
+    fn test_expand_markdown_user_templates() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let sc_dir = tmp.path().join("shortcodes");
+        std::fs::create_dir(&sc_dir).unwrap();
+        std::fs::write(sc_dir.join("badge.html"), "<b>{{ text }}</b>").unwrap();
+        std::fs::write(sc_dir.join("badge.md"), "**{{ text }}**\n").unwrap();
+        std::fs::write(sc_dir.join("chart.html"), "<canvas></canvas>").unwrap();
+        std::fs::write(sc_dir.join("youtube.html"), "<custom>{{ id }}</custom>").unwrap();
+
+        let registry = ShortcodeRegistry::new(&sc_dir).unwrap();
+        let (page, site) = empty_contexts();
+        let input = r#"{{< badge(text="new") >}} {{< chart() >}} {{< youtube(id="x") >}}"#;
+        let result = registry
+            .expand_markdown(input, &PathBuf::from("test.md"), &page, &site)
+            .unwrap();
+        // No markdown rendering (and the built-in one is dropped for an
+        // overridden youtube): left as written
+        assert_eq!(result, r#"**new** {{< chart() >}} {{< youtube(id="x") >}}"#);
+    }
+
+    #[test]
+    fn test_expand_markdown_unknown_shortcode_errors() {
+        let registry = test_registry();
+        let (page, site) = empty_contexts();
+        let input = r#"{{< nonexistent(x="y") >}}"#;
+        let result = registry.expand_markdown(input, &PathBuf::from("test.md"), &page, &site);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_user_shortcode_non_html_skipped() {
         let tmp = tempfile::TempDir::new().unwrap();
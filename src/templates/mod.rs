@@ -1,6 +1,7 @@
 use std::path::Path;
 
 use crate::config::CollectionConfig;
+use crate::content::Timestamp;
 use crate::error::Result;
 use crate::themes;
 
@@ -23,7 +24,7 @@ pub const DEFAULT_INDEX: &str = r#"{% extends "base.html" %}
     {% for item in collection.items %}
     <article>
         <h3><a href="{{ item.url }}">{{ item.title }}</a></h3>
-        {% if item.date %}<time>{{ item.date }}</time>{% endif %}
+        {% if item.date %}<time>{{ item.date | date }}</time>{% endif %}
         {% if item.description %}<p>{{ item.description }}</p>{% elif item.excerpt %}<div class="excerpt">{{ item.excerpt | safe }}</div>{% endif %}
     </article>
     {% endfor %}
@@ -49,7 +50,7 @@ pub const DEFAULT_POST: &str = r#"{% extends "base.html" %}
 {% block content %}
 <article>
     <h1>{{ page.title }}</h1>
-    {% if page.date %}<time>{{ page.date }}</time>{% endif %}
+    {% if page.date %}<time>{{ page.date | date }}</time>{% endif %}
     {% if page.reading_time %}<span class="reading-time">{{ page.reading_time }} {{ t.min_read }}</span>{% endif %}
     {% if page.tags | length > 0 %}
     <div class="tags">
@@ -64,6 +65,11 @@ pub const DEFAULT_DOC: &str = r##"{% extends "base.html" %}
 {% block title %}{{ page.title }} - {{ site.title }}{% endblock %}
 {% block content %}
 <article>
+    {% if breadcrumbs | length > 1 %}
+    <nav class="breadcrumbs" aria-label="{{ t.breadcrumb }}">
+        {% for crumb in breadcrumbs %}{% if loop.last %}<span aria-current="page">{{ crumb.title }}</span>{% elif crumb.url %}<a href="{{ crumb.url }}">{{ crumb.title }}</a> / {% else %}{{ crumb.title }} / {% endif %}{% endfor %}
+    </nav>
+    {% endif %}
     <h1>{{ page.title }}</h1>
     {% if page.toc | length > 1 %}
     <nav class="toc">
@@ -74,6 +80,12 @@ pub const DEFAULT_DOC: &str = r##"{% extends "base.html" %}
     </nav>
     {% endif %}
     <div class="content">{{ page.content | safe }}</div>
+    {% if prev or next %}
+    <nav class="doc-nav" aria-label="{{ t.doc_pagination }}">
+        {% if prev %}<a href="{{ prev.url }}" rel="prev">&larr; {{ prev.title }}</a>{% endif %}
+        {% if next %}<a href="{{ next.url }}" rel="next">{{ next.title }} &rarr;</a>{% endif %}
+    </nav>
+    {% endif %}
 </article>
 {% endblock %}"##;
 
@@ -98,10 +110,13 @@ pub const DEFAULT_DOCS_INDEX: &str = r##"{% extends "base.html" %}
 {% if nav %}
 {% for section in nav %}
 <section class="docs-section-overview">
-    {% if section.label %}<h2>{{ section.label }}</h2>{% endif %}
+    {% if section.label %}<h2>{% if section.url %}<a href="{{ section.url }}">{{ section.label }}</a>{% else %}{{ section.label }}{% endif %}</h2>{% endif %}
+    {% if section.description %}<p>{{ section.description }}</p>{% endif %}
     <ul class="docs-section-list">
     {% for item in section.items %}
-        <li><a href="{{ item.url }}">{{ item.title }}</a></li>
+        <li>{% if item.url %}<a href="{{ item.url }}">{{ item.title }}</a>{% else %}{{ item.title }}{% endif %}{% if item.description %} — {{ item.description }}{% endif %}
+        {% if item.items %}<ul>{% for child in item.items %}<li>{% if child.url %}<a href="{{ child.url }}">{{ child.title }}</a>{% else %}{{ child.title }}{% endif %}</li>{% endfor %}</ul>{% endif %}
+        </li>
     {% endfor %}
     </ul>
 </section>
@@ -153,15 +168,75 @@ pub const DEFAULT_TAG: &str = r##"{% extends "base.html" %}
     {% for item in items %}
     <article>
         <h3><a href="{{ item.url }}">{{ item.title }}</a></h3>
-        {% if item.date %}<time>{{ item.date }}</time>{% endif %}
+        {% if item.date %}<time>{{ item.date | date }}</time>{% endif %}
         {% if item.reading_time %}<span class="reading-time">{{ item.reading_time }} {{ t.min_read }}</span>{% endif %}
         {% if item.description %}<p>{{ item.description }}</p>{% elif item.excerpt %}<div class="excerpt">{{ item.excerpt | safe }}</div>{% endif %}
     </article>
     {% endfor %}
 </div>
+{% if feeds %}<p class="tag-feeds">{% for feed in feeds %}<a href="{{ feed.url }}" type="{{ feed.mime_type }}">{% if feed.format == "rss" %}RSS{% elif feed.format == "atom" %}Atom{% else %}JSON Feed{% endif %}</a>{% if not loop.last %} &middot; {% endif %}{% endfor %}</p>{% endif %}
 <p><a href="{{ tags_url }}">&larr; {{ t.all_tags }}</a></p>
 {% endblock %}"##;
 
+pub const DEFAULT_TAXONOMY: &str = r#"{% extends "base.html" %}
+{% block title %}{{ taxonomy.label }} — {{ site.title }}{% endblock %}
+{% block content %}
+<h1>{{ taxonomy.label }}</h1>
+<div class="tags-index taxonomy-index">
+    {% for term in terms %}
+    <a href="{{ term.url }}" class="tag-link">{{ term.name }} <span class="tag-count">({{ term.count }})</span></a>
+    {% endfor %}
+</div>
+{% endblock %}"#;
+
+pub const DEFAULT_TAXONOMY_TERM: &str = r##"{% extends "base.html" %}
+{% block title %}{{ taxonomy.label }}: {{ term.name }}{% if pagination and pagination.current_page > 1 %} — {{ t.page_n_of_total | replace(from="{n}", to=pagination.current_page ~ "") | replace(from="{total}", to=pagination.total_pages ~ "") }}{% endif %} — {{ site.title }}{% endblock %}
+{% block content %}
+<h1>{{ term.name }}</h1>
+{% if term.feed_url %}<p><a href="{{ term.feed_url }}" class="taxonomy-feed">RSS</a></p>{% endif %}
+<div class="tag-items">
+    {% for item in items %}
+    <article>
+        <h3><a href="{{ item.url }}">{{ item.title }}</a></h3>
+        {% if item.date %}<time>{{ item.date | date }}</time>{% endif %}
+        {% if item.reading_time %}<span class="reading-time">{{ item.reading_time }} {{ t.min_read }}</span>{% endif %}
+        {% if item.description %}<p>{{ item.description }}</p>{% elif item.excerpt %}<div class="excerpt">{{ item.excerpt | safe }}</div>{% endif %}
+    </article>
+    {% endfor %}
+</div>
+{% if pagination %}
+<nav class="pagination">
+    {% if pagination.prev_url %}<a href="{{ pagination.prev_url }}">&larr; {{ t.newer }}</a>{% endif %}
+    <span>{{ t.page_n_of_total | replace(from="{n}", to=pagination.current_page ~ "") | replace(from="{total}", to=pagination.total_pages ~ "") }}</span>
+    {% if pagination.next_url %}<a href="{{ pagination.next_url }}">{{ t.older }} &rarr;</a>{% endif %}
+</nav>
+{% endif %}
+<p><a href="{{ taxonomy.url }}">&larr; {{ taxonomy.label }}</a></p>
+{% endblock %}"##;
+
+pub const DEFAULT_AUTHOR: &str = r##"{% extends "base.html" %}
+{% block title %}{{ author.name }} — {{ site.title }}{% endblock %}
+{% block content %}
+<header class="author-profile">
+    {% if author.avatar %}<img class="author-avatar" src="{{ author.avatar }}" alt="{{ author.name }}" width="96" height="96">{% endif %}
+    <h1>{{ author.name }}</h1>
+    {% if author.bio %}<p class="author-bio">{{ author.bio }}</p>{% endif %}
+    <p class="author-links">
+        {% for network, link in author.social %}<a href="{{ link }}" rel="me">{{ network }}</a> {% endfor %}
+        <a href="{{ author.feed_url }}">RSS</a>
+    </p>
+</header>
+<div class="tag-items">
+    {% for item in items %}
+    <article>
+        <h3><a href="{{ item.url }}">{{ item.title }}</a></h3>
+        {% if item.date %}<time>{{ item.date | date }}</time>{% endif %}
+        {% if item.description %}<p>{{ item.description }}</p>{% elif item.excerpt %}<div class="excerpt">{{ item.excerpt | safe }}</div>{% endif %}
+    </article>
+    {% endfor %}
+</div>
+{% endblock %}"##;
+
 pub const DEFAULT_CHANGELOG_ENTRY: &str = r#"{% extends "base.html" %}
 {% block title %}{{ page.title }} — {{ t.changelog }} — {{ site.title }}{% endblock %}
 {% block content %}
@@ -170,7 +245,7 @@ pub const DEFAULT_CHANGELOG_ENTRY: &str = r#"{% extends "base.html" %}
     <header class="changelog-entry-header">
         <h1>{{ page.title }}</h1>
         <div class="changelog-meta">
-            {% if page.date %}<time datetime="{{ page.date }}">{{ page.date }}</time>{% endif %}
+            {% if page.date %}<time datetime="{{ page.date }}">{{ page.date | date }}</time>{% endif %}
             {% if page.tags | length > 0 %}
             <div class="changelog-tags">
                 {% for tag in page.tags %}<span class="changelog-tag changelog-tag--{{ tag | slugify }}">{{ tag }}</span>{% endfor %}
@@ -198,7 +273,7 @@ pub const DEFAULT_CHANGELOG_INDEX: &str = r#"{% extends "base.html" %}
     <article class="changelog-item">
         <div class="changelog-item-header">
             <h2><a href="{{ item.url }}">{{ item.title }}</a></h2>
-            {% if item.date %}<time datetime="{{ item.date }}">{{ item.date }}</time>{% endif %}
+            {% if item.date %}<time datetime="{{ item.date }}">{{ item.date | date }}</time>{% endif %}
         </div>
         {% if item.tags | length > 0 %}
         <div class="changelog-tags">
@@ -494,7 +569,7 @@ pub const DEFAULT_TRUST_INDEX: &str = r##"{% extends "base.html" %}
 </div>
 {% endblock %}"##;
 
-fn get_default_template(name: &str) -> Option<&'static str> {
+pub(crate) fn get_default_template(name: &str) -> Option<&'static str> {
     match name {
         "base.html" => Some(default_base()),
         "index.html" => Some(DEFAULT_INDEX),
@@ -507,6 +582,9 @@ fn get_default_template(name: &str) -> Option<&'static str> {
         "404.html" => Some(DEFAULT_404),
         "tags.html" => Some(DEFAULT_TAGS_INDEX),
         "tag.html" => Some(DEFAULT_TAG),
+        "taxonomy.html" => Some(DEFAULT_TAXONOMY),
+        "taxonomy-term.html" => Some(DEFAULT_TAXONOMY_TERM),
+        "author.html" => Some(DEFAULT_AUTHOR),
         "changelog-entry.html" => Some(DEFAULT_CHANGELOG_ENTRY),
         "changelog-index.html" => Some(DEFAULT_CHANGELOG_INDEX),
         "roadmap-item.html" => Some(DEFAULT_ROADMAP_ITEM),
@@ -518,12 +596,24 @@ fn get_default_template(name: &str) -> Option<&'static str> {
 }
 
 /// Load Tera templates from the user's template directory, falling back to
+/// templates in `shared_dir` (a workspace's shared templates) and then to
 /// embedded defaults for any template not provided.
-pub fn load_templates(template_dir: &Path, collections: &[CollectionConfig]) -> Result<tera::Tera> {
+pub fn load_templates(
+    template_dir: &Path,
+    shared_dir: Option<&Path>,
+    collections: &[CollectionConfig],
+) -> Result<tera::Tera> {
     #[allow(clippy::manual_unwrap_or_default)]
     let mut tera = if template_dir.exists() {
         let glob_pattern = format!("{}/**/*.html", template_dir.display());
-        match tera::Tera::new(&glob_pattern) {
+        // With a shared layer, site templates may extend shared ones, so the
+        // inheritance chains are only built once every layer is registered.
+        let parsed = if shared_dir.is_some() {
+            tera::Tera::parse(&glob_pattern)
+        } else {
+            tera::Tera::new(&glob_pattern)
+        };
+        match parsed {
             Ok(t) => t,
             Err(e) => {
                 eprintln!("⚠ Warning: failed to parse user templates, using defaults: {e}");
@@ -539,41 +629,151 @@ pub fn load_templates(template_dir: &Path, collections: &[CollectionConfig]) ->
     // in href attributes (e.g. `href="&#x2F;posts"` instead of `href="/posts"`).
     tera.autoescape_on(vec![]);
 
-    // Always ensure essential templates exist
-    for name in [
+    // Shared templates fill in anything the site does not define
+    let mut fallbacks = match shared_dir {
+        Some(dir) => read_shared_templates(dir, &tera)?,
+        None => Vec::new(),
+    };
+
+    // Always ensure essential templates exist, plus each collection's default
+    // template and collection-specific index template (e.g., trust-index.html)
+    let mut wanted: Vec<String> = [
         "base.html",
         "index.html",
         "404.html",
         "tags.html",
         "tag.html",
+        "taxonomy.html",
+        "taxonomy-term.html",
+        "author.html",
         "roadmap-kanban.html",
         "roadmap-timeline.html",
-    ] {
-        if tera.get_template(name).is_err() {
-            if let Some(content) = get_default_template(name) {
-                tera.add_raw_template(name, content)?;
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    for collection in collections {
+        wanted.push(collection.default_template.clone());
+        wanted.push(format!("{}-index.html", collection.name));
+    }
+    for name in wanted {
+        let defined = tera.get_template(&name).is_ok() || fallbacks.iter().any(|(n, _)| *n == name);
+        if !defined {
+            if let Some(content) = get_default_template(&name) {
+                fallbacks.push((name, content.to_string()));
             }
         }
     }
 
-    // Register default template for each collection's default_template
-    for collection in collections {
-        let tmpl_name = &collection.default_template;
-        if tera.get_template(tmpl_name).is_err() {
-            if let Some(content) = get_default_template(tmpl_name) {
-                tera.add_raw_template(tmpl_name, content)?;
-            }
-        }
-        // Also register collection-specific index templates (e.g., trust-index.html)
-        let index_tmpl = format!("{}-index.html", collection.name);
-        if tera.get_template(&index_tmpl).is_err() {
-            if let Some(content) = get_default_template(&index_tmpl) {
-                tera.add_raw_template(&index_tmpl, content)?;
-            }
+    // Added in one batch so shared templates can extend embedded defaults
+    tera.add_raw_templates(fallbacks)?;
+
+    Ok(tera)
+}
+
+/// Read `.html` templates from a shared template directory, skipping any
+/// name already defined by the site.
+fn read_shared_templates(dir: &Path, tera: &tera::Tera) -> Result<Vec<(String, String)>> {
+    let mut templates = Vec::new();
+    if !dir.exists() {
+        return Ok(templates);
+    }
+    for entry in walkdir::WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_type().is_file() && e.path().extension().is_some_and(|ext| ext == "html")
+        })
+    {
+        let name = entry
+            .path()
+            .strip_prefix(dir)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        if tera.get_template(&name).is_ok() {
+            continue;
         }
+        let content = std::fs::read_to_string(entry.path())?;
+        templates.push((name, content));
     }
+    Ok(templates)
+}
 
-    Ok(tera)
+/// The `date` filter, replacing Tera's built-in one so datetimes show in the
+/// site's timezone: `{{ page.date | date(format="%B %e, %Y %H:%M") }}`.
+///
+/// Accepts a frontmatter date or RFC 3339 datetime string, or a Unix
+/// timestamp. `timezone="America/New_York"` overrides `site.timezone`.
+/// Dates without a time are formatted as written.
+pub struct DateFilter {
+    tz: chrono_tz::Tz,
+}
+
+impl DateFilter {
+    pub fn new(tz: chrono_tz::Tz) -> Self {
+        Self { tz }
+    }
+}
+
+impl tera::Filter for DateFilter {
+    fn filter(
+        &self,
+        value: &tera::Value,
+        args: &std::collections::HashMap<String, tera::Value>,
+    ) -> tera::Result<tera::Value> {
+        let format = match args.get("format") {
+            Some(f) => f
+                .as_str()
+                .ok_or_else(|| tera::Error::msg("date filter: `format` must be a string"))?,
+            None => "%Y-%m-%d",
+        };
+        let items: Vec<chrono::format::Item> = chrono::format::StrftimeItems::new(format).collect();
+        if items.contains(&chrono::format::Item::Error) {
+            return Err(tera::Error::msg(format!(
+                "date filter: invalid format '{format}'"
+            )));
+        }
+        let tz = match args.get("timezone") {
+            Some(tz) => tz
+                .as_str()
+                .and_then(|tz| tz.parse::<chrono_tz::Tz>().ok())
+                .ok_or_else(|| tera::Error::msg(format!("date filter: unknown timezone {tz}")))?,
+            None => self.tz,
+        };
+
+        let formatted = match value {
+            tera::Value::String(s) => match s.parse::<Timestamp>() {
+                Ok(Timestamp::Date(date)) => date
+                    .and_time(chrono::NaiveTime::MIN)
+                    .format_with_items(items.into_iter())
+                    .to_string(),
+                Ok(Timestamp::DateTime(dt)) => dt
+                    .with_timezone(&tz)
+                    .format_with_items(items.into_iter())
+                    .to_string(),
+                Err(e) => return Err(tera::Error::msg(format!("date filter: {e}"))),
+            },
+            tera::Value::Number(n) => {
+                let dt = n
+                    .as_i64()
+                    .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+                    .ok_or_else(|| {
+                        tera::Error::msg(format!("date filter: invalid timestamp {n}"))
+                    })?;
+                dt.with_timezone(&tz)
+                    .format_with_items(items.into_iter())
+                    .to_string()
+            }
+            other => {
+                return Err(tera::Error::msg(format!(
+                    "date filter: expected a date string or timestamp, got {other}"
+                )))
+            }
+        };
+        Ok(tera::Value::String(formatted))
+    }
 }
 
 #[cfg(test)]
@@ -598,6 +798,9 @@ mod tests {
         assert!(get_default_template("404.html").is_some());
         assert!(get_default_template("tags.html").is_some());
         assert!(get_default_template("tag.html").is_some());
+        assert!(get_default_template("taxonomy.html").is_some());
+        assert!(get_default_template("taxonomy-term.html").is_some());
+        assert!(get_default_template("author.html").is_some());
         assert!(get_default_template("changelog-entry.html").is_some());
         assert!(get_default_template("changelog-index.html").is_some());
         assert!(get_default_template("roadmap-item.html").is_some());
@@ -617,7 +820,7 @@ mod tests {
     #[test]
     fn test_load_templates_no_dir() {
         let path = std::path::Path::new("/nonexistent/templates");
-        let tera = load_templates(path, &[]).unwrap();
+        let tera = load_templates(path, None, &[]).unwrap();
         // Essential templates should be loaded from defaults
         assert!(tera.get_template("base.html").is_ok());
         assert!(tera.get_template("index.html").is_ok());
@@ -631,7 +834,7 @@ mod tests {
             CollectionConfig::preset_posts(),
             CollectionConfig::preset_docs(),
         ];
-        let tera = load_templates(path, &collections).unwrap();
+        let tera = load_templates(path, None, &collections).unwrap();
         assert!(tera.get_template("post.html").is_ok());
         assert!(tera.get_template("doc.html").is_ok());
         assert!(tera.get_template("docs-index.html").is_ok());
@@ -641,7 +844,7 @@ mod tests {
     fn test_load_templates_with_changelog_collection() {
         let path = std::path::Path::new("/nonexistent/templates");
         let collections = vec![CollectionConfig::preset_changelog()];
-        let tera = load_templates(path, &collections).unwrap();
+        let tera = load_templates(path, None, &collections).unwrap();
         assert!(tera.get_template("changelog-entry.html").is_ok());
         assert!(tera.get_template("changelog-index.html").is_ok());
     }
@@ -650,7 +853,7 @@ mod tests {
     fn test_load_templates_with_roadmap_collection() {
         let path = std::path::Path::new("/nonexistent/templates");
         let collections = vec![CollectionConfig::preset_roadmap()];
-        let tera = load_templates(path, &collections).unwrap();
+        let tera = load_templates(path, None, &collections).unwrap();
         assert!(tera.get_template("roadmap-item.html").is_ok());
         assert!(tera.get_template("roadmap-index.html").is_ok());
     }
@@ -659,7 +862,7 @@ mod tests {
     fn test_load_templates_with_trust_collection() {
         let path = std::path::Path::new("/nonexistent/templates");
         let collections = vec![CollectionConfig::preset_trust()];
-        let tera = load_templates(path, &collections).unwrap();
+        let tera = load_templates(path, None, &collections).unwrap();
         assert!(tera.get_template("trust-item.html").is_ok());
         assert!(tera.get_template("trust-index.html").is_ok());
     }
@@ -675,11 +878,45 @@ mod tests {
             "<!DOCTYPE html><html><body>{% block content %}{% endblock %}</body></html>",
         )
         .unwrap();
-        let tera = load_templates(&tpl_dir, &[]).unwrap();
+        let tera = load_templates(&tpl_dir, None, &[]).unwrap();
         // Our custom template should be loaded
         assert!(tera.get_template("base.html").is_ok());
     }
 
+    #[test]
+    fn test_load_templates_shared_fallback_layer() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tpl_dir = tmp.path().join("templates");
+        let shared_dir = tmp.path().join("shared");
+        std::fs::create_dir_all(&tpl_dir).unwrap();
+        std::fs::create_dir_all(shared_dir.join("partials")).unwrap();
+        // Shared base and partial; the site overrides only page.html, which
+        // extends the shared base
+        std::fs::write(
+            shared_dir.join("base.html"),
+            "SHARED-BASE {% block content %}{% endblock %}",
+        )
+        .unwrap();
+        std::fs::write(shared_dir.join("partials/footer.html"), "SHARED-FOOTER").unwrap();
+        std::fs::write(shared_dir.join("page.html"), "SHARED-PAGE").unwrap();
+        std::fs::write(
+            tpl_dir.join("page.html"),
+            "{% extends \"base.html\" %}{% block content %}SITE-PAGE {% include \"partials/footer.html\" %}{% endblock %}",
+        )
+        .unwrap();
+
+        let tera = load_templates(
+            &tpl_dir,
+            Some(&shared_dir),
+            &[CollectionConfig::preset_posts()],
+        )
+        .unwrap();
+        let html = tera.render("page.html", &tera::Context::new()).unwrap();
+        assert_eq!(html, "SHARED-BASE SITE-PAGE SHARED-FOOTER");
+        // Templates missing from both layers still come from the defaults
+        assert!(tera.get_template("post.html").is_ok());
+    }
+
     #[test]
     fn test_all_default_templates_extend_base() {
         // All page-level templates should extend base.html
@@ -692,6 +929,9 @@ mod tests {
             "404.html",
             "tags.html",
             "tag.html",
+            "taxonomy.html",
+            "taxonomy-term.html",
+            "author.html",
             "changelog-entry.html",
             "changelog-index.html",
             "roadmap-item.html",
@@ -710,4 +950,39 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_date_filter_uses_site_timezone() {
+        let mut tera = tera::Tera::default();
+        tera.register_filter("date", DateFilter::new(chrono_tz::Asia::Tokyo));
+        let render = |tpl: &str, value: serde_json::Value| {
+            let mut ctx = tera::Context::new();
+            ctx.insert("d", &value);
+            tera.clone().render_str(tpl, &ctx).unwrap()
+        };
+        let dt = serde_json::json!("2025-06-01T20:30:00+02:00");
+        assert_eq!(render("{{ d | date }}", dt.clone()), "2025-06-02");
+        assert_eq!(
+            render(r#"{{ d | date(format="%Y-%m-%d %H:%M %Z") }}"#, dt.clone()),
+            "2025-06-02 03:30 JST"
+        );
+        assert_eq!(
+            render(
+                r#"{{ d | date(format="%H:%M", timezone="Europe/Berlin") }}"#,
+                dt
+            ),
+            "20:30"
+        );
+        // Dates without a time aren't shifted
+        let date = serde_json::json!("2025-06-01");
+        assert_eq!(
+            render(r#"{{ d | date(format="%B %e, %Y") }}"#, date),
+            "June  1, 2025"
+        );
+        assert_eq!(render("{{ d | date }}", serde_json::json!(0)), "1970-01-01");
+
+        let mut ctx = tera::Context::new();
+        ctx.insert("d", "soon");
+        assert!(tera.clone().render_str("{{ d | date }}", &ctx).is_err());
+    }
 }
@@ -1,13 +1,18 @@
-use std::path::Path;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use crate::build::{self, links, BuildOptions, BuildResult};
+use crate::build::{self, links, sitemap, BuildOptions, BuildResult};
+use crate::content::Timestamp;
 use crate::output::{human, CommandOutput};
 
 use super::{load_site_in_workspace, WorkspaceConfig};
 
 pub struct WorkspaceBuildOptions {
     pub include_drafts: bool,
+    pub as_of: Option<Timestamp>,
     pub strict: bool,
+    pub incremental: bool,
     pub site_filter: Option<String>,
 }
 
@@ -31,6 +36,13 @@ impl WorkspaceBuildResult {
     }
 }
 
+/// A workspace site's resolved base URL and output directory.
+struct SiteOutput {
+    name: String,
+    base_url: String,
+    output: PathBuf,
+}
+
 /// Build all (or filtered) sites in a workspace.
 pub fn build_workspace(
     ws_config: &WorkspaceConfig,
@@ -43,6 +55,7 @@ pub fn build_workspace(
 
     let total = sites.len();
     let mut site_results = Vec::new();
+    let cross_site_urls = ws_config.cross_site_urls(ws_root)?;
 
     for (i, ws_site) in sites.iter().enumerate() {
         human::header(&format!(
@@ -52,56 +65,67 @@ pub fn build_workspace(
             ws_site.name
         ));
 
-        let (config, paths) = load_site_in_workspace(ws_root, ws_site)?;
+        let (config, paths) = load_site_in_workspace(ws_config, ws_root, ws_site)?;
 
         let build_opts = BuildOptions {
             include_drafts: opts.include_drafts,
+            as_of: opts.as_of,
+            incremental: opts.incremental,
+            cross_site_urls: cross_site_urls.clone(),
         };
 
         let result = build::build_site(&config, &paths, &build_opts)?;
         human::success(&result.stats.human_display());
 
         // Link validation results from the post-process pass (no extra file walk)
-        if !result.link_check.broken_links.is_empty() {
-            let grouped = links::group_broken_links(&result.link_check.broken_links);
-            let count = result.link_check.broken_links.len();
-            let target_count = grouped.len();
-
-            let header = format!(
-                "Site '{}': {count} broken internal link{} ({target_count} broken target{})",
-                ws_site.name,
-                if count == 1 { "" } else { "s" },
-                if target_count == 1 { "" } else { "s" },
-            );
+        report_broken_links(
+            &ws_site.name,
+            &result.link_check.broken_links,
+            "internal",
+            opts.strict,
+        )?;
+
+        site_results.push((ws_site.name.clone(), result));
+    }
 
-            if opts.strict {
-                human::error(&header);
-            } else {
-                human::warning(&header);
+    let cross = &ws_config.cross_site;
+    if cross.cross_site_links || cross.unified_sitemap || cross.unified_search {
+        // Cross-site outputs always cover every site, including ones not
+        // rebuilt this time (`--site`), using whatever is in their output dir.
+        let outputs = site_outputs(ws_config, ws_root)?;
+
+        if cross.cross_site_links {
+            check_cross_site_links(&site_results, &outputs, opts.strict)?;
+        }
+
+        if cross.unified_sitemap || cross.unified_search {
+            let ws_output = cross.output_path(ws_root);
+            if let Some(site) = outputs.iter().find(|s| s.output == ws_output) {
+                anyhow::bail!(
+                    "[cross_site] output_dir '{}' is the output directory of site '{}' — choose a separate directory",
+                    ws_output.display(),
+                    site.name
+                );
             }
+            fs::create_dir_all(&ws_output)?;
 
-            for (href, sources) in &grouped {
+            if cross.unified_sitemap {
+                let count = write_unified_sitemap(&outputs, &ws_output)?;
                 human::info(&format!(
-                    "  {} (linked from {} file{})",
-                    href,
-                    sources.len(),
-                    if sources.len() == 1 { "" } else { "s" }
+                    "Unified sitemap: {} ({count} site{})",
+                    ws_output.join("sitemap_index.xml").display(),
+                    if count == 1 { "" } else { "s" }
                 ));
-                for source in sources {
-                    human::info(&format!("    - {source}"));
-                }
             }
-
-            if opts.strict {
-                anyhow::bail!(
-                    "Build failed: site '{}' has {count} broken internal link{}",
-                    ws_site.name,
-                    if count == 1 { "" } else { "s" },
-                );
+            if cross.unified_search {
+                let count = write_unified_search(&outputs, &ws_output)?;
+                human::info(&format!(
+                    "Unified search index: {} ({count} entr{})",
+                    ws_output.join("search-index.json").display(),
+                    if count == 1 { "y" } else { "ies" }
+                ));
             }
         }
-
-        site_results.push((ws_site.name.clone(), result));
     }
 
     human::header("Workspace build complete");
@@ -110,3 +134,263 @@ pub fn build_workspace(
 
     Ok(ws_result)
 }
+
+/// Resolve the base URL and output directory of every site in the workspace.
+fn site_outputs(ws_config: &WorkspaceConfig, ws_root: &Path) -> anyhow::Result<Vec<SiteOutput>> {
+    let mut outputs = Vec::new();
+    for ws_site in &ws_config.sites {
+        let (config, paths) = load_site_in_workspace(ws_config, ws_root, ws_site)?;
+        outputs.push(SiteOutput {
+            name: ws_site.name.clone(),
+            base_url: config.site.base_url.trim_end_matches('/').to_string(),
+            output: paths.output,
+        });
+    }
+    Ok(outputs)
+}
+
+/// Print grouped broken links for a site. Fails the build in strict mode.
+fn report_broken_links(
+    site_name: &str,
+    broken: &[links::BrokenLink],
+    kind: &str,
+    strict: bool,
+) -> anyhow::Result<()> {
+    if broken.is_empty() {
+        return Ok(());
+    }
+
+    let grouped = links::group_broken_links(broken);
+    let count = broken.len();
+    let target_count = grouped.len();
+
+    let header = format!(
+        "Site '{site_name}': {count} broken {kind} link{} ({target_count} broken target{})",
+        if count == 1 { "" } else { "s" },
+        if target_count == 1 { "" } else { "s" },
+    );
+
+    if strict {
+        human::error(&header);
+    } else {
+        human::warning(&header);
+    }
+
+    for (href, sources) in &grouped {
+        human::info(&format!(
+            "  {} (linked from {} file{})",
+            href,
+            sources.len(),
+            if sources.len() == 1 { "" } else { "s" }
+        ));
+        for source in sources {
+            human::info(&format!("    - {source}"));
+        }
+    }
+
+    if strict {
+        anyhow::bail!(
+            "Build failed: site '{site_name}' has {count} broken {kind} link{}",
+            if count == 1 { "" } else { "s" },
+        );
+    }
+
+    Ok(())
+}
+
+/// Validate `@site:` links collected during each site's build against the
+/// target site's output. Links into a site that has not been built yet (e.g.
+/// after `seite build --site X`) can't be checked and are reported as such.
+fn check_cross_site_links(
+    site_results: &[(String, BuildResult)],
+    outputs: &[SiteOutput],
+    strict: bool,
+) -> anyhow::Result<()> {
+    let mut valid_urls: HashMap<&str, HashSet<String>> = HashMap::new();
+
+    for (site_name, result) in site_results {
+        let mut broken = Vec::new();
+        let mut unchecked = Vec::new();
+        let mut unbuilt = BTreeSet::new();
+        for link in &result.link_check.cross_site_links {
+            let href = links::BrokenLink {
+                source_file: link.source_file.clone(),
+                href: format!("{}{}{}", links::CROSS_SITE_PREFIX, link.site, link.path),
+            };
+            match outputs.iter().find(|s| s.name == link.site) {
+                None => broken.push(href),
+                Some(target) if !target.output.exists() => {
+                    unbuilt.insert(target.name.as_str());
+                    unchecked.push(href);
+                }
+                Some(target) => {
+                    let valid = valid_urls
+                        .entry(target.name.as_str())
+                        .or_insert_with(|| links::build_valid_urls(&target.output))
+                        .contains(&link.path);
+                    if !valid {
+                        broken.push(href);
+                    }
+                }
+            }
+        }
+        report_unchecked_links(site_name, &unchecked, &unbuilt);
+        report_broken_links(site_name, &broken, "cross-site", strict)?;
+    }
+
+    Ok(())
+}
+
+/// Warn about cross-site links whose target site has no output to check
+/// them against.
+fn report_unchecked_links(
+    site_name: &str,
+    unchecked: &[links::BrokenLink],
+    unbuilt: &BTreeSet<&str>,
+) {
+    if unchecked.is_empty() {
+        return;
+    }
+    let count = unchecked.len();
+    let names: Vec<String> = unbuilt.iter().map(|name| format!("'{name}'")).collect();
+    human::warning(&format!(
+        "Site '{site_name}': {count} cross-site link{} unchecked, {} {} not been built yet (build the whole workspace to check {})",
+        if count == 1 { "" } else { "s" },
+        names.join(", "),
+        if names.len() == 1 { "has" } else { "have" },
+        if count == 1 { "it" } else { "them" },
+    ));
+    for (href, sources) in &links::group_broken_links(unchecked) {
+        human::info(&format!("  {href} (linked from {})", sources.join(", ")));
+    }
+}
+
+/// Write `sitemap_index.xml` listing the sitemap of every built site.
+/// Returns the number of sites included.
+fn write_unified_sitemap(outputs: &[SiteOutput], ws_output: &Path) -> anyhow::Result<usize> {
+    let mut sitemap_urls = Vec::new();
+    let mut sites = 0;
+    for site in outputs {
+        let files = site_sitemaps(&site.output);
+        if !files.is_empty() {
+            sites += 1;
+        }
+        sitemap_urls.extend(files.iter().map(|f| format!("{}/{f}", site.base_url)));
+    }
+    let xml = sitemap::generate_sitemap_index(&sitemap_urls)?;
+    fs::write(ws_output.join("sitemap_index.xml"), xml)?;
+    Ok(sites)
+}
+
+/// File names of a site's sitemaps. A split sitemap lists its `sitemap-N.xml`
+/// parts, since a sitemap index can't reference another index.
+fn site_sitemaps(output: &Path) -> Vec<String> {
+    if output.join("sitemap.xml").exists() {
+        return vec!["sitemap.xml".to_string()];
+    }
+    (1..)
+        .map(|n| format!("sitemap-{n}.xml"))
+        .take_while(|name| output.join(name).exists())
+        .collect()
+}
+
+/// Merge every site's search index (including per-language indexes) into one
+/// `search-index.json`. Each entry gains a `site` field and an absolute `url`.
+/// Returns the number of entries written.
+fn write_unified_search(outputs: &[SiteOutput], ws_output: &Path) -> anyhow::Result<usize> {
+    let mut merged = Vec::new();
+
+    for site in outputs {
+        for index_path in site_search_indexes(&site.output) {
+            let contents = fs::read_to_string(&index_path)?;
+            let entries: Vec<serde_json::Value> = serde_json::from_str(&contents)
+                .map_err(|e| anyhow::anyhow!("failed to parse {}: {e}", index_path.display()))?;
+            merged.extend(
+                entries
+                    .into_iter()
+                    .map(|entry| tag_search_entry(entry, &site.name, &site.base_url)),
+            );
+        }
+    }
+
+    let count = merged.len();
+    fs::write(
+        ws_output.join("search-index.json"),
+        serde_json::to_string(&merged)?,
+    )?;
+    Ok(count)
+}
+
+/// Find `search-index.json` at the root of a site's output and in its
+/// language subdirectories.
+fn site_search_indexes(output: &Path) -> Vec<PathBuf> {
+    let mut indexes = Vec::new();
+    let root_index = output.join("search-index.json");
+    if root_index.exists() {
+        indexes.push(root_index);
+    }
+    if let Ok(entries) = fs::read_dir(output) {
+        let mut lang_indexes: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path().join("search-index.json"))
+            .filter(|p| p.exists())
+            .collect();
+        lang_indexes.sort();
+        indexes.extend(lang_indexes);
+    }
+    indexes
+}
+
+/// Add the `site` field to a search entry and make its root-relative URL absolute.
+fn tag_search_entry(mut entry: serde_json::Value, site: &str, base_url: &str) -> serde_json::Value {
+    if let Some(obj) = entry.as_object_mut() {
+        if let Some(url) = obj.get("url").and_then(|u| u.as_str()) {
+            if url.starts_with('/') {
+                let absolute = format!("{base_url}{url}");
+                obj.insert("url".into(), serde_json::Value::String(absolute));
+            }
+        }
+        obj.insert("site".into(), serde_json::Value::String(site.to_string()));
+    }
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_search_entry_adds_site_and_absolute_url() {
+        let entry = serde_json::json!({"title": "Intro", "url": "/docs/intro"});
+        let tagged = tag_search_entry(entry, "docs", "https://docs.example.com");
+        assert_eq!(tagged["site"], "docs");
+        assert_eq!(tagged["url"], "https://docs.example.com/docs/intro");
+        assert_eq!(tagged["title"], "Intro");
+    }
+
+    #[test]
+    fn test_tag_search_entry_keeps_absolute_url() {
+        let entry = serde_json::json!({"url": "https://elsewhere.com/x"});
+        let tagged = tag_search_entry(entry, "blog", "https://blog.example.com");
+        assert_eq!(tagged["url"], "https://elsewhere.com/x");
+        assert_eq!(tagged["site"], "blog");
+    }
+
+    #[test]
+    fn test_site_search_indexes_includes_language_dirs() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        fs::write(tmp.path().join("search-index.json"), "[]").unwrap();
+        fs::create_dir_all(tmp.path().join("es")).unwrap();
+        fs::write(tmp.path().join("es/search-index.json"), "[]").unwrap();
+        fs::create_dir_all(tmp.path().join("posts")).unwrap();
+
+        let indexes = site_search_indexes(tmp.path());
+        assert_eq!(
+            indexes,
+            vec![
+                tmp.path().join("search-index.json"),
+                tmp.path().join("es/search-index.json"),
+            ]
+        );
+    }
+}
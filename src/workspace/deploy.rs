@@ -35,6 +35,7 @@ pub fn deploy_workspace(
     check_domain_conflicts(ws_config, ws_root, &sites);
 
     let total = sites.len();
+    let cross_site_urls = ws_config.cross_site_urls(ws_root)?;
 
     for (i, ws_site) in sites.iter().enumerate() {
         human::header(&format!(
@@ -44,7 +45,7 @@ pub fn deploy_workspace(
             ws_site.name
         ));
 
-        let (config, paths) = load_site_in_workspace(ws_root, ws_site)?;
+        let (config, paths) = load_site_in_workspace(ws_config, ws_root, ws_site)?;
 
         let target_str = match &config.deploy.target {
             DeployTarget::GithubPages => "github-pages",
@@ -92,6 +93,9 @@ pub fn deploy_workspace(
             };
             let build_opts = BuildOptions {
                 include_drafts: false,
+                as_of: None,
+                incremental: false,
+                cross_site_urls: cross_site_urls.clone(),
             };
             let result = build::build_site(&build_config, &paths, &build_opts)?;
             human::success(&result.stats.human_display());
@@ -155,7 +159,7 @@ pub fn deploy_workspace(
 /// Collect all effective domains across workspace sites and warn on conflicts.
 /// Each site contributes its base_url domain plus any subdomain collection domains.
 fn check_domain_conflicts(
-    _ws_config: &WorkspaceConfig,
+    ws_config: &WorkspaceConfig,
     ws_root: &Path,
     sites: &[&super::WorkspaceSite],
 ) {
@@ -163,7 +167,7 @@ fn check_domain_conflicts(
     let mut domain_map: HashMap<String, Vec<(String, String)>> = HashMap::new();
 
     for ws_site in sites {
-        let Ok((config, _paths)) = load_site_in_workspace(ws_root, ws_site) else {
+        let Ok((config, _paths)) = load_site_in_workspace(ws_config, ws_root, ws_site) else {
             continue;
         };
 
@@ -2,11 +2,12 @@ pub mod build;
 pub mod deploy;
 pub mod server;
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-use crate::config::{ResolvedPaths, SiteConfig};
+use crate::config::{ResolvedPaths, SharedPaths, SiteConfig};
 use crate::error::{PageError, Result};
 
 /// Workspace configuration loaded from `seite-workspace.toml`.
@@ -44,12 +45,26 @@ pub struct WorkspaceSite {
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct CrossSiteSection {
+    /// Write a sitemap index covering every site's sitemap.
     #[serde(default)]
     pub unified_sitemap: bool,
+    /// Merge every site's search index into one, tagging entries with their site.
     #[serde(default)]
     pub unified_search: bool,
+    /// Resolve `@site:<name>/<path>` links and check them across sites.
     #[serde(default)]
     pub cross_site_links: bool,
+    /// Where workspace-level outputs are written (relative to workspace root).
+    /// Defaults to `dist`.
+    #[serde(default)]
+    pub output_dir: Option<String>,
+}
+
+impl CrossSiteSection {
+    /// Resolve the workspace-level output directory.
+    pub fn output_path(&self, ws_root: &Path) -> PathBuf {
+        ws_root.join(self.output_dir.as_deref().unwrap_or("dist"))
+    }
 }
 
 /// The execution context resolved at startup: either standalone or workspace.
@@ -65,7 +80,7 @@ pub enum ExecutionContext {
     },
 }
 
-const WORKSPACE_FILE: &str = "seite-workspace.toml";
+pub(crate) const WORKSPACE_FILE: &str = "seite-workspace.toml";
 
 impl WorkspaceConfig {
     /// Load workspace config from a `seite-workspace.toml` file.
@@ -117,6 +132,20 @@ impl WorkspaceConfig {
         Ok(())
     }
 
+    /// Base URLs of every site keyed by site name, for resolving `@site:` links.
+    /// Empty unless `cross_site.cross_site_links` is enabled.
+    pub fn cross_site_urls(&self, ws_root: &Path) -> Result<HashMap<String, String>> {
+        let mut urls = HashMap::new();
+        if !self.cross_site.cross_site_links {
+            return Ok(urls);
+        }
+        for site in &self.sites {
+            let (config, _) = load_site_in_workspace(self, ws_root, site)?;
+            urls.insert(site.name.clone(), config.site.base_url);
+        }
+        Ok(urls)
+    }
+
     /// Find a site by name.
     pub fn find_site(&self, name: &str) -> Option<&WorkspaceSite> {
         self.sites.iter().find(|s| s.name == name)
@@ -186,6 +215,7 @@ pub fn resolve_context(
 
 /// Load a site's config and paths within a workspace context.
 pub fn load_site_in_workspace(
+    ws_config: &WorkspaceConfig,
     ws_root: &Path,
     ws_site: &WorkspaceSite,
 ) -> Result<(SiteConfig, ResolvedPaths)> {
@@ -204,6 +234,14 @@ pub fn load_site_in_workspace(
         paths.output = ws_root.join(output_dir);
     }
 
+    // Attach shared workspace directories as fallbacks behind the site's own
+    let shared = &ws_config.workspace;
+    paths.shared = SharedPaths {
+        data_dir: shared.shared_data.as_ref().map(|d| ws_root.join(d)),
+        static_dir: shared.shared_static.as_ref().map(|d| ws_root.join(d)),
+        templates: shared.shared_templates.as_ref().map(|d| ws_root.join(d)),
+    };
+
     Ok((config, paths))
 }
 
@@ -420,7 +458,12 @@ default_template = "post.html"
             output_dir: Some("dist/blog".into()),
         };
 
-        let (config, paths) = load_site_in_workspace(tmp.path(), &ws_site).unwrap();
+        let (config, paths) = load_site_in_workspace(
+            &make_workspace_config(vec![ws_site.clone()]),
+            tmp.path(),
+            &ws_site,
+        )
+        .unwrap();
         assert_eq!(config.site.base_url, "https://blog.example.com");
         assert_eq!(paths.output, tmp.path().join("dist/blog"));
     }
@@ -451,10 +494,44 @@ default_template = "post.html"
         std::fs::write(blog_dir.join("seite.toml"), site_toml).unwrap();
 
         let ws_site = make_site("blog", "sites/blog");
-        let (config, paths) = load_site_in_workspace(tmp.path(), &ws_site).unwrap();
+        let (config, paths) = load_site_in_workspace(
+            &make_workspace_config(vec![ws_site.clone()]),
+            tmp.path(),
+            &ws_site,
+        )
+        .unwrap();
         assert_eq!(config.site.base_url, "http://localhost:3000");
         // output should be relative to the site root, not workspace root
         assert_eq!(paths.output, blog_dir.join("dist"));
+        // no shared directories configured
+        assert!(paths.shared.data_dir.is_none());
+        assert!(paths.shared.static_dir.is_none());
+        assert!(paths.shared.templates.is_none());
+    }
+
+    #[test]
+    fn test_load_site_in_workspace_attaches_shared_dirs() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let blog_dir = tmp.path().join("sites/blog");
+        std::fs::create_dir_all(&blog_dir).unwrap();
+        std::fs::write(
+            blog_dir.join("seite.toml"),
+            "collections = []\n\n[site]\ntitle = \"Blog\"\nbase_url = \"http://localhost:3000\"\n",
+        )
+        .unwrap();
+
+        let ws_site = make_site("blog", "sites/blog");
+        let mut ws_config = make_workspace_config(vec![ws_site.clone()]);
+        ws_config.workspace.shared_data = Some("data".into());
+        ws_config.workspace.shared_static = Some("static".into());
+        ws_config.workspace.shared_templates = Some("templates".into());
+
+        let (_config, paths) = load_site_in_workspace(&ws_config, tmp.path(), &ws_site).unwrap();
+        assert_eq!(paths.shared.data_dir, Some(tmp.path().join("data")));
+        assert_eq!(paths.shared.static_dir, Some(tmp.path().join("static")));
+        assert_eq!(paths.shared.templates, Some(tmp.path().join("templates")));
+        // site-local directories are unchanged
+        assert_eq!(paths.data_dir, blog_dir.join("data"));
     }
 
     #[test]
@@ -464,4 +541,25 @@ default_template = "post.html"
         assert!(!css.unified_search);
         assert!(!css.cross_site_links);
     }
+
+    #[test]
+    fn test_cross_site_output_path_default() {
+        let css = CrossSiteSection::default();
+        assert_eq!(css.output_path(Path::new("/ws")), Path::new("/ws/dist"));
+        let css = CrossSiteSection {
+            output_dir: Some("public-ws".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            css.output_path(Path::new("/ws")),
+            Path::new("/ws/public-ws")
+        );
+    }
+
+    #[test]
+    fn test_cross_site_urls_disabled_is_empty() {
+        let config = make_workspace_config(vec![make_site("blog", "sites/blog")]);
+        let urls = config.cross_site_urls(Path::new("/nonexistent")).unwrap();
+        assert!(urls.is_empty());
+    }
 }
@@ -1,37 +1,36 @@
+use std::collections::HashSet;
 use std::fs;
 use std::net::TcpStream;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use notify::{RecursiveMode, Watcher};
 use tiny_http::{Header, Response, Server};
 
 use crate::build::{self, BuildOptions};
+use crate::config::ResolvedPaths;
 use crate::error::{PageError, Result};
 use crate::output::human;
 use crate::output::CommandOutput;
+use crate::server::livereload::{BuildErrorInfo, LiveReload, LiveReloadEvent};
+use crate::server::{
+    inject_livereload, is_config_file, path_variants, redirect_response, sync_watches,
+};
 
-use super::{load_site_in_workspace, WorkspaceConfig};
+use super::{load_site_in_workspace, WorkspaceConfig, WORKSPACE_FILE};
 
-const LIVERELOAD_SCRIPT: &str = r#"<script>
-(function(){
-  var v = "0";
-  setInterval(function(){
-    fetch("/__livereload").then(function(r){ return r.text(); }).then(function(t){
-      if (v !== "0" && t !== v) location.reload();
-      v = t;
-    }).catch(function(){});
-  }, 1000);
-})();
-</script>"#;
+/// Site name → output directory, as routed by the server. Replaced when a
+/// config change moves an output directory or adds or removes sites.
+type Routes = Arc<RwLock<Vec<(String, PathBuf)>>>;
 
 /// Handle to a running workspace dev server.
 pub struct WorkspaceServerHandle {
     stop: Arc<AtomicBool>,
     port: u16,
+    routes: Routes,
 }
 
 impl WorkspaceServerHandle {
@@ -39,6 +38,11 @@ impl WorkspaceServerHandle {
         self.port
     }
 
+    /// The sites currently served and their output directories.
+    pub fn sites(&self) -> Vec<(String, PathBuf)> {
+        self.routes.read().expect("routes lock poisoned").clone()
+    }
+
     pub fn stop(&self) {
         self.stop.store(true, Ordering::Relaxed);
     }
@@ -52,10 +56,53 @@ impl Drop for WorkspaceServerHandle {
 
 struct SiteServerInfo {
     name: String,
+    /// Directory holding the site's `seite.toml`.
+    root: PathBuf,
     output_dir: PathBuf,
     watch_dirs: Vec<PathBuf>,
 }
 
+impl SiteServerInfo {
+    fn new(name: &str, paths: &ResolvedPaths) -> Self {
+        let mut watch_dirs = vec![
+            paths.content.clone(),
+            paths.templates.clone(),
+            paths.static_dir.clone(),
+            paths.public_dir.clone(),
+            paths.data_dir.clone(),
+        ];
+        // Shared workspace directories feed into this site's build too
+        watch_dirs.extend(paths.shared.data_dir.clone());
+        watch_dirs.extend(paths.shared.static_dir.clone());
+        watch_dirs.extend(paths.shared.templates.clone());
+        Self {
+            name: name.to_string(),
+            root: paths.root.clone(),
+            output_dir: paths.output.clone(),
+            watch_dirs,
+        }
+    }
+}
+
+/// Load every site of the workspace for serving.
+fn load_sites(ws_config: &WorkspaceConfig, ws_root: &Path) -> Result<Vec<SiteServerInfo>> {
+    ws_config
+        .sites
+        .iter()
+        .map(|ws_site| {
+            let (_config, paths) = load_site_in_workspace(ws_config, ws_root, ws_site)?;
+            Ok(SiteServerInfo::new(&ws_site.name, &paths))
+        })
+        .collect()
+}
+
+fn routes_of(sites: &[SiteServerInfo]) -> Vec<(String, PathBuf)> {
+    sites
+        .iter()
+        .map(|s| (s.name.clone(), s.output_dir.clone()))
+        .collect()
+}
+
 /// Start a workspace dev server that routes requests by path prefix.
 /// `localhost:3000/blog/...` -> sites/blog/dist/...
 pub fn start(
@@ -77,23 +124,7 @@ pub fn start(
         (server, port)
     };
 
-    // Collect site info for routing
-    let mut sites = Vec::new();
-    for ws_site in &ws_config.sites {
-        let (_config, paths) = load_site_in_workspace(ws_root, ws_site)?;
-        let watch_dirs = vec![
-            paths.content.clone(),
-            paths.templates.clone(),
-            paths.static_dir.clone(),
-            paths.public_dir.clone(),
-            paths.data_dir.clone(),
-        ];
-        sites.push(SiteServerInfo {
-            name: ws_site.name.clone(),
-            output_dir: paths.output.clone(),
-            watch_dirs,
-        });
-    }
+    let sites = load_sites(ws_config, ws_root)?;
 
     if actual_port != port {
         human::info(&format!(
@@ -115,47 +146,45 @@ pub fn start(
     }
 
     let stop = Arc::new(AtomicBool::new(false));
-    let build_version = Arc::new(AtomicU64::new(1));
+    let live_reload = LiveReload::new();
+
+    let routes: Routes = Arc::new(RwLock::new(routes_of(&sites)));
 
     // Spawn file watcher thread
     let watcher_stop = stop.clone();
-    let watcher_version = build_version.clone();
+    let watcher_live_reload = live_reload.clone();
     let watcher_ws_config = ws_config.clone();
     let watcher_ws_root = ws_root.to_path_buf();
-    let watcher_sites: Vec<(String, Vec<PathBuf>)> = sites
-        .iter()
-        .map(|s| (s.name.clone(), s.watch_dirs.clone()))
-        .collect();
+    let watcher_routes = routes.clone();
     std::thread::spawn(move || {
         watch_and_rebuild_workspace(
-            &watcher_ws_config,
+            watcher_ws_config,
             &watcher_ws_root,
-            &watcher_sites,
+            sites,
+            &watcher_routes,
             &watcher_stop,
-            &watcher_version,
+            &watcher_live_reload,
         );
     });
 
     // Spawn HTTP server thread
     let server_stop = stop.clone();
-    let server_sites: Vec<(String, PathBuf)> = sites
-        .iter()
-        .map(|s| (s.name.clone(), s.output_dir.clone()))
-        .collect();
+    let server_routes = routes.clone();
     std::thread::spawn(move || {
-        run_serve_loop(server, &server_sites, &build_version, &server_stop);
+        run_serve_loop(server, &server_routes, &live_reload, &server_stop);
     });
 
     Ok(WorkspaceServerHandle {
         stop,
         port: actual_port,
+        routes,
     })
 }
 
 fn run_serve_loop(
     server: Server,
-    sites: &[(String, PathBuf)],
-    build_version: &AtomicU64,
+    routes: &RwLock<Vec<(String, PathBuf)>>,
+    live_reload: &Arc<LiveReload>,
     stop: &AtomicBool,
 ) {
     loop {
@@ -165,13 +194,12 @@ fn run_serve_loop(
         match server.recv_timeout(Duration::from_secs(1)) {
             Ok(Some(request)) => {
                 let url_path = request.url().to_string();
+                let sites = routes.read().expect("routes lock poisoned").clone();
+                let sites = sites.as_slice();
 
-                // Live reload polling endpoint
+                // Live reload event stream
                 if url_path == "/__livereload" {
-                    let version = build_version.load(Ordering::Relaxed).to_string();
-                    let header =
-                        Header::from_bytes("Content-Type", "text/plain").expect("valid header");
-                    let _ = request.respond(Response::from_string(version).with_header(header));
+                    live_reload.serve(request);
                     continue;
                 }
 
@@ -180,6 +208,10 @@ fn run_serve_loop(
                     if file_path.exists() && file_path.is_file() {
                         let content = fs::read(&file_path).unwrap_or_default();
                         let mime = guess_mime(&file_path);
+                        if let Some(response) = redirect_response(mime, &content) {
+                            let _ = request.respond(response);
+                            continue;
+                        }
 
                         let content = if mime == "text/html; charset=utf-8" {
                             inject_livereload(&content)
@@ -211,6 +243,7 @@ fn run_serve_loop(
             Err(_) => break,
         }
     }
+    live_reload.close();
 }
 
 /// Route a request to a site's output directory based on URL path prefix.
@@ -259,21 +292,6 @@ fn resolve_file_path(output_dir: &Path, url_path: &str) -> Option<PathBuf> {
     None
 }
 
-fn inject_livereload(html_bytes: &[u8]) -> Vec<u8> {
-    let html = String::from_utf8_lossy(html_bytes);
-    if let Some(pos) = html.rfind("</body>") {
-        let mut result = String::with_capacity(html.len() + LIVERELOAD_SCRIPT.len() + 1);
-        result.push_str(&html[..pos]);
-        result.push('\n');
-        result.push_str(LIVERELOAD_SCRIPT);
-        result.push('\n');
-        result.push_str(&html[pos..]);
-        result.into_bytes()
-    } else {
-        html_bytes.to_vec()
-    }
-}
-
 fn generate_workspace_index(sites: &[(String, PathBuf)]) -> String {
     let mut links = String::new();
     for (name, _) in sites {
@@ -320,11 +338,12 @@ fn guess_mime(path: &Path) -> &'static str {
 }
 
 fn watch_and_rebuild_workspace(
-    ws_config: &WorkspaceConfig,
+    mut ws_config: WorkspaceConfig,
     ws_root: &Path,
-    sites: &[(String, Vec<PathBuf>)],
+    mut sites: Vec<SiteServerInfo>,
+    routes: &RwLock<Vec<(String, PathBuf)>>,
     stop: &AtomicBool,
-    build_version: &AtomicU64,
+    live_reload: &LiveReload,
 ) {
     let (tx, rx) = mpsc::channel();
 
@@ -336,78 +355,193 @@ fn watch_and_rebuild_workspace(
         }
     };
 
-    // Watch all sites' directories
-    for (_name, dirs) in sites {
-        for dir in dirs {
-            if dir.exists() {
-                if let Err(e) = watcher.watch(dir, RecursiveMode::Recursive) {
-                    human::error(&format!("Failed to watch {}: {e}", dir.display()));
+    // `seite-workspace.toml` and each site's `seite.toml` are watched through
+    // their directories, since editors often save by replacing the file
+    let mut watched_roots = HashSet::new();
+    let mut watched = HashSet::new();
+    let ws_root_variants = path_variants(&[ws_root.to_path_buf()]);
+    let mut sync = |watcher: &mut notify::RecommendedWatcher, sites: &[SiteServerInfo]| {
+        for root in std::iter::once(ws_root).chain(sites.iter().map(|s| s.root.as_path())) {
+            if watched_roots.insert(root.to_path_buf()) {
+                if let Err(e) = watcher.watch(root, RecursiveMode::NonRecursive) {
+                    human::error(&format!("Failed to watch {}: {e}", root.display()));
                 }
             }
         }
-    }
+        // Shared directories appear once per dependent site
+        let dirs: Vec<PathBuf> = sites.iter().flat_map(|s| s.watch_dirs.clone()).collect();
+        sync_watches(watcher, &mut watched, &dirs);
+    };
+    sync(&mut watcher, &sites);
 
     let debounce = Duration::from_millis(200);
+    let mut cross_site_urls = ws_config.cross_site_urls(ws_root).unwrap_or_default();
 
     while !stop.load(Ordering::Relaxed) {
         match rx.recv_timeout(Duration::from_secs(1)) {
             Ok(Ok(event)) => {
-                // Drain additional events within debounce window
-                while rx.recv_timeout(debounce).is_ok() {}
-
-                // Determine which site(s) changed based on the event path
-                let changed_path = event.paths.first();
-                let affected_site = changed_path.and_then(|p| {
-                    sites
-                        .iter()
-                        .find(|(_, dirs)| dirs.iter().any(|d| p.starts_with(d)))
+                // Drain additional events within debounce window, keeping their paths
+                let mut changed = event.paths;
+                while let Ok(next) = rx.recv_timeout(debounce) {
+                    if let Ok(next) = next {
+                        changed.extend(next.paths);
+                    }
+                }
+
+                let mut ws_config_changed = changed.iter().any(|p| {
+                    p.file_name().is_some_and(|n| n == WORKSPACE_FILE)
+                        && p.parent()
+                            .is_some_and(|parent| ws_root_variants.iter().any(|r| r == parent))
                 });
+                // A failed reload leaves the last valid config in place: the
+                // edits batched with it are still rebuilt, and the error stays
+                // on screen
+                let mut ws_error = None;
+                if ws_config_changed {
+                    let reloaded = WorkspaceConfig::load(&ws_root.join(WORKSPACE_FILE))
+                        .and_then(|config| Ok((load_sites(&config, ws_root)?, config)));
+                    match reloaded {
+                        Ok((new_sites, new_config)) => {
+                            ws_config = new_config;
+                            sites = new_sites;
+                            cross_site_urls =
+                                ws_config.cross_site_urls(ws_root).unwrap_or_default();
+                            *routes.write().expect("routes lock poisoned") = routes_of(&sites);
+                            sync(&mut watcher, &sites);
+                            human::info(&format!(
+                                "Reloaded {WORKSPACE_FILE}, rebuilding all sites..."
+                            ));
+                        }
+                        Err(e) => {
+                            human::error(&format!("Failed to reload {WORKSPACE_FILE}: {e}"));
+                            human::info("Still serving with the last valid config");
+                            let mut info = BuildErrorInfo::from_error(&e, ws_root);
+                            info.file.get_or_insert_with(|| WORKSPACE_FILE.into());
+                            ws_error = Some(info);
+                            ws_config_changed = false;
+                        }
+                    }
+                }
 
-                if let Some((site_name, _)) = affected_site {
-                    // Rebuild only the affected site
-                    human::info(&format!("Changes detected in '{site_name}', rebuilding..."));
-                    if let Some(ws_site) = ws_config.find_site(site_name) {
-                        if let Ok((config, paths)) = load_site_in_workspace(ws_root, ws_site) {
-                            let opts = BuildOptions {
-                                include_drafts: true,
-                            };
-                            match build::build_site(&config, &paths, &opts) {
-                                Ok(result) => {
-                                    build_version.fetch_add(1, Ordering::Relaxed);
-                                    human::success(&result.stats.human_display());
-                                }
-                                Err(e) => {
-                                    human::error(&format!("Rebuild of '{site_name}' failed: {e}"));
-                                }
+                // Other files next to the config files (output dirs, the build
+                // cache) are not site inputs
+                let config_changed: HashSet<String> = sites
+                    .iter()
+                    .filter(|s| {
+                        let roots = path_variants(std::slice::from_ref(&s.root));
+                        changed.iter().any(|p| is_config_file(p, &roots))
+                    })
+                    .map(|s| s.name.clone())
+                    .collect();
+                let site_changes: Vec<(String, Vec<PathBuf>)> = sites
+                    .iter()
+                    .map(|s| {
+                        let dirs = path_variants(&s.watch_dirs);
+                        let paths = changed
+                            .iter()
+                            .filter(|p| dirs.iter().any(|d| p.starts_with(d)))
+                            .cloned()
+                            .collect();
+                        (s.name.clone(), paths)
+                    })
+                    .collect();
+
+                // A workspace change rebuilds every site. Otherwise rebuild the
+                // sites whose config or input directories changed; shared
+                // directories are in every dependent site's watch list.
+                let to_rebuild: Vec<&(String, Vec<PathBuf>)> = site_changes
+                    .iter()
+                    .filter(|(name, paths)| {
+                        ws_config_changed || config_changed.contains(name) || !paths.is_empty()
+                    })
+                    .collect();
+                if to_rebuild.is_empty() {
+                    if let Some(info) = ws_error {
+                        live_reload.send(&LiveReloadEvent::BuildError(info));
+                    }
+                    continue;
+                }
+                if !ws_config_changed {
+                    let names: Vec<String> =
+                        to_rebuild.iter().map(|(n, _)| format!("'{n}'")).collect();
+                    human::info(&format!(
+                        "Changes detected in {}, rebuilding...",
+                        names.join(", ")
+                    ));
+                }
+
+                let mut events = Vec::new();
+                let mut error = ws_error;
+                for (site_name, site_changed) in to_rebuild {
+                    let Some(ws_site) = ws_config.find_site(site_name) else {
+                        continue;
+                    };
+                    let (config, paths) = match load_site_in_workspace(&ws_config, ws_root, ws_site)
+                    {
+                        Ok(loaded) => loaded,
+                        Err(e) => {
+                            human::error(&format!(
+                                "Failed to reload seite.toml of '{site_name}': {e}"
+                            ));
+                            human::info("Still serving with the last valid config");
+                            let mut info = BuildErrorInfo::from_error(&e, ws_root);
+                            info.file.get_or_insert_with(|| {
+                                Path::new(&ws_site.path)
+                                    .join("seite.toml")
+                                    .display()
+                                    .to_string()
+                            });
+                            error = Some(info);
+                            continue;
+                        }
+                    };
+                    let site_config_changed = config_changed.contains(site_name);
+                    if site_config_changed {
+                        // Serve from a changed `output_dir` and watch moved directories
+                        let info = SiteServerInfo::new(site_name, &paths);
+                        if let Some(site) = sites.iter_mut().find(|s| s.name == *site_name) {
+                            if site.output_dir != info.output_dir {
+                                human::info(&format!(
+                                    "Output directory of '{site_name}' is now {}",
+                                    info.output_dir.display()
+                                ));
                             }
+                            *site = info;
                         }
+                        *routes.write().expect("routes lock poisoned") = routes_of(&sites);
+                        sync(&mut watcher, &sites);
+                        human::info(&format!(
+                            "Reloaded seite.toml of '{site_name}', rebuilding..."
+                        ));
                     }
-                } else {
-                    // Could not determine site — rebuild all
-                    human::info("Changes detected, rebuilding all sites...");
-                    for ws_site in &ws_config.sites {
-                        if let Ok((config, paths)) = load_site_in_workspace(ws_root, ws_site) {
-                            let opts = BuildOptions {
-                                include_drafts: true,
-                            };
-                            match build::build_site(&config, &paths, &opts) {
-                                Ok(result) => {
-                                    human::success(&format!(
-                                        "[{}] {}",
-                                        ws_site.name,
-                                        result.stats.human_display()
-                                    ));
-                                }
-                                Err(e) => {
-                                    human::error(&format!(
-                                        "Rebuild of '{}' failed: {e}",
-                                        ws_site.name
-                                    ));
-                                }
-                            }
+                    let opts = BuildOptions {
+                        include_drafts: true,
+                        as_of: None,
+                        incremental: true,
+                        cross_site_urls: cross_site_urls.clone(),
+                    };
+                    match build::build_site(&config, &paths, &opts) {
+                        Ok(result) => {
+                            human::success(&format!(
+                                "[{site_name}] {}",
+                                result.stats.human_display()
+                            ));
+                            events.push(if ws_config_changed || site_config_changed {
+                                LiveReloadEvent::Reload
+                            } else {
+                                crate::server::reload_event(&config, &paths, site_changed)
+                            });
+                        }
+                        Err(e) => {
+                            human::error(&format!("Rebuild of '{site_name}' failed: {e}"));
+                            error = Some(BuildErrorInfo::from_error(&e, &paths.root));
                         }
                     }
-                    build_version.fetch_add(1, Ordering::Relaxed);
+                }
+                if let Some(info) = error {
+                    live_reload.send(&LiveReloadEvent::BuildError(info));
+                } else if !events.is_empty() {
+                    live_reload.send(&combine_reload_events(events));
                 }
             }
             Ok(Err(e)) => {
@@ -419,6 +553,21 @@ fn watch_and_rebuild_workspace(
     }
 }
 
+/// Merge the events of several rebuilt sites: stylesheets are swapped in
+/// place only if every site had stylesheet-only changes.
+fn combine_reload_events(events: Vec<LiveReloadEvent>) -> LiveReloadEvent {
+    let mut css_paths = Vec::new();
+    for event in events {
+        match event {
+            LiveReloadEvent::CssUpdate { paths } => css_paths.extend(paths),
+            _ => return LiveReloadEvent::Reload,
+        }
+    }
+    css_paths.sort();
+    css_paths.dedup();
+    LiveReloadEvent::CssUpdate { paths: css_paths }
+}
+
 fn port_is_available(port: u16) -> bool {
     TcpStream::connect_timeout(
         &format!("127.0.0.1:{port}")
@@ -441,3 +590,71 @@ fn try_bind_auto(start_port: u16) -> Result<(Server, u16)> {
     }
     Err(PageError::Server("no available port found".into()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SITE_CONFIG: &str = "collections = []\n[site]\ntitle = \"T\"\ndescription = \"\"\nbase_url = \"http://localhost\"\nlanguage = \"en\"\nauthor = \"\"\n";
+    const WS_CONFIG: &str =
+        "[workspace]\nname = \"ws\"\n\n[[sites]]\nname = \"blog\"\npath = \"sites/blog\"\n";
+
+    fn wait_for(handle: &WorkspaceServerHandle, expected: &[(&str, PathBuf)], what: &str) {
+        let expected: Vec<(String, PathBuf)> = expected
+            .iter()
+            .map(|(name, dir)| (name.to_string(), dir.clone()))
+            .collect();
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while handle.sites() != expected {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "{what} was not reloaded"
+            );
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_config_changes_reload_sites() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        for site in ["blog", "docs"] {
+            fs::create_dir_all(root.join("sites").join(site)).unwrap();
+            fs::write(
+                root.join("sites").join(site).join("seite.toml"),
+                SITE_CONFIG,
+            )
+            .unwrap();
+        }
+        fs::write(root.join(WORKSPACE_FILE), WS_CONFIG).unwrap();
+        let ws_config = WorkspaceConfig::load(&root.join(WORKSPACE_FILE)).unwrap();
+        let handle = start(&ws_config, root, 38_600, true).unwrap();
+        // Let the watcher register before editing
+        std::thread::sleep(Duration::from_millis(300));
+
+        // A member's `seite.toml` moves its output directory
+        fs::write(
+            root.join("sites/blog/seite.toml"),
+            format!("{SITE_CONFIG}[build]\noutput_dir = \"out\"\n"),
+        )
+        .unwrap();
+        let blog = ("blog", root.join("sites/blog/out"));
+        wait_for(&handle, std::slice::from_ref(&blog), "seite.toml");
+
+        // An invalid workspace config is reported and the previous one kept
+        fs::write(root.join(WORKSPACE_FILE), "[workspace\n").unwrap();
+        std::thread::sleep(Duration::from_millis(1500));
+        assert_eq!(handle.sites().len(), 1);
+
+        // A site added to the workspace is served
+        fs::write(
+            root.join(WORKSPACE_FILE),
+            format!("{WS_CONFIG}\n[[sites]]\nname = \"docs\"\npath = \"sites/docs\"\n"),
+        )
+        .unwrap();
+        let docs = ("docs", root.join("sites/docs/dist"));
+        wait_for(&handle, &[blog, docs], WORKSPACE_FILE);
+        handle.stop();
+    }
+}
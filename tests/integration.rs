@@ -192,6 +192,179 @@ fn test_build_excludes_drafts_by_default() {
     assert!(site_dir.join("dist/posts/secret-draft.html").exists());
 }
 
+#[test]
+fn test_build_scheduled_and_expired_content() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Schedule Test", "posts");
+    let site_dir = tmp.path().join("site");
+    let posts = site_dir.join("content/posts");
+    fs::write(
+        posts.join("2025-01-01-future.md"),
+        "---\ntitle: Future Launch\npublish_at: 2999-01-01T09:00:00+02:00\n---\n\nComing soon.\n",
+    )
+    .unwrap();
+    fs::write(
+        posts.join("2025-01-02-expired.md"),
+        "---\ntitle: Old Offer\nexpires_at: 2025-02-01\n---\n\nGone.\n",
+    )
+    .unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let dist = site_dir.join("dist");
+    assert!(!dist.join("posts/future.html").exists());
+    assert!(!dist.join("posts/future.md").exists());
+    assert!(!dist.join("posts/expired.html").exists());
+    for file in ["feed.xml", "sitemap.xml", "llms.txt", "search-index.json"] {
+        let contents = fs::read_to_string(dist.join(file)).unwrap();
+        assert!(!contents.contains("Future Launch"), "{file}");
+        assert!(!contents.contains("/posts/future"), "{file}");
+        assert!(!contents.contains("/posts/expired"), "{file}");
+    }
+
+    // Preview the site as it will be once the post goes live
+    page_cmd()
+        .args(["build", "--as-of", "2999-01-01T08:00:00Z"])
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    assert!(dist.join("posts/future.html").exists());
+    assert!(!dist.join("posts/expired.html").exists());
+    let feed = fs::read_to_string(dist.join("feed.xml")).unwrap();
+    assert!(feed.contains("Future Launch"));
+
+    // An hour earlier it is still scheduled
+    page_cmd()
+        .args(["build", "--as-of", "2999-01-01T06:59:00Z"])
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    assert!(!dist.join("posts/future.html").exists());
+
+    page_cmd()
+        .args(["build", "--as-of", "tomorrow"])
+        .current_dir(&site_dir)
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_build_datetimes_and_site_timezone() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Changelog", "posts");
+    let site_dir = tmp.path().join("site");
+    let config_path = site_dir.join("seite.toml");
+    let config = fs::read_to_string(&config_path).unwrap();
+    fs::write(
+        &config_path,
+        config.replacen("[site]\n", "[site]\ntimezone = \"Europe/Berlin\"\n", 1),
+    )
+    .unwrap();
+    let posts = site_dir.join("content/posts");
+    fs::write(
+        posts.join("2025-03-10-morning.md"),
+        "---\ntitle: Morning Release\ndate: 2025-03-10T08:00:00Z\n---\n\nFirst.\n",
+    )
+    .unwrap();
+    fs::write(
+        posts.join("2025-03-10-late.md"),
+        "---\ntitle: Late Release\ndate: 2025-03-10T23:30:00Z\n---\n\nSecond.\n",
+    )
+    .unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let dist = site_dir.join("dist");
+    // Same-day posts sort by time, newest first
+    let index = fs::read_to_string(dist.join("posts/index.html")).unwrap();
+    let late = index.find("Late Release").unwrap();
+    let morning = index.find("Morning Release").unwrap();
+    assert!(late < morning);
+
+    let feed = fs::read_to_string(dist.join("feed.xml")).unwrap();
+    assert!(feed.contains("Mon, 10 Mar 2025 23:30:00 +0000"));
+    let sitemap = fs::read_to_string(dist.join("sitemap.xml")).unwrap();
+    assert!(sitemap.contains("<lastmod>2025-03-10T23:30:00+00:00</lastmod>"));
+
+    // 23:30 UTC is already the next day in Berlin
+    let page = fs::read_to_string(dist.join("posts/late.html")).unwrap();
+    assert!(page.contains("<time>2025-03-11</time>"));
+
+    fs::write(
+        &config_path,
+        fs::read_to_string(&config_path)
+            .unwrap()
+            .replace("Europe/Berlin", "Mars/Olympus"),
+    )
+    .unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_publish_command() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Publish Test", "posts,pages");
+    let site_dir = tmp.path().join("site");
+    fs::write(
+        site_dir.join("content/posts/2024-03-01-launch.md"),
+        "---\ntitle: Launch\ndate: 2024-03-01\ndraft: true\n# keep this comment\ntags: [news]\n---\n\nBody.\n",
+    )
+    .unwrap();
+
+    page_cmd()
+        .args([
+            "publish",
+            "content/posts/2024-03-01-launch.md",
+            "--date",
+            "2025-05-20",
+        ])
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    assert!(!site_dir.join("content/posts/2024-03-01-launch.md").exists());
+    let published =
+        fs::read_to_string(site_dir.join("content/posts/2025-05-20-launch.md")).unwrap();
+    assert_eq!(
+        published,
+        "---\ntitle: Launch\ndate: 2025-05-20\n# keep this comment\ntags: [news]\n---\n\nBody.\n"
+    );
+
+    // Pages have no date: only the draft flag is cleared
+    fs::write(
+        site_dir.join("content/pages/faq.md"),
+        "---\ntitle: FAQ\ndraft: true\n---\n\nQuestions.\n",
+    )
+    .unwrap();
+    page_cmd()
+        .args(["publish", "content/pages/faq.md"])
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    let faq = fs::read_to_string(site_dir.join("content/pages/faq.md")).unwrap();
+    assert_eq!(faq, "---\ntitle: FAQ\n---\n\nQuestions.\n");
+
+    page_cmd()
+        .args(["build"])
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    assert!(site_dir.join("dist/posts/launch.html").exists());
+    assert!(site_dir.join("dist/faq.html").exists());
+}
+
 #[test]
 fn test_build_with_docs() {
     let tmp = TempDir::new().unwrap();
@@ -1074,6 +1247,55 @@ fn test_build_search_index_multilingual() {
     assert!(!es_raw.contains("Hello World"));
 }
 
+#[test]
+fn test_build_full_text_search_index() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Full Text", "posts,pages");
+
+    let site_dir = tmp.path().join("site");
+    add_language(&site_dir, "es", "Texto completo");
+    let mut config = fs::read_to_string(site_dir.join("seite.toml")).unwrap();
+    config.push_str("\n[search]\n");
+    fs::write(site_dir.join("seite.toml"), config).unwrap();
+
+    let post = "---\ntitle: Deploying\ndate: 2025-01-10\n---\n\nIntro.\n\n## Caching layers\n\nThe edge caches responses.\n";
+    fs::write(site_dir.join("content/posts/2025-01-10-deploying.md"), post).unwrap();
+    let es_post = "---\ntitle: Hola Mundo\ndate: 2025-01-15\n---\n\nContenido publicado.\n";
+    fs::write(
+        site_dir.join("content/posts/2025-01-15-hola-mundo.es.md"),
+        es_post,
+    )
+    .unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let dist = site_dir.join("dist");
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dist.join("search/index.json")).unwrap()).unwrap();
+    assert_eq!(manifest["prefix_length"], 2);
+    let sections = manifest["sections"].as_array().unwrap();
+    assert!(sections
+        .iter()
+        .any(|s| s[1] == "Caching layers" && s[2] == "caching-layers"));
+    assert!(dist.join("search/search.js").exists());
+
+    // Body words are indexed under their stem
+    let shard: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dist.join("search/shards/ca.json")).unwrap())
+            .unwrap();
+    assert_eq!(shard["words"]["caches"], "cach");
+    assert!(shard["terms"]["cach"].as_array().is_some());
+
+    // Non-default languages get their own index
+    let es_manifest = fs::read_to_string(dist.join("es/search/index.json")).unwrap();
+    assert!(es_manifest.contains("Hola Mundo"));
+    assert!(!es_manifest.contains("Deploying"));
+}
+
 // --- pagination ---
 
 /// Helper: add `paginate = N` to the [[collections]] entry for `collection_name` in seite.toml.
@@ -1288,6 +1510,44 @@ fn test_build_fingerprint_writes_manifest() {
     );
 }
 
+#[test]
+fn test_build_fingerprint_rewrites_html_and_css_references() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Fingerprint Refs", "posts");
+    let site_dir = tmp.path().join("site");
+
+    write_static_css(&site_dir, "main.css", "body { background: url(logo.png); }");
+    fs::write(site_dir.join("static/logo.png"), b"not really a png").unwrap();
+    fs::write(
+        site_dir.join("templates/base.html"),
+        r#"<html><head><link rel="stylesheet" href="/static/main.css"><link rel="preload" as="image" href="{{ asset_url(path='/static/logo.png') }}"></head><body>{% block content %}{% endblock %}</body></html>"#,
+    )
+    .unwrap();
+    set_build_option(&site_dir, "fingerprint", "true");
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let manifest: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(site_dir.join("dist/asset-manifest.json")).unwrap(),
+    )
+    .unwrap();
+    let css_url = manifest["/static/main.css"].as_str().unwrap();
+    let logo_url = manifest["/static/logo.png"].as_str().unwrap();
+
+    let html = fs::read_to_string(site_dir.join("dist/index.html")).unwrap();
+    assert!(html.contains(&format!(r#"href="{css_url}""#)));
+    assert!(html.contains(&format!(r#"href="{logo_url}""#)));
+
+    // The fingerprinted stylesheet points at the fingerprinted image
+    let logo_name = logo_url.rsplit('/').next().unwrap();
+    let css = fs::read_to_string(site_dir.join(format!("dist{css_url}"))).unwrap();
+    assert!(css.contains(&format!("url({logo_name})")));
+}
+
 #[test]
 fn test_build_no_minify_by_default() {
     let tmp = TempDir::new().unwrap();
@@ -1755,6 +2015,89 @@ fn test_build_image_resize_and_webp() {
     );
 }
 
+#[test]
+fn test_build_image_cache_restores_variants() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Image Cache", "posts");
+    let site_dir = tmp.path().join("site");
+
+    write_test_image(&site_dir, "photo.png");
+    set_images_config(&site_dir, "[48]");
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    let cache_dir = site_dir.join(".seite/cache/images");
+    assert!(fs::read_dir(cache_dir.join("entries")).unwrap().count() > 0);
+
+    // A fresh output directory is filled from the cache
+    fs::remove_dir_all(site_dir.join("dist")).unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    assert!(site_dir.join("dist/static/images/photo-48w.webp").exists());
+
+    // --clean-cache starts over from an empty cache
+    fs::write(cache_dir.join("stray"), "x").unwrap();
+    page_cmd()
+        .args(["build", "--clean-cache"])
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    assert!(!cache_dir.join("stray").exists());
+    assert!(fs::read_dir(cache_dir.join("entries")).unwrap().count() > 0);
+    assert!(site_dir.join("dist/static/images/photo-48w.png").exists());
+}
+
+#[test]
+fn test_build_image_placeholder_and_image_meta() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Placeholder Test", "posts");
+    let site_dir = tmp.path().join("site");
+
+    write_test_image(&site_dir, "photo.png");
+    set_images_config(&site_dir, "[48]");
+    let toml_path = site_dir.join("seite.toml");
+    let mut config = fs::read_to_string(&toml_path).unwrap();
+    config.push_str("placeholder = \"blur\"\n");
+    fs::write(&toml_path, config).unwrap();
+
+    fs::write(
+        site_dir.join("content/posts/2025-01-01-photo.md"),
+        "---\ntitle: Photo\n---\n\n![Red](/static/images/photo.png)\n",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("templates/post.html"),
+        "{% extends \"base.html\" %}{% block content %}\
+         {% set hero = image_meta(path=\"/static/images/photo.png\") %}\
+         <div class=\"hero\" data-size=\"{{ hero.width }}x{{ hero.height }}\" \
+         data-color=\"{{ hero.color }}\" data-srcset=\"{{ hero.srcset }}\"></div>\
+         {% set missing = image_meta(path=\"/static/images/none.png\") %}\
+         {% if not missing %}<p class=\"no-meta\"></p>{% endif %}\
+         {{ page.content | safe }}{% endblock %}",
+    )
+    .unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let html = fs::read_to_string(site_dir.join("dist/posts/photo.html")).unwrap();
+    assert!(html.contains(r#"data-size="100x100""#));
+    assert!(html.contains(r##"data-color="#ff0000""##));
+    assert!(html.contains("/static/images/photo-48w.png 48w"));
+    assert!(html.contains(r#"<p class="no-meta">"#));
+    // The image in the content paints its placeholder while loading
+    assert!(html.contains(r##"style="background:#ff0000 url(data:image/webp;base64,"##));
+}
+
 #[test]
 fn test_build_image_lazy_loading() {
     let tmp = TempDir::new().unwrap();
@@ -1972,6 +2315,109 @@ fn test_build_image_avif_picture_element() {
     );
 }
 
+#[test]
+fn test_build_page_bundle() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Bundle Test", "posts");
+    let site_dir = tmp.path().join("site");
+    set_images_config(&site_dir, "[48]");
+
+    let bundle = site_dir.join("content/posts/2025-03-01-trip");
+    fs::create_dir_all(&bundle).unwrap();
+    fs::write(
+        bundle.join("index.md"),
+        "---\ntitle: Trip\nimage: cover.png\n---\n\n![Map](map.png)\n\n[Itinerary](files/plan.pdf)\n",
+    )
+    .unwrap();
+    let img = image::RgbImage::from_fn(100, 100, |_, _| image::Rgb([0u8, 0, 255]));
+    img.save(bundle.join("cover.png")).unwrap();
+    img.save(bundle.join("map.png")).unwrap();
+    fs::create_dir_all(bundle.join("files")).unwrap();
+    fs::write(bundle.join("files/plan.pdf"), "pdf").unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    // The directory names the page; its files are served under the page URL
+    let dist = site_dir.join("dist");
+    let html = fs::read_to_string(dist.join("posts/trip.html")).unwrap();
+    assert!(html.contains("2025-03-01"));
+    assert!(html.contains(r#"src="/posts/trip/map.png""#));
+    assert!(html.contains("/posts/trip/map-48w.png 48w"));
+    assert!(html.contains(r#"href="/posts/trip/files/plan.pdf""#));
+    assert!(html.contains("http://localhost:3000/posts/trip/cover.png"));
+    assert!(dist.join("posts/trip/files/plan.pdf").exists());
+    assert!(dist.join("posts/trip/map-48w.webp").exists());
+    let md = fs::read_to_string(dist.join("posts/trip.md")).unwrap();
+    assert!(md.contains("![Map](/posts/trip/map.png)"));
+
+    // A reference to a file the bundle doesn't have fails the build
+    fs::remove_file(bundle.join("map.png")).unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1 missing page bundle file(s)"))
+        .stderr(predicate::str::contains(
+            "content/posts/2025-03-01-trip/index.md: no bundle file `map.png`",
+        ));
+}
+
+#[test]
+fn test_build_og_cards() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Card Site", "posts");
+    let site_dir = tmp.path().join("site");
+    let logo = image::RgbaImage::from_pixel(64, 64, image::Rgba([255, 200, 0, 255]));
+    fs::create_dir_all(site_dir.join("static")).unwrap();
+    logo.save(site_dir.join("static/logo.png")).unwrap();
+    let toml_path = site_dir.join("seite.toml");
+    let mut config = fs::read_to_string(&toml_path).unwrap();
+    config.push_str("\n[og]\nlogo = \"/static/logo.png\"\nbackground_color = \"#1e3a8a\"\n");
+    fs::write(&toml_path, config).unwrap();
+
+    fs::write(
+        site_dir.join("content/posts/2025-01-01-plain.md"),
+        "---\ntitle: A Post Without Its Own Image\n---\n\nBody.\n",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/posts/2025-01-02-pictured.md"),
+        "---\ntitle: Pictured\nimage: /static/logo.png\n---\n\nBody.\n",
+    )
+    .unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let dist = site_dir.join("dist");
+    let card = image::open(dist.join("og/posts/plain.png")).unwrap();
+    assert_eq!((card.width(), card.height()), (1200, 630));
+    let html = fs::read_to_string(dist.join("posts/plain.html")).unwrap();
+    assert!(html.contains(
+        r#"<meta property="og:image" content="http://localhost:3000/og/posts/plain.png">"#
+    ));
+    // Pages with their own image keep it
+    assert!(!dist.join("og/posts/pictured.png").exists());
+    let html = fs::read_to_string(dist.join("posts/pictured.html")).unwrap();
+    assert!(html.contains("http://localhost:3000/static/logo.png"));
+
+    // Unchanged cards survive an incremental rebuild
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    assert!(dist.join("og/posts/plain.png").exists());
+}
+
 // ── Math/LaTeX rendering ──
 
 #[test]
@@ -2418,6 +2864,316 @@ fn test_build_tag_pages_multilingual() {
     );
 }
 
+#[test]
+fn test_build_feed_formats_and_tag_feeds() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Feeds", "posts,changelog");
+    let site_dir = tmp.path().join("site");
+    add_language(&site_dir, "es", "Feeds ES");
+
+    let config = fs::read_to_string(site_dir.join("seite.toml")).unwrap();
+    let config = config.replacen(
+        "name = \"posts\"",
+        "name = \"posts\"\nfeeds = [\"rss\", \"atom\", \"json\"]",
+        1,
+    );
+    let config = format!("{config}\n[feed]\nfull_content = true\ntags = true\n");
+    fs::write(site_dir.join("seite.toml"), config).unwrap();
+
+    fs::write(
+        site_dir.join("content/posts/2025-01-15-tagged.md"),
+        "---\ntitle: Tagged\ndate: 2025-01-15\ntags:\n  - rust\n---\n\nSee [the docs](/docs/intro).\n",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/posts/2025-01-15-tagged.es.md"),
+        "---\ntitle: Etiquetado\ndate: 2025-01-15\ntags:\n  - rust\n---\n\nHola.\n",
+    )
+    .unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let dist = site_dir.join("dist");
+    let rss = fs::read_to_string(dist.join("feed.xml")).unwrap();
+    assert!(rss.contains("<category>rust</category>"));
+    assert!(rss.contains("content:encoded"));
+    assert!(rss.contains("href=&quot;http://localhost:3000/docs/intro&quot;"));
+    let atom = fs::read_to_string(dist.join("atom.xml")).unwrap();
+    assert!(atom.contains("<title>Tagged</title>"));
+    // Changelog entries stay RSS-only
+    assert!(!atom.contains("changelog"));
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dist.join("feed.json")).unwrap()).unwrap();
+    let tagged = json["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["title"] == "Tagged")
+        .unwrap();
+    assert!(tagged["content_html"]
+        .as_str()
+        .unwrap()
+        .contains("http://localhost:3000/docs/intro"));
+    assert!(dist.join("es/atom.xml").exists());
+
+    // Per-tag feeds, per language, linked from the tag pages
+    let tag_rss = fs::read_to_string(dist.join("tags/rust/feed.xml")).unwrap();
+    assert!(tag_rss.contains("<title>Tagged</title>"));
+    assert!(!tag_rss.contains("Etiquetado"));
+    assert!(dist.join("tags/rust/feed.json").exists());
+    let tag_html = fs::read_to_string(dist.join("tags/rust/index.html")).unwrap();
+    assert!(tag_html.contains(r#"href="/tags/rust/atom.xml""#));
+    let es_tag_rss = fs::read_to_string(dist.join("es/tags/rust/feed.xml")).unwrap();
+    assert!(es_tag_rss.contains("Etiquetado"));
+    let es_tag_html = fs::read_to_string(dist.join("es/tags/rust/index.html")).unwrap();
+    assert!(es_tag_html.contains(r#"href="/es/tags/rust/feed.xml""#));
+}
+
+#[test]
+fn test_build_custom_taxonomies() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Taxonomies", "posts");
+    let site_dir = tmp.path().join("site");
+    add_language(&site_dir, "es", "Taxonomias");
+
+    let toml_path = site_dir.join("seite.toml");
+    let config = fs::read_to_string(&toml_path).unwrap();
+    fs::write(
+        &toml_path,
+        config.replace(
+            "[build]",
+            r#"[[taxonomies]]
+name = "categories"
+paginate = 1
+feed = true
+
+[[taxonomies]]
+name = "series"
+label = "Series"
+url_prefix = "/in-series"
+
+[build]"#,
+        ),
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/posts/2025-01-15-first.md"),
+        "---\ntitle: First\ndate: 2025-01-15\ncategories: [Rust, Web]\nseries: Getting Started\n---\nFirst.",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/posts/2025-02-15-second.md"),
+        "---\ntitle: Second\ndate: 2025-02-15\ncategories: Rust\n---\nSecond.",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/posts/2025-01-15-first.es.md"),
+        "---\ntitle: Primero\ndate: 2025-01-15\ncategories: [Rust]\n---\nPrimero.",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("templates/post.html"),
+        "{% extends \"base.html\" %}{% block content %}<h1>{{ page.title }}</h1>\
+         {% for term in taxonomies.categories %}<a class=\"term\" href=\"{{ term.url }}\">{{ term.name }}</a>{% endfor %}{% endblock %}",
+    )
+    .unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let dist = site_dir.join("dist");
+    let index = fs::read_to_string(dist.join("categories/index.html")).unwrap();
+    assert!(index.contains("/categories/rust/") && index.contains("(2)"));
+    // Paginated newest first, one item per page
+    let rust = fs::read_to_string(dist.join("categories/rust/index.html")).unwrap();
+    assert!(rust.contains("Second") && !rust.contains("First"));
+    let page2 = fs::read_to_string(dist.join("categories/rust/page/2/index.html")).unwrap();
+    assert!(page2.contains("First"));
+    let feed = fs::read_to_string(dist.join("categories/rust/feed.xml")).unwrap();
+    assert!(feed.contains("<title>Taxonomies — Categories: Rust</title>"));
+    assert!(feed.contains("/posts/first"));
+    assert!(dist.join("in-series/getting-started/index.html").exists());
+    // Per-language term pages
+    let es = fs::read_to_string(dist.join("es/categories/rust/index.html")).unwrap();
+    assert!(es.contains("Primero") && !es.contains("Second"));
+
+    // Any page can list its terms
+    let post = fs::read_to_string(dist.join("posts/first.html")).unwrap();
+    assert!(post.contains(r#"<a class="term" href="/categories/rust/">Rust</a>"#));
+    assert!(post.contains(r#"href="/categories/web/">Web</a>"#));
+
+    let sitemap = fs::read_to_string(dist.join("sitemap.xml")).unwrap();
+    assert!(sitemap.contains("/categories/rust/page/2/"));
+    assert!(sitemap.contains("/es/categories/rust/"));
+    assert!(sitemap.contains("/in-series/"));
+}
+
+#[test]
+fn test_build_author_profiles() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Authors", "posts");
+    let site_dir = tmp.path().join("site");
+    add_language(&site_dir, "es", "Autores");
+    fs::create_dir_all(site_dir.join("data")).unwrap();
+    fs::write(
+        site_dir.join("data/authors.yaml"),
+        "jane:\n  name: Jane Doe\n  bio: Writes about compilers.\n  social:\n    github: https://github.com/jane\nsam:\n  name: Sam\n",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/posts/2025-01-15-joint.md"),
+        "---\ntitle: Joint Post\ndate: 2025-01-15\nauthors: [jane, sam]\n---\nBody.",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/posts/2025-01-15-joint.es.md"),
+        "---\ntitle: Articulo Conjunto\ndate: 2025-01-15\nauthors: [jane]\n---\nCuerpo.",
+    )
+    .unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let dist = site_dir.join("dist");
+    let jane = fs::read_to_string(dist.join("authors/jane/index.html")).unwrap();
+    assert!(jane.contains("Jane Doe") && jane.contains("Writes about compilers."));
+    assert!(jane.contains("Joint Post") && jane.contains(r#"href="https://github.com/jane""#));
+    let feed = fs::read_to_string(dist.join("authors/jane/feed.xml")).unwrap();
+    assert!(feed.contains("<title>Authors — Jane Doe</title>"));
+    assert!(feed.contains("/posts/joint"));
+    assert!(dist.join("authors/sam/index.html").exists());
+    let es = fs::read_to_string(dist.join("es/authors/jane/index.html")).unwrap();
+    assert!(es.contains("Articulo Conjunto") && !es.contains("Joint Post"));
+    assert!(!dist.join("es/authors/sam/index.html").exists());
+
+    // JSON-LD credits every author as a Person
+    let post = fs::read_to_string(dist.join("posts/joint.html")).unwrap();
+    assert!(post.contains(
+        r#""author":[{"@type":"Person","name":"Jane Doe","url":"http://localhost:3000/authors/jane/","sameAs":["https://github.com/jane"]},{"@type":"Person","name":"Sam","url":"http://localhost:3000/authors/sam/"}]"#
+    ));
+    let sitemap = fs::read_to_string(dist.join("sitemap.xml")).unwrap();
+    assert!(sitemap.contains("/es/authors/jane/"));
+
+    // Unknown authors fail the build, all of them reported
+    fs::write(
+        site_dir.join("content/posts/2025-02-01-other.md"),
+        "---\ntitle: Other\ndate: 2025-02-01\nauthors: [jnae, bob]\n---\nBody.",
+    )
+    .unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("2 unknown author(s)"))
+        .stderr(predicate::str::contains(
+            "content/posts/2025-02-01-other.md: unknown author `jnae`",
+        ));
+}
+
+#[test]
+fn test_build_related_content() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Related", "posts,pages");
+    let site_dir = tmp.path().join("site");
+    let templates_dir = site_dir.join("templates");
+    fs::create_dir_all(&templates_dir).unwrap();
+    fs::write(
+        templates_dir.join("post.html"),
+        r#"{% extends "base.html" %}
+{% block content %}{% for r in page.related %}<a class="related" href="{{ r.url }}">{{ r.title }}</a>{% endfor %}{% endblock %}"#,
+    )
+    .unwrap();
+    let posts = site_dir.join("content/posts");
+    fs::write(
+        posts.join("2025-01-01-borrowing.md"),
+        "---\ntitle: Borrowing\ndate: 2025-01-01\ntags: [rust]\nrelated: [welcome]\n---\nThe borrow checker.",
+    )
+    .unwrap();
+    fs::write(
+        posts.join("2025-01-02-lifetimes.md"),
+        "---\ntitle: Lifetimes\ndate: 2025-01-02\ntags: [rust]\n---\nLifetimes and the borrow checker.",
+    )
+    .unwrap();
+    fs::write(
+        posts.join("2025-01-03-bread.md"),
+        "---\ntitle: Bread\ndate: 2025-01-03\n---\nFlour and water.",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/pages/welcome.md"),
+        "---\ntitle: Welcome\n---\nHi.",
+    )
+    .unwrap();
+
+    // Without [related], only explicit entries are listed
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    let dist = site_dir.join("dist");
+    let html = fs::read_to_string(dist.join("posts/borrowing.html")).unwrap();
+    assert!(html.contains(r#"<a class="related" href="/welcome">Welcome</a>"#));
+    assert!(!html.contains("Lifetimes</a>"));
+
+    let config_path = site_dir.join("seite.toml");
+    let config = fs::read_to_string(&config_path).unwrap();
+    fs::write(&config_path, format!("{config}\n[related]\n")).unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let html = fs::read_to_string(dist.join("posts/borrowing.html")).unwrap();
+    assert!(html.contains(
+        r#"<a class="related" href="/welcome">Welcome</a><a class="related" href="/posts/lifetimes">Lifetimes</a>"#
+    ));
+    assert!(!html.contains("Bread</a>"));
+    let md = fs::read_to_string(dist.join("posts/borrowing.md")).unwrap();
+    assert!(md.contains("related:\n- /welcome\n- /posts/lifetimes\n"));
+
+    // Editing a post re-renders only the pages whose related list changes
+    fs::write(
+        posts.join("2025-01-03-bread.md"),
+        "---\ntitle: Bread\ndate: 2025-01-03\n---\nFlour, water and salt.",
+    )
+    .unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 pages re-rendered"));
+
+    // An explicit entry that matches no page fails the build
+    fs::write(
+        posts.join("2025-01-04-typo.md"),
+        "---\ntitle: Typo\ndate: 2025-01-04\nrelated: [welcom]\n---\nBody.",
+    )
+    .unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1 unknown related page(s)"))
+        .stderr(predicate::str::contains(
+            "content/posts/2025-01-04-typo.md: no page matches `welcom`",
+        ));
+}
+
 // --- custom templates and extra frontmatter ---
 
 #[test]
@@ -2547,6 +3303,165 @@ fn test_build_detects_url_collision() {
         .stderr(predicate::str::contains("URL collision"));
 }
 
+#[test]
+fn test_build_permalink_patterns() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Permalinks", "posts,docs");
+    let site_dir = tmp.path().join("site");
+    let config_path = site_dir.join("seite.toml");
+    let config = fs::read_to_string(&config_path)
+        .unwrap()
+        .replace(
+            "default_template = \"post.html\"\n",
+            "default_template = \"post.html\"\npermalink = \"/:year/:month/:slug\"\n",
+        )
+        .replace(
+            "default_template = \"doc.html\"\n",
+            "default_template = \"doc.html\"\npermalink = \"/docs/:section/:slug\"\n",
+        );
+    fs::write(&config_path, &config).unwrap();
+    fs::write(
+        site_dir.join("content/posts/2025-03-07-launch.md"),
+        "---\ntitle: Launch\n---\n\nWe launched.\n",
+    )
+    .unwrap();
+    let guides = site_dir.join("content/docs/guides");
+    fs::create_dir_all(&guides).unwrap();
+    fs::write(
+        guides.join("install.md"),
+        "---\ntitle: Install\n---\n\nSee [launch](/2025/03/launch).\n",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/docs/intro.md"),
+        "---\ntitle: Intro\n---\n\nHello.\n",
+    )
+    .unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let dist = site_dir.join("dist");
+    assert!(dist.join("2025/03/launch.html").exists());
+    assert!(dist.join("2025/03/launch.md").exists());
+    assert!(!dist.join("posts/launch.html").exists());
+    assert!(dist.join("docs/guides/install.html").exists());
+    assert!(dist.join("docs/intro.html").exists());
+    let sitemap = fs::read_to_string(dist.join("sitemap.xml")).unwrap();
+    assert!(sitemap.contains("/2025/03/launch</loc>"));
+    let index = fs::read_to_string(dist.join("posts/index.html")).unwrap();
+    assert!(index.contains("href=\"/2025/03/launch\""));
+
+    // A pattern without :slug makes same-month posts claim one URL
+    fs::write(
+        site_dir.join("content/posts/2025-03-20-follow-up.md"),
+        "---\ntitle: Follow-up\n---\n\nMore.\n",
+    )
+    .unwrap();
+    fs::write(
+        &config_path,
+        config.replace("/:year/:month/:slug\"", "/:year/:month\""),
+    )
+    .unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("URL collision"))
+        .stderr(predicate::str::contains(
+            "permalink '/:year/:month' of posts",
+        ));
+    fs::remove_file(site_dir.join("content/posts/2025-03-20-follow-up.md")).unwrap();
+
+    // A trailing slash needs directory-style output, or the old links 404
+    let slashed = config.replace("/:year/:month/:slug\"", "/:year/:month/:slug/\"");
+    fs::write(&config_path, &slashed).unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ends with '/'"))
+        .stderr(predicate::str::contains("url_style = \"directory\""));
+    fs::write(
+        &config_path,
+        slashed.replacen("[build]\n", "[build]\nurl_style = \"directory\"\n", 1),
+    )
+    .unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    assert!(dist.join("2025/03/launch/index.html").exists());
+    let sitemap = fs::read_to_string(dist.join("sitemap.xml")).unwrap();
+    assert!(sitemap.contains("/2025/03/launch/</loc>"));
+}
+
+#[test]
+fn test_build_directory_url_style() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Directories", "posts,docs");
+    let site_dir = tmp.path().join("site");
+    let config_path = site_dir.join("seite.toml");
+    let config = fs::read_to_string(&config_path).unwrap();
+    fs::write(
+        &config_path,
+        config.replacen("[build]\n", "[build]\nurl_style = \"directory\"\n", 1),
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/posts/2025-03-07-launch.md"),
+        "---\ntitle: Launch\ndescription: We launched\n---\n\nRead the [intro](/docs/intro).\n",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/docs/intro.md"),
+        "---\ntitle: Intro\n---\n\nSee the [launch](/posts/launch/).\n",
+    )
+    .unwrap();
+
+    let output = page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let log = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!log.contains("broken internal link"), "{log}");
+
+    let dist = site_dir.join("dist");
+    assert!(dist.join("posts/launch/index.html").exists());
+    assert!(dist.join("posts/launch/index.md").exists());
+    assert!(dist.join("docs/intro/index.html").exists());
+    assert!(dist.join("posts/index.html").exists());
+
+    // The slashless form redirects to the canonical URL
+    let stub = fs::read_to_string(dist.join("posts/launch.html")).unwrap();
+    assert!(stub.contains("url=/posts/launch/"));
+
+    let page = fs::read_to_string(dist.join("posts/launch/index.html")).unwrap();
+    assert!(page.contains("rel=\"canonical\" href=\"http://localhost:3000/posts/launch/\""));
+    assert!(page.contains("href=\"http://localhost:3000/posts/launch/index.md\""));
+    let sitemap = fs::read_to_string(dist.join("sitemap.xml")).unwrap();
+    assert!(sitemap.contains("<loc>http://localhost:3000/posts/launch/</loc>"));
+    assert!(!sitemap.contains("/posts/launch</loc>"));
+    let feed = fs::read_to_string(dist.join("feed.xml")).unwrap();
+    assert!(feed.contains("<link>http://localhost:3000/posts/launch/</link>"));
+    let llms = fs::read_to_string(dist.join("llms.txt")).unwrap();
+    assert!(llms.contains("(http://localhost:3000/posts/launch/index.md)"));
+    let search = fs::read_to_string(dist.join("search-index.json")).unwrap();
+    assert!(search.contains("\"url\":\"/docs/intro/\""));
+}
+
 #[test]
 fn test_build_warns_missing_content_dir() {
     let tmp = TempDir::new().unwrap();
@@ -2947,14 +3862,25 @@ fn test_build_user_defined_shortcode() {
 }
 
 #[test]
-fn test_build_shortcode_raw_body_preserved_in_md() {
+fn test_build_shortcode_markdown_rendering_in_md() {
     let tmp = TempDir::new().unwrap();
     init_site(&tmp, "sctest8", "SC Test", "posts");
     let site_dir = tmp.path().join("sctest8");
 
+    fs::create_dir_all(site_dir.join("templates/shortcodes")).unwrap();
+    fs::write(
+        site_dir.join("templates/shortcodes/badge.html"),
+        "<span class=\"badge\">{{ text }}</span>",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("templates/shortcodes/badge.md"),
+        "**[{{ text }}]**",
+    )
+    .unwrap();
     fs::write(
         site_dir.join("content/posts/2025-01-15-raw.md"),
-        "---\ntitle: Raw MD\n---\n\n{{< youtube(id=\"test123\") >}}\n",
+        "---\ntitle: Raw MD\n---\n\n{{< youtube(id=\"test123\") >}}\n\n{{% callout(type=\"warning\") %}}\nMind the gap.\n{{% end %}}\n\n{{< badge(text=\"beta\") >}}\n",
     )
     .unwrap();
 
@@ -2968,9 +3894,16 @@ fn test_build_shortcode_raw_body_preserved_in_md() {
     let html = fs::read_to_string(site_dir.join("dist/posts/raw.html")).unwrap();
     assert!(html.contains("youtube.com/embed/test123"));
 
-    // MD output should have the original unexpanded shortcode
+    // MD output and llms-full.txt carry the markdown renderings
     let md = fs::read_to_string(site_dir.join("dist/posts/raw.md")).unwrap();
-    assert!(md.contains("{{< youtube(id=\"test123\") >}}"));
+    let llms_full = fs::read_to_string(site_dir.join("dist/llms-full.txt")).unwrap();
+    for output in [&md, &llms_full] {
+        assert!(!output.contains("{{<"));
+        assert!(!output.contains("{{%"));
+        assert!(output.contains("[YouTube video](https://www.youtube.com/watch?v=test123)"));
+        assert!(output.contains("> **Warning**\n>\n> Mind the gap."));
+        assert!(output.contains("**[beta]**"));
+    }
 }
 
 #[test]
@@ -3128,6 +4061,83 @@ fn test_build_link_check_strict_fails_on_broken_links() {
         .stderr(predicate::str::contains("broken internal link"));
 }
 
+#[test]
+fn test_build_redirects_from_aliases_and_data() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Redirects", "posts,pages");
+    let site_dir = tmp.path().join("site");
+
+    fs::write(
+        site_dir.join("content/pages/setup.md"),
+        "---\ntitle: Setup\naliases:\n  - /guides/setup\n  - /install/\n---\n\nSetup.\n",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/posts/2025-01-01-links.md"),
+        "---\ntitle: Links\ndate: 2025-01-01\n---\n\n[Old](/guides/setup) [Older](/old-blog)\n",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("data/redirects.yaml"),
+        "- from: /old-blog\n  to: /posts/\n",
+    )
+    .unwrap();
+
+    // Links to old paths are valid, so --strict passes
+    page_cmd()
+        .args(["build", "--strict"])
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let dist = site_dir.join("dist");
+    let stub = fs::read_to_string(dist.join("guides/setup.html")).unwrap();
+    assert!(stub.contains(r#"<meta http-equiv="refresh" content="0; url=/setup">"#));
+    assert!(dist.join("install/index.html").exists());
+    let stub = fs::read_to_string(dist.join("old-blog.html")).unwrap();
+    assert!(stub.contains("url=/posts/"));
+    // GitHub Pages only gets the stubs
+    assert!(!dist.join("_redirects").exists());
+
+    let toml_path = site_dir.join("seite.toml");
+    let config = fs::read_to_string(&toml_path).unwrap();
+    fs::write(
+        &toml_path,
+        config.replace("target = \"github-pages\"", "target = \"netlify\""),
+    )
+    .unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    let rules = fs::read_to_string(dist.join("_redirects")).unwrap();
+    assert!(rules.contains("/guides/setup /setup 301\n"));
+    assert!(rules.contains("/install/ /setup 301\n"));
+    assert!(rules.contains("/old-blog /posts/ 301\n"));
+}
+
+#[test]
+fn test_build_alias_conflicting_with_page_fails() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Redirect Conflict", "posts,pages");
+    let site_dir = tmp.path().join("site");
+
+    fs::write(
+        site_dir.join("content/pages/setup.md"),
+        "---\ntitle: Setup\naliases: [/posts]\n---\n\nSetup.\n",
+    )
+    .unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "redirect from '/posts' conflicts with an existing page or file",
+        ));
+}
+
 #[test]
 fn test_build_link_check_passes_with_valid_links() {
     let tmp = TempDir::new().unwrap();
@@ -5181,6 +6191,189 @@ fn test_new_changelog_entry() {
     assert!(content.contains("date:"));
 }
 
+/// Helper: declare a `[collections.fields]` schema on the posts collection.
+fn set_posts_fields(site_dir: &std::path::Path, fields: &str) {
+    let toml_path = site_dir.join("seite.toml");
+    let config = fs::read_to_string(&toml_path).unwrap();
+    let config = config.replace(
+        "default_template = \"post.html\"\n",
+        &format!("default_template = \"post.html\"\n\n[collections.fields]\n{fields}\n"),
+    );
+    fs::write(&toml_path, config).unwrap();
+}
+
+#[test]
+fn test_build_reports_all_schema_violations() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Schema", "posts");
+    let site_dir = tmp.path().join("site");
+    set_posts_fields(
+        &site_dir,
+        "kind = { type = \"string\", required = true, enum = [\"news\", \"guide\"] }\n\
+         featured = { type = \"bool\", default = false }",
+    );
+    fs::write(
+        site_dir.join("content/posts/2025-01-01-a.md"),
+        "---\ntitle: A\nextra:\n  kind: rumour\n---\nA",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("content/posts/2025-01-02-b.md"),
+        "---\ntitle: B\nextra:\n  kind: news\n  featurd: true\n---\nB",
+    )
+    .unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "2025-01-01-a.md: field `kind` must be one of: news, guide (got \"rumour\")",
+        ))
+        .stderr(predicate::str::contains(
+            "2025-01-02-b.md: unknown field `featurd` (did you mean `featured`?)",
+        ))
+        // The starter post has no `kind`
+        .stderr(predicate::str::contains("missing required field `kind`"));
+
+    // Fix every file, and defaults reach templates
+    for entry in fs::read_dir(site_dir.join("content/posts")).unwrap() {
+        let path = entry.unwrap().path();
+        let text = fs::read_to_string(&path).unwrap();
+        let text = text
+            .replace("kind: rumour", "kind: guide")
+            .replace("  featurd: true\n", "");
+        let text = if text.contains("kind:") {
+            text
+        } else {
+            text.replacen("---\n", "---\nextra:\n  kind: news\n", 1)
+        };
+        fs::write(&path, text).unwrap();
+    }
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_new_prefills_required_schema_fields() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Schema New", "posts");
+    let site_dir = tmp.path().join("site");
+    set_posts_fields(
+        &site_dir,
+        "kind = { type = \"string\", required = true, enum = [\"news\", \"guide\"] }\n\
+         featured = { type = \"bool\", default = false }",
+    );
+
+    page_cmd()
+        .args(["new", "post", "Schema Post"])
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let entry = fs::read_dir(site_dir.join("content/posts"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .find(|e| e.file_name().to_str().unwrap_or("").contains("schema-post"))
+        .unwrap();
+    let content = fs::read_to_string(entry.path()).unwrap();
+    assert!(content.contains("extra:\n  kind: news"), "{content}");
+    assert!(!content.contains("featured"));
+}
+
+#[test]
+fn test_build_data_backed_collection() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Data Pages", "posts,pages");
+    let site_dir = tmp.path().join("site");
+
+    let toml_path = site_dir.join("seite.toml");
+    let config = fs::read_to_string(&toml_path).unwrap();
+    fs::write(
+        &toml_path,
+        config.replace(
+            "[build]",
+            r#"[[collections]]
+name = "integrations"
+label = "Integrations"
+directory = "integrations"
+url_prefix = "/integrations"
+default_template = "integration.html"
+listed = true
+paginate = 1
+data = "catalogue/integrations"
+slug_field = "id"
+title_field = "name"
+body_field = "details"
+
+[build]"#,
+        ),
+    )
+    .unwrap();
+    fs::create_dir_all(site_dir.join("data/catalogue")).unwrap();
+    fs::write(
+        site_dir.join("data/catalogue/integrations.yaml"),
+        "- id: stripe\n  name: Stripe\n  tags: [payments]\n  logo: /static/stripe.svg\n  details: Take **payments**.\n\
+         - id: slack\n  name: Slack\n  details: Post to channels.\n",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("templates/integration.html"),
+        "{% extends \"base.html\" %}{% block content %}<h1>{{ page.title }}</h1>\
+         <img class=\"logo\" src=\"{{ page.extra.logo | default(value='') }}\">{{ page.content | safe }}{% endblock %}",
+    )
+    .unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let dist = site_dir.join("dist");
+    let html = fs::read_to_string(dist.join("integrations/stripe.html")).unwrap();
+    assert!(html.contains("<h1>Stripe</h1>"));
+    assert!(html.contains(r#"src="/static/stripe.svg""#));
+    assert!(html.contains("<strong>payments</strong>"));
+    let md = fs::read_to_string(dist.join("integrations/stripe.md")).unwrap();
+    assert!(md.contains("title: Stripe") && md.contains("Take **payments**."));
+    assert!(dist.join("integrations/slack.html").exists());
+    // Paginated index, tags, sitemap, search and llms.txt include the entries
+    assert!(dist.join("integrations/page/2/index.html").exists());
+    assert!(fs::read_to_string(dist.join("tags/payments/index.html"))
+        .unwrap()
+        .contains("Stripe"));
+    assert!(fs::read_to_string(dist.join("sitemap.xml"))
+        .unwrap()
+        .contains("/integrations/slack"));
+    assert!(fs::read_to_string(dist.join("search-index.json"))
+        .unwrap()
+        .contains("\"url\":\"/integrations/stripe\""));
+    assert!(fs::read_to_string(dist.join("llms.txt"))
+        .unwrap()
+        .contains("[Slack]"));
+
+    // Entries without a slug are reported against the data file
+    fs::write(
+        site_dir.join("data/catalogue/integrations.yaml"),
+        "- name: Nameless\n",
+    )
+    .unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("integrations.yaml"))
+        .stderr(predicate::str::contains(
+            "entry 1: missing `id` (the slug field)",
+        ));
+}
+
 // --- roadmap collection ---
 
 #[test]
@@ -7098,69 +8291,263 @@ fn test_deploy_dry_run_no_commit() {
 fn test_workspace_build() {
     let tmp = TempDir::new().unwrap();
 
-    // Init workspace
+    // Init workspace
+    page_cmd()
+        .args(["workspace", "init", "ws-build"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    // Add a site with a unique name
+    page_cmd()
+        .args([
+            "workspace",
+            "add",
+            "myblog",
+            "--title",
+            "My Blog",
+            "--collections",
+            "posts",
+        ])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    // Build all sites in workspace
+    page_cmd()
+        .args(["build"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    // Verify output was created
+    assert!(tmp.path().join("sites/myblog/dist").exists());
+}
+
+#[test]
+fn test_workspace_build_with_site_filter() {
+    let tmp = TempDir::new().unwrap();
+
+    page_cmd()
+        .args(["workspace", "init", "ws-filter"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    page_cmd()
+        .args([
+            "workspace",
+            "add",
+            "docs",
+            "--title",
+            "Docs",
+            "--collections",
+            "docs",
+        ])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    // Build only the docs site
+    page_cmd()
+        .args(["build", "--site", "docs"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+}
+
+/// Create a workspace with a `news` (posts) and `handbook` (docs) site and enable all
+/// `[cross_site]` features. The news post links into the handbook site.
+fn init_cross_site_workspace(tmp: &TempDir, post_link: &str) {
+    page_cmd()
+        .args(["workspace", "init", "ws-cross"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    for (name, collections) in [("news", "posts"), ("handbook", "docs")] {
+        page_cmd()
+            .args(["workspace", "add", name, "--collections", collections])
+            .current_dir(tmp.path())
+            .assert()
+            .success();
+    }
+
+    let ws_file = tmp.path().join("seite-workspace.toml");
+    let mut ws = fs::read_to_string(&ws_file).unwrap();
+    ws.push_str(
+        "\n[cross_site]\nunified_sitemap = true\nunified_search = true\ncross_site_links = true\n",
+    );
+    fs::write(&ws_file, ws).unwrap();
+
+    fs::write(
+        tmp.path()
+            .join("sites/handbook/content/docs/getting-started.md"),
+        "---\ntitle: Getting Started\n---\n\nInstall it.\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path()
+            .join("sites/news/content/posts/2025-01-01-launch.md"),
+        format!("---\ntitle: Launch\ndate: 2025-01-01\n---\n\nRead the [guide]({post_link}).\n"),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_workspace_cross_site_links_resolve_to_base_url() {
+    let tmp = TempDir::new().unwrap();
+    init_cross_site_workspace(&tmp, "@site:handbook/docs/getting-started");
+
+    page_cmd()
+        .args(["build"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    let html = fs::read_to_string(tmp.path().join("sites/news/dist/posts/launch.html")).unwrap();
+    assert!(html.contains(r#"href="http://localhost:3000/handbook/docs/getting-started""#));
+    assert!(!html.contains("@site:"));
+}
+
+#[test]
+fn test_workspace_cross_site_broken_link_reported() {
+    let tmp = TempDir::new().unwrap();
+    init_cross_site_workspace(&tmp, "@site:handbook/docs/missing");
+
+    page_cmd()
+        .args(["build"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("broken cross-site link"))
+        .stdout(predicate::str::contains("@site:handbook/docs/missing"));
+}
+
+#[test]
+fn test_workspace_cross_site_link_to_unbuilt_site_unchecked() {
+    let tmp = TempDir::new().unwrap();
+    init_cross_site_workspace(&tmp, "@site:handbook/docs/missing");
+
+    // The handbook has no output to check against: the link is neither
+    // passed nor reported broken
     page_cmd()
-        .args(["workspace", "init", "ws-build"])
+        .args(["build", "--site", "news"])
         .current_dir(tmp.path())
         .assert()
-        .success();
+        .success()
+        .stdout(predicate::str::contains(
+            "cross-site links unchecked, 'handbook' has not been built yet",
+        ))
+        .stdout(predicate::str::contains("@site:handbook/docs/missing"))
+        .stdout(predicate::str::contains("broken cross-site link").not());
 
-    // Add a site with a unique name
+    // Once the handbook is built the same link is checked and reported
     page_cmd()
-        .args([
-            "workspace",
-            "add",
-            "myblog",
-            "--title",
-            "My Blog",
-            "--collections",
-            "posts",
-        ])
+        .args(["build", "--site", "handbook"])
         .current_dir(tmp.path())
         .assert()
         .success();
+    page_cmd()
+        .args(["build", "--site", "news"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("unchecked").not())
+        .stdout(predicate::str::contains("broken cross-site link"));
+}
+
+#[test]
+fn test_workspace_unified_sitemap_and_search() {
+    let tmp = TempDir::new().unwrap();
+    init_cross_site_workspace(&tmp, "@site:handbook/docs/getting-started");
 
-    // Build all sites in workspace
     page_cmd()
         .args(["build"])
         .current_dir(tmp.path())
         .assert()
         .success();
 
-    // Verify output was created
-    assert!(tmp.path().join("sites/myblog/dist").exists());
+    let index = fs::read_to_string(tmp.path().join("dist/sitemap_index.xml")).unwrap();
+    assert!(index.contains("<sitemapindex"));
+    assert!(index.contains("<loc>http://localhost:3000/news/sitemap.xml</loc>"));
+    assert!(index.contains("<loc>http://localhost:3000/handbook/sitemap.xml</loc>"));
+
+    let search: Vec<serde_json::Value> = serde_json::from_str(
+        &fs::read_to_string(tmp.path().join("dist/search-index.json")).unwrap(),
+    )
+    .unwrap();
+    let docs_entry = search
+        .iter()
+        .find(|e| e["title"] == "Getting Started")
+        .expect("docs entry in unified search index");
+    assert_eq!(docs_entry["site"], "handbook");
+    assert_eq!(
+        docs_entry["url"],
+        "http://localhost:3000/handbook/docs/getting-started"
+    );
+    assert!(search
+        .iter()
+        .any(|e| e["title"] == "Launch" && e["site"] == "news"));
 }
 
 #[test]
-fn test_workspace_build_with_site_filter() {
+fn test_workspace_shared_data_static_and_templates() {
     let tmp = TempDir::new().unwrap();
-
     page_cmd()
-        .args(["workspace", "init", "ws-filter"])
+        .args(["workspace", "init", "ws-shared"])
         .current_dir(tmp.path())
         .assert()
         .success();
-
     page_cmd()
-        .args([
-            "workspace",
-            "add",
-            "docs",
-            "--title",
-            "Docs",
-            "--collections",
-            "docs",
-        ])
+        .args(["workspace", "add", "news", "--collections", "posts"])
         .current_dir(tmp.path())
         .assert()
         .success();
 
-    // Build only the docs site
+    // Enable the shared directories scaffolded by `workspace init`
+    let ws_file = tmp.path().join("seite-workspace.toml");
+    let ws = fs::read_to_string(&ws_file)
+        .unwrap()
+        .replace("# shared_", "shared_");
+    fs::write(&ws_file, ws).unwrap();
+
+    // Shared data, deep-merged under the site's own data (site wins)
+    fs::write(
+        tmp.path().join("data/company.yaml"),
+        "name: Acme\nphone: \"555-0100\"\n",
+    )
+    .unwrap();
+    let site = tmp.path().join("sites/news");
+    fs::create_dir_all(site.join("data")).unwrap();
+    fs::write(site.join("data/company.yaml"), "name: Acme News\n").unwrap();
+
+    // Shared template used because the site doesn't define post.html
+    fs::write(
+        tmp.path().join("templates/post.html"),
+        "{% extends \"base.html\" %}{% block content %}SHARED-POST {{ data.company.name }} {{ data.company.phone }}{% endblock %}",
+    )
+    .unwrap();
+
+    // Shared static files; the site's own copy of brand.css wins
+    fs::write(tmp.path().join("static/shared.js"), "console.log(1);").unwrap();
+    fs::write(tmp.path().join("static/brand.css"), "/* shared */").unwrap();
+    fs::write(site.join("static/brand.css"), "/* site */").unwrap();
+
+    create_post(&site, "2025-01-01", "hello", "Hello");
+
     page_cmd()
-        .args(["build", "--site", "docs"])
+        .args(["build"])
         .current_dir(tmp.path())
         .assert()
         .success();
+
+    let html = fs::read_to_string(site.join("dist/posts/hello.html")).unwrap();
+    assert!(html.contains("SHARED-POST Acme News 555-0100"));
+    assert!(site.join("dist/static/shared.js").exists());
+    assert_eq!(
+        fs::read_to_string(site.join("dist/static/brand.css")).unwrap(),
+        "/* site */"
+    );
 }
 
 // --- self-update ---
@@ -7340,6 +8727,52 @@ fn test_build_generates_sitemap() {
     assert!(sitemap.contains("<url>"));
 }
 
+#[test]
+fn test_build_sitemap_page_controls_and_images() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "smap", "Sitemap Test", "posts");
+    let site_dir = tmp.path().join("smap");
+
+    write_test_image(&site_dir, "photo.png");
+    set_images_config(&site_dir, "[48]");
+    let posts = site_dir.join("content/posts");
+    fs::write(
+        posts.join("2025-01-01-hinted.md"),
+        "---\ntitle: Hinted\ndate: 2025-01-01\nsitemap:\n  priority: 0.9\n  changefreq: monthly\n---\n\n![Photo](/static/images/photo.png)\n",
+    )
+    .unwrap();
+    fs::write(
+        posts.join("2025-01-02-hidden.md"),
+        "---\ntitle: Hidden\ndate: 2025-01-02\nrobots: noindex\n---\n\nHidden.\n",
+    )
+    .unwrap();
+    fs::write(
+        posts.join("2025-01-03-excluded.md"),
+        "---\ntitle: Excluded\ndate: 2025-01-03\nsitemap:\n  exclude: true\n---\n\nExcluded.\n",
+    )
+    .unwrap();
+
+    page_cmd()
+        .args(["build"])
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let sitemap = fs::read_to_string(site_dir.join("dist/sitemap.xml")).unwrap();
+    assert!(sitemap.contains("<changefreq>monthly</changefreq><priority>0.9</priority>"));
+    assert!(
+        sitemap.contains("<image:loc>http://localhost:3000/static/images/photo.png</image:loc>")
+    );
+    assert!(!sitemap.contains("/posts/hidden"));
+    assert!(!sitemap.contains("/posts/excluded"));
+    // Left out of the sitemap, but still built
+    assert!(site_dir.join("dist/posts/hidden.html").exists());
+    assert!(!site_dir.join("dist/sitemap_index.xml").exists());
+
+    let robots = fs::read_to_string(site_dir.join("dist/robots.txt")).unwrap();
+    assert!(robots.contains("/sitemap.xml"));
+}
+
 #[test]
 fn test_build_with_data_files() {
     let tmp = TempDir::new().unwrap();
@@ -9683,3 +11116,278 @@ fn test_paginated_docs_nav_fallback_for_missing_lang() {
     // Build succeeds for Spanish even though no Spanish docs content
     // (nav cache has "en" but not "es" — exercises empty_nav_value fallback)
 }
+
+// --- incremental builds ---
+
+#[test]
+fn test_incremental_build_reuses_unchanged_pages() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Incremental", "posts,pages");
+    let site_dir = tmp.path().join("site");
+    create_post(&site_dir, "2025-01-01", "first", "First Post");
+    create_post(&site_dir, "2025-01-02", "second", "Second Post");
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("reused").not());
+    assert!(site_dir.join(".seite/cache/build/dist/index.json").exists());
+
+    // Nothing changed: every page is restored from the previous build
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 pages re-rendered"));
+    assert!(site_dir.join("dist/posts/first.html").exists());
+    assert!(site_dir.join("dist/posts/first.md").exists());
+    assert!(site_dir.join("dist/feed.xml").exists());
+    assert!(site_dir.join("dist/sitemap.xml").exists());
+    assert!(site_dir.join("dist/search-index.json").exists());
+
+    // Editing one post re-renders only that post
+    fs::write(
+        site_dir.join("content/posts/2025-01-02-second.md"),
+        "---\ntitle: \"Second Post Edited\"\ndate: 2025-01-02\n---\n\nNew body.\n",
+    )
+    .unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 pages re-rendered"));
+    let second = fs::read_to_string(site_dir.join("dist/posts/second.html")).unwrap();
+    assert!(second.contains("Second Post Edited"));
+    let feed = fs::read_to_string(site_dir.join("dist/feed.xml")).unwrap();
+    assert!(feed.contains("Second Post Edited"));
+    let index = fs::read_to_string(site_dir.join("dist/index.html")).unwrap();
+    assert!(index.contains("Second Post Edited"));
+}
+
+#[test]
+fn test_incremental_build_removes_outputs_of_deleted_sources() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Incremental Delete", "posts");
+    let site_dir = tmp.path().join("site");
+    create_post(&site_dir, "2025-01-01", "keep", "Keep Me");
+    create_post(&site_dir, "2025-01-02", "gone", "Delete Me");
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    assert!(site_dir.join("dist/posts/gone.html").exists());
+
+    fs::remove_file(site_dir.join("content/posts/2025-01-02-gone.md")).unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    assert!(site_dir.join("dist/posts/keep.html").exists());
+    assert!(!site_dir.join("dist/posts/gone.html").exists());
+    assert!(!site_dir.join("dist/posts/gone.md").exists());
+    let sitemap = fs::read_to_string(site_dir.join("dist/sitemap.xml")).unwrap();
+    assert!(!sitemap.contains("/posts/gone"));
+    let cache_dir = site_dir.join(".seite/cache/build/dist");
+    assert!(!cache_dir.join("staging").exists());
+    assert!(!cache_dir.join("old-output").exists());
+}
+
+#[test]
+fn test_failed_build_keeps_previous_output() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Failed Build", "posts");
+    let site_dir = tmp.path().join("site");
+    create_post(&site_dir, "2025-01-01", "keep", "Keep Me");
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    // The output is only replaced once a build succeeds, so a server keeps
+    // serving the last good build
+    fs::write(
+        site_dir.join("content/posts/2025-01-02-broken.md"),
+        "---
+title: [unclosed
+---
+
+Body.
+",
+    )
+    .unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .failure();
+    assert!(site_dir.join("dist/posts/keep.html").exists());
+    assert!(site_dir.join("dist/index.html").exists());
+
+    fs::remove_file(site_dir.join("content/posts/2025-01-02-broken.md")).unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 pages re-rendered"));
+}
+
+#[test]
+fn test_incremental_build_template_change_rerenders_dependents() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Incremental Templates", "posts,pages");
+    let site_dir = tmp.path().join("site");
+    create_post(&site_dir, "2025-01-01", "first", "First Post");
+    fs::write(
+        site_dir.join("content/pages/about.md"),
+        "---\ntitle: About\n---\n\nAbout us.\n",
+    )
+    .unwrap();
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    // A post template that extends base.html: only the two posts (including
+    // the scaffolded hello-world post) depend on it, not the about page
+    fs::write(
+        site_dir.join("templates/post.html"),
+        "{% extends \"base.html\" %}{% block content %}<p class=\"custom-post\">{{ page.title }}</p>{% endblock %}",
+    )
+    .unwrap();
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 pages re-rendered, 1 reused"));
+    let post = fs::read_to_string(site_dir.join("dist/posts/first.html")).unwrap();
+    assert!(post.contains("custom-post"));
+}
+
+#[test]
+fn test_build_full_ignores_cache() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "site", "Full Build", "posts");
+    let site_dir = tmp.path().join("site");
+    create_post(&site_dir, "2025-01-01", "first", "First Post");
+
+    page_cmd()
+        .arg("build")
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+    page_cmd()
+        .args(["build", "--full"])
+        .current_dir(&site_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("reused").not());
+    assert!(site_dir.join("dist/posts/first.html").exists());
+}
+
+#[test]
+fn test_init_gitignore_includes_build_cache() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "gitcache", "Gitignore Cache Test", "posts");
+    let gitignore = fs::read_to_string(tmp.path().join("gitcache/.gitignore")).unwrap();
+    assert!(
+        gitignore.contains("/.seite/cache"),
+        ".gitignore should exclude the build cache"
+    );
+}
+
+#[test]
+fn test_build_docs_nav_sections_and_overrides() {
+    let tmp = TempDir::new().unwrap();
+    init_site(&tmp, "docsnav", "Docs Nav", "docs,pages");
+    let site_dir = tmp.path().join("docsnav");
+    page_cmd()
+        .args(["theme", "apply", "docs"])
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let docs = site_dir.join("content/docs");
+    fs::create_dir_all(docs.join("guides/deploy")).unwrap();
+    fs::create_dir_all(docs.join("reference")).unwrap();
+    let write_doc = |rel: &str, frontmatter: &str| {
+        fs::write(docs.join(rel), format!("---\n{frontmatter}\n---\nBody.\n")).unwrap();
+    };
+    write_doc("intro.md", "title: Intro\nweight: 1");
+    write_doc(
+        "guides/_index.md",
+        "title: How-To Guides\nweight: 2\ncollapsed: true",
+    );
+    write_doc("guides/setup.md", "title: Setup");
+    write_doc("guides/deploy/_index.md", "title: Deploying");
+    write_doc("guides/deploy/aws.md", "title: AWS");
+    write_doc("reference/cli.md", "title: CLI");
+
+    page_cmd()
+        .args(["build"])
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    // Section files describe sections and are not pages
+    assert!(!site_dir.join("dist/docs/guides/_index.html").exists());
+    let aws = fs::read_to_string(site_dir.join("dist/docs/guides/deploy/aws.html")).unwrap();
+    assert!(aws.contains("<h3>How-To Guides</h3>"));
+    assert!(aws.contains("<summary>Deploying</summary>"));
+    assert!(aws.contains(
+        "<a href=\"/docs/\">Documentation</a> / How-To Guides / Deploying / <span aria-current=\"page\">AWS</span>"
+    ));
+    // Nav order: Intro, Guides (Deploying/AWS, Setup), Reference (CLI)
+    assert!(aws.contains("<a href=\"/docs/intro\" rel=\"prev\">"));
+    assert!(aws.contains("<a href=\"/docs/guides/setup\" rel=\"next\">"));
+    let setup = fs::read_to_string(site_dir.join("dist/docs/guides/setup.html")).unwrap();
+    assert!(setup.contains("<a href=\"/docs/reference/cli\" rel=\"next\">"));
+
+    // A nav file replaces the order, next to the site menu in data/nav.yaml
+    fs::create_dir_all(site_dir.join("data/nav")).unwrap();
+    fs::write(
+        site_dir.join("data/nav.yaml"),
+        "- title: Blog\n  url: /posts\n",
+    )
+    .unwrap();
+    fs::write(
+        site_dir.join("data/nav/docs.yaml"),
+        "- reference/cli\n- title: Start Here\n  items:\n    - guides/deploy/aws\n    - intro\n",
+    )
+    .unwrap();
+    page_cmd()
+        .args(["build"])
+        .current_dir(&site_dir)
+        .assert()
+        .success();
+
+    let aws = fs::read_to_string(site_dir.join("dist/docs/guides/deploy/aws.html")).unwrap();
+    assert!(aws.contains("<h3>Start Here</h3>"));
+    assert!(aws.contains("<a href=\"/docs/reference/cli\" rel=\"prev\">"));
+    assert!(aws.contains("<a href=\"/docs/intro\" rel=\"next\">"));
+    assert!(aws.contains("Blog"), "site menu should still render");
+    // Pages left out of the nav file are built but not listed
+    let setup = fs::read_to_string(site_dir.join("dist/docs/guides/setup.html")).unwrap();
+    assert!(!setup.contains("class=\"doc-nav\""));
+    assert!(!aws.contains("/docs/guides/setup"));
+
+    fs::write(site_dir.join("data/nav/docs.yaml"), "- missing\n").unwrap();
+    page_cmd()
+        .args(["build"])
+        .current_dir(&site_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no page or section 'missing'"));
+}